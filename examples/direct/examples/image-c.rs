@@ -23,11 +23,12 @@ fn main() {
 
 fn render_image(ncd: &mut NcDirect, blit: NcBlitter) {
     let image_path = shared::project_root_path_string("examples/res/image-16x16.png");
+    let image_path = string::cstring(&image_path).expect("the path contains a nul byte");
 
     unsafe {
         if ncdirect_render_image(
             ncd,
-            cstring![image_path],
+            image_path.as_ptr(),
             NCALIGN_CENTER,
             blit,
             NCSCALE_NONE,
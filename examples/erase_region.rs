@@ -3,37 +3,50 @@
 use libnotcurses_sys::*;
 
 /// contains the state of the program
-struct State<'nc, 'p> {
-    nc: &'nc mut Nc,
-    showcase: &'p mut NcPlane,
-    info: &'p mut NcPlane,
-    cursor: &'p mut NcPlane,
+struct State<'nc> {
+    nc: &'nc NcContext,
+    showcase: NcPlaneHandle<'nc>,
+    info: NcPlaneHandle<'nc>,
+    cursor: NcPlaneHandle<'nc>,
 }
-impl<'nc, 'p> State<'nc, 'p> {
-    /// exits the program cleanly
-    fn exit(&mut self, exit_code: i32) -> NcResult<()> {
-        self.showcase.destroy()?;
-        self.info.destroy()?;
-        self.cursor.destroy()?;
-        self.nc.stop()?;
-        std::process::exit(exit_code);
+impl<'nc> State<'nc> {
+    /// destroys the planes
+    fn destroy(self) -> NcResult<()> {
+        unsafe {
+            self.showcase.destroy()?;
+            self.info.destroy()?;
+            self.cursor.destroy()?;
+        }
+        Ok(())
     }
 }
 
+/// the message of the error returned when the user chooses to quit
+static QUIT: &str = "quit";
+
 static LOREM_IPSUM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat";
 
-#[rustfmt::skip]
 fn main() -> NcResult<()> {
-    let mut _nc = Nc::new()?;
-    let mut _showcase = NcPlane::new_bound(_nc.stdplane(), 1, 10, 10, 20)?;
-    let mut _info = NcPlane::new_bound(_nc.stdplane(), 12, 1, 3, 54)?;
-    let mut _cursor = NcPlane::new_bound(_nc.stdplane(), 12, 1, 1, 1)?;
+    let nc = NcContext::new()?;
+    let stdplane = nc.stdplane();
     let mut state = State {
-        nc: _nc,
-        showcase: _showcase,
-        info: _info,
-        cursor: _cursor,
+        nc: &nc,
+        showcase: stdplane.new_bound(1, 10, 10, 20)?,
+        info: stdplane.new_bound(12, 1, 3, 54)?,
+        cursor: stdplane.new_bound(12, 1, 1, 1)?,
     };
+    let res = run(&mut state);
+
+    // bye (the context is stopped when it goes out of scope)
+    state.destroy()?;
+    match res {
+        Err(e) if e.msg == QUIT => Ok(()),
+        res => res,
+    }
+}
+
+#[rustfmt::skip]
+fn run(state: &mut State) -> NcResult<()> {
     state.showcase.set_base("▒", 0, NcChannels::from_rgb(0x662222, 0x557755))?;
     state.showcase.set_channels(NcChannels::from_rgb(0x222222, 0x449944));
     state.info.set_base(" ", 0, NcChannels::from_rgb(0x222222, 0xAAAAAA))?;
@@ -46,28 +59,25 @@ fn main() -> NcResult<()> {
     state.info.putstrln(" Showcase on a 10x20 plane the results of calling")?;
     state.info.putstrln("`NcPlane.erase_region()` with different arguments.")?;
     print_info_row(
-        &mut state,
+        state,
         2,
         Some(NcChannels::from_rgb(0x113355, 0x8899CC)), // blue
         NcAlign::CENTER,
         "(press 'q' to quit, or any other key to continue)"
     )?;
-    render_and_wait_input(&mut state)?;
+    render_and_wait_input(state)?;
 
     // THE ACTUAL SLIDESHOW
-    erase_region_slideshow(&mut state)?;
+    erase_region_slideshow(state)?;
 
     // the last slide
     state.showcase.erase();
     state.info.erase();
-    print_info_row(&mut state, 1, None, NcAlign::CENTER, "that's all, folks! ")?;
+    print_info_row(state, 1, None, NcAlign::CENTER, "that's all, folks! ")?;
     state
         .showcase
         .set_base(" ", 0, NcChannels::from_rgb(0x224411, 0x992222))?;
-    render_and_wait_input(&mut state)?;
-
-    // bye
-    state.exit(0)?;
+    render_and_wait_input(state)?;
     Ok(())
 }
 
@@ -181,7 +191,7 @@ fn render_and_wait_input(state: &mut State) -> NcResult<()> {
     state.nc.render()?;
     let key = state.nc.getc_blocking(None)?;
     if key == 'q' {
-        state.exit(0)?;
+        return Err(NcError::new_msg(QUIT));
     }
     Ok(())
 }
@@ -1,8 +1,8 @@
 use libnotcurses_sys::*;
 
 fn main() -> NcResult<()> {
-    let nc = Nc::new_cli()?;
-    let splane = nc.stdplane();
+    let nc = NcContext::new_cli()?;
+    let mut splane = nc.stdplane();
     splane.set_scrolling(true);
 
     putstrln!(splane, "ENVIRONMENT\n-----------")?;
@@ -50,10 +50,9 @@ Palette size: {11:?}
         t_rows,
         t_cols
     )?;
-    let pgeom = nc.stdplane().pixel_geom();
+    let pgeom = splane.pixel_geom();
     putstr!(splane, "{:#?}.", pgeom)?;
 
     nc.render()?;
-    nc.stop()?;
     Ok(())
 }
@@ -5,11 +5,11 @@
 use libnotcurses_sys::*;
 
 fn main() -> NcResult<()> {
-    let nc = Nc::with_flags(
+    let nc = NcContext::with_flags(
         NcOptions::SUPPRESS_BANNERS | NcOptions::NO_WINCH_SIGHANDLER | NcOptions::NO_QUIT_SIGHANDLERS,
     )?;
-    nc.stdplane().set_scrolling(true);
-    let splane = nc.stdplane();
+    let mut splane = nc.stdplane();
+    splane.set_scrolling(true);
 
     putstrln!(splane, "Input example.\nPress any key to continue:")?;
     nc.render()?;
@@ -38,6 +38,5 @@ fn main() -> NcResult<()> {
         }
     }
 
-    nc.stop()?;
     Ok(())
 }
@@ -6,10 +6,9 @@ use rand::{distributions::Uniform, Rng};
 use libnotcurses_sys::*;
 
 fn main() -> NcResult<()> {
-    let mut nc = Nc::new()?;
+    let nc = NcContext::new()?;
 
     if nc.check_pixel_support() == NcPixelImpl::NOPIXEL {
-        nc.stop()?;
         return Err(NcError::new_msg("Current terminal doesn't support pixels."));
     }
 
@@ -47,11 +46,11 @@ fn main() -> NcResult<()> {
     let v1 = NcVisual::from_rgba(buffer.as_slice(), pg.cell_y, pg.cell_x * 4, pg.cell_x)?;
     let voptions =
         NcVisualOptions::without_plane(1, 2, 0, 0, pg.cell_y, pg.cell_x, NcBlitter::PIXEL, 0, 0);
-    v1.render(&mut nc, &voptions)?;
-    nrs![&mut nc, 1];
+    NcPlaneHandle::from_visual(&nc, v1, &voptions)?;
+    nrs![nc, 1];
 
     // show the ncvisual, scaled with interpolated values
-    let mut vplane2 = NcPlane::new_bound(&mut splane, 7, 4, 5, 4)?;
+    let mut vplane2 = splane.new_bound(7, 4, 5, 4)?;
     let voptions2 = NcVisualOptions::with_plane(
         &mut vplane2,
         NcScale::SCALE,
@@ -65,11 +64,11 @@ fn main() -> NcResult<()> {
         0,
         0,
     );
-    v1.render(&mut nc, &voptions2)?;
-    nrs![&mut nc, 0, 250];
+    vplane2.blit(v1, &voptions2)?;
+    nrs![nc, 0, 250];
 
     // show the ncvisual, scaled without using interpolation
-    let mut vplane3 = NcPlane::new_bound(&mut splane, 7, 19, 5, 4)?;
+    let mut vplane3 = splane.new_bound(7, 19, 5, 4)?;
     let voptions3 = NcVisualOptions::with_plane(
         &mut vplane3,
         NcScale::SCALE,
@@ -83,28 +82,29 @@ fn main() -> NcResult<()> {
         NcVisualOptions::NOINTERPOLATE,
         0,
     );
-    v1.render(&mut nc, &voptions3)?;
-    nrs![&mut nc, 0, 250];
+    vplane3.blit(v1, &voptions3)?;
+    nrs![nc, 0, 250];
 
     // resize the ncvisual (doesn't use interpolation)
     let voptions4 =
         NcVisualOptions::without_plane(7, 39, 0, 0, pg.cell_y, pg.cell_x, NcBlitter::PIXEL, 0, 0);
     v1.resize_noninterpolative(pg.cell_y * 4, pg.cell_x * 4)?;
-    v1.render(&mut nc, &voptions4)?;
-    nrs![&mut nc, 0, 250];
+    NcPlaneHandle::from_visual(&nc, v1, &voptions4)?;
+    nrs![nc, 0, 250];
 
     // resize the ncvisual (uses interpolation)
     let v5 = NcVisual::from_rgba(buffer.as_slice(), pg.cell_y, pg.cell_x * 4, pg.cell_x)?;
     let voptions5 =
         NcVisualOptions::without_plane(7, 56, 0, 0, pg.cell_y, pg.cell_x, NcBlitter::PIXEL, 0, 0);
     v5.resize(pg.cell_y * 4, pg.cell_x * 4)?;
-    v5.render(&mut nc, &voptions5)?;
-    nrs![&mut nc, 0, 250];
+    NcPlaneHandle::from_visual(&nc, v5, &voptions5)?;
+    nrs![nc, 0, 250];
 
     sleep![2];
 
-    v1.destroy();
-    v5.destroy();
-    nc.stop()?;
+    unsafe {
+        v1.destroy();
+        v5.destroy();
+    }
     Ok(())
 }
@@ -3,9 +3,9 @@
 use libnotcurses_sys::*;
 
 fn main() -> NcResult<()> {
-    let mut nc = Nc::new()?;
+    let nc = NcContext::new()?;
 
-    let plane = nc.stdplane();
+    let mut plane = nc.stdplane();
     plane.set_scrolling(true);
 
     let mut wc = '\u{4e00}'; // 一
@@ -18,7 +18,7 @@ fn main() -> NcResult<()> {
         if wc == '\u{9fa5}' {
             wc = '\u{4e00}';
         }
-        nrs![&mut nc, 0, 0, 30];
+        nrs![nc, 0, 0, 30];
     }
 
     // nc.stop()?;
@@ -10,27 +10,27 @@ fn main() -> NcResult<()> {
     let dimx = ncd.dim_x() as i32;
     for _ in 0..dimy {
         for _ in 0..dimx {
-            printf!("X");
+            printf!("X")?;
         }
     }
     ncd.flush()?;
 
     ncd.set_fg_rgb(0xff8080)?;
-    printf!(" erp erp \n");
+    printf!(" erp erp \n")?;
     ncd.set_fg_rgb(0x80ff80)?;
-    printf!(" erp erp \n");
+    printf!(" erp erp \n")?;
     ncd.set_fg_rgb(0xff8080)?;
-    printf!(" erp erp \n");
+    printf!(" erp erp \n")?;
     ncd.cursor_right(dimx / 2)?;
     ncd.cursor_up(dimy / 2)?;
-    printf!(" erperperp! \n");
+    printf!(" erperperp! \n")?;
 
     let (mut y, x);
 
     if let Ok((_y, _x)) = ncd.cursor_yx() {
         y = _y;
         x = _x;
-        printf!("\n\tRead cursor position: y: %d x: %d\n", y, x);
+        printf!("\n\tRead cursor position: y: %d x: %d\n", y, x)?;
 
         y += 2;
         while y > 3 {
@@ -50,7 +50,7 @@ fn main() -> NcResult<()> {
                 eprintln!("Expected {}, got {}", y, newy);
                 break;
             }
-            printf!("\n\tRead cursor position: y: %d x: %d\n", newy, x);
+            printf!("\n\tRead cursor position: y: %d x: %d\n", newy, x)?;
             y += 2;
         }
     } else {
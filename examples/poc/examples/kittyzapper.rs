@@ -7,18 +7,18 @@ fn main() -> NcResult<()> {
 
     ncd.set_fg_rgb8(100, 100, 100)?;
     ncd.set_bg_rgb8(0xff, 0xff, 0xff)?;
-    printf!("a");
+    printf!("a")?;
     ncd.set_bg_rgb8(0, 0, 0)?;
-    printf!("b");
-    printf!(" ");
-    printf!(" ");
+    printf!("b")?;
+    printf!(" ")?;
+    printf!(" ")?;
     ncd.set_bg_rgb8(0, 0, 1)?;
-    printf!("c");
-    printf!(" ");
-    printf!(" ");
+    printf!("c")?;
+    printf!(" ")?;
+    printf!(" ")?;
     ncd.set_bg_rgb8(0xff, 0xff, 0xff)?;
-    printf!("d");
-    printf!("\n");
+    printf!("d")?;
+    printf!("\n")?;
 
     ncd.stop()?;
     Ok(())
@@ -7,7 +7,7 @@ use libnotcurses_sys::*;
 use widgets::*;
 
 fn main() -> NcResult<()> {
-    let mut nc = Nc::new()?;
+    let mut nc = NcContext::new()?;
     nc.mouse_enable()?;
 
     let mut demo_items = [
@@ -38,10 +38,18 @@ fn main() -> NcResult<()> {
     mopts.section_channels_mut().set_fg_rgb(0xb0d700);
     mopts.section_channels_mut().set_bg_rgb(0x002000);
 
-    let stdplane = nc.stdplane();
+    let mut stdplane = nc.stdplane();
     let (dim_y, _dim_x) = stdplane.dim_yx();
 
-    let menu_top = NcMenu::new(stdplane, mopts)?;
+    let menu_top = NcMenu::new(&mut stdplane, mopts)?;
+
+    // the menu copies the strings, so they can be freed
+    unsafe {
+        sections.iter_mut().for_each(|section| section.free());
+        demo_items.iter_mut().for_each(|item| item.free());
+        file_items.iter_mut().for_each(|item| item.free());
+        help_items.iter_mut().for_each(|item| item.free());
+    }
     //menu_top.item_set_status("Schwarzgerät", "Disabled", false)?;
     //menu_top.item_set_status("Schwarzgerät", "Restart", false)?;
 
@@ -56,7 +64,7 @@ fn main() -> NcResult<()> {
         " -=+ menu poc. press q to exit +=-",
     )?;
 
-    run_menu(&mut nc, menu_top)?;
+    run_menu(&nc, menu_top)?;
 
     stdplane.erase(); // is this needed?
 
@@ -66,18 +74,18 @@ fn main() -> NcResult<()> {
     stdplane.putstr_aligned(2, NcAlign::LEFT, " -=+ menu poc. press q to exit +=-")?;
 
     mopts.flags |= NCMENU_OPTION_BOTTOM;
-    let menu_bottom = NcMenu::new(stdplane, mopts)?;
+    let menu_bottom = NcMenu::new(&mut stdplane, mopts)?;
 
-    run_menu(&mut nc, menu_bottom)?;
+    run_menu(&nc, menu_bottom)?;
 
     Ok(())
 }
 
-fn run_menu(nc: &mut Nc, menu: &mut NcMenu) -> NcResult<()> {
+fn run_menu(nc: &NcContext, menu: &mut NcMenu) -> NcResult<()> {
     // yellow rectangle
     let planeopts = NcPlaneOptions::new_aligned(10, NcAlign::CENTER, 10, 40);
-    let stdplane = nc.stdplane();
-    let selplane = NcPlane::with_options_bound(stdplane, planeopts)?;
+    let mut stdplane = nc.stdplane();
+    let mut selplane = stdplane.with_options_bound(planeopts)?;
     selplane.set_fg_rgb(0);
     selplane.set_bg_rgb(0xdddddd);
     let mut channels = 0;
@@ -102,21 +110,19 @@ fn run_menu(nc: &mut Nc, menu: &mut NcMenu) -> NcResult<()> {
         nc.render()?;
 
         // BUG FIXME: always returns false:
-        if !menu.offer_input(ni) {
+        if !menu.offer_input(ni)? {
             match keypress {
                 'q' => {
-                    menu.destroy()?;
-                    selplane.destroy()?;
-                    nc.stop()?;
+                    unsafe { menu.destroy()? };
+                    unsafe { selplane.destroy()? };
                     return Ok(());
                 }
                 NcKey::ENTER => {
                     if let Some(selection) = menu.selected(Some(&mut ni)) {
                         match selection.as_ref() {
                             "Quit" => {
-                                menu.destroy()?;
-                                selplane.destroy()?;
-                                nc.stop()?;
+                                unsafe { menu.destroy()? };
+                                unsafe { selplane.destroy()? };
                                 return Ok(());
                             }
                             _ => (),
@@ -8,11 +8,11 @@
 
 use libnotcurses_sys::{
     // Core
-    Nc,
+    NcContext,
     NcResult,
     NcError,
     // Plane
-    NcPlane,
+    NcPlaneHandle,
     NcPlaneOptions,
     // Visual
     NcVisual,
@@ -32,11 +32,10 @@ fn main() -> NcResult<()> {
     let filepath: String = std::env::args().nth(1).expect("Error: Must pass image file path as first argument. Usage: pixel image.png");
 
     // Init notcurses context
-    let nc: &mut Nc = Nc::new()?;
+    let nc: NcContext = NcContext::new()?;
 
     // Clause: Pixel must be supported
     if 0 == nc.check_pixel_support() {
-        nc.stop()?;
         return Err(NcError::with_msg(1, "Error: This program requires pixel graphics support"));
     }
 
@@ -45,14 +44,14 @@ fn main() -> NcResult<()> {
 
     // Create full screen plane
     // -- Notcurses report an error if image is draw on the stdplane
-    let stdplane: &mut NcPlane = nc.stdplane();
+    let stdplane: NcPlaneHandle = nc.stdplane();
     let (row, col) = stdplane.dim_yx();
     let planeopts: NcPlaneOptions = NcPlaneOptions::new(0, 0, row, col);
-    let pixelplane: &mut NcPlane = NcPlane::with_options_bound(stdplane, planeopts)?;
+    let mut pixelplane: NcPlaneHandle = stdplane.with_options_bound(planeopts)?;
 
     // Craft some visual options (here full screen)
     let opts: NcVisualOptions = NcVisualOptions::with_plane(
-        pixelplane,
+        &mut pixelplane,
         NcScale::SCALE,
         0, 0,  // x,y offset relative to plane
         0, 0,  // begx, begy offset of the rendered section
@@ -63,7 +62,7 @@ fn main() -> NcResult<()> {
     );
         
     // Render the visual in the virtual space
-    visual.render(nc, &opts)?;
+    pixelplane.blit(visual, &opts)?;
 
     // Render the virtual space in the real terminal space
     nc.render()?;
@@ -89,7 +88,7 @@ fn main() -> NcResult<()> {
     }
 
     // Destroy visual
-    visual.destroy();
+    unsafe { visual.destroy() };
 
     // Restore the terminal context
     nc.stop()?;
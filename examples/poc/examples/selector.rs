@@ -24,23 +24,23 @@ use libnotcurses_sys::{
     //// Selector:
     widgets::{NcSelector, NcSelectorItem, NcSelectorOptions},
     // Core
-    Nc,
     NcAlign,
     NcAlignApi,
+    NcContext,
     NcEvType,
     NcEvTypeApi,
     // Input
     NcInput,
     NcKey,
     // Plane
-    NcPlane,
+    NcPlaneHandle,
     NcPlaneOptions,
     NcResult,
 };
 
 fn main() -> NcResult<()> {
     // Init context
-    let nc: &mut Nc = Nc::new()?;
+    let mut nc: NcContext = NcContext::new()?;
 
     // Enable mouse
     nc.mouse_enable()?;
@@ -65,7 +65,7 @@ fn main() -> NcResult<()> {
 
     // Pack those items in the selector custom option struct
     // -- with title, description and footer
-    let opts: NcSelectorOptions = NcSelectorOptions::new(
+    let mut opts: NcSelectorOptions = NcSelectorOptions::new(
         "this is truly, absolutely an awfully long example of a selector title",
         "pick one (you will die regardless)",
         "press q to exit (there is no exit)",
@@ -73,7 +73,7 @@ fn main() -> NcResult<()> {
     );
 
     // Create first plane (full screen)
-    let stdplane: &mut NcPlane = nc.stdplane();
+    let mut stdplane: NcPlaneHandle = nc.stdplane();
 
     // Set font color (green)
     stdplane.set_fg_rgb(0x40f040);
@@ -85,14 +85,20 @@ fn main() -> NcResult<()> {
     // Create selection plane
     // y: NcOffset, x: NcOffset, rows: NcDim, cols: NcDim
     let planeopts: NcPlaneOptions = NcPlaneOptions::new_aligned(1, NcAlign::LEFT, 15, 80);
-    let selplane: &mut NcPlane = NcPlane::with_options_bound(stdplane, planeopts)?;
+    let mut selplane: NcPlaneHandle = stdplane.with_options_bound(planeopts)?;
 
     // Create selector
-    let selector: &mut NcSelector = NcSelector::new(selplane, opts)?;
+    let selector: &mut NcSelector = NcSelector::new(&mut selplane, opts)?;
+
+    // The selector copies the strings, so they can be freed
+    unsafe {
+        opts.free();
+        selector_items.iter_mut().for_each(|item| item.free());
+    }
 
     // Create description plane
     let planeopts2: NcPlaneOptions = NcPlaneOptions::new_aligned(15, NcAlign::LEFT, 30, 80);
-    let descplane: &mut NcPlane = NcPlane::with_options_bound(stdplane, planeopts2)?;
+    let mut descplane: NcPlaneHandle = stdplane.with_options_bound(planeopts2)?;
     descplane.set_scrolling(true);
     descplane.puttext(
         0,
@@ -104,10 +110,10 @@ fn main() -> NcResult<()> {
     )?;
 
     // Render loop
-    let selected: String = run_selector(nc, selector)?;
+    let selected: String = run_selector(&nc, selector)?;
 
     // Destroy ressources
-    selector.destroy()?;
+    unsafe { selector.destroy()? };
 
     // Restore context, TERM status like cursor
     nc.stop()?;
@@ -119,7 +125,7 @@ fn main() -> NcResult<()> {
 }
 
 /// Helper to avoid having a render loop in the main function
-fn run_selector(nc: &mut Nc, selector: &mut NcSelector) -> NcResult<String> {
+fn run_selector(nc: &NcContext, selector: &mut NcSelector) -> NcResult<String> {
     // Allocate input placeholder
     let mut ni: NcInput = NcInput::new_empty();
 
@@ -130,7 +136,7 @@ fn run_selector(nc: &mut Nc, selector: &mut NcSelector) -> NcResult<String> {
         // Wait until user acts
         let keypress: char = nc.getc_blocking(Some(&mut ni))?;
 
-        if !selector.offer_input(ni) {
+        if !selector.offer_input(ni)? {
             // Do not consider release key: only press
             if ni.evtype == NcEvType::RELEASE {
                 continue;
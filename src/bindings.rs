@@ -751,7 +751,9 @@ pub use ffi::{
 #[doc(inline)]
 pub use ffi::{
     // functions
+    nctree_add,
     nctree_create,
+    nctree_del,
     nctree_destroy,
     nctree_focused,
     nctree_goto,
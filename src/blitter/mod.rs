@@ -77,7 +77,7 @@ crate::impl_api![
         let data_ptr: *const c_void = data as *const _ as *const c_void;
         let res = unsafe { c_api::ncblit_rgba(data_ptr, line_size as i32, vopts) };
         error![
-            res,
+            @call "ncblit_rgba", res,
             &format!["NcBlitter::blit_rgba(data, {}, {:?})", line_size, vopts],
             res as usize
         ];
@@ -89,7 +89,7 @@ crate::impl_api![
         let data_ptr: *const c_void = data as *const _ as *const c_void;
         let res = unsafe { c_api::ncblit_bgrx(data_ptr, line_size as i32, vopts) };
         error![
-            res,
+            @call "ncblit_bgrx", res,
             &format!["NcBlitter::blit_bgrx(data, {}, {:?})", line_size, vopts],
             res as usize
         ];
@@ -111,7 +111,7 @@ crate::impl_api![
         let res =
             unsafe { c_api::ncblit_rgb_packed(data_ptr, line_size as i32, vopts, alpha as i32) };
         error![
-            res,
+            @call "ncblit_rgb_packed", res,
             &format![
                 "NcBlitter::blit_rgb_packed(data, {}, {:?}, {})",
                 line_size, vopts, alpha
@@ -135,7 +135,7 @@ crate::impl_api![
         let res =
             unsafe { c_api::ncblit_rgb_loose(data_ptr, line_size as i32, vopts, alpha as i32) };
         error![
-            res,
+            @call "ncblit_rgb_loose", res,
             &format![
                 "NcBlitter::blit_rgb_loose(data, {}, {:?}, {})",
                 line_size, vopts, alpha
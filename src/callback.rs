@@ -0,0 +1,49 @@
+//! Helpers for the Rust closures called back from C.
+
+use core::cell::Cell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::{c_api, NcError, NcIntResult, NcResult};
+
+thread_local! {
+    /// The number of guarded callbacks running in the current thread.
+    static DEPTH: Cell<usize> = Cell::new(0);
+}
+
+/// Returns `true` if the current thread is running a guarded callback,
+/// called from C.
+///
+/// The panic hook mustn't stop the contexts meanwhile, since the callback
+/// returns into the C library, which still uses them.
+pub(crate) fn in_callback() -> bool {
+    DEPTH.try_with(|depth| depth.get() > 0).unwrap_or(false)
+}
+
+/// Runs the body of a C callback that calls a Rust closure, catching any
+/// panic, so that it doesn't unwind across the FFI boundary, which would be
+/// undefined behavior.
+///
+/// Returns an error with the panic message if it panicked. In that case the
+/// callback should also be unset, if possible, since its state can be broken.
+pub(crate) fn ffi_guard<T>(f: impl FnOnce() -> T) -> Result<T, NcError> {
+    let _ = DEPTH.try_with(|depth| depth.set(depth.get() + 1));
+    let res = catch_unwind(AssertUnwindSafe(f));
+    let _ = DEPTH.try_with(|depth| depth.set(depth.get() - 1));
+    res.map_err(|payload| {
+        let msg = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("Box<dyn Any>");
+        NcError::new_msg(&format!["a callback panicked: {}", msg])
+    })
+}
+
+/// Converts the result of a callback closure into the integer returned to C.
+pub(crate) fn int_result(res: &Result<NcResult<()>, NcError>) -> NcIntResult {
+    match res {
+        Ok(Ok(())) => c_api::NCRESULT_OK,
+        Ok(Err(e)) if e.int < 0 => e.int,
+        _ => c_api::NCRESULT_ERR,
+    }
+}
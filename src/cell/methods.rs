@@ -1,8 +1,10 @@
 //! `NcCell` methods and associated functions.
 
+use std::borrow::Cow;
+
 use crate::{
     c_api::{self, nccell_load},
-    cstring, error, rstring, NcAlpha, NcCell, NcChannels, NcComponent, NcError, NcIntResult,
+    error, string, NcAlpha, NcCell, NcChannels, NcComponent, NcError, NcErrorKind, NcIntResult,
     NcIntResultApi, NcPaletteIndex, NcPlane, NcResult, NcRgb, NcStyle,
 };
 
@@ -16,7 +18,10 @@ impl NcCell {
     #[allow(clippy::unnecessary_cast)]
     pub fn from_char7b(ch: char) -> NcResult<Self> {
         if !ch.is_ascii() {
-            return Err(NcError::new());
+            return Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "NcCell::from_char7b(): not a 7-bit char",
+            ));
         }
         Ok(NcCell {
             gcluster: (ch as u32).to_le(),
@@ -33,9 +38,12 @@ impl NcCell {
     #[inline]
     pub fn from_char(plane: &mut NcPlane, ch: char) -> NcResult<Self> {
         let mut cell = Self::new();
-        let res = unsafe { nccell_load(plane, &mut cell, cstring![ch.to_string()]) };
+        let mut buf = [0; 4];
+        let res = string::with_cstr(ch.encode_utf8(&mut buf), |egc| unsafe {
+            nccell_load(plane, &mut cell, egc)
+        })?;
         if res == NcIntResult::ERR {
-            return Err(NcError::new());
+            return Err(NcError::new().calling("nccell_load"));
         }
         Ok(cell)
     }
@@ -46,9 +54,9 @@ impl NcCell {
     #[inline]
     pub fn from_str(plane: &mut NcPlane, string: &str) -> NcResult<Self> {
         let mut cell = Self::new();
-        let res = unsafe { nccell_load(plane, &mut cell, cstring![string]) };
+        let res = string::with_cstr(string, |s| unsafe { nccell_load(plane, &mut cell, s) })?;
         if res == NcIntResult::ERR {
-            return Err(NcError::new());
+            return Err(NcError::new().calling("nccell_load"));
         }
         Ok(cell)
     }
@@ -65,9 +73,9 @@ impl NcCell {
     /// The styling of the cell is left untouched, but any resources are released.
     /// *C style function: [nccell_load()][c_api::nccell_load].*
     pub fn load(plane: &mut NcPlane, cell: &mut NcCell, egc: &str) -> NcResult<u32> {
-        let bytes = unsafe { c_api::nccell_load(plane, cell, cstring![egc]) };
+        let bytes = string::with_cstr(egc, |egc| unsafe { c_api::nccell_load(plane, cell, egc) })?;
         error![
-            bytes,
+            @call "nccell_load", bytes,
             &format!["NcCell.load(NcPlane, NcCell, {:?})", egc],
             bytes as u32
         ]
@@ -89,8 +97,9 @@ impl NcCell {
         style: NcStyle,
         channels: NcChannels,
     ) -> NcResult<u32> {
+        string::check_nul(gcluster)?;
         let bytes = c_api::nccell_prime(plane, cell, gcluster, style, channels);
-        error![bytes, "", bytes as u32]
+        error![@call "nccell_prime", bytes, "", bytes as u32]
     }
 
     /// Duplicate this `NcCell` into another one.
@@ -101,7 +110,7 @@ impl NcCell {
     pub fn duplicate(&self, common_plane: &mut NcPlane) -> NcResult<NcCell> {
         let mut target = NcCell::new();
         let res = unsafe { c_api::nccell_duplicate(common_plane, &mut target, self) };
-        error![res, "NcCell.duplicate()", target]
+        error![@call "nccell_duplicate", res, "NcCell.duplicate()", target]
     }
 
     /// Initializes (zeroes out) this `NcCell`.
@@ -390,10 +399,12 @@ impl NcCell {
     /// This pointer can be invalidated by any further operation on the referred
     /// plane, so… watch out!
     ///
+    /// Any invalid UTF-8 sequences are replaced with `U+FFFD`.
+    ///
     /// *C style function: [nccell_extended_gcluster()][c_api::nccell_wide_left_p].*
-    pub fn extended_gcluster(&self, plane: &NcPlane) -> &str {
+    pub fn extended_gcluster<'a>(&'a self, plane: &'a NcPlane) -> Cow<'a, str> {
         let egcpointer = unsafe { c_api::nccell_extended_gcluster(plane, self) };
-        rstring![egcpointer]
+        unsafe { string::rstr_lossy(egcpointer) }
     }
 
     /// Copies the UTF8-encoded `EGC` out of this NcCell,
@@ -450,9 +461,10 @@ impl NcCell {
         vl: &mut NcCell,
         gcluster: &str,
     ) -> NcResult<()> {
-        error![c_api::nccells_load_box(
+        string::check_nul(gcluster)?;
+        error![@call "nccells_load_box", c_api::nccells_load_box(
             plane, style, channels, ul, ur, ll, lr, hl, vl, gcluster
-        )]
+        ), "NcCell::load_box()"]
     }
 
     /// NcCell.[load_box()][NcCell#method.box] with the double box-drawing characters.
@@ -469,9 +481,9 @@ impl NcCell {
         hl: &mut NcCell,
         vl: &mut NcCell,
     ) -> NcResult<()> {
-        error![unsafe {
+        error![@call "nccells_double_box", unsafe {
             c_api::nccells_double_box(plane, style as u32, channels, ul, ur, ll, lr, hl, vl)
-        }]
+        }, "NcCell::double_box()"]
     }
 
     /// NcCell.[load_box()][NcCell#method.box] with the rounded box-drawing characters.
@@ -488,9 +500,9 @@ impl NcCell {
         hl: &mut NcCell,
         vl: &mut NcCell,
     ) -> NcResult<()> {
-        error![unsafe {
+        error![@call "nccells_rounded_box", unsafe {
             c_api::nccells_rounded_box(plane, style as u32, channels, ul, ur, ll, lr, hl, vl)
-        }]
+        }, "NcCell::rounded_box()"]
     }
 
     /// NcCell.[load_box()][NcCell#method.box] with ASCII characters.
@@ -507,9 +519,9 @@ impl NcCell {
         hl: &mut NcCell,
         vl: &mut NcCell,
     ) -> NcResult<()> {
-        error![c_api::nccells_ascii_box(
+        error![@call "nccells_ascii_box", c_api::nccells_ascii_box(
             plane, style, channels, ul, ur, ll, lr, hl, vl
-        )]
+        ), "NcCell::ascii_box()"]
     }
     /// NcCell.[load_box()][NcCell#method.box] with the heavy line
     /// box-drawing characters.
@@ -526,9 +538,9 @@ impl NcCell {
         hl: &mut NcCell,
         vl: &mut NcCell,
     ) -> NcResult<()> {
-        error![c_api::nccells_heavy_box(
+        error![@call "nccells_heavy_box", c_api::nccells_heavy_box(
             plane, style, channels, ul, ur, ll, lr, hl, vl
-        )]
+        ), "NcCell::heavy_box()"]
     }
 
     /// NcCell.[load_box()][NcCell#method.box] with the light line
@@ -546,8 +558,8 @@ impl NcCell {
         hl: &mut NcCell,
         vl: &mut NcCell,
     ) -> NcResult<()> {
-        error![c_api::nccells_light_box(
+        error![@call "nccells_light_box", c_api::nccells_light_box(
             plane, style, channels, ul, ur, ll, lr, hl, vl
-        )]
+        ), "NcCell::light_box()"]
     }
 }
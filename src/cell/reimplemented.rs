@@ -6,8 +6,8 @@ use libc::strcmp;
 
 use crate::{
     c_api::{self, ffi, nccell_release},
-    cstring, rstring, NcAlpha, NcAlphaApi, NcCell, NcChannel, NcChannels, NcChannelsApi,
-    NcComponent, NcIntResult, NcIntResultApi, NcPaletteIndex, NcPlane, NcRgb, NcStyle, NcStyleApi,
+    NcAlpha, NcAlphaApi, NcCell, NcChannel, NcChannels, NcChannelsApi, NcComponent, NcIntResult,
+    NcIntResultApi, NcPaletteIndex, NcPlane, NcRgb, NcStyle, NcStyleApi,
 };
 
 const NCBOXLIGHT: &str = "┌┐└┘─│";
@@ -348,7 +348,7 @@ pub const fn nccell_wide_left_p(cell: &NcCell) -> bool {
 /// *Method: NcCell.[strdup()][NcCell#method.strdup].*
 #[inline]
 pub fn nccell_strdup(plane: &NcPlane, cell: &NcCell) -> String {
-    rstring![libc::strdup(c_api::nccell_extended_gcluster(plane, cell))].into()
+    unsafe { crate::string::rstr_lossy(c_api::nccell_extended_gcluster(plane, cell)) }.into_owned()
 }
 
 // Misc. -----------------------------------------------------------------------
@@ -420,7 +420,10 @@ pub fn nccell_prime(
 ) -> NcIntResult {
     cell.stylemask = style;
     cell.channels = channels;
-    unsafe { c_api::nccell_load(plane, cell, cstring![gcluster]) }
+    crate::string::with_cstr(gcluster, |gcluster| unsafe {
+        c_api::nccell_load(plane, cell, gcluster)
+    })
+    .unwrap_or(NcIntResult::ERR)
 }
 
 /// Loads up six cells with the `EGC`s necessary to draw a box.
@@ -445,34 +448,36 @@ pub fn nccells_load_box(
     vl: &mut NcCell,
     gcluster: &str,
 ) -> NcIntResult {
-    assert![gcluster.len() >= 6]; // DEBUG
+    if gcluster.len() < 6 {
+        return NcIntResult::ERR;
+    }
 
-    // TODO: CHECK: mutable copy for pointer arithmetics:
-    let mut gclu = cstring![gcluster];
+    // the rest of the EGCs, after the ones already loaded:
+    let mut gclu = gcluster;
 
     let mut ulen: NcIntResult;
 
-    ulen = nccell_prime(plane, ul, gcluster, style, channels);
+    ulen = nccell_prime(plane, ul, gclu, style, channels);
 
     if ulen > 0 {
-        gclu = unsafe { gclu.offset(ulen as isize) };
-        ulen = nccell_prime(plane, ur, gcluster, style, channels);
+        gclu = gclu.get(ulen as usize..).unwrap_or_default();
+        ulen = nccell_prime(plane, ur, gclu, style, channels);
 
         if ulen > 0 {
-            gclu = unsafe { gclu.offset(ulen as isize) };
-            ulen = nccell_prime(plane, ll, gcluster, style, channels);
+            gclu = gclu.get(ulen as usize..).unwrap_or_default();
+            ulen = nccell_prime(plane, ll, gclu, style, channels);
 
             if ulen > 0 {
-                gclu = unsafe { gclu.offset(ulen as isize) };
-                ulen = nccell_prime(plane, lr, gcluster, style, channels);
+                gclu = gclu.get(ulen as usize..).unwrap_or_default();
+                ulen = nccell_prime(plane, lr, gclu, style, channels);
 
                 if ulen > 0 {
-                    gclu = unsafe { gclu.offset(ulen as isize) };
-                    ulen = nccell_prime(plane, hl, gcluster, style, channels);
+                    gclu = gclu.get(ulen as usize..).unwrap_or_default();
+                    ulen = nccell_prime(plane, hl, gclu, style, channels);
 
                     if ulen > 0 {
-                        let _gclu = unsafe { gclu.offset(ulen as isize) };
-                        ulen = nccell_prime(plane, vl, gcluster, style, channels);
+                        gclu = gclu.get(ulen as usize..).unwrap_or_default();
+                        ulen = nccell_prime(plane, vl, gclu, style, channels);
 
                         if ulen > 0 {
                             return NcIntResult::OK;
@@ -66,7 +66,7 @@ fn constructors() -> crate::NcResult<()> {
 
     // cleanup
     cell.release(plane);
-    plane.destroy()?;
+    unsafe { plane.destroy()? };
     nc.stop()?;
     Ok(())
 }
@@ -0,0 +1,510 @@
+//! `NcContext` & `NcDirectContext`
+//!
+//! Owned wrappers over [`Nc`] and [`NcDirect`] that stop the terminal on drop.
+
+use core::{
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+use std::panic;
+
+use crate::{
+    c_api, callback, error, Nc, NcDirect, NcDirectFlags, NcError, NcErrorKind, NcInput, NcLogLevel,
+    NcOptions, NcPlaneHandle, NcResult, NcTime,
+};
+
+#[cfg(test)]
+mod test;
+
+/// The maximum number of contexts tracked at the same time.
+pub(crate) const REGISTRY_SLOTS: usize = 16;
+
+/// A fixed-size, lock-free set of context addresses.
+///
+/// It's used to know whether a raw `Nc` or `NcDirect` is owned by one of the
+/// context types, so that stopping it through the C-like API can be refused,
+/// and to know which contexts are alive, so that the panic hook can stop them.
+///
+/// Each address is tagged with the thread that registered it.
+pub(crate) struct Registry {
+    slots: [AtomicUsize; REGISTRY_SLOTS],
+    threads: [AtomicUsize; REGISTRY_SLOTS],
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_SLOT: AtomicUsize = AtomicUsize::new(0);
+
+impl Registry {
+    pub(crate) const fn new() -> Self {
+        Self {
+            slots: [EMPTY_SLOT; REGISTRY_SLOTS],
+            threads: [EMPTY_SLOT; REGISTRY_SLOTS],
+        }
+    }
+
+    /// Adds an address to the registry.
+    ///
+    /// Returns `false` if there were no free slots left.
+    pub(crate) fn insert(&self, addr: usize) -> bool {
+        self.slots
+            .iter()
+            .zip(self.threads.iter())
+            .any(|(slot, thread)| {
+                let inserted = slot
+                    .compare_exchange(0, addr, Ordering::AcqRel, Ordering::Acquire)
+                    .is_ok();
+                if inserted {
+                    thread.store(thread_tag(), Ordering::Release);
+                }
+                inserted
+            })
+    }
+
+    /// Removes an address from the registry.
+    ///
+    /// Returns `false` if the address was not registered.
+    pub(crate) fn remove(&self, addr: usize) -> bool {
+        self.slots
+            .iter()
+            .zip(self.threads.iter())
+            .any(|(slot, thread)| {
+                if slot.load(Ordering::Acquire) != addr {
+                    return false;
+                }
+                thread.store(0, Ordering::Release);
+                slot.compare_exchange(addr, 0, Ordering::AcqRel, Ordering::Acquire)
+                    .is_ok()
+            })
+    }
+
+    /// Returns `true` if the address is registered.
+    pub(crate) fn contains(&self, addr: usize) -> bool {
+        self.slots
+            .iter()
+            .any(|slot| slot.load(Ordering::Acquire) == addr)
+    }
+
+    /// Returns the addresses registered by the current thread.
+    pub(crate) fn current_thread(&self) -> impl Iterator<Item = usize> + '_ {
+        let tag = thread_tag();
+        self.slots
+            .iter()
+            .zip(self.threads.iter())
+            .filter(move |(_, thread)| tag != 0 && thread.load(Ordering::Acquire) == tag)
+            .map(|(slot, _)| slot.load(Ordering::Acquire))
+            .filter(|addr| *addr != 0)
+    }
+}
+
+/// Returns a non-zero number unique to the current thread,
+/// or 0 if it's not available anymore because the thread is exiting.
+fn thread_tag() -> usize {
+    static NEXT_TAG: AtomicUsize = AtomicUsize::new(1);
+    thread_local! {
+        static TAG: usize = NEXT_TAG.fetch_add(1, Ordering::Relaxed);
+    }
+    TAG.try_with(|tag| *tag).unwrap_or(0)
+}
+
+/// Tracks which contexts are alive, so that each one is stopped only once,
+/// either by its owner or by the panic hook.
+pub(crate) struct Tracker {
+    /// The contexts owned by one of the context types.
+    pub(crate) owned: Registry,
+    /// The contexts initialized from Rust that haven't been stopped yet.
+    live: Registry,
+    /// The contexts stopped by the panic hook, whose owner hasn't noticed yet.
+    stopped: Registry,
+}
+
+impl Tracker {
+    pub(crate) const fn new() -> Self {
+        Self {
+            owned: Registry::new(),
+            live: Registry::new(),
+            stopped: Registry::new(),
+        }
+    }
+
+    /// Starts tracking a newly initialized context.
+    ///
+    /// Returns `false` if there were no free slots left, in which case the
+    /// panic hook won't be able to stop it.
+    pub(crate) fn register(&self, addr: usize) -> bool {
+        // the address may have been reused after a panic
+        self.stopped.remove(addr);
+        self.live.contains(addr) || self.live.insert(addr)
+    }
+
+    /// Starts tracking a context owned by one of the context types.
+    ///
+    /// Returns an error if there were no free slots left.
+    pub(crate) fn own(&self, addr: usize, name: &str) -> NcResult<()> {
+        self.register(addr);
+        if self.owned.contains(addr) || self.owned.insert(addr) {
+            Ok(())
+        } else {
+            Err(NcError::with_kind(
+                NcErrorKind::OutOfBounds,
+                &format![
+                    "{}: too many contexts alive, the maximum is {}",
+                    name, REGISTRY_SLOTS
+                ],
+            ))
+        }
+    }
+
+    /// Stops tracking a context, returning `true` if it must be stopped, or
+    /// `false` if it has already been stopped by the panic hook.
+    pub(crate) fn claim(&self, addr: usize) -> bool {
+        self.live.remove(addr) || !self.stopped.remove(addr)
+    }
+
+    /// Returns `true` if the context has been stopped by the panic hook, and
+    /// it hasn't been claimed by its owner yet.
+    pub(crate) fn is_stopped(&self, addr: usize) -> bool {
+        self.stopped.contains(addr)
+    }
+
+    /// Claims and stops all the contexts registered from the current thread.
+    ///
+    /// Returns `true` if any context was stopped.
+    pub(crate) fn stop_current_thread(&self, stop: impl Fn(usize)) -> bool {
+        let mut stopped_any = false;
+        for addr in self.current_thread() {
+            // mark it as stopped before claiming it, so that a concurrent
+            // claim from another thread never finds it in neither registry.
+            self.stopped.insert(addr);
+            if self.live.remove(addr) {
+                stop(addr);
+                stopped_any = true;
+            } else {
+                self.stopped.remove(addr);
+            }
+        }
+        stopped_any
+    }
+}
+
+/// The tracked [`Nc`] contexts.
+pub(crate) static NC_TRACKER: Tracker = Tracker::new();
+
+/// The tracked [`NcDirect`] contexts.
+pub(crate) static NCDIRECT_TRACKER: Tracker = Tracker::new();
+
+/// Returns `true` if the panic hook has stopped the [`Nc`] context at `addr`,
+/// in which case the owned objects that depend on it must not be released.
+///
+/// The objects that don't depend on any context pass 0.
+#[cfg_attr(not(feature = "owned"), allow(dead_code))]
+pub(crate) fn stopped_by_panic(addr: usize) -> bool {
+    addr != 0 && NC_TRACKER.is_stopped(addr)
+}
+
+/// Installs the panic hook, only once. See [`Nc::install_panic_hook`].
+pub(crate) fn install_panic_hook() {
+    static INSTALLED: AtomicBool = AtomicBool::new(false);
+    if INSTALLED.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        // the panic is going to be caught before returning into C
+        if !callback::in_callback() {
+            NC_TRACKER.stop_current_thread(|addr| unsafe {
+                c_api::notcurses_stop(addr as *mut Nc);
+            });
+            NCDIRECT_TRACKER.stop_current_thread(|addr| unsafe {
+                c_api::ncdirect_stop(addr as *mut NcDirect);
+            });
+        }
+        previous(info);
+    }));
+}
+
+/// An owned [`Nc`] context, that is stopped when dropped.
+///
+/// It dereferences to [`Nc`], so all of its methods are available.
+///
+/// The terminal gets restored when the context goes out of scope, including
+/// on early returns with `?` and while unwinding from a panic.
+///
+/// It can be stopped explicitly with [`stop`][NcContext#method.stop], which
+/// consumes it and returns any error. Calling [`Nc.stop()`][Nc#method.stop]
+/// on the dereferenced context returns an error instead of stopping it,
+/// so it can never be stopped twice.
+///
+/// # Example
+///
+/// ```ignore
+/// use libnotcurses_sys::*;
+///
+/// fn main() -> NcResult<()> {
+///     let nc = NcContext::new_cli()?;
+///     nc.stdplane().putstr("hello world")?;
+///     nc.render()?;
+///     Ok(())
+/// } // the context is stopped here
+/// ```
+#[derive(Debug)]
+pub struct NcContext {
+    nc: NonNull<Nc>,
+}
+
+/// # `NcContext` constructors & destructors
+impl NcContext {
+    /// New owned notcurses context (without banners).
+    pub fn new() -> NcResult<Self> {
+        Self::with_flags(NcOptions::SUPPRESS_BANNERS)
+    }
+
+    /// New owned notcurses context in CLI mode.
+    ///
+    /// See [`Nc::new_cli`] for the list of flags.
+    pub fn new_cli() -> NcResult<Self> {
+        Self::with_flags(
+            NcOptions::SUPPRESS_BANNERS
+                | NcOptions::NO_ALTERNATE_SCREEN
+                | NcOptions::NO_CLEAR_BITMAPS
+                | NcOptions::PRESERVE_CURSOR,
+        )
+    }
+
+    /// New owned notcurses context, with banners.
+    pub fn with_banners() -> NcResult<Self> {
+        Self::with_flags(0)
+    }
+
+    /// New owned notcurses context, expects `NcOptions::*` flags.
+    pub fn with_flags(flags: u64) -> NcResult<Self> {
+        Self::with_options(NcOptions::with_flags(flags))
+    }
+
+    /// New owned notcurses context, expects [`NcLogLevel`] and flags.
+    pub fn with_debug(loglevel: NcLogLevel, flags: u64) -> NcResult<Self> {
+        Self::with_options(NcOptions::with_all_options(loglevel, 0, 0, 0, 0, flags))
+    }
+
+    /// New owned notcurses context, expects [`NcOptions`].
+    ///
+    /// *C style function: [notcurses_init()][c_api::notcurses_init].*
+    pub fn with_options(options: NcOptions) -> NcResult<Self> {
+        let nc = Nc::with_options(options)?;
+        unsafe { Self::from_nc(nc) }.map_err(|e| {
+            let _ = nc.stop();
+            e
+        })
+    }
+
+    /// Takes ownership of an already initialized [`Nc`] context.
+    ///
+    /// Returns an error if there are already too many contexts alive, in
+    /// which case `nc` is left untouched.
+    ///
+    /// # Safety
+    ///
+    /// `nc` must not have been stopped, and must not be stopped afterwards
+    /// by any other means than this context.
+    pub unsafe fn from_nc(nc: &mut Nc) -> NcResult<Self> {
+        NC_TRACKER.own(nc as *mut Nc as usize, "NcContext::from_nc()")?;
+        Ok(Self {
+            nc: NonNull::from(nc),
+        })
+    }
+
+    /// Releases the ownership of the context, without stopping it.
+    ///
+    /// The returned [`Nc`] will have to be stopped manually.
+    pub fn into_nc<'a>(self) -> &'a mut Nc {
+        let nc = self.nc;
+        core::mem::forget(self);
+        NC_TRACKER.owned.remove(nc.as_ptr() as usize);
+        unsafe { &mut *nc.as_ptr() }
+    }
+
+    /// Stops the context, consuming it.
+    ///
+    /// *C style function: [notcurses_stop()][c_api::notcurses_stop].*
+    pub fn stop(self) -> NcResult<()> {
+        let nc = self.into_nc();
+        if !NC_TRACKER.claim(nc as *mut Nc as usize) {
+            return Ok(());
+        }
+        error![@call "notcurses_stop", unsafe { c_api::notcurses_stop(nc) }, "NcContext.stop()"]
+    }
+}
+
+/// # `NcContext` methods
+///
+/// These methods only need a shared reference to the context, so that they
+/// can be used while there are [`NcPlaneHandle`]s alive.
+impl NcContext {
+    /// Returns a handle to the standard plane.
+    ///
+    /// *C style function: [notcurses_stdplane()][c_api::notcurses_stdplane].*
+    pub fn stdplane(&self) -> NcPlaneHandle<'_> {
+        NcPlaneHandle::stdplane(self)
+    }
+
+    /// Renders and rasterizes the standard pile in one shot. Blocking call.
+    ///
+    /// *C style function: [notcurses_render()][c_api::notcurses_render].*
+    pub fn render(&self) -> NcResult<()> {
+        error![
+            @call "notcurses_render", unsafe { c_api::notcurses_render(self.nc.as_ptr()) },
+            "NcContext.render()"
+        ]
+    }
+
+    /// Returns a [`char`] representing a single unicode point.
+    ///
+    /// See [`Nc.get()`][Nc#method.get].
+    ///
+    /// *C style function: [notcurses_get()][c_api::notcurses_get].*
+    pub fn get(&self, time: Option<NcTime>, input: Option<&mut NcInput>) -> NcResult<char> {
+        Nc::get(unsafe { &mut *self.nc.as_ptr() }, time, input)
+    }
+
+    /// Reads input blocking until an event is processed or a signal is received.
+    ///
+    /// See [`Nc.getc_blocking()`][Nc#method.getc_blocking].
+    ///
+    /// *C style function: [notcurses_getc_blocking()][c_api::notcurses_getc_blocking].*
+    pub fn getc_blocking(&self, input: Option<&mut NcInput>) -> NcResult<char> {
+        Nc::getc_blocking(unsafe { &mut *self.nc.as_ptr() }, input)
+    }
+
+    /// Reads input without blocking.
+    ///
+    /// See [`Nc.getc_nblock()`][Nc#method.getc_nblock].
+    ///
+    /// *C style function: [notcurses_getc_nblock()][c_api::notcurses_getc_nblock].*
+    pub fn getc_nblock(&self, input: Option<&mut NcInput>) -> NcResult<char> {
+        Nc::getc_nblock(unsafe { &mut *self.nc.as_ptr() }, input)
+    }
+}
+
+impl Deref for NcContext {
+    type Target = Nc;
+    fn deref(&self) -> &Nc {
+        unsafe { self.nc.as_ref() }
+    }
+}
+
+impl DerefMut for NcContext {
+    fn deref_mut(&mut self) -> &mut Nc {
+        unsafe { self.nc.as_mut() }
+    }
+}
+
+impl Drop for NcContext {
+    fn drop(&mut self) {
+        let addr = self.nc.as_ptr() as usize;
+        NC_TRACKER.owned.remove(addr);
+        if NC_TRACKER.claim(addr) {
+            let _ = unsafe { c_api::notcurses_stop(self.nc.as_ptr()) };
+        }
+    }
+}
+
+/// An owned [`NcDirect`] context, that is stopped when dropped.
+///
+/// It dereferences to [`NcDirect`], so all of its methods are available.
+///
+/// The terminal gets restored when the context goes out of scope, including
+/// on early returns with `?` and while unwinding from a panic.
+///
+/// It can be stopped explicitly with [`stop`][NcDirectContext#method.stop],
+/// which consumes it and returns any error. Calling
+/// [`NcDirect.stop()`][NcDirect#method.stop] on the dereferenced context
+/// returns an error instead of stopping it, so it can never be stopped twice.
+#[derive(Debug)]
+pub struct NcDirectContext {
+    ncd: NonNull<NcDirect>,
+}
+
+/// # `NcDirectContext` constructors & destructors
+impl NcDirectContext {
+    /// New owned `NcDirect` context with the default options.
+    pub fn new() -> NcResult<Self> {
+        Self::with_flags(0)
+    }
+
+    /// New owned `NcDirect` context with optional flags.
+    ///
+    /// *C style function: [ncdirect_init()][c_api::ncdirect_init].*
+    pub fn with_flags(flags: NcDirectFlags) -> NcResult<Self> {
+        let ncd = NcDirect::with_flags(flags)?;
+        unsafe { Self::from_ncdirect(ncd) }.map_err(|e| {
+            let _ = ncd.stop();
+            e
+        })
+    }
+
+    /// Takes ownership of an already initialized [`NcDirect`] context.
+    ///
+    /// Returns an error if there are already too many contexts alive, in
+    /// which case `ncd` is left untouched.
+    ///
+    /// # Safety
+    ///
+    /// `ncd` must not have been stopped, and must not be stopped afterwards
+    /// by any other means than this context.
+    pub unsafe fn from_ncdirect(ncd: &mut NcDirect) -> NcResult<Self> {
+        NCDIRECT_TRACKER.own(
+            ncd as *mut NcDirect as usize,
+            "NcDirectContext::from_ncdirect()",
+        )?;
+        Ok(Self {
+            ncd: NonNull::from(ncd),
+        })
+    }
+
+    /// Releases the ownership of the context, without stopping it.
+    ///
+    /// The returned [`NcDirect`] will have to be stopped manually.
+    pub fn into_ncdirect<'a>(self) -> &'a mut NcDirect {
+        let ncd = self.ncd;
+        core::mem::forget(self);
+        NCDIRECT_TRACKER.owned.remove(ncd.as_ptr() as usize);
+        unsafe { &mut *ncd.as_ptr() }
+    }
+
+    /// Stops the context, consuming it.
+    ///
+    /// *C style function: [ncdirect_stop()][c_api::ncdirect_stop].*
+    pub fn stop(self) -> NcResult<()> {
+        let ncd = self.into_ncdirect();
+        if !NCDIRECT_TRACKER.claim(ncd as *mut NcDirect as usize) {
+            return Ok(());
+        }
+        error![
+            @call "ncdirect_stop", unsafe { c_api::ncdirect_stop(ncd) },
+            "NcDirectContext.stop()"
+        ]
+    }
+}
+
+impl Deref for NcDirectContext {
+    type Target = NcDirect;
+    fn deref(&self) -> &NcDirect {
+        unsafe { self.ncd.as_ref() }
+    }
+}
+
+impl DerefMut for NcDirectContext {
+    fn deref_mut(&mut self) -> &mut NcDirect {
+        unsafe { self.ncd.as_mut() }
+    }
+}
+
+impl Drop for NcDirectContext {
+    fn drop(&mut self) {
+        let addr = self.ncd.as_ptr() as usize;
+        NCDIRECT_TRACKER.owned.remove(addr);
+        if NCDIRECT_TRACKER.claim(addr) {
+            let _ = unsafe { c_api::ncdirect_stop(self.ncd.as_ptr()) };
+        }
+    }
+}
@@ -0,0 +1,4 @@
+//! `NcContext` & `NcDirectContext` tests.
+
+#[cfg(test)]
+mod tracker;
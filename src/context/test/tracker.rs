@@ -0,0 +1,90 @@
+//! Test the registry and the tracker of the contexts.
+
+use std::cell::RefCell;
+
+use serial_test::serial;
+
+use crate::context::{Registry, Tracker, REGISTRY_SLOTS};
+use crate::NcErrorKind;
+
+#[test]
+#[serial]
+fn registry_insert_remove() {
+    let registry = Registry::new();
+    assert![!registry.contains(1)];
+    assert![registry.insert(1)];
+    assert![registry.contains(1)];
+    assert_eq![vec![1], registry.current_thread().collect::<Vec<_>>()];
+
+    assert![registry.remove(1)];
+    assert![!registry.remove(1)];
+    assert![!registry.contains(1)];
+    assert_eq![0, registry.current_thread().count()];
+}
+
+#[test]
+#[serial]
+fn registry_full() {
+    let registry = Registry::new();
+    for addr in 1..=REGISTRY_SLOTS {
+        assert![registry.insert(addr)];
+    }
+    assert![!registry.insert(REGISTRY_SLOTS + 1)];
+    assert![!registry.contains(REGISTRY_SLOTS + 1)];
+
+    // a freed slot can be reused
+    assert![registry.remove(3)];
+    assert![registry.insert(REGISTRY_SLOTS + 1)];
+}
+
+#[test]
+#[serial]
+fn registry_other_thread() {
+    static REGISTRY: Registry = Registry::new();
+    assert![REGISTRY.insert(1)];
+    std::thread::spawn(|| {
+        assert![REGISTRY.insert(2)];
+        assert_eq![vec![2], REGISTRY.current_thread().collect::<Vec<_>>()];
+    })
+    .join()
+    .unwrap();
+    assert_eq![vec![1], REGISTRY.current_thread().collect::<Vec<_>>()];
+}
+
+#[test]
+#[serial]
+fn tracker_claim_once() {
+    let tracker = Tracker::new();
+    assert![tracker.register(1)];
+    assert![tracker.claim(1)];
+
+    // stopped by the panic hook, then claimed by its owner
+    assert![tracker.register(2)];
+    let stopped = RefCell::new(vec![]);
+    assert![tracker.stop_current_thread(|addr| stopped.borrow_mut().push(addr))];
+    assert_eq![vec![2], *stopped.borrow()];
+    assert![tracker.is_stopped(2)];
+    assert![!tracker.is_stopped(1)];
+    assert![!tracker.claim(2)];
+    assert![!tracker.is_stopped(2)];
+    assert![!tracker.stop_current_thread(|_| panic!["stopped twice"])];
+
+    // an address reused after being stopped by the panic hook
+    assert![tracker.register(3)];
+    tracker.stop_current_thread(|_| ());
+    assert![tracker.register(3)];
+    assert![tracker.claim(3)];
+}
+
+#[test]
+#[serial]
+fn tracker_own_full() {
+    let tracker = Tracker::new();
+    for addr in 1..=REGISTRY_SLOTS {
+        assert![tracker.own(addr, "test").is_ok()];
+    }
+    // owning it again is fine
+    assert![tracker.own(1, "test").is_ok()];
+    let err = tracker.own(REGISTRY_SLOTS + 1, "test").unwrap_err();
+    assert_eq![NcErrorKind::OutOfBounds, err.kind()];
+}
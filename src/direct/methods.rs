@@ -1,10 +1,11 @@
 //! `NcDirect` methods and associated functions.
 
 use core::ptr::{null, null_mut};
+use std::os::raw::c_int;
 
 use crate::{
-    c_api, cstring, error, error_ref_mut, rstring_free, NcAlign, NcBlitter, NcCapabilities,
-    NcChannels, NcComponent, NcDim, NcDirect, NcDirectFlags, NcError, NcInput, NcOffset,
+    c_api, error, error_ref_mut, string, NcAlign, NcBlitter, NcCapabilities, NcChannels,
+    NcComponent, NcDim, NcDirect, NcDirectFlags, NcError, NcErrorKind, NcEvent, NcInput, NcOffset,
     NcPaletteIndex, NcPlane, NcResult, NcRgb, NcScale, NcStyle, NcTime,
 };
 
@@ -29,14 +30,31 @@ impl NcDirect {
     /// *C style function: [ncdirect_init()][c_api::ncdirect_init].*
     pub fn with_flags<'a>(flags: NcDirectFlags) -> NcResult<&'a mut NcDirect> {
         let res = unsafe { c_api::ncdirect_init(null(), null_mut(), flags) };
-        error_ref_mut![res, "Initializing NcDirect"]
+        if !res.is_null() {
+            crate::context::NCDIRECT_TRACKER.register(res as usize);
+        }
+        error_ref_mut![@call "ncdirect_init", res, "Initializing NcDirect"]
     }
 
     /// Releases this NcDirect and any associated resources.
     ///
+    /// Returns an error without doing anything if the context is owned by an
+    /// [`NcDirectContext`][crate::NcDirectContext], which stops it when dropped.
+    ///
     /// *C style function: [ncdirect_stop()][c_api::ncdirect_stop].*
     pub fn stop(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncdirect_stop(self) }, "NcDirect.stop()"]
+        let tracker = &crate::context::NCDIRECT_TRACKER;
+        if tracker.owned.contains(self as *mut NcDirect as usize) {
+            return Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "NcDirect.stop(): owned by an NcDirectContext, use NcDirectContext.stop() instead",
+            ));
+        }
+        if !tracker.claim(self as *mut NcDirect as usize) {
+            // already stopped by the panic hook
+            return Ok(());
+        }
+        error![@call "ncdirect_stop", unsafe { c_api::ncdirect_stop(self) }, "NcDirect.stop()"]
     }
 }
 
@@ -46,14 +64,14 @@ impl NcDirect {
     ///
     /// *C style function: [ncdirect_clear()][c_api::ncdirect_clear].*
     pub fn clear(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncdirect_clear(self) }, "NcDirect.clear()"]
+        error![@call "ncdirect_clear", unsafe { c_api::ncdirect_clear(self) }, "NcDirect.clear()"]
     }
 
     /// Forces a flush.
     ///
     /// *C style function: [ncdirect_flush()][c_api::ncdirect_flush].*
     pub fn flush(&self) -> NcResult<()> {
-        error![unsafe { c_api::ncdirect_flush(self) }, "NcDirect.clear()"]
+        error![@call "ncdirect_flush", unsafe { c_api::ncdirect_flush(self) }, "NcDirect.clear()"]
     }
 
     /// Takes the result of [`render_frame`][NcDirect#method.render_frame]
@@ -62,7 +80,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_raster_frame()][c_api::ncdirect_raster_frame].*
     pub fn raster_frame(&mut self, frame: &mut NcPlane, align: NcAlign) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_raster_frame(self, frame, align) },
+            @call "ncdirect_raster_frame", unsafe { c_api::ncdirect_raster_frame(self, frame, align) },
             "NcDirect.raster_frame()"
         ]
     }
@@ -79,26 +97,19 @@ impl NcDirect {
     /// are used for scaling; the terminal's geometry is otherwise used.
     ///
     /// *C style function: [ncdirect_render_frame()][c_api::ncdirect_render_frame].*
-    pub fn render_frame<'a>(
+    pub fn render_frame(
         &mut self,
         filename: &str,
         blitter: NcBlitter,
         scale: NcScale,
         max_y: NcDim,
         max_x: NcDim,
-    ) -> NcResult<&'a mut NcPlane> {
-        let res = unsafe {
-            c_api::ncdirect_render_frame(
-                self,
-                cstring![filename],
-                blitter,
-                scale,
-                max_y as i32,
-                max_x as i32,
-            )
-        };
+    ) -> NcResult<&mut NcPlane> {
+        let res = string::with_cstr(filename, |filename| unsafe {
+            c_api::ncdirect_render_frame(self, filename, blitter, scale, max_y as i32, max_x as i32)
+        })?;
         error_ref_mut![
-            res,
+            @call "ncdirect_render_frame", res,
             &format!(
                 "NcDirect.render_frame({:?}, {:?}, {:?})",
                 filename, blitter, scale
@@ -124,9 +135,9 @@ impl NcDirect {
         scale: NcScale,
     ) -> NcResult<()> {
         error![
-            unsafe {
-                c_api::ncdirect_render_image(self, cstring![filename], align, blitter, scale)
-            },
+            @call "ncdirect_render_image", string::with_cstr(filename, |filename| unsafe {
+                c_api::ncdirect_render_image(self, filename, align, blitter, scale)
+            })?,
             &format!(
                 "NcDirect.render_image({:?}, {:?}, {:?}, {:?})",
                 filename, align, blitter, scale
@@ -142,7 +153,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_set_fg_palindex()][c_api::ncdirect_set_fg_palindex].*
     pub fn set_fg_palindex(&mut self, index: NcPaletteIndex) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_set_fg_palindex(self, index as i32) },
+            @call "ncdirect_set_fg_palindex", unsafe { c_api::ncdirect_set_fg_palindex(self, index as i32) },
             &format!("NcDirect.set_fg_palindex({})", index)
         ]
     }
@@ -152,7 +163,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_set_bg_palindex()][c_api::ncdirect_set_bg_palindex].*
     pub fn set_bg_palindex(&mut self, index: NcPaletteIndex) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_set_bg_palindex(self, index as i32) },
+            @call "ncdirect_set_bg_palindex", unsafe { c_api::ncdirect_set_bg_palindex(self, index as i32) },
             &format!("NcDirect.set_fg_palindex({})", index)
         ]
     }
@@ -167,10 +178,10 @@ impl NcDirect {
     pub fn palette_size(&self) -> NcResult<u32> {
         let res = unsafe { c_api::ncdirect_palette_size(self) };
         if res == 1 {
-            return Err(NcError::with_msg(
-                1,
-                "No color support ← NcDirect.palette_size()",
-            ));
+            return Err(
+                NcError::with_msg(1, "No color support ← NcDirect.palette_size()")
+                    .of_kind(NcErrorKind::Unsupported),
+            );
         }
         Ok(res)
     }
@@ -180,7 +191,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_set_fg_rgb()][c_api::ncdirect_set_fg_rgb].*
     pub fn set_fg_rgb(&mut self, rgb: NcRgb) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_set_fg_rgb(self, rgb) },
+            @call "ncdirect_set_fg_rgb", unsafe { c_api::ncdirect_set_fg_rgb(self, rgb) },
             &format!("NcDirect.set_fg_rgb({})", rgb)
         ]
     }
@@ -190,7 +201,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_set_bg_rgb()][c_api::ncdirect_set_bg_rgb].*
     pub fn set_bg_rgb(&mut self, rgb: NcRgb) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_set_bg_rgb(self, rgb) },
+            @call "ncdirect_set_bg_rgb", unsafe { c_api::ncdirect_set_bg_rgb(self, rgb) },
             &format!("NcDirect.set_bg_rgb({})", rgb)
         ]
     }
@@ -205,7 +216,7 @@ impl NcDirect {
         blue: NcComponent,
     ) -> NcResult<()> {
         error![
-            c_api::ncdirect_set_fg_rgb8(self, red, green, blue),
+            @call "ncdirect_set_fg_rgb8", c_api::ncdirect_set_fg_rgb8(self, red, green, blue),
             &format!("NcDirect.set_fg_rgb8({}, {}, {})", red, green, blue)
         ]
     }
@@ -220,7 +231,7 @@ impl NcDirect {
         blue: NcComponent,
     ) -> NcResult<()> {
         error![
-            c_api::ncdirect_set_bg_rgb8(self, red, green, blue),
+            @call "ncdirect_set_bg_rgb8", c_api::ncdirect_set_bg_rgb8(self, red, green, blue),
             &format!("NcDirect.set_bg_rgb8({}, {}, {})", red, green, blue)
         ]
     }
@@ -237,7 +248,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_off_styles()][c_api::ncdirect_off_styles].*
     pub fn styles_off(&mut self, stylebits: NcStyle) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_off_styles(self, stylebits.into()) },
+            @call "ncdirect_off_styles", unsafe { c_api::ncdirect_off_styles(self, stylebits.into()) },
             &format!("NcDirect.styles_off({:0X})", stylebits)
         ]
     }
@@ -247,7 +258,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_on_styles()][c_api::ncdirect_on_styles].*
     pub fn styles_on(&mut self, stylebits: NcStyle) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_on_styles(self, stylebits.into()) },
+            @call "ncdirect_on_styles", unsafe { c_api::ncdirect_on_styles(self, stylebits.into()) },
             &format!("NcDirect.styles_on({:0X})", stylebits)
         ]
     }
@@ -257,7 +268,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_set_styles()][c_api::ncdirect_set_styles].*
     pub fn styles_set(&mut self, stylebits: NcStyle) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_set_styles(self, stylebits.into()) },
+            @call "ncdirect_set_styles", unsafe { c_api::ncdirect_set_styles(self, stylebits.into()) },
             &format!("NcDirect.styles_set({:0X})", stylebits)
         ]
     }
@@ -279,7 +290,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_set_fg_default()][c_api::ncdirect_set_fg_default].*
     pub fn set_fg_default(&mut self) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_set_fg_default(self) },
+            @call "ncdirect_set_fg_default", unsafe { c_api::ncdirect_set_fg_default(self) },
             "NcDirect.set_fg_default()"
         ]
     }
@@ -289,7 +300,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_set_bg_default()][c_api::ncdirect_set_bg_default].*
     pub fn set_bg_default(&mut self) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_set_bg_default(self) },
+            @call "ncdirect_set_bg_default", unsafe { c_api::ncdirect_set_bg_default(self) },
             "NcDirect.set_bg_default()"
         ]
     }
@@ -418,7 +429,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_cursor_disable()][c_api::ncdirect_cursor_disable].*
     pub fn cursor_disable(&mut self) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_cursor_disable(self) },
+            @call "ncdirect_cursor_disable", unsafe { c_api::ncdirect_cursor_disable(self) },
             "NcDirect.cursor_disable()"
         ]
     }
@@ -428,7 +439,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_cursor_enable()][c_api::ncdirect_cursor_enable].*
     pub fn cursor_enable(&mut self) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_cursor_enable(self) },
+            @call "ncdirect_cursor_enable", unsafe { c_api::ncdirect_cursor_enable(self) },
             "NcDirect.cursor_enable()"
         ]
     }
@@ -438,7 +449,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_cursor_down()][c_api::ncdirect_cursor_down].*
     pub fn cursor_down(&mut self, rows: NcOffset) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_cursor_down(self, rows as i32) },
+            @call "ncdirect_cursor_down", unsafe { c_api::ncdirect_cursor_down(self, rows as i32) },
             &format!("NcDirect.cursor_down({})", rows)
         ]
     }
@@ -448,7 +459,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_cursor_left()][c_api::ncdirect_cursor_left].*
     pub fn cursor_left(&mut self, cols: NcOffset) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_cursor_left(self, cols as i32) },
+            @call "ncdirect_cursor_left", unsafe { c_api::ncdirect_cursor_left(self, cols as i32) },
             &format!("NcDirect.cursor_left({})", cols)
         ]
     }
@@ -458,7 +469,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_cursor_right()][c_api::ncdirect_cursor_right].*
     pub fn cursor_right(&mut self, cols: NcOffset) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_cursor_right(self, cols as i32) },
+            @call "ncdirect_cursor_right", unsafe { c_api::ncdirect_cursor_right(self, cols as i32) },
             &format!("NcDirect.cursor_right({})", cols)
         ]
     }
@@ -468,7 +479,7 @@ impl NcDirect {
     /// *C style function: [ncdirect_cursor_up()][c_api::ncdirect_cursor_up].*
     pub fn cursor_up(&mut self, rows: NcOffset) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_cursor_up(self, rows as i32) },
+            @call "ncdirect_cursor_up", unsafe { c_api::ncdirect_cursor_up(self, rows as i32) },
             &format!("NcDirect.cursor_up({})", rows)
         ]
     }
@@ -477,21 +488,21 @@ impl NcDirect {
     ///
     /// *C style function: [ncdirect_cursor_move_yx()][c_api::ncdirect_cursor_move_yx].*
     pub fn cursor_set_yx(&mut self, y: NcDim, x: NcDim) -> NcResult<()> {
-        error![unsafe { c_api::ncdirect_cursor_move_yx(self, y as i32, x as i32) }]
+        error![@call "ncdirect_cursor_move_yx", unsafe { c_api::ncdirect_cursor_move_yx(self, y as i32, x as i32) }, "NcDirect.cursor_set_yx()"]
     }
 
     /// Sets the cursor to the specified row `y`.
     ///
     /// *(No equivalent C style function)*
     pub fn cursor_set_y(&mut self, y: NcDim) -> NcResult<()> {
-        error![unsafe { c_api::ncdirect_cursor_move_yx(self, y as i32, -1) }]
+        error![@call "ncdirect_cursor_move_yx", unsafe { c_api::ncdirect_cursor_move_yx(self, y as i32, -1) }, "NcDirect.cursor_set_y()"]
     }
 
     /// Sets the cursor to the specified column `x`.
     ///
     /// *(No equivalent C style function)*
     pub fn cursor_set_x(&mut self, x: NcDim) -> NcResult<()> {
-        error![unsafe { c_api::ncdirect_cursor_move_yx(self, -1, x as i32) }]
+        error![@call "ncdirect_cursor_move_yx", unsafe { c_api::ncdirect_cursor_move_yx(self, -1, x as i32) }, "NcDirect.cursor_set_x()"]
     }
 
     /// Gets the cursor (y, x) position, when supported.
@@ -504,8 +515,7 @@ impl NcDirect {
     pub fn cursor_yx(&mut self) -> NcResult<(NcDim, NcDim)> {
         let (mut y, mut x) = (0, 0);
         error![
-            unsafe { c_api::ncdirect_cursor_yx(self, &mut y, &mut x) },
-            "",
+            @call "ncdirect_cursor_yx", unsafe { c_api::ncdirect_cursor_yx(self, &mut y, &mut x) }, "NcDirect.cursor_yx()",
             (y as NcDim, x as NcDim)
         ]
     }
@@ -516,7 +526,7 @@ impl NcDirect {
     ///
     /// *C style function: [ncdirect_cursor_push()][c_api::ncdirect_cursor_push].*
     pub fn cursor_push(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncdirect_cursor_push(self) }]
+        error![@call "ncdirect_cursor_push", unsafe { c_api::ncdirect_cursor_push(self) }, "NcDirect.cursor_push()"]
     }
 
     /// Pops the cursor location from the terminal's stack.
@@ -525,7 +535,7 @@ impl NcDirect {
     ///
     /// *C style function: [ncdirect_cursor_pop()][c_api::ncdirect_cursor_pop].*
     pub fn cursor_pop(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncdirect_cursor_pop(self) }]
+        error![@call "ncdirect_cursor_pop", unsafe { c_api::ncdirect_cursor_pop(self) }, "NcDirect.cursor_pop()"]
     }
 
     /// Gets the current number of rows.
@@ -555,7 +565,7 @@ impl NcDirect {
     ///
     /// *C style function: [ncdirect_detected_terminal()][c_api::ncdirect_detected_terminal].*
     pub fn detected_terminal(&self) -> String {
-        rstring_free![c_api::ncdirect_detected_terminal(self)]
+        unsafe { string::rstring_free_lossy(c_api::ncdirect_detected_terminal(self)) }
     }
 }
 
@@ -596,6 +606,29 @@ impl NcDirect {
             .ok_or_else(|| NcError::with_msg(res as i32, &format!["Nc.get(time: {:?})", time]))
     }
 
+    /// Returns the next input [`NcEvent`], or `None` on timeout.
+    ///
+    /// Provide a None `time` to block at length, a `time` of 0 for non-blocking
+    /// operation, and otherwise a timespec to bound blocking.
+    ///
+    /// *C style function: [ncdirect_get()][c_api::ncdirect_get].*
+    pub fn get_event(&mut self, time: Option<NcTime>) -> NcResult<Option<NcEvent>> {
+        let mut input = NcInput::new_empty();
+        let key = self.get(time, Some(&mut input))?;
+        if key == '\0' {
+            return Ok(None);
+        }
+        Ok(Some(NcEvent::from((key, input))))
+    }
+
+    /// Returns the next input [`NcEvent`] without blocking,
+    /// or `None` if no event is ready.
+    ///
+    /// *C style function: [ncdirect_get()][c_api::ncdirect_get].*
+    pub fn get_event_nblock(&mut self) -> NcResult<Option<NcEvent>> {
+        self.get_event(Some(NcTime::new(0, 0)))
+    }
+
     /// Reads input blocking until an event is processed or a signal is received.
     ///
     /// Will optionally write the event details in `input`.
@@ -631,8 +664,9 @@ impl NcDirect {
     /// with stdin (but it might be!).
     ///
     /// *C style function: [ncdirect_inputready_fd()][c_api::ncdirect_inputready_fd].*
-    pub fn inputready_fd(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncdirect_inputready_fd(self) }]
+    pub fn inputready_fd(&mut self) -> NcResult<c_int> {
+        let res = unsafe { c_api::ncdirect_inputready_fd(self) };
+        error![@call "ncdirect_inputready_fd", res, "NcDirect.inputready_fd()", res]
     }
 
     /// Outputs the `string` according to the `channels`, and
@@ -647,7 +681,9 @@ impl NcDirect {
     /// *C style function: [ncdirect_putstr()][c_api::ncdirect_putstr].*
     pub fn putstr(&mut self, channels: NcChannels, string: &str) -> NcResult<()> {
         error![
-            unsafe { c_api::ncdirect_putstr(self, channels, cstring![string]) },
+            @call "ncdirect_putstr", string::with_cstr(string, |s| unsafe {
+                c_api::ncdirect_putstr(self, channels, s)
+            })?,
             &format!("NcDirect.putstr({:0X}, {:?})", channels, string)
         ]
     }
@@ -664,14 +700,15 @@ impl NcDirect {
     //
     // FIXME: memory leak still reported by valgrind
     pub fn readline(&mut self, prompt: &str) -> NcResult<String> {
-        let res = unsafe { c_api::ncdirect_readline(self, cstring![prompt]) };
+        let res = string::with_cstr(prompt, |p| unsafe { c_api::ncdirect_readline(self, p) })?;
         if !res.is_null() {
-            return Ok(rstring_free![res]);
+            unsafe { string::rstring_free(res) }
         } else {
-            Err(NcError::with_msg(
-                c_api::NCRESULT_ERR,
+            Err(NcError::with_kind(
+                NcErrorKind::NullPointer,
                 &format!["NcDirect.readline({})", prompt],
-            ))
+            )
+            .calling("ncdirect_readline"))
         }
     }
 
@@ -698,7 +735,7 @@ impl NcDirect {
         ctlword: u32,
     ) -> NcResult<()> {
         error![
-            unsafe {
+            @call "ncdirect_box", unsafe {
                 let wchars = core::mem::transmute(wchars);
                 c_api::ncdirect_box(
                     self,
@@ -732,9 +769,9 @@ impl NcDirect {
         x_len: NcDim,
         ctlword: u32,
     ) -> NcResult<()> {
-        error![unsafe {
+        error![@call "ncdirect_double_box", unsafe {
             c_api::ncdirect_double_box(self, ul, ur, ll, lr, y_len as i32, x_len as i32, ctlword)
-        }]
+        }, "NcDirect.double_box()"]
     }
 
     /// NcDirect.[box()][NcDirect#method.box] with the rounded box-drawing characters.
@@ -750,9 +787,9 @@ impl NcDirect {
         x_len: NcDim,
         ctlword: u32,
     ) -> NcResult<()> {
-        error![unsafe {
+        error![@call "ncdirect_rounded_box", unsafe {
             c_api::ncdirect_rounded_box(self, ul, ur, ll, lr, y_len as i32, x_len as i32, ctlword)
-        }]
+        }, "NcDirect.rounded_box()"]
     }
 
     /// Draws horizontal lines using the specified [NcChannels]s, interpolating
@@ -774,7 +811,8 @@ impl NcDirect {
         h1: NcChannels,
         h2: NcChannels,
     ) -> NcResult<()> {
-        error![c_api::ncdirect_hline_interp(self, egc, len, h1, h2)]
+        string::check_nul(egc)?;
+        error![@call "ncdirect_hline_interp", c_api::ncdirect_hline_interp(self, egc, len, h1, h2), "NcDirect.hline_interp()"]
     }
 
     /// Draws horizontal lines using the specified [NcChannels]s, interpolating
@@ -796,6 +834,7 @@ impl NcDirect {
         h1: NcChannels,
         h2: NcChannels,
     ) -> NcResult<()> {
-        error![c_api::ncdirect_vline_interp(self, egc, len, h1, h2)]
+        string::check_nul(egc)?;
+        error![@call "ncdirect_vline_interp", c_api::ncdirect_vline_interp(self, egc, len, h1, h2), "NcDirect.vline_interp()"]
     }
 }
@@ -3,8 +3,8 @@
 use core::ptr::{null, null_mut};
 
 use crate::{
-    c_api, cstring, NcCapabilities, NcChannels, NcComponent, NcDim, NcDirect, NcInput, NcIntResult,
-    NcRgb, NcTime,
+    c_api, NcCapabilities, NcChannels, NcComponent, NcDim, NcDirect, NcInput, NcIntResult, NcRgb,
+    NcTime,
 };
 
 /// Can we directly specify RGB values per cell, or only use palettes?
@@ -150,12 +150,10 @@ pub fn ncdirect_hline_interp(
     h1: NcChannels,
     h2: NcChannels,
 ) -> NcIntResult {
-    #[cfg(any(target_arch = "armv7l", target_arch = "i686"))]
-    let egc_ptr = cstring![egc] as *const i8;
-    #[cfg(not(any(target_arch = "armv7l", target_arch = "i686")))]
-    let egc_ptr = cstring![egc];
-
-    unsafe { crate::bindings::ffi::ncdirect_hline_interp(ncd, egc_ptr, len as i32, h1, h2) }
+    crate::string::with_cstr(egc, |egc| unsafe {
+        crate::bindings::ffi::ncdirect_hline_interp(ncd, egc, len as i32, h1, h2)
+    })
+    .unwrap_or(c_api::NCRESULT_ERR)
 }
 
 /// Draws horizontal lines using the specified [NcChannels]s, interpolating
@@ -177,10 +175,8 @@ pub fn ncdirect_vline_interp(
     h1: NcChannels,
     h2: NcChannels,
 ) -> NcIntResult {
-    #[cfg(any(target_arch = "armv7l", target_arch = "i686"))]
-    let egc_ptr = cstring![egc] as *const i8;
-    #[cfg(not(any(target_arch = "armv7l", target_arch = "i686")))]
-    let egc_ptr = cstring![egc];
-
-    unsafe { crate::bindings::ffi::ncdirect_vline_interp(ncd, egc_ptr, len as i32, h1, h2) }
+    crate::string::with_cstr(egc, |egc| unsafe {
+        crate::bindings::ffi::ncdirect_vline_interp(ncd, egc, len as i32, h1, h2)
+    })
+    .unwrap_or(c_api::NCRESULT_ERR)
 }
@@ -0,0 +1,230 @@
+//! Error handling with `Error`, `NcResult` & `NcIntResult` for error handling
+
+use std::{error, fmt, io, sync::Arc};
+
+#[cfg(test)]
+mod test;
+
+/// The [`i32`] value used to return errors by the underlying C API.
+///
+/// A value < 0 means error, (usually -1).
+///
+/// # Defined constants:
+pub type NcIntResult = i32;
+
+crate::impl_api![
+    NcIntResult,
+    NcIntResultApi,
+    /// OK value, for the functions that return [`NcIntResult`].
+    const OK: i32 = 0;,
+    /// ERROR value, for the functions that return an [`NcIntResult`].
+    const ERR: i32 = -1;,
+    /// MAX value, for the functions that return [`NcIntResult`].
+    const MAX: i32 = i32::MAX;
+];
+
+pub(crate) mod constants {
+    #[allow(unused_imports)]
+    use crate::NcIntResult;
+
+    /// OK value, for the functions that return [`NcIntResult`].
+    pub const NCRESULT_OK: i32 = 0;
+
+    /// ERROR value, for the functions that return an [`NcIntResult`].
+    pub const NCRESULT_ERR: i32 = -1;
+
+    /// MAX value, for the functions that return [`NcIntResult`].
+    pub const NCRESULT_MAX: i32 = i32::MAX;
+}
+
+/// The kind of an [`NcError`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NcErrorKind {
+    /// A C function returned an error value, without further details.
+    Other,
+    /// A C function returned a null pointer.
+    NullPointer,
+    /// An argument was not valid.
+    InvalidArgument,
+    /// A coordinate or an index was out of bounds.
+    OutOfBounds,
+    /// An I/O error, with its `errno` value.
+    Io(i32),
+    /// The operation is not supported by the terminal.
+    Unsupported,
+}
+
+impl Default for NcErrorKind {
+    fn default() -> Self {
+        Self::Other
+    }
+}
+
+impl fmt::Display for NcErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Other => write!(f, "error"),
+            Self::NullPointer => write!(f, "null pointer"),
+            Self::InvalidArgument => write!(f, "invalid argument"),
+            Self::OutOfBounds => write!(f, "out of bounds"),
+            Self::Io(errno) => write!(f, "I/O error {}", errno),
+            Self::Unsupported => write!(f, "unsupported"),
+        }
+    }
+}
+
+/// The error type for the Rust methods API.
+///
+/// Besides the [`NcIntResult`] and the message, it can hold the [kind] of
+/// the error, the name of the C function that failed, and the underlying
+/// error.
+///
+/// Since those are private fields, an `NcError` can no longer be built with
+/// a struct literal like `NcError { int, msg }`. Use one of the constructors
+/// instead, like [`with_msg`][NcError#method.with_msg].
+///
+/// [kind]: NcErrorKind
+#[derive(Debug, Clone, Default)]
+pub struct NcError {
+    /// [NcIntResult].
+    pub int: i32,
+    pub msg: String,
+    kind: NcErrorKind,
+    call: Option<String>,
+    source: Option<Arc<dyn error::Error + Send + Sync>>,
+}
+
+impl fmt::Display for NcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "NcError {}", self.int)?;
+        if self.kind != NcErrorKind::Other {
+            write!(f, " ({})", self.kind)?;
+        }
+        write!(f, ": {}", self.msg)?;
+        if let Some(call) = &self.call {
+            if !self.msg.is_empty() {
+                write!(f, ", ")?;
+            }
+            write!(f, "calling `{}()`", call)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for NcError {
+    fn description(&self) -> &str {
+        &self.msg
+    }
+
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|e| e.as_ref() as &(dyn error::Error + 'static))
+    }
+}
+
+impl NcError {
+    /// New NcError with default
+    /// [`NcIntResult::ERR`][NcIntResult#associatedconstant.ERR]
+    /// error number, and no message.
+    pub fn new() -> Self {
+        Self {
+            int: NcIntResult::ERR,
+            ..Default::default()
+        }
+    }
+
+    /// New NcError with custom error number, and without message.
+    pub fn new_err(int: NcIntResult) -> Self {
+        Self {
+            int,
+            ..Default::default()
+        }
+    }
+
+    /// New NcError with default
+    /// [`NcIntResult::ERR`][NcIntResult#associatedconstant.ERR]
+    /// error number and a custom message.
+    pub fn new_msg(msg: &str) -> Self {
+        Self {
+            int: NcIntResult::ERR,
+            msg: msg.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// New NcError with both a custom error number and a custom message.
+    pub fn with_msg(int: NcIntResult, msg: &str) -> Self {
+        Self {
+            int,
+            msg: msg.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// New NcError with default
+    /// [`NcIntResult::ERR`][NcIntResult#associatedconstant.ERR]
+    /// error number, a kind and a custom message.
+    pub fn with_kind(kind: NcErrorKind, msg: &str) -> Self {
+        Self {
+            int: NcIntResult::ERR,
+            msg: msg.to_string(),
+            kind,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the kind of the error.
+    pub fn of_kind(mut self, kind: NcErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets the name of the C function that failed, like `"ncplane_putc"`.
+    pub fn calling(mut self, function: &str) -> Self {
+        self.call = Some(function.trim_end_matches("()").to_string());
+        self
+    }
+
+    /// Sets the underlying error, returned by [`source`][error::Error::source].
+    pub fn caused_by<E: error::Error + Send + Sync + 'static>(mut self, source: E) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> NcErrorKind {
+        self.kind
+    }
+
+    /// Returns the name of the C function that failed, if known.
+    pub fn call(&self) -> Option<&str> {
+        self.call.as_deref()
+    }
+}
+
+impl From<io::Error> for NcError {
+    /// Converts an I/O error into an [`NcErrorKind::Io`] error, with the
+    /// I/O error as its source.
+    fn from(err: io::Error) -> Self {
+        let errno = err.raw_os_error().unwrap_or(0);
+        Self::with_kind(NcErrorKind::Io(errno), &err.to_string()).caused_by(err)
+    }
+}
+
+impl From<NcError> for io::Error {
+    /// Converts an [`NcError`] into an I/O error of the corresponding
+    /// [`io::ErrorKind`], wrapping it.
+    fn from(err: NcError) -> Self {
+        let kind = match err.kind {
+            NcErrorKind::InvalidArgument | NcErrorKind::OutOfBounds => io::ErrorKind::InvalidInput,
+            NcErrorKind::Io(errno) if errno != 0 => io::Error::from_raw_os_error(errno).kind(),
+            _ => io::ErrorKind::Other,
+        };
+        io::Error::new(kind, err)
+    }
+}
+
+/// The result type for the Rust methods API.
+pub type NcResult<T> = Result<T, NcError>;
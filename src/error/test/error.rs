@@ -0,0 +1,45 @@
+//! Test the C function names recorded by `NcError`.
+
+use core::ptr::null_mut;
+
+use serial_test::serial;
+
+use crate::{error, error_ref_mut, NcError, NcIntResult, NcResult};
+
+fn check(res: NcIntResult) -> NcResult<()> {
+    error![@call "ncplane_putc", res, "NcPlane.putc()"]
+}
+
+fn check_ptr(ptr: *mut u32) -> NcResult<&'static mut u32> {
+    error_ref_mut![@call "ncplane_create", ptr, "NcPlane.new_bound()"]
+}
+
+#[test]
+#[serial]
+fn macros_call() {
+    assert![check(0).is_ok()];
+    let error = check(-1).unwrap_err();
+    assert_eq![Some("ncplane_putc"), error.call()];
+    assert_eq![
+        "NcError -1: NcPlane.putc(), calling `ncplane_putc()`",
+        error.to_string()
+    ];
+
+    let error = check_ptr(null_mut()).unwrap_err();
+    assert_eq![Some("ncplane_create"), error.call()];
+}
+
+#[test]
+#[serial]
+fn calling() {
+    let error = NcError::new().calling("ncplane_putc");
+    assert_eq![Some("ncplane_putc"), error.call()];
+    assert_eq!["NcError -1: calling `ncplane_putc()`", error.to_string()];
+
+    let error = NcError::new_msg("NcPlane.putc()").calling("ncplane_putc()");
+    assert_eq![Some("ncplane_putc"), error.call()];
+    assert_eq![
+        "NcError -1: NcPlane.putc(), calling `ncplane_putc()`",
+        error.to_string()
+    ];
+}
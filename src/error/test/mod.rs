@@ -0,0 +1,4 @@
+//! `NcError` tests.
+
+#[cfg(test)]
+mod error;
@@ -1,4 +1,4 @@
-//! `NcFadeCb` & `NcFadeCtx`
+//! `NcFadeCb`, `NcFadeCtx` & `NcFadeIter`
 
 // functions already exported by bindgen : 3
 // -------------------------------------------
@@ -9,9 +9,13 @@
 //W  ncfadectx_iterations
 //W  ncfadectx_setup
 
+use core::ptr::{null_mut, NonNull};
 use std::ffi::c_void;
 
-use crate::{c_api, Nc, NcIntResult, NcPlane, NcTime};
+use crate::{
+    c_api, callback::ffi_guard, error, Nc, NcError, NcErrorKind, NcIntResult, NcPlane, NcResult,
+    NcTime,
+};
 
 /// Called for each fade iteration on the NcPlane.
 ///
@@ -36,10 +40,13 @@ impl NcFadeCtx {
     }
 
     /// Releases the resources associated.
-    pub fn free(&mut self) {
-        unsafe {
-            c_api::ncfadectx_free(self);
-        }
+    ///
+    /// # Safety
+    ///
+    /// The context must not be used after it's freed, and it must not be
+    /// owned by an `OwnedFadeCtx`.
+    pub unsafe fn free(&mut self) {
+        c_api::ncfadectx_free(self);
     }
 
     /// Returns the number of iterations through which will fade.
@@ -47,3 +54,168 @@ impl NcFadeCtx {
         unsafe { c_api::ncfadectx_iterations(self) as u32 }
     }
 }
+
+/// The state passed as the curry to [`fadecb_trampoline`].
+struct NcFadeCurry<F> {
+    fader: F,
+    error: Option<NcError>,
+}
+
+/// The C fade callback that calls the closure in the curry.
+///
+/// An error returned by the closure, or a panic, ceases the fading operation.
+unsafe extern "C" fn fadecb_trampoline<F>(
+    nc: *mut Nc,
+    plane: *mut NcPlane,
+    time: *const NcTime,
+    curry: *mut c_void,
+) -> NcIntResult
+where
+    F: FnMut(&mut Nc, &mut NcPlane, &NcTime) -> NcResult<()>,
+{
+    let curry = &mut *(curry as *mut NcFadeCurry<F>);
+    let fader = &mut curry.fader;
+    match ffi_guard(|| fader(&mut *nc, &mut *plane, &*time)) {
+        Ok(Ok(())) => c_api::NCRESULT_OK,
+        Ok(Err(e)) | Err(e) => {
+            curry.error = Some(e);
+            c_api::NCRESULT_ERR
+        }
+    }
+}
+
+/// Calls the C fading function named `call` with the `fader` closure,
+/// returning the error returned by the closure, if any.
+pub(crate) fn with_fader<F>(
+    fader: F,
+    msg: &str,
+    call: &str,
+    fade: impl FnOnce(NcFadeCb, *mut c_void) -> NcIntResult,
+) -> NcResult<()>
+where
+    F: FnMut(&mut Nc, &mut NcPlane, &NcTime) -> NcResult<()>,
+{
+    let mut curry = NcFadeCurry { fader, error: None };
+    let res = fade(
+        Some(fadecb_trampoline::<F>),
+        &mut curry as *mut NcFadeCurry<F> as *mut c_void,
+    );
+    if let Some(e) = curry.error {
+        return Err(e);
+    }
+    error![@call call, res, msg]
+}
+
+/// The progress of a fade, yielded by [`NcFadeIter`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NcFadeStep {
+    /// The iteration that has just been rendered, starting at 0.
+    pub iteration: u32,
+    /// The total number of iterations.
+    pub iterations: u32,
+}
+
+impl NcFadeStep {
+    /// Returns the fraction of the fade completed, between `0.0` and `1.0`.
+    pub fn progress(&self) -> f64 {
+        if self.iterations == 0 {
+            1.0
+        } else {
+            (self.iteration + 1) as f64 / self.iterations as f64
+        }
+    }
+}
+
+/// An iterator that fades a plane one step at a time, rendering each step.
+///
+/// Created by [`NcPlane.fadein_iter()`][NcPlane#method.fadein_iter] and
+/// [`NcPlane.fadeout_iter()`][NcPlane#method.fadeout_iter].
+///
+/// The fade can be cancelled at any point just by not iterating any further.
+/// The fade context is freed when the iterator is dropped.
+///
+/// # Example
+///
+/// ```ignore
+/// # use libnotcurses_sys::*;
+/// # fn fade(plane: &mut NcPlane) -> NcResult<()> {
+/// for step in plane.fadeout_iter()? {
+///     if step?.progress() > 0.5 {
+///         break;
+///     }
+///     sleep![0, 10];
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct NcFadeIter<'a> {
+    plane: &'a mut NcPlane,
+    ctx: NonNull<NcFadeCtx>,
+    fadein: bool,
+    iteration: u32,
+    iterations: u32,
+}
+
+impl<'a> NcFadeIter<'a> {
+    /// New fade iterator for `plane`.
+    ///
+    /// *C style function: [ncfadectx_setup()][c_api::ncfadectx_setup].*
+    pub(crate) fn new(plane: &'a mut NcPlane, fadein: bool) -> NcResult<Self> {
+        let ctx = NonNull::new(unsafe { c_api::ncfadectx_setup(plane) }).ok_or_else(|| {
+            NcError::with_kind(NcErrorKind::NullPointer, "NcFadeIter::new()")
+                .calling("ncfadectx_setup")
+        })?;
+        let iterations = unsafe { c_api::ncfadectx_iterations(ctx.as_ptr()) } as u32;
+        Ok(Self {
+            plane,
+            ctx,
+            fadein,
+            iteration: 0,
+            iterations,
+        })
+    }
+}
+
+impl<'a> Iterator for NcFadeIter<'a> {
+    type Item = NcResult<NcFadeStep>;
+
+    /// Renders the next step of the fade.
+    ///
+    /// *C style function: [ncplane_fadein_iteration()][c_api::ncplane_fadein_iteration],
+    /// [ncplane_fadeout_iteration()][c_api::ncplane_fadeout_iteration].*
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iteration >= self.iterations {
+            return None;
+        }
+        let (plane, ctx, iter) = (&mut *self.plane, self.ctx.as_ptr(), self.iteration as i32);
+        let res = unsafe {
+            if self.fadein {
+                c_api::ncplane_fadein_iteration(plane, ctx, iter, None, null_mut())
+            } else {
+                c_api::ncplane_fadeout_iteration(plane, ctx, iter, None, null_mut())
+            }
+        };
+        let step = NcFadeStep {
+            iteration: self.iteration,
+            iterations: self.iterations,
+        };
+        if res < 0 {
+            self.iteration = self.iterations;
+            return Some(Err(NcError::with_msg(res, "NcFadeIter.next()")));
+        }
+        self.iteration += 1;
+        Some(Ok(step))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.iterations - self.iteration) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> Drop for NcFadeIter<'a> {
+    fn drop(&mut self) {
+        unsafe { c_api::ncfadectx_free(self.ctx.as_ptr()) };
+    }
+}
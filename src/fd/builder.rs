@@ -0,0 +1,284 @@
+//! `NcSubprocBuilder`
+
+use core::ptr::null;
+use std::{
+    env,
+    ffi::{CString, OsString},
+    fmt,
+    os::raw::c_char,
+};
+
+use super::{
+    methods::{check_plane, donecb_trampoline, fdcb_trampoline, keep_callbacks, FdCallbacks},
+    NcFdCb, NcFdDoneCb,
+};
+use crate::{c_api, error_ref_mut, string, NcPlane, NcResult, NcSubproc, NcSubprocOptions};
+
+/// A builder for [`NcSubproc`], similar to [`std::process::Command`].
+///
+/// The standard output of the subprocess is read from another thread, and
+/// written to the plane, which is made scrolling, unless a data callback is
+/// set with [on_data][NcSubprocBuilder#method.on_data], whenever the
+/// subprocess is [polled][NcSubproc#method.poll].
+///
+/// # Example
+///
+/// ```ignore
+/// let subproc = NcSubproc::command("ls")
+///     .arg("-l")
+///     .env("LC_ALL", "C")
+///     .on_done(|plane, _errno| {
+///         plane.putstr("-- done --")?;
+///         Ok(())
+///     })
+///     .spawn(plane)?;
+///
+/// loop {
+///     if subproc.poll()? {
+///         nc.render()?;
+///     }
+///     // ...
+/// }
+/// ```
+pub struct NcSubprocBuilder {
+    program: String,
+    args: Vec<String>,
+    /// The changes to the environment, where `None` removes the variable.
+    env: Vec<(String, Option<String>)>,
+    env_clear: bool,
+    path_lookup: bool,
+    restart_period: u64,
+    on_data: Option<NcFdCb>,
+    on_done: Option<NcFdDoneCb>,
+}
+
+impl fmt::Debug for NcSubprocBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NcSubprocBuilder")
+            .field("program", &self.program)
+            .field("args", &self.args)
+            .field("env", &self.env)
+            .field("env_clear", &self.env_clear)
+            .field("path_lookup", &self.path_lookup)
+            .field("restart_period", &self.restart_period)
+            .field("on_data", &self.on_data.is_some())
+            .field("on_done", &self.on_done.is_some())
+            .finish()
+    }
+}
+
+/// # `NcSubprocBuilder` constructors
+impl NcSubprocBuilder {
+    /// New NcSubprocBuilder for running `program`, looked up in the `PATH`,
+    /// without arguments, and inheriting the environment.
+    pub fn new(program: &str) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+            env: Vec::new(),
+            env_clear: false,
+            path_lookup: true,
+            restart_period: 0,
+            on_data: None,
+            on_done: None,
+        }
+    }
+}
+
+/// # `NcSubprocBuilder` methods
+impl NcSubprocBuilder {
+    /// Adds an argument.
+    pub fn arg(mut self, arg: &str) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Adds several arguments.
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.args
+            .extend(args.into_iter().map(|a| a.as_ref().into()));
+        self
+    }
+
+    /// Sets an environment variable.
+    pub fn env(mut self, key: &str, val: &str) -> Self {
+        self.env.push((key.into(), Some(val.into())));
+        self
+    }
+
+    /// Sets several environment variables.
+    pub fn envs<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.env.extend(
+            vars.into_iter()
+                .map(|(k, v)| (k.as_ref().into(), Some(v.as_ref().into()))),
+        );
+        self
+    }
+
+    /// Removes an environment variable.
+    pub fn env_remove(mut self, key: &str) -> Self {
+        self.env.push((key.into(), None));
+        self
+    }
+
+    /// Clears the environment, including the variables set before.
+    pub fn env_clear(mut self) -> Self {
+        self.env.clear();
+        self.env_clear = true;
+        self
+    }
+
+    /// Sets whether the program is looked up in the `PATH`, when it
+    /// doesn't contain a slash. The default is `true`.
+    ///
+    /// It's always looked up if the environment is changed.
+    pub fn path_lookup(mut self, path_lookup: bool) -> Self {
+        self.path_lookup = path_lookup;
+        self
+    }
+
+    /// Restarts the subprocess this many seconds after it exits.
+    /// The default is 0, which doesn't restart it.
+    pub fn restart_period(mut self, seconds: u64) -> Self {
+        self.restart_period = seconds;
+        self
+    }
+
+    /// Sets the callback called with the output of the subprocess,
+    /// instead of writing it to the plane.
+    ///
+    /// It's called by [poll][NcSubproc#method.poll]. See [`NcFdCb`].
+    pub fn on_data<F>(mut self, on_data: F) -> Self
+    where
+        F: FnMut(&mut NcPlane, &[u8]) -> NcResult<()> + 'static,
+    {
+        self.on_data = Some(Box::new(on_data));
+        self
+    }
+
+    /// Sets the callback called at the end of the output of the subprocess.
+    ///
+    /// It's called by [poll][NcSubproc#method.poll]. See [`NcFdDoneCb`].
+    pub fn on_done<F>(mut self, on_done: F) -> Self
+    where
+        F: FnMut(&mut NcPlane, i32) -> NcResult<()> + 'static,
+    {
+        self.on_done = Some(Box::new(on_done));
+        self
+    }
+
+    /// Returns the resulting environment, if it's changed.
+    ///
+    /// The inherited variables are kept even if they're not valid UTF-8.
+    pub(super) fn environment(&self) -> Option<Vec<(OsString, OsString)>> {
+        if !self.env_clear && self.env.is_empty() {
+            return None;
+        }
+        let mut vars: Vec<(OsString, OsString)> = if self.env_clear {
+            Vec::new()
+        } else {
+            env::vars_os().collect()
+        };
+        for (key, val) in &self.env {
+            vars.retain(|(k, _)| k != key.as_str());
+            if let Some(val) = val {
+                vars.push((key.into(), val.into()));
+            }
+        }
+        Some(vars)
+    }
+
+    /// Spawns the subprocess, writing its output to the `plane`.
+    ///
+    /// Returns an error if any string contains a nul byte, or if the
+    /// subprocess couldn't be spawned.
+    ///
+    /// *C style function: [ncsubproc_createv()][c_api::ncsubproc_createv],
+    /// [ncsubproc_createvp()][c_api::ncsubproc_createvp],
+    /// [ncsubproc_createvpe()][c_api::ncsubproc_createvpe].*
+    pub fn spawn<'a>(self, plane: &'a mut NcPlane) -> NcResult<&'a mut NcSubproc> {
+        let program = string::cstring(&self.program)?;
+        // the first argument is the program itself
+        let args = Some(&self.program)
+            .into_iter()
+            .chain(self.args.iter())
+            .map(|arg| string::cstring(arg))
+            .collect::<NcResult<Vec<CString>>>()?;
+        let mut argv: Vec<*const c_char> = args.iter().map(|arg| arg.as_ptr()).collect();
+        argv.push(null());
+
+        let env = self
+            .environment()
+            .map(|vars| {
+                vars.into_iter()
+                    .map(|(mut var, val)| {
+                        var.push("=");
+                        var.push(val);
+                        string::cstring_os(&var)
+                    })
+                    .collect::<NcResult<Vec<CString>>>()
+            })
+            .transpose()?;
+        let envp: Option<Vec<*const c_char>> = env.as_ref().map(|env| {
+            let mut envp: Vec<*const c_char> = env.iter().map(|var| var.as_ptr()).collect();
+            envp.push(null());
+            envp
+        });
+
+        check_plane(plane, "NcSubprocBuilder.spawn()")?;
+        if self.on_data.is_none() {
+            plane.set_scrolling(true);
+        }
+        let callbacks = FdCallbacks::new(self.on_data, self.on_done);
+        let options = NcSubprocOptions {
+            curry: callbacks.curry(),
+            ..NcSubprocOptions::new(self.restart_period, 0)
+        };
+
+        let res = unsafe {
+            match (&envp, self.path_lookup) {
+                (Some(envp), _) => c_api::ncsubproc_createvpe(
+                    plane,
+                    &options,
+                    program.as_ptr(),
+                    argv.as_ptr(),
+                    envp.as_ptr(),
+                    Some(fdcb_trampoline),
+                    Some(donecb_trampoline),
+                ),
+                (None, true) => c_api::ncsubproc_createvp(
+                    plane,
+                    &options,
+                    program.as_ptr(),
+                    argv.as_ptr(),
+                    Some(fdcb_trampoline),
+                    Some(donecb_trampoline),
+                ),
+                (None, false) => c_api::ncsubproc_createv(
+                    plane,
+                    &options,
+                    program.as_ptr(),
+                    argv.as_ptr(),
+                    Some(fdcb_trampoline),
+                    Some(donecb_trampoline),
+                ),
+            }
+        };
+        keep_callbacks(plane, callbacks, !res.is_null());
+        let call = match (&envp, self.path_lookup) {
+            (Some(_), _) => "ncsubproc_createvpe",
+            (None, true) => "ncsubproc_createvp",
+            (None, false) => "ncsubproc_createv",
+        };
+        error_ref_mut![@call call, res, "NcSubprocBuilder.spawn()"]
+    }
+}
@@ -1,16 +1,353 @@
-//! `NcVisual*` methods and associated functions.
+//! `NcFdPlane` & `NcSubproc` methods and associated functions.
 
-// use core::ptr::null_mut;
-use libc::c_void;
+use core::{ptr::null_mut, slice, str};
+use std::{
+    os::raw::{c_int, c_void},
+    sync::mpsc::{channel, Receiver, Sender, TryRecvError},
+};
 
-use crate::{NcSubproc, NcSubprocOptions};
+use super::{NcFdCb, NcFdDoneCb, NcSubprocBuilder};
+use crate::{
+    c_api, callback::ffi_guard, error_ref_mut, plane::userptr, NcError, NcErrorKind, NcFdPlane,
+    NcFdPlaneOptions, NcPlane, NcResult, NcSubproc, NcSubprocOptions,
+};
 
-/// # NcVisualOptions Constructors
+/// A message from the thread that reads the data.
+enum FdMsg {
+    Data(Vec<u8>),
+    Done(c_int),
+}
+
+/// The curry of an [`NcFdPlane`] or an [`NcSubproc`], only used from the
+/// thread that reads the data, to forward it to the thread that owns them.
+struct FdCurry(Sender<FdMsg>);
+
+/// The callbacks of an [`NcFdPlane`] or an [`NcSubproc`], called from their
+/// `poll` method with the data forwarded by their curry.
+///
+/// They're stored in the userptr of their plane, and they own the curry.
+pub(super) struct FdCallbacks {
+    curry: *mut FdCurry,
+    receiver: Receiver<FdMsg>,
+    data: NcFdCb,
+    done: Option<NcFdDoneCb>,
+    /// Whether the data callback has failed, and mustn't be called anymore.
+    failed: bool,
+}
+
+impl FdCallbacks {
+    /// Returns new callbacks, along with their curry.
+    ///
+    /// Without a data callback, the data is written to the plane.
+    pub(super) fn new(data: Option<NcFdCb>, done: Option<NcFdDoneCb>) -> Self {
+        let (sender, receiver) = channel();
+        FdCallbacks {
+            curry: Box::into_raw(Box::new(FdCurry(sender))),
+            receiver,
+            data: data.unwrap_or_else(plane_writer),
+            done,
+            failed: false,
+        }
+    }
+
+    /// Returns the curry to pass to the C library.
+    pub(super) fn curry(&self) -> *mut c_void {
+        self.curry as *mut c_void
+    }
+}
+
+impl Drop for FdCallbacks {
+    fn drop(&mut self) {
+        drop(unsafe { Box::from_raw(self.curry) });
+    }
+}
+
+/// Returns an error if the userptr of the `plane` already stores the state of
+/// another widget, which would be replaced by the callbacks.
+pub(super) fn check_plane(plane: &NcPlane, method: &str) -> NcResult<()> {
+    match unsafe { userptr::get_const(plane) } {
+        Some(data) if data.widget_data.is_some() => Err(NcError::with_kind(
+            NcErrorKind::InvalidArgument,
+            &format!["{}: the plane is already used by another widget", method],
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Stores the `callbacks` in the userptr of the `plane`, after the object
+/// using them has been created, or else frees them.
+///
+/// The plane must have been checked with [`check_plane`].
+pub(super) fn keep_callbacks(plane: &mut NcPlane, callbacks: FdCallbacks, created: bool) {
+    if created {
+        userptr::get_or_init(plane).widget_data = Some(Box::new(callbacks));
+    }
+}
+
+/// Returns the callbacks stored in the userptr of the `plane`, if any.
+///
+/// # Safety
+/// `plane` must be valid, and there must not be any other live references
+/// to its data.
+unsafe fn get_callbacks<'a>(plane: *mut NcPlane) -> Option<&'a mut FdCallbacks> {
+    userptr::get(plane)?.widget_data.as_mut()?.downcast_mut()
+}
+
+/// Frees the callbacks stored in the userptr of the `plane`, after the
+/// object using them has been destroyed.
+unsafe fn free_callbacks(plane: *mut NcPlane) {
+    if let Some(data) = userptr::get(plane) {
+        if data
+            .widget_data
+            .as_ref()
+            .map_or(false, |d| d.is::<FdCallbacks>())
+        {
+            data.widget_data = None;
+        }
+    }
+}
+
+/// Calls the callbacks stored in the userptr of the `plane` with the data
+/// forwarded since the last call.
+///
+/// Returns `true` if there was any.
+///
+/// # Safety
+/// `plane` must be valid, and there must not be any other live references
+/// to its data.
+unsafe fn poll_callbacks(plane: *mut NcPlane) -> NcResult<bool> {
+    // the callbacks are boxed, so they stay put while the plane is borrowed
+    let callbacks = match get_callbacks(plane) {
+        Some(callbacks) => callbacks as *mut FdCallbacks,
+        None => return Ok(false),
+    };
+    let mut polled = false;
+    loop {
+        let msg = match (*callbacks).receiver.try_recv() {
+            Ok(msg) => msg,
+            Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => return Ok(polled),
+        };
+        polled = true;
+        match msg {
+            FdMsg::Data(_) if (*callbacks).failed => (),
+            FdMsg::Data(data) => {
+                if let Err(e) = ((*callbacks).data)(&mut *plane, &data) {
+                    (*callbacks).failed = true;
+                    return Err(e);
+                }
+            }
+            FdMsg::Done(errno) => {
+                if let Some(done) = (*callbacks).done.as_mut() {
+                    done(&mut *plane, errno)?;
+                }
+            }
+        }
+    }
+}
+
+/// Returns a data callback that writes the data to the plane, as UTF-8.
+///
+/// The incomplete characters at the end of the data are kept until the
+/// next call, and the invalid ones are replaced with `U+FFFD`.
+pub(super) fn plane_writer() -> NcFdCb {
+    let mut pending = Vec::new();
+    Box::new(move |plane: &mut NcPlane, data: &[u8]| {
+        pending.extend_from_slice(data);
+        let valid = match str::from_utf8(&pending) {
+            Ok(s) => s.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => pending.len(),
+        };
+        let text = String::from_utf8_lossy(&pending[..valid]).replace('\0', "");
+        pending.drain(..valid);
+        if !text.is_empty() {
+            plane.putstr(&text)?;
+        }
+        Ok(())
+    })
+}
+
+/// The C data callback that forwards the data to the [`FdCurry`].
+///
+/// It always returns 0, so that the calling object doesn't destroy itself.
+pub(super) unsafe extern "C" fn fdcb_trampoline(
+    _fdplane: *mut NcFdPlane,
+    buf: *const c_void,
+    len: usize,
+    curry: *mut c_void,
+) -> c_int {
+    let _ = ffi_guard(|| {
+        let curry = (curry as *const FdCurry).as_ref()?;
+        let data = if buf.is_null() {
+            Vec::new()
+        } else {
+            slice::from_raw_parts(buf as *const u8, len).to_vec()
+        };
+        curry.0.send(FdMsg::Data(data)).ok()
+    });
+    0
+}
+
+/// The C done callback that forwards the end of the data to the [`FdCurry`].
+///
+/// It always returns 0, so that the calling object doesn't destroy itself.
+pub(super) unsafe extern "C" fn donecb_trampoline(
+    _fdplane: *mut NcFdPlane,
+    fderrno: c_int,
+    curry: *mut c_void,
+) -> c_int {
+    let _ = ffi_guard(|| {
+        let curry = (curry as *const FdCurry).as_ref()?;
+        curry.0.send(FdMsg::Done(fderrno)).ok()
+    });
+    0
+}
+
+/// # `NcFdPlaneOptions` constructors
+impl NcFdPlaneOptions {
+    /// New NcFdPlaneOptions for [`NcFdPlane`].
+    ///
+    /// With `follow`, the reading continues after EOF, like `tail -f`.
+    pub const fn new(follow: bool) -> Self {
+        Self::with_flags(follow, 0)
+    }
+
+    /// New NcFdPlaneOptions for [`NcFdPlane`], with flags.
+    ///
+    /// The curry is set by the constructors of [`NcFdPlane`].
+    pub const fn with_flags(follow: bool, flags: u64) -> Self {
+        Self {
+            curry: null_mut(),
+            follow,
+            flags,
+        }
+    }
+}
+
+/// # `NcFdPlane` constructors & destructors
+impl NcFdPlane {
+    /// New NcFdPlane, that reads `fd` from another thread, and writes the
+    /// data to the `plane`, which is made scrolling, when
+    /// [polled][NcFdPlane#method.poll].
+    ///
+    /// The `fd` is closed when the NcFdPlane is destroyed.
+    ///
+    /// *C style function: [ncfdplane_create()][c_api::ncfdplane_create].*
+    pub fn new<'a>(
+        plane: &'a mut NcPlane,
+        fd: c_int,
+        options: &NcFdPlaneOptions,
+    ) -> NcResult<&'a mut Self> {
+        plane.set_scrolling(true);
+        Self::create(plane, fd, options, None, None)
+    }
+
+    /// New NcFdPlane, that reads `fd` from another thread, and calls
+    /// `on_data` with the data read, and `on_done` at the end, when
+    /// [polled][NcFdPlane#method.poll].
+    ///
+    /// The `fd` is closed when the NcFdPlane is destroyed.
+    ///
+    /// *C style function: [ncfdplane_create()][c_api::ncfdplane_create].*
+    pub fn with_callbacks<'a, F, D>(
+        plane: &'a mut NcPlane,
+        fd: c_int,
+        options: &NcFdPlaneOptions,
+        on_data: F,
+        on_done: D,
+    ) -> NcResult<&'a mut Self>
+    where
+        F: FnMut(&mut NcPlane, &[u8]) -> NcResult<()> + 'static,
+        D: FnMut(&mut NcPlane, i32) -> NcResult<()> + 'static,
+    {
+        Self::create(
+            plane,
+            fd,
+            options,
+            Some(Box::new(on_data)),
+            Some(Box::new(on_done)),
+        )
+    }
+
+    fn create<'a>(
+        plane: &'a mut NcPlane,
+        fd: c_int,
+        options: &NcFdPlaneOptions,
+        on_data: Option<NcFdCb>,
+        on_done: Option<NcFdDoneCb>,
+    ) -> NcResult<&'a mut Self> {
+        check_plane(plane, "NcFdPlane::new()")?;
+        let callbacks = FdCallbacks::new(on_data, on_done);
+        let options = NcFdPlaneOptions {
+            curry: callbacks.curry(),
+            ..*options
+        };
+        let res = unsafe {
+            c_api::ncfdplane_create(
+                plane,
+                &options,
+                fd,
+                Some(fdcb_trampoline),
+                Some(donecb_trampoline),
+            )
+        };
+        keep_callbacks(plane, callbacks, !res.is_null());
+        error_ref_mut![@call "ncfdplane_create", res, "NcFdPlane::new()"]
+    }
+
+    /// Destroys the NcFdPlane, closing its file descriptor, and dropping the
+    /// callbacks, along with the data not polled yet.
+    ///
+    /// The plane is not destroyed.
+    ///
+    /// # Safety
+    ///
+    /// It must be called once and only once, and the `NcFdPlane` must not be used
+    /// afterwards, nor be owned by an `OwnedFdPlane`. It must not be called from
+    /// its own callbacks.
+    ///
+    /// *C style function: [ncfdplane_destroy()][c_api::ncfdplane_destroy].*
+    pub unsafe fn destroy(&mut self) -> NcResult<()> {
+        let plane = c_api::ncfdplane_plane(self);
+        let res = c_api::ncfdplane_destroy(self);
+        free_callbacks(plane);
+        if res < c_api::NCRESULT_OK {
+            return Err(NcError::with_msg(res, "NcFdPlane.destroy()").calling("ncfdplane_destroy"));
+        }
+        Ok(())
+    }
+}
+
+/// # `NcFdPlane` methods
+impl NcFdPlane {
+    /// Returns a reference to the NcFdPlane's plane.
+    ///
+    /// *C style function: [ncfdplane_plane()][c_api::ncfdplane_plane].*
+    pub fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![@call "ncfdplane_plane", unsafe { c_api::ncfdplane_plane(self) }, "NcFdPlane.plane()"]
+    }
+
+    /// Calls the callbacks with the data read since the last call, or writes
+    /// it to the plane, from the calling thread.
+    ///
+    /// Returns `true` if there was any data, or the end of it, so the plane
+    /// may need to be rendered, or an error returned by a callback.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn poll(&mut self) -> NcResult<bool> {
+        unsafe { poll_callbacks(c_api::ncfdplane_plane(self)) }
+    }
+}
+
+/// # `NcSubprocOptions` constructors
 impl NcSubprocOptions {
+    /// New NcSubprocOptions for [`NcSubproc`].
     ///
-    pub fn new(curry: *mut c_void, restart_period: u64, flags: u64) -> Self {
+    /// With a `restart_period`, the subprocess is restarted that many seconds
+    /// after it exits. The curry is set by the [`NcSubprocBuilder`].
+    pub const fn new(restart_period: u64, flags: u64) -> Self {
         Self {
-            curry,
+            curry: null_mut(),
             // restart this many seconds after an exit (watch)
             restart_period,
             // bitfield over NCOPTION_SUBPROC_* (none yet)
@@ -19,8 +356,55 @@ impl NcSubprocOptions {
     }
 }
 
-/// # NcSubproc Constructors & Destructors
-impl NcSubproc {}
+/// # `NcSubproc` constructors & destructors
+impl NcSubproc {
+    /// Returns a new [`NcSubprocBuilder`] for running `program`.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn command(program: &str) -> NcSubprocBuilder {
+        NcSubprocBuilder::new(program)
+    }
+
+    /// Destroys the NcSubproc, killing the subprocess if it's still running,
+    /// and dropping the callbacks, along with the data not polled yet.
+    ///
+    /// The plane is not destroyed.
+    ///
+    /// # Safety
+    ///
+    /// It must be called once and only once, and the `NcSubproc` must not be used
+    /// afterwards, nor be owned by an `OwnedSubproc`. It must not be called from
+    /// its own callbacks.
+    ///
+    /// *C style function: [ncsubproc_destroy()][c_api::ncsubproc_destroy].*
+    pub unsafe fn destroy(&mut self) -> NcResult<()> {
+        let plane = c_api::ncsubproc_plane(self);
+        let res = c_api::ncsubproc_destroy(self);
+        free_callbacks(plane);
+        if res < c_api::NCRESULT_OK {
+            return Err(NcError::with_msg(res, "NcSubproc.destroy()").calling("ncsubproc_destroy"));
+        }
+        Ok(())
+    }
+}
+
+/// # `NcSubproc` methods
+impl NcSubproc {
+    /// Returns a reference to the NcSubproc's plane.
+    ///
+    /// *C style function: [ncsubproc_plane()][c_api::ncsubproc_plane].*
+    pub fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![@call "ncsubproc_plane", unsafe { c_api::ncsubproc_plane(self) }, "NcSubproc.plane()"]
+    }
 
-/// # NcSubproc Methods
-impl NcSubproc {}
+    /// Calls the callbacks with the output of the subprocess since the last
+    /// call, or writes it to the plane, from the calling thread.
+    ///
+    /// Returns `true` if there was any output, or the end of it, so the plane
+    /// may need to be rendered, or an error returned by a callback.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn poll(&mut self) -> NcResult<bool> {
+        unsafe { poll_callbacks(c_api::ncsubproc_plane(self)) }
+    }
+}
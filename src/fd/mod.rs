@@ -1,16 +1,16 @@
 // functions already exported by bindgen : 8
 // -----------------------------------------
-// (W) wrap: 0
+// (W) wrap: 8
 // (#) test: 0
 // -----------------------------------------
-//   ncfdplane_create
-//   ncfdplane_destroy
-//   ncfdplane_plane
-//   ncsubproc_createv,
-//   ncsubproc_createvp,
-//   ncsubproc_createvpe,
-//   ncsubproc_destroy,
-//   ncsubproc_plane,
+//W ncfdplane_create
+//W ncfdplane_destroy
+//W ncfdplane_plane
+//W ncsubproc_createv,
+//W ncsubproc_createvp,
+//W ncsubproc_createvpe,
+//W ncsubproc_destroy,
+//W ncsubproc_plane,
 
 //! from: <https://notcurses.com/notcurses_fds.3.html>
 //!
@@ -32,14 +32,48 @@
 //!
 //! It is essential that the destroy function be called once and only once,
 //! whether it is from within the thread's context, or external to that context.
+//!
+//! The Rust callbacks are closures, that never make their calling object
+//! destroy itself, so that it's only destroyed by its `destroy` method, or
+//! when its owned counterpart is dropped.
+//!
+//! The data read is forwarded to the thread that owns the object, where the
+//! closures are called by its `poll` method, since the plane can't be safely
+//! accessed from the reading thread.
 
-#![allow(dead_code)]
+use std::os::raw::{c_int, c_void};
 
-#[allow(unused_imports)] // for the doc comments
-use crate::NcPlane;
+use crate::{NcPlane, NcResult};
 
+mod builder;
 mod methods;
 
+#[cfg(test)]
+mod test;
+
+pub use builder::NcSubprocBuilder;
+
+/// A callback closure called with the data read by an [`NcFdPlane`] or an
+/// [`NcSubproc`], along with their plane.
+///
+/// It's called from their `poll` method. Returning an error stops the calls
+/// for the rest of the data, and the error is returned by `poll`.
+pub type NcFdCb = Box<dyn FnMut(&mut NcPlane, &[u8]) -> NcResult<()>>;
+
+/// A callback closure called when an [`NcFdPlane`] or an [`NcSubproc`]
+/// reaches the end of their data, along with their plane and the `errno`
+/// of the read error, or 0 on EOF.
+///
+/// It's called from their `poll` method, which returns its error, if any.
+pub type NcFdDoneCb = Box<dyn FnMut(&mut NcPlane, i32) -> NcResult<()>>;
+
+/// The unsafe version of [`NcFdCb`] expected by the notcurses C API.
+pub type NcFdCbUnsafe =
+    unsafe extern "C" fn(*mut NcFdPlane, *const c_void, usize, *mut c_void) -> c_int;
+
+/// The unsafe version of [`NcFdDoneCb`] expected by the notcurses C API.
+pub type NcFdDoneCbUnsafe = unsafe extern "C" fn(*mut NcFdPlane, c_int, *mut c_void) -> c_int;
+
 /// I/O wrapper to dump file descriptor to [`NcPlane`].
 ///
 /// `type in C: ncfdplane (struct)`
@@ -0,0 +1,70 @@
+//! Test `NcSubprocBuilder`.
+
+use std::{env, ffi::OsString};
+
+use crate::NcSubproc;
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn environment() -> crate::NcResult<()> {
+    // unchanged
+    assert![NcSubproc::command("env").environment().is_none()];
+
+    env::set_var("NC_TEST_INHERITED", "1");
+    env::set_var("NC_TEST_REMOVED", "1");
+    env::set_var("NC_TEST_REPLACED", "1");
+    let vars = NcSubproc::command("env")
+        .env("NC_TEST_REPLACED", "2")
+        .env("NC_TEST_ADDED", "3")
+        .env_remove("NC_TEST_REMOVED")
+        .environment()
+        .unwrap();
+    let get = |key: &str| {
+        vars.iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+            .collect::<Vec<OsString>>()
+    };
+    assert_eq![get("NC_TEST_INHERITED"), ["1"]];
+    assert![get("NC_TEST_REMOVED").is_empty()];
+    assert_eq![get("NC_TEST_REPLACED"), ["2"]];
+    assert_eq![get("NC_TEST_ADDED"), ["3"]];
+
+    // the variables set before clearing are forgotten
+    let vars = NcSubproc::command("env")
+        .env("NC_TEST_FORGOTTEN", "1")
+        .env_clear()
+        .env("NC_TEST_ADDED", "1")
+        .environment()
+        .unwrap();
+    assert_eq![
+        vars,
+        vec![(OsString::from("NC_TEST_ADDED"), OsString::from("1"))]
+    ];
+
+    env::remove_var("NC_TEST_INHERITED");
+    env::remove_var("NC_TEST_REMOVED");
+    env::remove_var("NC_TEST_REPLACED");
+    Ok(())
+}
+
+#[test]
+#[serial]
+#[cfg(unix)]
+fn environment_non_utf8() -> crate::NcResult<()> {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let val = OsStr::from_bytes(b"\xff\xfe");
+    env::set_var("NC_TEST_NON_UTF8", val);
+    let vars = NcSubproc::command("env")
+        .env("NC_TEST_ADDED", "1")
+        .environment()
+        .unwrap();
+    env::remove_var("NC_TEST_NON_UTF8");
+
+    // the inherited variables are kept as they are
+    assert![vars.contains(&("NC_TEST_NON_UTF8".into(), val.into()))];
+    Ok(())
+}
@@ -0,0 +1,30 @@
+//! Test `NcFdPlane` & `NcSubproc` methods.
+
+use super::super::methods::plane_writer;
+use crate::{NcContext, NcPlaneHandle};
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn plane_writer_utf8() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 1, 20)?;
+    let mut writer = plane_writer();
+
+    // an incomplete character is kept until the rest of it arrives
+    writer(&mut plane, b"a\xc3")?;
+    assert_eq!["a", plane.contents(0, 0, None, None).trim_end()];
+    writer(&mut plane, b"\xa9b")?;
+    assert_eq!["a\u{e9}b", plane.contents(0, 0, None, None).trim_end()];
+
+    // the invalid bytes are replaced, and the nul bytes are skipped
+    writer(&mut plane, b"\xff\0c")?;
+    assert_eq![
+        "a\u{e9}b\u{fffd}c",
+        plane.contents(0, 0, None, None).trim_end()
+    ];
+
+    unsafe { plane.destroy()? };
+    Ok(())
+}
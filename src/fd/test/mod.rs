@@ -0,0 +1,7 @@
+//! `NcFdPlane` & `NcSubproc` tests.
+
+#[cfg(test)]
+mod builder;
+
+#[cfg(test)]
+mod methods;
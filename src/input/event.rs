@@ -0,0 +1,370 @@
+//! `NcEvent`
+
+use super::constants::{NCEVTYPE_PRESS, NCEVTYPE_RELEASE, NCEVTYPE_REPEAT, NCEVTYPE_UNKNOWN};
+use crate::{c_api, NcEvType, NcInput, NcKey};
+
+/// An input event, decoded from the [`char`] and the [`NcInput`] returned by
+/// [`Nc.get()`][crate::Nc#method.get].
+///
+/// The conversion from `(char, NcInput)` is lossless, except for the
+/// coordinates of the non-mouse events, so the pair can be recovered with
+/// `<(char, NcInput)>::from(event)`.
+///
+/// # Example
+///
+/// ```ignore
+/// while let Some(event) = nc.get_event(None)? {
+///     match event {
+///         NcEvent::Text(c) => reader.write_egc(&c.to_string())?,
+///         NcEvent::Key { code: NcKeyCode::Esc, .. } => break,
+///         NcEvent::Mouse { button: 1, y, x, kind: NcEventKind::Press, .. } => click(y, x),
+///         NcEvent::Resize => nc.refresh()?,
+///         _ => (),
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NcEvent {
+    /// A key that doesn't produce text by itself, a key with a modifier,
+    /// or a text key that was repeated or released.
+    Key {
+        code: NcKeyCode,
+        mods: NcKeyMods,
+        kind: NcEventKind,
+    },
+
+    /// A mouse button, from 1 to 11, pressed or released at `y`, `x`.
+    ///
+    /// Buttons 4 and 5 are the scroll wheel.
+    Mouse {
+        button: u8,
+        y: i32,
+        x: i32,
+        mods: NcKeyMods,
+        kind: NcEventKind,
+    },
+
+    /// The terminal was resized.
+    Resize,
+
+    /// A printable character, typed without modifiers, by a terminal that
+    /// doesn't report the [kind][NcEventKind] of the events.
+    ///
+    /// The terminals that report it, return [`NcEvent::Key`] instead, with
+    /// a [`NcKeyCode::Char`] code, or see [`NcEvent::text()`].
+    Text(char),
+}
+
+/// The kind of an [`NcEvent`].
+///
+/// Repeat and release events are only reported by the terminals that
+/// support them, otherwise the kind is `Unknown`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NcEventKind {
+    /// The terminal doesn't report the kind of the events.
+    Unknown,
+    /// A key or button was pressed.
+    Press,
+    /// A key is being held pressed.
+    Repeat,
+    /// A key or button was released.
+    Release,
+}
+
+/// The modifiers of an [`NcEvent`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NcKeyMods {
+    pub alt: bool,
+    pub shift: bool,
+    pub ctrl: bool,
+}
+
+/// A key of an [`NcEvent`], with a variant for each [`NcKey`] constant.
+///
+/// The synonyms map to the same variant, e.g. [`NcKey::SCROLL_UP`] to
+/// `Button(4)` and [`NcKey::RETURN`] to `Enter`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NcKeyCode {
+    Invalid,
+    /// Generated internally in response to `SIGWINCH`.
+    Resize,
+    Up,
+    Right,
+    Down,
+    Left,
+    Ins,
+    Del,
+    /// Backspace (sometimes).
+    Backspace,
+    PgDown,
+    PgUp,
+    Home,
+    End,
+    /// A function key, from 0 to 60.
+    F(u8),
+    Enter,
+    /// "clear-screen or erase"
+    Cls,
+    /// Down + left on keypad.
+    DLeft,
+    DRight,
+    /// Up + left on keypad.
+    ULeft,
+    URight,
+    Center,
+    Begin,
+    Cancel,
+    Close,
+    Command,
+    Copy,
+    Exit,
+    Print,
+    Refresh,
+    /// A mouse button, from 1 to 11.
+    Button(u8),
+    /// The end of input was reached.
+    Eof,
+    Esc,
+    Space,
+    /// Any other character.
+    Char(char),
+}
+
+// NcEvent ---------------------------------------------------------------------
+
+/// # `NcEvent` methods
+impl NcEvent {
+    /// Returns the character typed, if this is a text event, or the press or
+    /// repeat of a character key with no modifiers other than shift.
+    pub fn text(&self) -> Option<char> {
+        match *self {
+            NcEvent::Text(c) => Some(c),
+            NcEvent::Key { code, mods, kind }
+                if !mods.alt && !mods.ctrl && kind != NcEventKind::Release =>
+            {
+                match code {
+                    NcKeyCode::Char(c) if !c.is_control() => Some(c),
+                    NcKeyCode::Space => Some(' '),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the kind of the event.
+    ///
+    /// The `Resize` and `Text` events are of `Unknown` kind.
+    pub fn kind(&self) -> NcEventKind {
+        match *self {
+            NcEvent::Key { kind, .. } | NcEvent::Mouse { kind, .. } => kind,
+            _ => NcEventKind::Unknown,
+        }
+    }
+
+    /// Returns the modifiers of the event.
+    pub fn mods(&self) -> NcKeyMods {
+        match *self {
+            NcEvent::Key { mods, .. } | NcEvent::Mouse { mods, .. } => mods,
+            _ => NcKeyMods::default(),
+        }
+    }
+}
+
+impl From<(char, NcInput)> for NcEvent {
+    fn from((key, input): (char, NcInput)) -> Self {
+        let code = NcKeyCode::from(key);
+        let mods = NcKeyMods::from(&input);
+        let kind = NcEventKind::from(input.evtype);
+        match code {
+            NcKeyCode::Resize => NcEvent::Resize,
+            NcKeyCode::Button(button) => NcEvent::Mouse {
+                button,
+                y: input.y,
+                x: input.x,
+                mods,
+                kind,
+            },
+            _ if mods.is_none()
+                && kind == NcEventKind::Unknown
+                && !key.is_control()
+                && !c_api::nckey_supppuab_p(key) =>
+            {
+                NcEvent::Text(key)
+            }
+            _ => NcEvent::Key { code, mods, kind },
+        }
+    }
+}
+
+impl From<NcEvent> for (char, NcInput) {
+    fn from(event: NcEvent) -> Self {
+        let (key, y, x, mods, kind) = match event {
+            NcEvent::Key { code, mods, kind } => (code.into(), -1, -1, mods, kind),
+            NcEvent::Mouse {
+                button,
+                y,
+                x,
+                mods,
+                kind,
+            } => (NcKeyCode::Button(button).into(), y, x, mods, kind),
+            NcEvent::Resize => (
+                NcKey::RESIZE,
+                -1,
+                -1,
+                NcKeyMods::default(),
+                NcEventKind::Unknown,
+            ),
+            NcEvent::Text(c) => (c, -1, -1, NcKeyMods::default(), NcEventKind::Unknown),
+        };
+        let input = NcInput {
+            id: key as u32,
+            y,
+            x,
+            alt: mods.alt,
+            shift: mods.shift,
+            ctrl: mods.ctrl,
+            evtype: kind.into(),
+        };
+        (key, input)
+    }
+}
+
+// NcEventKind -----------------------------------------------------------------
+
+impl From<NcEvType> for NcEventKind {
+    fn from(evtype: NcEvType) -> Self {
+        match evtype {
+            NCEVTYPE_PRESS => NcEventKind::Press,
+            NCEVTYPE_REPEAT => NcEventKind::Repeat,
+            NCEVTYPE_RELEASE => NcEventKind::Release,
+            _ => NcEventKind::Unknown,
+        }
+    }
+}
+
+impl From<NcEventKind> for NcEvType {
+    fn from(kind: NcEventKind) -> Self {
+        match kind {
+            NcEventKind::Unknown => NCEVTYPE_UNKNOWN,
+            NcEventKind::Press => NCEVTYPE_PRESS,
+            NcEventKind::Repeat => NCEVTYPE_REPEAT,
+            NcEventKind::Release => NCEVTYPE_RELEASE,
+        }
+    }
+}
+
+// NcKeyMods -------------------------------------------------------------------
+
+/// # `NcKeyMods` methods
+impl NcKeyMods {
+    /// Returns `true` if no modifier is active.
+    pub const fn is_none(&self) -> bool {
+        !self.alt && !self.shift && !self.ctrl
+    }
+}
+
+impl From<&NcInput> for NcKeyMods {
+    fn from(input: &NcInput) -> Self {
+        Self {
+            alt: input.alt,
+            shift: input.shift,
+            ctrl: input.ctrl,
+        }
+    }
+}
+
+// NcKeyCode -------------------------------------------------------------------
+
+impl From<char> for NcKeyCode {
+    fn from(key: char) -> Self {
+        use NcKeyCode::*;
+        match key {
+            NcKey::INVALID => Invalid,
+            NcKey::RESIZE => Resize,
+            NcKey::UP => Up,
+            NcKey::RIGHT => Right,
+            NcKey::DOWN => Down,
+            NcKey::LEFT => Left,
+            NcKey::INS => Ins,
+            NcKey::DEL => Del,
+            NcKey::BACKSPACE => Backspace,
+            NcKey::PGDOWN => PgDown,
+            NcKey::PGUP => PgUp,
+            NcKey::HOME => Home,
+            NcKey::END => End,
+            NcKey::F00..=NcKey::F60 => F((key as u32 - NcKey::F00 as u32) as u8),
+            NcKey::ENTER => Enter,
+            NcKey::CLS => Cls,
+            NcKey::DLEFT => DLeft,
+            NcKey::DRIGHT => DRight,
+            NcKey::ULEFT => ULeft,
+            NcKey::URIGHT => URight,
+            NcKey::CENTER => Center,
+            NcKey::BEGIN => Begin,
+            NcKey::CANCEL => Cancel,
+            NcKey::CLOSE => Close,
+            NcKey::COMMAND => Command,
+            NcKey::COPY => Copy,
+            NcKey::EXIT => Exit,
+            NcKey::PRINT => Print,
+            NcKey::REFRESH => Refresh,
+            NcKey::BUTTON1..=NcKey::BUTTON11 => {
+                Button((key as u32 - NcKey::BUTTON1 as u32 + 1) as u8)
+            }
+            NcKey::EOF => Eof,
+            NcKey::ESC => Esc,
+            NcKey::SPACE => Space,
+            _ => Char(key),
+        }
+    }
+}
+
+impl From<NcKeyCode> for char {
+    /// Returns [`NcKey::INVALID`] for the function keys and mouse buttons
+    /// out of range.
+    fn from(code: NcKeyCode) -> Self {
+        use NcKeyCode::*;
+        match code {
+            Invalid => NcKey::INVALID,
+            Resize => NcKey::RESIZE,
+            Up => NcKey::UP,
+            Right => NcKey::RIGHT,
+            Down => NcKey::DOWN,
+            Left => NcKey::LEFT,
+            Ins => NcKey::INS,
+            Del => NcKey::DEL,
+            Backspace => NcKey::BACKSPACE,
+            PgDown => NcKey::PGDOWN,
+            PgUp => NcKey::PGUP,
+            Home => NcKey::HOME,
+            End => NcKey::END,
+            F(n) if n <= 60 => offset(NcKey::F00, n as u32),
+            Enter => NcKey::ENTER,
+            Cls => NcKey::CLS,
+            DLeft => NcKey::DLEFT,
+            DRight => NcKey::DRIGHT,
+            ULeft => NcKey::ULEFT,
+            URight => NcKey::URIGHT,
+            Center => NcKey::CENTER,
+            Begin => NcKey::BEGIN,
+            Cancel => NcKey::CANCEL,
+            Close => NcKey::CLOSE,
+            Command => NcKey::COMMAND,
+            Copy => NcKey::COPY,
+            Exit => NcKey::EXIT,
+            Print => NcKey::PRINT,
+            Refresh => NcKey::REFRESH,
+            Button(n) if (1..=11).contains(&n) => offset(NcKey::BUTTON1, n as u32 - 1),
+            Eof => NcKey::EOF,
+            Esc => NcKey::ESC,
+            Space => NcKey::SPACE,
+            Char(c) => c,
+            F(_) | Button(_) => NcKey::INVALID,
+        }
+    }
+}
+
+fn offset(key: char, n: u32) -> char {
+    core::char::from_u32(key as u32 + n).unwrap_or(NcKey::INVALID)
+}
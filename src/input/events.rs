@@ -0,0 +1,44 @@
+//! `NcEvents`
+
+use crate::{Nc, NcEvent, NcResult, NcTime};
+
+/// A blocking iterator over the input [`NcEvent`]s of an [`Nc`] context.
+///
+/// It's created with [`Nc.events()`][Nc#method.events].
+///
+/// Each call to `next` waits for the next event, at most the timeout, if any,
+/// and returns `None` when it expires. It's not fused: iterating again
+/// waits again, so a `for` loop stops at the first timeout, while calling
+/// `next` in a loop can tell the timeouts apart from the events.
+///
+/// # Example
+///
+/// ```ignore
+/// for event in nc.events(None) {
+///     match event? {
+///         NcEvent::Key { code: NcKeyCode::Esc, .. } => break,
+///         event => handle(event),
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct NcEvents<'nc> {
+    nc: &'nc mut Nc,
+    time: Option<NcTime>,
+}
+
+impl<'nc> NcEvents<'nc> {
+    /// New iterator over the events of `nc`, waiting at most `time` for each
+    /// one, or blocking at length if `None`.
+    pub fn new(nc: &'nc mut Nc, time: Option<NcTime>) -> Self {
+        Self { nc, time }
+    }
+}
+
+impl<'nc> Iterator for NcEvents<'nc> {
+    type Item = NcResult<NcEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nc.get_event(self.time).transpose()
+    }
+}
@@ -0,0 +1,368 @@
+//! `NcGesture` & `NcGestureRecognizer`
+
+use std::time::{Duration, Instant};
+
+use crate::{NcEvent, NcEventKind, NcInput, NcKeyMods};
+
+/// The default maximum time between the clicks of a double or triple click.
+pub const NCGESTURE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// A mouse gesture, recognized by an [`NcGestureRecognizer`].
+///
+/// All of them carry the coordinates of the event that completed them,
+/// and the modifiers held at that moment.
+///
+/// The drag gestures also carry the movement `dy`, `dx` since the previous
+/// drag gesture, or since the button was pressed, for `DragStart`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NcGesture {
+    /// A button was pressed and released on the same cell.
+    ///
+    /// The `count` is 2 for a double click, 3 for a triple click, and it
+    /// starts again from 1 after that.
+    Click {
+        button: u8,
+        y: i32,
+        x: i32,
+        count: u8,
+        mods: NcKeyMods,
+    },
+
+    /// A button started moving while pressed.
+    DragStart {
+        button: u8,
+        y: i32,
+        x: i32,
+        dy: i32,
+        dx: i32,
+        mods: NcKeyMods,
+    },
+
+    /// A button kept moving while pressed.
+    DragMove {
+        button: u8,
+        y: i32,
+        x: i32,
+        dy: i32,
+        dx: i32,
+        mods: NcKeyMods,
+    },
+
+    /// A button was released after moving.
+    DragEnd {
+        button: u8,
+        y: i32,
+        x: i32,
+        dy: i32,
+        dx: i32,
+        mods: NcKeyMods,
+    },
+
+    /// The wheel was scrolled one step, up (`dy == -1`), down (`dy == 1`),
+    /// left (`dx == -1`) or right (`dx == 1`).
+    Scroll {
+        y: i32,
+        x: i32,
+        dy: i32,
+        dx: i32,
+        mods: NcKeyMods,
+    },
+}
+
+/// # `NcGesture` methods
+impl NcGesture {
+    /// Returns the coordinates of the gesture, as `(y, x)`.
+    pub fn yx(&self) -> (i32, i32) {
+        match *self {
+            NcGesture::Click { y, x, .. }
+            | NcGesture::DragStart { y, x, .. }
+            | NcGesture::DragMove { y, x, .. }
+            | NcGesture::DragEnd { y, x, .. }
+            | NcGesture::Scroll { y, x, .. } => (y, x),
+        }
+    }
+
+    /// Returns the modifiers of the gesture.
+    pub fn mods(&self) -> NcKeyMods {
+        match *self {
+            NcGesture::Click { mods, .. }
+            | NcGesture::DragStart { mods, .. }
+            | NcGesture::DragMove { mods, .. }
+            | NcGesture::DragEnd { mods, .. }
+            | NcGesture::Scroll { mods, .. } => mods,
+        }
+    }
+}
+
+/// The button being held.
+#[derive(Clone, Copy, Debug)]
+struct Press {
+    button: u8,
+    y: i32,
+    x: i32,
+    dragging: bool,
+    /// When it was pressed, or last moved.
+    time: Instant,
+}
+
+/// The last click, to count the double and triple clicks.
+#[derive(Clone, Copy, Debug)]
+struct Click {
+    button: u8,
+    y: i32,
+    x: i32,
+    count: u8,
+    time: Instant,
+}
+
+/// Recognizes mouse gestures from the raw button press and release events.
+///
+/// It must be fed all the input events, as returned by either
+/// [`Nc.get_event()`][crate::Nc#method.get_event] or
+/// [`NcDirect.get_event()`][crate::NcDirect#method.get_event], or by their
+/// `get()` counterparts. The events that aren't from the mouse are ignored.
+///
+/// With `Nc`, the mouse events must be enabled with
+/// [`mouse_enable()`][crate::Nc#method.mouse_enable].
+///
+/// A click is recognized when a button is released on the same cell where it
+/// was pressed. A drag starts when a button moves while pressed, which is
+/// reported as a new press of the same button on a different cell.
+/// The wheel works while a button is held.
+///
+/// Since a release can be lost, e.g. when it happens outside the terminal,
+/// the held button is considered released when another button is pressed,
+/// or when it's reported again on a different cell after more than the
+/// [click interval][NcGestureRecognizer#method.interval]. That ends its drag,
+/// if any, and starts tracking the new press.
+///
+/// # Example
+///
+/// ```ignore
+/// nc.mouse_enable()?;
+/// let mut gestures = NcGestureRecognizer::new();
+///
+/// while let Some(event) = nc.get_event(None)? {
+///     match gestures.feed_event(&event) {
+///         Some(NcGesture::Click { count: 2, y, x, .. }) => select_word(y, x),
+///         Some(NcGesture::DragMove { dy, dx, .. }) => pan(dy, dx),
+///         Some(NcGesture::Scroll { dy, .. }) => scroll(dy),
+///         _ => (),
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct NcGestureRecognizer {
+    interval: Duration,
+    press: Option<Press>,
+    click: Option<Click>,
+}
+
+impl Default for NcGestureRecognizer {
+    fn default() -> Self {
+        Self {
+            interval: NCGESTURE_CLICK_INTERVAL,
+            press: None,
+            click: None,
+        }
+    }
+}
+
+/// # `NcGestureRecognizer` constructors
+impl NcGestureRecognizer {
+    /// New `NcGestureRecognizer`, with the default click interval.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// New `NcGestureRecognizer`, with a custom click `interval`.
+    pub fn with_interval(interval: Duration) -> Self {
+        Self {
+            interval,
+            ..Self::default()
+        }
+    }
+}
+
+/// # `NcGestureRecognizer` methods
+impl NcGestureRecognizer {
+    /// Returns the maximum time between the clicks of a double or triple click.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Sets the maximum time between the clicks of a double or triple click.
+    ///
+    /// Default: [`NCGESTURE_CLICK_INTERVAL`].
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Returns the button being dragged, if any.
+    pub fn dragging(&self) -> Option<u8> {
+        self.press.filter(|p| p.dragging).map(|p| p.button)
+    }
+
+    /// Forgets the button being held and the last click, e.g. after the
+    /// mouse was disabled.
+    pub fn reset(&mut self) {
+        self.press = None;
+        self.click = None;
+    }
+
+    /// Feeds the next input `event`, and returns the gesture it completes,
+    /// if any.
+    pub fn feed_event(&mut self, event: &NcEvent) -> Option<NcGesture> {
+        let (button, y, x, mods, kind) = match *event {
+            NcEvent::Mouse {
+                button,
+                y,
+                x,
+                mods,
+                kind,
+            } => (button, y, x, mods, kind),
+            _ => return None,
+        };
+
+        // the wheel
+        if (4..=7).contains(&button) {
+            if kind == NcEventKind::Release {
+                return None;
+            }
+            let (dy, dx) = match button {
+                4 => (-1, 0),
+                5 => (1, 0),
+                6 => (0, -1),
+                _ => (0, 1),
+            };
+            return Some(NcGesture::Scroll { y, x, dy, dx, mods });
+        }
+
+        let now = Instant::now();
+        if let Some(press) = self.press {
+            let lost_release = kind != NcEventKind::Release
+                && (press.button != button
+                    || ((y, x) != (press.y, press.x)
+                        && now.duration_since(press.time) > self.interval));
+            if lost_release {
+                self.press = Some(Press {
+                    button,
+                    y,
+                    x,
+                    dragging: false,
+                    time: now,
+                });
+                return if press.dragging {
+                    Some(NcGesture::DragEnd {
+                        button: press.button,
+                        y: press.y,
+                        x: press.x,
+                        dy: 0,
+                        dx: 0,
+                        mods,
+                    })
+                } else {
+                    None
+                };
+            }
+        }
+
+        match self.press {
+            None if kind != NcEventKind::Release => {
+                self.press = Some(Press {
+                    button,
+                    y,
+                    x,
+                    dragging: false,
+                    time: now,
+                });
+                None
+            }
+            Some(press) if press.button == button && kind == NcEventKind::Release => {
+                self.press = None;
+                let (dy, dx) = (y - press.y, x - press.x);
+                if press.dragging {
+                    Some(NcGesture::DragEnd {
+                        button,
+                        y,
+                        x,
+                        dy,
+                        dx,
+                        mods,
+                    })
+                } else if (dy, dx) == (0, 0) {
+                    Some(self.click(button, y, x, mods))
+                } else {
+                    // moved without reporting it, so it's not a click
+                    None
+                }
+            }
+            Some(mut press) if press.button == button && (y, x) != (press.y, press.x) => {
+                let (dy, dx) = (y - press.y, x - press.x);
+                let started = press.dragging;
+                press.y = y;
+                press.x = x;
+                press.dragging = true;
+                press.time = now;
+                self.press = Some(press);
+                if started {
+                    Some(NcGesture::DragMove {
+                        button,
+                        y,
+                        x,
+                        dy,
+                        dx,
+                        mods,
+                    })
+                } else {
+                    Some(NcGesture::DragStart {
+                        button,
+                        y,
+                        x,
+                        dy,
+                        dx,
+                        mods,
+                    })
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Feeds the next `input`, and returns the gesture it completes, if any,
+    /// like [`feed_event`][NcGestureRecognizer#method.feed_event].
+    pub fn feed_input(&mut self, input: NcInput) -> Option<NcGesture> {
+        let key = core::char::from_u32(input.id)?;
+        self.feed_event(&NcEvent::from((key, input)))
+    }
+
+    /// Registers a click, counting the previous ones.
+    fn click(&mut self, button: u8, y: i32, x: i32, mods: NcKeyMods) -> NcGesture {
+        let now = Instant::now();
+        let count = match self.click {
+            Some(c)
+                if c.button == button
+                    && (c.y, c.x) == (y, x)
+                    && c.count < 3
+                    && now.duration_since(c.time) <= self.interval =>
+            {
+                c.count + 1
+            }
+            _ => 1,
+        };
+        self.click = Some(Click {
+            button,
+            y,
+            x,
+            count,
+            time: now,
+        });
+        NcGesture::Click {
+            button,
+            y,
+            x,
+            count,
+            mods,
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//! `NcKeyBinding` & `NcKeyChord`
+
+use core::{fmt, str::FromStr};
+
+use crate::{NcError, NcErrorKind, NcEvent, NcEventKind, NcInput, NcKeyCode, NcKeyMods, NcResult};
+
+/// A key with its modifiers, that can be parsed from a human string like
+/// `"Ctrl-Alt-x"`, `"F5"`, `"Shift+PgDown"` or `"Esc"`, and formatted back.
+///
+/// The modifiers are separated by `-` or `+`, and both them and the key
+/// names are case insensitive. The letters are normalized to lowercase, so
+/// `"X"`, `"Shift-X"` and `"Shift+x"` are the same binding, which is formatted
+/// as `"Shift+x"`. So are the control characters, so `"\u{18}"` is `"Ctrl+x"`.
+///
+/// # Example
+///
+/// ```ignore
+/// let save: NcKeyBinding = "Ctrl-s".parse()?;
+/// if save.matches_event(&event) {
+///     save_file()?;
+/// }
+/// assert_eq![save.to_string(), "Ctrl+s"];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NcKeyBinding {
+    code: NcKeyCode,
+    mods: NcKeyMods,
+}
+
+/// A sequence of [`NcKeyBinding`]s that must be typed one after another,
+/// like `"g g"`, parsed from a string of whitespace separated bindings.
+///
+/// # Example
+///
+/// ```ignore
+/// let chord: NcKeyChord = "Ctrl-x Ctrl-s".parse()?;
+/// assert_eq![chord.len(), 2];
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NcKeyChord(Vec<NcKeyBinding>);
+
+// NcKeyBinding ----------------------------------------------------------------
+
+/// # `NcKeyBinding` constructors
+impl NcKeyBinding {
+    /// New `NcKeyBinding`, from its key `code` and its `mods`.
+    pub fn new(code: NcKeyCode, mods: NcKeyMods) -> Self {
+        Self::normalized(code, mods)
+    }
+
+    /// Parses a key binding from a human string, like `"Ctrl-Alt-x"`.
+    pub fn parse(binding: &str) -> NcResult<Self> {
+        let invalid = || {
+            NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                &format!["NcKeyBinding::parse(): invalid key {:?}", binding],
+            )
+        };
+        let mut mods = NcKeyMods::default();
+        let mut rest = binding.trim();
+        while let Some(split) = rest.find(|c: char| c == '-' || c == '+') {
+            // a lone separator is the key itself, like in "Ctrl--"
+            if split == 0 {
+                break;
+            }
+            match rest[..split].to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => mods.ctrl = true,
+                "alt" | "meta" => mods.alt = true,
+                "shift" => mods.shift = true,
+                _ => break,
+            }
+            rest = &rest[split + 1..];
+        }
+        let code = code_from_name(rest).ok_or_else(invalid)?;
+        Ok(Self::normalized(code, mods))
+    }
+
+    /// Returns the binding of a key or mouse button press, or `None` for
+    /// the releases and the resize events.
+    ///
+    /// A repeated key is the same as a pressed one.
+    pub fn from_event(event: &NcEvent) -> Option<Self> {
+        match *event {
+            NcEvent::Text(c) => Some(Self::normalized(c.into(), NcKeyMods::default())),
+            NcEvent::Key { code, mods, kind } if kind != NcEventKind::Release => {
+                Some(Self::normalized(code, mods))
+            }
+            NcEvent::Mouse {
+                button, mods, kind, ..
+            } if kind != NcEventKind::Release => {
+                Some(Self::normalized(NcKeyCode::Button(button), mods))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the binding of a key or mouse button press, or `None` for
+    /// the releases and the resize events.
+    pub fn from_input(input: NcInput) -> Option<Self> {
+        let key = core::char::from_u32(input.id)?;
+        Self::from_event(&NcEvent::from((key, input)))
+    }
+
+    fn normalized(code: NcKeyCode, mut mods: NcKeyMods) -> Self {
+        let code = match code {
+            NcKeyCode::Char(c) if c.is_uppercase() => {
+                mods.shift = true;
+                NcKeyCode::Char(c.to_lowercase().next().unwrap_or(c))
+            }
+            NcKeyCode::Char(c @ '\u{1}'..='\u{1a}') if !matches!(c, '\t' | '\n' | '\r') => {
+                mods.ctrl = true;
+                NcKeyCode::Char((c as u8 - 1 + b'a') as char)
+            }
+            NcKeyCode::Char(' ') => NcKeyCode::Space,
+            NcKeyCode::Char('\u{1b}') => NcKeyCode::Esc,
+            code => code,
+        };
+        Self { code, mods }
+    }
+}
+
+/// # `NcKeyBinding` methods
+impl NcKeyBinding {
+    /// Returns the key code.
+    pub fn code(&self) -> NcKeyCode {
+        self.code
+    }
+
+    /// Returns the modifiers.
+    pub fn mods(&self) -> NcKeyMods {
+        self.mods
+    }
+
+    /// Returns `true` if the `event` is a press of this binding.
+    pub fn matches_event(&self, event: &NcEvent) -> bool {
+        Self::from_event(event).map_or(false, |b| b == *self)
+    }
+
+    /// Returns `true` if the `input` is a press of this binding.
+    pub fn matches_input(&self, input: NcInput) -> bool {
+        Self::from_input(input).map_or(false, |b| b == *self)
+    }
+}
+
+impl FromStr for NcKeyBinding {
+    type Err = NcError;
+
+    fn from_str(binding: &str) -> NcResult<Self> {
+        Self::parse(binding)
+    }
+}
+
+impl From<NcKeyCode> for NcKeyBinding {
+    fn from(code: NcKeyCode) -> Self {
+        Self::normalized(code, NcKeyMods::default())
+    }
+}
+
+impl fmt::Display for NcKeyBinding {
+    /// Formats the binding for displaying it to the user, e.g. in a help
+    /// screen, like `"Ctrl+Alt+x"`. It can be parsed back.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.mods.ctrl {
+            f.write_str("Ctrl+")?;
+        }
+        if self.mods.alt {
+            f.write_str("Alt+")?;
+        }
+        if self.mods.shift {
+            f.write_str("Shift+")?;
+        }
+        use NcKeyCode::*;
+        let name = match self.code {
+            F(n) => return write!(f, "F{}", n),
+            Button(n) => return write!(f, "Button{}", n),
+            Char('\t') => "Tab",
+            Char(c) => return write!(f, "{}", c),
+            Invalid => "Invalid",
+            Resize => "Resize",
+            Up => "Up",
+            Right => "Right",
+            Down => "Down",
+            Left => "Left",
+            Ins => "Ins",
+            Del => "Del",
+            Backspace => "Backspace",
+            PgDown => "PgDown",
+            PgUp => "PgUp",
+            Home => "Home",
+            End => "End",
+            Enter => "Enter",
+            Cls => "Cls",
+            DLeft => "DLeft",
+            DRight => "DRight",
+            ULeft => "ULeft",
+            URight => "URight",
+            Center => "Center",
+            Begin => "Begin",
+            Cancel => "Cancel",
+            Close => "Close",
+            Command => "Command",
+            Copy => "Copy",
+            Exit => "Exit",
+            Print => "Print",
+            Refresh => "Refresh",
+            Eof => "Eof",
+            Esc => "Esc",
+            Space => "Space",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Returns the key code of a key `name`, or of a single character.
+fn code_from_name(name: &str) -> Option<NcKeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(c.into());
+    }
+    let lower = name.to_ascii_lowercase();
+    if let Some(n) = lower.strip_prefix('f') {
+        return n.parse().ok().filter(|n| *n <= 60).map(NcKeyCode::F);
+    }
+    if let Some(n) = lower.strip_prefix("button") {
+        return n
+            .parse()
+            .ok()
+            .filter(|n| (1..=11).contains(n))
+            .map(NcKeyCode::Button);
+    }
+    use NcKeyCode::*;
+    let code = match lower.as_str() {
+        "up" => Up,
+        "right" => Right,
+        "down" => Down,
+        "left" => Left,
+        "ins" | "insert" => Ins,
+        "del" | "delete" => Del,
+        "backspace" => Backspace,
+        "pgdown" | "pagedown" => PgDown,
+        "pgup" | "pageup" => PgUp,
+        "home" => Home,
+        "end" => End,
+        "enter" | "return" => Enter,
+        "cls" => Cls,
+        "dleft" => DLeft,
+        "dright" => DRight,
+        "uleft" => ULeft,
+        "uright" => URight,
+        "center" => Center,
+        "begin" => Begin,
+        "cancel" => Cancel,
+        "close" => Close,
+        "command" => Command,
+        "copy" => Copy,
+        "exit" => Exit,
+        "print" => Print,
+        "refresh" => Refresh,
+        "eof" => Eof,
+        "esc" | "escape" => Esc,
+        "space" => Space,
+        "tab" => Char('\t'),
+        _ => return None,
+    };
+    Some(code)
+}
+
+// NcKeyChord ------------------------------------------------------------------
+
+/// # `NcKeyChord` constructors
+impl NcKeyChord {
+    /// New `NcKeyChord`, from a non-empty sequence of `bindings`.
+    pub fn new(bindings: Vec<NcKeyBinding>) -> NcResult<Self> {
+        if bindings.is_empty() {
+            return Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "NcKeyChord::new(): empty chord",
+            ));
+        }
+        Ok(Self(bindings))
+    }
+
+    /// Parses a chord from a string of whitespace separated bindings,
+    /// like `"g g"` or `"Ctrl-x Ctrl-s"`.
+    pub fn parse(chord: &str) -> NcResult<Self> {
+        Self::new(
+            chord
+                .split_whitespace()
+                .map(NcKeyBinding::parse)
+                .collect::<NcResult<_>>()?,
+        )
+    }
+}
+
+/// # `NcKeyChord` methods
+impl NcKeyChord {
+    /// Returns the bindings of the chord.
+    pub fn bindings(&self) -> &[NcKeyBinding] {
+        &self.0
+    }
+
+    /// Returns the number of bindings of the chord.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Always returns `false`, since a chord can't be empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the chord starts with the `bindings`.
+    pub fn starts_with(&self, bindings: &[NcKeyBinding]) -> bool {
+        self.0.starts_with(bindings)
+    }
+}
+
+impl FromStr for NcKeyChord {
+    type Err = NcError;
+
+    fn from_str(chord: &str) -> NcResult<Self> {
+        Self::parse(chord)
+    }
+}
+
+impl From<NcKeyBinding> for NcKeyChord {
+    fn from(binding: NcKeyBinding) -> Self {
+        Self(vec![binding])
+    }
+}
+
+impl fmt::Display for NcKeyChord {
+    /// Formats the chord like `"Ctrl+x Ctrl+s"`. It can be parsed back.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, binding) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", binding)?;
+        }
+        Ok(())
+    }
+}
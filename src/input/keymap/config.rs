@@ -0,0 +1,75 @@
+//! Loading and saving the bindings of an `NcKeymap`.
+
+use std::{fs, path::Path};
+
+use super::NcKeymap;
+use crate::{NcError, NcErrorKind, NcResult};
+
+/// # `NcKeymap` config methods
+///
+/// The config format has one binding per line, with the keys of the chord,
+/// an `=` sign, and the name of the action. Empty lines and lines starting
+/// with `#` are ignored:
+///
+/// ```text
+/// # vim-like movement
+/// g g    = top
+/// Shift-g = bottom
+/// Ctrl-x Ctrl-s = save
+/// ```
+impl NcKeymap {
+    /// Binds the chords in the `config` to the actions registered with their
+    /// names.
+    ///
+    /// Returns an error with the line number if a line can't be parsed or if
+    /// it names an unknown action, in which case the bindings from the
+    /// previous lines are kept.
+    pub fn load(&mut self, config: &str) -> NcResult<&mut Self> {
+        for (number, line) in config.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |msg: &str| {
+                NcError::with_kind(
+                    NcErrorKind::InvalidArgument,
+                    &format!["NcKeymap.load(): line {}: {}", number + 1, msg],
+                )
+            };
+            // the keys can contain an `=`, but the action names can't
+            let split = line.rfind('=').ok_or_else(|| error("missing `=`"))?;
+            let (keys, name) = (line[..split].trim(), line[split + 1..].trim());
+            if keys.is_empty() || name.is_empty() {
+                return Err(error("missing keys or action"));
+            }
+            self.bind_action(keys, name)
+                .map_err(|e| error("invalid binding").caused_by(e))?;
+        }
+        Ok(self)
+    }
+
+    /// Binds the chords in the config file at `path`,
+    /// like [`load`][NcKeymap#method.load].
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> NcResult<&mut Self> {
+        let config = fs::read_to_string(path.as_ref()).map_err(|e| {
+            NcError::with_kind(
+                NcErrorKind::Io(e.raw_os_error().unwrap_or(0)),
+                &format!["NcKeymap.load_file(): {}", path.as_ref().display()],
+            )
+            .caused_by(e)
+        })?;
+        self.load(&config)
+    }
+
+    /// Returns the bindings to named actions in the config format,
+    /// so they can be loaded back.
+    pub fn config(&self) -> String {
+        let mut config = String::new();
+        for (chord, name) in self.bindings() {
+            if let Some(name) = name {
+                config.push_str(&format!["{} = {}\n", chord, name]);
+            }
+        }
+        config
+    }
+}
@@ -0,0 +1,338 @@
+//! `NcKeymap`
+
+use std::{
+    collections::HashMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::{NcError, NcErrorKind, NcEvent, NcInput, NcResult};
+
+mod binding;
+mod config;
+
+pub use binding::{NcKeyBinding, NcKeyChord};
+
+/// An action bound to a key in an [`NcKeymap`].
+///
+/// The error it returns is propagated by [`NcKeymap::handle_event`].
+pub type NcKeymapAction = Box<dyn FnMut() -> NcResult<()>>;
+
+/// The default time allowed between the keys of a chord.
+pub const NCKEYMAP_CHORD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Binds [`NcKeyChord`]s to actions, and dispatches the input events to them.
+///
+/// The keys can be bound directly to a closure, or to a named action, which
+/// allows to load the bindings from a [config file][NcKeymap#method.load].
+///
+/// When a chord like `"g g"` is being typed, the keys are consumed while
+/// they're a prefix of any chord, until the chord is complete, or until
+/// they stop matching, or until the [timeout][NcKeymap#method.set_timeout]
+/// between two keys expires.
+///
+/// When a chord is also the prefix of a longer one, like `"g"` and `"g g"`,
+/// its action is run when the next key doesn't continue the longer one, or
+/// when calling [`flush`][NcKeymap#method.flush] after the timeout expires.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut keymap = NcKeymap::new();
+/// keymap.bind("Ctrl-l", move || view.redraw())?;
+/// keymap.action("top", move || view.top());
+/// keymap.load("g g = top\nHome = top\n")?;
+///
+/// loop {
+///     let time = keymap
+///         .pending_timeout()
+///         .map(|t| NcTime::new(t.as_secs() as i64, t.subsec_nanos() as i64));
+///     match nc.get_event(time)? {
+///         Some(event) => {
+///             if !keymap.handle_event(&event)? {
+///                 handle(event);
+///             }
+///         }
+///         None => {
+///             keymap.flush()?;
+///         }
+///     }
+/// }
+/// ```
+pub struct NcKeymap {
+    bindings: Vec<(NcKeyChord, usize)>,
+    /// The actions, with `None` in the slots freed by unbinding or
+    /// rebinding the chords of unnamed actions.
+    actions: Vec<Option<NcKeymapAction>>,
+    names: HashMap<String, usize>,
+    pending: Vec<NcKeyBinding>,
+    last: Option<Instant>,
+    timeout: Duration,
+}
+
+impl Default for NcKeymap {
+    fn default() -> Self {
+        Self {
+            bindings: vec![],
+            actions: vec![],
+            names: HashMap::new(),
+            pending: vec![],
+            last: None,
+            timeout: NCKEYMAP_CHORD_TIMEOUT,
+        }
+    }
+}
+
+impl fmt::Debug for NcKeymap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bindings: Vec<_> = self.bindings().map(|(c, n)| (c.to_string(), n)).collect();
+        f.debug_struct("NcKeymap")
+            .field("bindings", &bindings)
+            .field("pending", &self.pending)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// # `NcKeymap` constructors
+impl NcKeymap {
+    /// New empty `NcKeymap`, with the default chord timeout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// # `NcKeymap` methods
+impl NcKeymap {
+    /// Binds the `keys` chord to the `action` closure.
+    ///
+    /// Replaces the previous binding of the same chord, if any.
+    pub fn bind<F>(&mut self, keys: &str, action: F) -> NcResult<&mut Self>
+    where
+        F: FnMut() -> NcResult<()> + 'static,
+    {
+        let chord = NcKeyChord::parse(keys)?;
+        let action: NcKeymapAction = Box::new(action);
+        let index = match self.actions.iter().position(Option::is_none) {
+            Some(index) => {
+                self.actions[index] = Some(action);
+                index
+            }
+            None => {
+                self.actions.push(Some(action));
+                self.actions.len() - 1
+            }
+        };
+        self.insert(chord, index);
+        Ok(self)
+    }
+
+    /// Registers the `action` closure with a `name`, to be bound with
+    /// [`bind_action`][NcKeymap#method.bind_action] or from a config file.
+    ///
+    /// Replaces the previous action with the same name, if any,
+    /// keeping its bindings.
+    pub fn action<F>(&mut self, name: &str, action: F) -> &mut Self
+    where
+        F: FnMut() -> NcResult<()> + 'static,
+    {
+        match self.names.get(name) {
+            Some(&index) => self.actions[index] = Some(Box::new(action)),
+            None => {
+                self.actions.push(Some(Box::new(action)));
+                self.names.insert(name.into(), self.actions.len() - 1);
+            }
+        }
+        self
+    }
+
+    /// Binds the `keys` chord to the action registered with `name`.
+    ///
+    /// Returns an error if there's no action with that name.
+    pub fn bind_action(&mut self, keys: &str, name: &str) -> NcResult<&mut Self> {
+        let chord = NcKeyChord::parse(keys)?;
+        let index = *self.names.get(name).ok_or_else(|| {
+            NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                &format!["NcKeymap.bind_action(): unknown action {:?}", name],
+            )
+        })?;
+        self.insert(chord, index);
+        Ok(self)
+    }
+
+    /// Removes the binding of the `keys` chord.
+    ///
+    /// Returns `true` if it was bound.
+    pub fn unbind(&mut self, keys: &str) -> NcResult<bool> {
+        let chord = NcKeyChord::parse(keys)?;
+        match self.bindings.iter().position(|(c, _)| *c == chord) {
+            Some(pos) => {
+                let (_, index) = self.bindings.remove(pos);
+                self.release(index);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Returns an iterator over the bound chords, with the names of their
+    /// actions, if they have one, e.g. for listing them in a help screen.
+    pub fn bindings(&self) -> impl Iterator<Item = (&NcKeyChord, Option<&str>)> {
+        self.bindings.iter().map(move |(chord, index)| {
+            let name = self
+                .names
+                .iter()
+                .find(|(_, i)| *i == index)
+                .map(|(name, _)| name.as_str());
+            (chord, name)
+        })
+    }
+
+    /// Returns the chords bound to the action registered with `name`.
+    pub fn keys_of(&self, name: &str) -> Vec<&NcKeyChord> {
+        match self.names.get(name) {
+            Some(index) => self
+                .bindings
+                .iter()
+                .filter(|(_, i)| i == index)
+                .map(|(chord, _)| chord)
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Returns the time allowed between the keys of a chord.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Sets the time allowed between the keys of a chord.
+    ///
+    /// Default: [`NCKEYMAP_CHORD_TIMEOUT`].
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the keys of the chord being typed, e.g. for displaying them
+    /// in a status line.
+    pub fn pending(&self) -> &[NcKeyBinding] {
+        &self.pending
+    }
+
+    /// Returns the time left for typing the next key of the chord,
+    /// or `None` if no chord is being typed.
+    ///
+    /// It can be used as the timeout for waiting for the next event,
+    /// and calling [`flush`][NcKeymap#method.flush] if there's none.
+    pub fn pending_timeout(&self) -> Option<Duration> {
+        let last = self.last.filter(|_| !self.pending.is_empty())?;
+        Some(self.timeout.checked_sub(last.elapsed()).unwrap_or_default())
+    }
+
+    /// Runs the action of the chord being typed, if it's bound, and discards
+    /// the chord.
+    ///
+    /// Returns `true` if an action was run.
+    pub fn flush(&mut self) -> NcResult<bool> {
+        let pending = core::mem::take(&mut self.pending);
+        self.last = None;
+        match self.find(&pending) {
+            Some(index) => self.run(index),
+            None => Ok(false),
+        }
+    }
+
+    /// Dispatches the `event` to the bound actions.
+    ///
+    /// Returns `true` if it was consumed, either by running an action, or
+    /// as part of a chord being typed, or `false` if it ought to be
+    /// processed further.
+    ///
+    /// Key and button releases are never consumed.
+    pub fn handle_event(&mut self, event: &NcEvent) -> NcResult<bool> {
+        let key = match NcKeyBinding::from_event(event) {
+            Some(key) => key,
+            None => return Ok(false),
+        };
+        if self.pending_timeout() == Some(Duration::default()) {
+            self.flush()?;
+        }
+        self.pending.push(key);
+        loop {
+            if self.is_prefix(&self.pending) {
+                self.last = Some(Instant::now());
+                return Ok(true);
+            }
+            if let Some(index) = self.find(&self.pending) {
+                self.pending.clear();
+                self.last = None;
+                return self.run(index);
+            }
+            if self.pending.len() == 1 {
+                self.pending.clear();
+                self.last = None;
+                return Ok(false);
+            }
+            // the previous keys can be a complete chord by themselves
+            self.pending.pop();
+            self.flush()?;
+            self.pending.push(key);
+        }
+    }
+
+    /// Dispatches the `input` to the bound actions,
+    /// like [`handle_event`][NcKeymap#method.handle_event].
+    pub fn handle_input(&mut self, input: NcInput) -> NcResult<bool> {
+        match core::char::from_u32(input.id) {
+            Some(key) => self.handle_event(&NcEvent::from((key, input))),
+            None => Ok(false),
+        }
+    }
+
+    fn insert(&mut self, chord: NcKeyChord, index: usize) {
+        match self.bindings.iter_mut().find(|(c, _)| *c == chord) {
+            Some(binding) => {
+                let old = core::mem::replace(&mut binding.1, index);
+                if old != index {
+                    self.release(old);
+                }
+            }
+            None => self.bindings.push((chord, index)),
+        }
+    }
+
+    /// Frees the slot of the action at `index` if it's unnamed and no longer
+    /// bound, so that it can be reused.
+    fn release(&mut self, index: usize) {
+        let named = self.names.values().any(|&i| i == index);
+        let bound = self.bindings.iter().any(|&(_, i)| i == index);
+        if !named && !bound {
+            self.actions[index] = None;
+        }
+    }
+
+    /// Runs the action at `index`, returning `Ok(true)` if it succeeds.
+    fn run(&mut self, index: usize) -> NcResult<bool> {
+        match self.actions[index].as_mut() {
+            Some(action) => action().map(|_| true),
+            None => Ok(false),
+        }
+    }
+
+    /// Returns the index of the action bound exactly to the `keys`.
+    fn find(&self, keys: &[NcKeyBinding]) -> Option<usize> {
+        self.bindings
+            .iter()
+            .find(|(chord, _)| chord.bindings() == keys)
+            .map(|(_, index)| *index)
+    }
+
+    /// Returns `true` if the `keys` are the prefix of a longer chord.
+    fn is_prefix(&self, keys: &[NcKeyBinding]) -> bool {
+        self.bindings
+            .iter()
+            .any(|(chord, _)| chord.len() > keys.len() && chord.starts_with(keys))
+    }
+}
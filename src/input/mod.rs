@@ -12,7 +12,27 @@
 
 use crate::NcDim;
 
+#[cfg(test)]
+mod test;
+
+mod event;
+mod events;
+mod gesture;
+mod keymap;
 pub(crate) mod reimplemented;
+mod router;
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+mod stream;
+
+pub use event::{NcEvent, NcEventKind, NcKeyCode, NcKeyMods};
+pub use events::NcEvents;
+pub use gesture::{NcGesture, NcGestureRecognizer, NCGESTURE_CLICK_INTERVAL};
+pub use keymap::{NcKeyBinding, NcKeyChord, NcKeymap, NcKeymapAction, NCKEYMAP_CHORD_TIMEOUT};
+pub use router::{NcMouseEvent, NcMouseHandler, NcMouseRouter};
+#[cfg(feature = "async-std")]
+pub use stream::NcAsyncStdEvents;
+#[cfg(feature = "tokio")]
+pub use stream::NcTokioEvents;
 
 /// Reads and decodes input events.
 ///
@@ -0,0 +1,325 @@
+//! `NcMouseRouter`
+
+use core::marker::PhantomData;
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    c_api, pile::local_yx, plane::userptr, Nc, NcEvent, NcEventKind, NcKeyMods, NcOffset, NcPile,
+    NcPlane, NcPlaneHandle, NcResult,
+};
+
+/// A mouse event delivered by an [`NcMouseRouter`] to a plane, with the
+/// coordinates relative to the origin of the plane.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NcMouseEvent {
+    /// The pointer entered the plane, or one of its descendants.
+    Enter { y: NcOffset, x: NcOffset },
+
+    /// The pointer left the plane, and all of its descendants.
+    Leave,
+
+    /// A mouse button was pressed or released over the plane.
+    Button {
+        button: u8,
+        y: NcOffset,
+        x: NcOffset,
+        mods: NcKeyMods,
+        kind: NcEventKind,
+    },
+}
+
+/// A handler of the [`NcMouseEvent`]s of a plane, registered in an
+/// [`NcMouseRouter`].
+///
+/// It receives the plane, and returns `true` if it consumed the event.
+pub type NcMouseHandler<'a> = Box<dyn FnMut(&mut NcPlane, NcMouseEvent) -> NcResult<bool> + 'a>;
+
+/// Routes the mouse events to handlers registered per plane.
+///
+/// Each button event is delivered to the topmost plane under the pointer,
+/// as returned by [`NcPile::plane_at`], in coordinates relative to it. If that
+/// plane has no handler, or if its handler doesn't consume the event, it
+/// bubbles up to the planes it's bound to, until one of them consumes it.
+///
+/// While a button is held, its events are delivered to the plane where it was
+/// pressed, even if the pointer moves outside of it.
+///
+/// When the plane under the pointer changes, a `Leave` event is delivered to
+/// the planes the pointer left, and an `Enter` event to the planes it entered,
+/// including the planes they're bound to. Note that the terminal only reports
+/// the movements of the pointer while a button is held.
+///
+/// The planes are identified by their Rust data, so the handler of a
+/// destroyed plane is never called again, nor inherited by a new plane
+/// allocated at its address. It's only freed when it's
+/// [removed][NcMouseRouter#method.remove], or when the router is dropped.
+///
+/// # Example
+///
+/// ```ignore
+/// nc.mouse_enable()?;
+/// let pile = NcPile::stdpile(&nc);
+/// let mut router = NcMouseRouter::new();
+///
+/// router.on(&mut button, |plane, event| match event {
+///     NcMouseEvent::Enter { .. } => plane.set_bg_rgb(0x404040).map(|_| true),
+///     NcMouseEvent::Leave => plane.set_bg_rgb(0x202020).map(|_| true),
+///     NcMouseEvent::Button { kind: NcEventKind::Release, .. } => Ok(clicked()),
+///     _ => Ok(false),
+/// });
+///
+/// while let Some(event) = nc.get_event(None)? {
+///     if !router.route(&pile, &event)? {
+///         handle(event);
+///     }
+/// }
+/// ```
+pub struct NcMouseRouter<'nc, 'a> {
+    handlers: HashMap<PlaneKey, NcMouseHandler<'a>>,
+    /// The planes with data under the pointer, the topmost first.
+    hovered: Vec<PlaneKey>,
+    /// The plane where the button being held was pressed, or the nearest
+    /// plane with data it's bound to.
+    grab: Option<(u8, PlaneKey)>,
+    _nc: PhantomData<&'nc Nc>,
+}
+
+/// Identifies a plane by its address and by the id of its Rust data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct PlaneKey {
+    plane: *mut NcPlane,
+    id: usize,
+}
+
+impl PlaneKey {
+    /// Returns the key of the `plane`, or `None` if it has no Rust data.
+    ///
+    /// # Safety
+    /// `plane` must be valid.
+    unsafe fn of(plane: *mut NcPlane) -> Option<Self> {
+        userptr::get_const(plane).map(|data| Self { plane, id: data.id })
+    }
+
+    /// Returns `true` if the plane is still in the `pile`, and it's the same.
+    fn is_live(&self, pile: &NcPile) -> bool {
+        is_in(pile, self.plane) && unsafe { Self::of(self.plane) } == Some(*self)
+    }
+}
+
+impl<'nc, 'a> Default for NcMouseRouter<'nc, 'a> {
+    fn default() -> Self {
+        Self {
+            handlers: HashMap::new(),
+            hovered: vec![],
+            grab: None,
+            _nc: PhantomData,
+        }
+    }
+}
+
+impl<'nc, 'a> fmt::Debug for NcMouseRouter<'nc, 'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let planes: Vec<_> = self.handlers.keys().collect();
+        f.debug_struct("NcMouseRouter")
+            .field("handlers", &planes)
+            .field("hovered", &self.hovered)
+            .field("grab", &self.grab)
+            .finish()
+    }
+}
+
+/// # `NcMouseRouter` constructors
+impl<'nc, 'a> NcMouseRouter<'nc, 'a> {
+    /// New `NcMouseRouter`, without handlers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// # `NcMouseRouter` methods
+impl<'nc, 'a> NcMouseRouter<'nc, 'a> {
+    /// Registers the `handler` of the mouse events of `plane`.
+    ///
+    /// Replaces the previous handler of the plane, if any.
+    ///
+    /// The `plane` can also be the plane of a widget.
+    pub fn on<F>(&mut self, plane: &mut NcPlane, handler: F) -> &mut Self
+    where
+        F: FnMut(&mut NcPlane, NcMouseEvent) -> NcResult<bool> + 'a,
+    {
+        let key = PlaneKey {
+            plane: plane as *mut NcPlane,
+            id: userptr::get_or_init(plane).id,
+        };
+        self.handlers.insert(key, Box::new(handler));
+        self
+    }
+
+    /// Removes the handler of `plane`, and forgets about it.
+    ///
+    /// Returns `true` if it had a handler.
+    pub fn remove(&mut self, plane: &NcPlane) -> bool {
+        let key = match unsafe { PlaneKey::of(plane as *const NcPlane as *mut NcPlane) } {
+            Some(key) => key,
+            None => return false,
+        };
+        self.hovered.retain(|k| *k != key);
+        if self.grab.map_or(false, |(_, k)| k == key) {
+            self.grab = None;
+        }
+        self.handlers.remove(&key).is_some()
+    }
+
+    /// Returns `true` if the pointer is over `plane`, or over any of its
+    /// descendants.
+    ///
+    /// Only the planes with a handler, or with other Rust data, are tracked.
+    pub fn is_hovered(&self, plane: &NcPlane) -> bool {
+        match unsafe { PlaneKey::of(plane as *const NcPlane as *mut NcPlane) } {
+            Some(key) => self.hovered.contains(&key),
+            None => false,
+        }
+    }
+
+    /// Routes the `event` to the handlers of the planes of `pile`.
+    ///
+    /// Returns `true` if a handler consumed the button event, or `false` if
+    /// none did, or if it's not a mouse event.
+    pub fn route(&mut self, pile: &NcPile<'nc>, event: &NcEvent) -> NcResult<bool> {
+        let (button, y, x, mods, kind) = match *event {
+            NcEvent::Mouse {
+                button,
+                y,
+                x,
+                mods,
+                kind,
+            } => (button, y, x, mods, kind),
+            _ => return Ok(false),
+        };
+
+        let hit = pile.hit(y, x);
+        self.hover(pile, hit, y, x)?;
+
+        // the grabbing plane could have been destroyed
+        let grab = self.grab.filter(|(_, key)| key.is_live(pile));
+        let target = match grab {
+            Some((grabbed, key)) if grabbed == button => {
+                if kind == NcEventKind::Release {
+                    self.grab = None;
+                }
+                Some(key.plane)
+            }
+            _ => {
+                // the wheel buttons are never held
+                if kind != NcEventKind::Release && !(4..=7).contains(&button) {
+                    self.grab = hit
+                        .filter(|plane| is_in(pile, *plane))
+                        .and_then(nearest_key)
+                        .map(|key| (button, key));
+                }
+                hit
+            }
+        };
+
+        // a handler can destroy its plane, or the planes it's bound to
+        let mut next = target;
+        while let Some(plane) = next.filter(|plane| is_in(pile, *plane)) {
+            next = parent(plane);
+            let key = unsafe { PlaneKey::of(plane) };
+            if let Some(handler) = key.and_then(|key| self.handlers.get_mut(&key)) {
+                let mut plane = unsafe { NcPlaneHandle::from_ptr(plane) };
+                let (ly, lx) = local_yx(&plane, y, x);
+                let event = NcMouseEvent::Button {
+                    button,
+                    y: ly,
+                    x: lx,
+                    mods,
+                    kind,
+                };
+                if handler(&mut *plane, event)? {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Updates the hovered planes, delivering the `Leave` and `Enter` events.
+    ///
+    /// All the events are delivered, even if a handler fails, and the first
+    /// error is returned.
+    fn hover(
+        &mut self,
+        pile: &NcPile<'nc>,
+        hit: Option<*mut NcPlane>,
+        y: NcOffset,
+        x: NcOffset,
+    ) -> NcResult<()> {
+        let mut hovered = vec![];
+        let mut next = hit;
+        while let Some(plane) = next {
+            hovered.extend(unsafe { PlaneKey::of(plane) });
+            next = parent(plane);
+        }
+
+        let left: Vec<_> = self
+            .hovered
+            .iter()
+            .copied()
+            .filter(|key| !hovered.contains(key))
+            .collect();
+        let entered: Vec<_> = hovered
+            .iter()
+            .copied()
+            .filter(|key| !self.hovered.contains(key))
+            .collect();
+        self.hovered = hovered;
+
+        // the destroyed planes can't be left, and a handler can destroy
+        // the planes
+        let mut res = Ok(());
+        for key in left {
+            if let Some(handler) = self.handlers.get_mut(&key).filter(|_| key.is_live(pile)) {
+                let mut plane = unsafe { NcPlaneHandle::from_ptr(key.plane) };
+                res = res.and(handler(&mut *plane, NcMouseEvent::Leave).map(|_| ()));
+            }
+        }
+        for key in entered.into_iter().rev() {
+            if let Some(handler) = self.handlers.get_mut(&key).filter(|_| key.is_live(pile)) {
+                let mut plane = unsafe { NcPlaneHandle::from_ptr(key.plane) };
+                let (ly, lx) = local_yx(&plane, y, x);
+                let event = NcMouseEvent::Enter { y: ly, x: lx };
+                res = res.and(handler(&mut *plane, event).map(|_| ()));
+            }
+        }
+        res
+    }
+}
+
+/// Returns the key of `plane`, or of the nearest plane it's bound to with
+/// Rust data.
+fn nearest_key(plane: *mut NcPlane) -> Option<PlaneKey> {
+    let mut next = Some(plane);
+    while let Some(plane) = next {
+        if let Some(key) = unsafe { PlaneKey::of(plane) } {
+            return Some(key);
+        }
+        next = parent(plane);
+    }
+    None
+}
+
+/// Returns `true` if `plane` is in the `pile`.
+fn is_in(pile: &NcPile, plane: *mut NcPlane) -> bool {
+    pile.contains(plane)
+}
+
+/// Returns the plane `plane` is bound to, or `None` if it's a root plane.
+fn parent(plane: *mut NcPlane) -> Option<*mut NcPlane> {
+    let parent = unsafe { c_api::ncplane_parent(plane) };
+    if parent.is_null() || parent == plane {
+        None
+    } else {
+        Some(parent)
+    }
+}
@@ -0,0 +1,192 @@
+//! Asynchronous streams of input events.
+//!
+//! They wait for the [input readiness file descriptor][crate::Nc#method.inputready_fd]
+//! to become readable, and then read the available events without blocking,
+//! so they can be polled along with other asynchronous sources.
+
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::{
+    io,
+    os::unix::io::{AsRawFd, RawFd},
+};
+
+use futures_core::Stream;
+
+#[cfg(feature = "async-std")]
+use crate::c_api::libc::{self, c_int};
+use crate::{Nc, NcError, NcErrorKind, NcEvent, NcResult};
+
+/// The input readiness file descriptor, which is owned by notcurses,
+/// and is not closed when dropped.
+#[derive(Debug)]
+struct InputFd(RawFd);
+
+impl AsRawFd for InputFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0
+    }
+}
+
+fn io_error(error: io::Error, msg: &str) -> NcError {
+    NcError::with_kind(
+        NcErrorKind::Io(error.raw_os_error().unwrap_or(0)),
+        &format!["{}: {}", msg, error],
+    )
+}
+
+/// Returns the next event that is ready, or waits for the readiness of the
+/// input with `poll_ready`.
+fn poll_event(
+    nc: &mut Nc,
+    cx: &mut Context<'_>,
+    mut poll_ready: impl FnMut(&mut Context<'_>) -> Poll<io::Result<()>>,
+) -> Poll<Option<NcResult<NcEvent>>> {
+    loop {
+        // there can be events already read by notcurses
+        match nc.get_event_nblock() {
+            Ok(Some(event)) => return Poll::Ready(Some(Ok(event))),
+            Ok(None) => (),
+            Err(e) => return Poll::Ready(Some(Err(e))),
+        }
+        match poll_ready(cx) {
+            Poll::Ready(Ok(())) => continue,
+            Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(io_error(e, "polling the input")))),
+            Poll::Pending => return Poll::Pending,
+        }
+    }
+}
+
+/// A [`Stream`] of the input [`NcEvent`]s of an [`Nc`] context,
+/// for the tokio runtime.
+///
+/// It's created with [`Nc.tokio_events()`][Nc#method.tokio_events],
+/// and it never ends.
+///
+/// *Requires the `tokio` feature.*
+///
+/// # Example
+///
+/// ```ignore
+/// let mut events = nc.tokio_events()?;
+/// loop {
+///     tokio::select! {
+///         Some(event) = events.next() => handle(event?),
+///         Ok(conn) = listener.accept() => serve(conn),
+///     }
+/// }
+/// ```
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct NcTokioEvents<'nc> {
+    nc: &'nc mut Nc,
+    fd: tokio_crate::io::unix::AsyncFd<InputFd>,
+}
+
+#[cfg(feature = "tokio")]
+impl<'nc> NcTokioEvents<'nc> {
+    /// New stream over the events of `nc`.
+    ///
+    /// It must be called from within a tokio runtime, with IO enabled.
+    pub fn new(nc: &'nc mut Nc) -> NcResult<Self> {
+        let fd = InputFd(nc.inputready_fd()?);
+        let fd =
+            tokio_crate::io::unix::AsyncFd::with_interest(fd, tokio_crate::io::Interest::READABLE)
+                .map_err(|e| io_error(e, "NcTokioEvents::new()"))?;
+        Ok(Self { nc, fd })
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<'nc> Stream for NcTokioEvents<'nc> {
+    type Item = NcResult<NcEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let fd = &this.fd;
+        poll_event(this.nc, cx, |cx| match fd.poll_read_ready(cx) {
+            Poll::Ready(Ok(mut guard)) => {
+                // the events are read in the next iteration
+                guard.clear_ready();
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        })
+    }
+}
+
+/// A [`Stream`] of the input [`NcEvent`]s of an [`Nc`] context,
+/// for the async-std runtime.
+///
+/// It's created with [`Nc.async_std_events()`][Nc#method.async_std_events],
+/// and it never ends.
+///
+/// *Requires the `async-std` feature.*
+///
+/// # Example
+///
+/// ```ignore
+/// let mut events = nc.async_std_events()?.fuse();
+/// loop {
+///     futures::select! {
+///         event = events.next() => handle(event.unwrap()?),
+///         _ = timer => tick(),
+///     }
+/// }
+/// ```
+#[cfg(feature = "async-std")]
+#[derive(Debug)]
+pub struct NcAsyncStdEvents<'nc> {
+    nc: &'nc mut Nc,
+    fd: async_io::Async<InputFd>,
+    /// The file status flags of the file descriptor, restored when dropped.
+    flags: c_int,
+}
+
+#[cfg(feature = "async-std")]
+impl<'nc> NcAsyncStdEvents<'nc> {
+    /// New stream over the events of `nc`.
+    ///
+    /// Note that the input readiness file descriptor is put in non-blocking
+    /// mode while the stream is alive, and its flags are restored when the
+    /// stream is dropped.
+    pub fn new(nc: &'nc mut Nc) -> NcResult<Self> {
+        let raw = nc.inputready_fd()?;
+        let flags = unsafe { libc::fcntl(raw, libc::F_GETFL) };
+        if flags < 0 {
+            return Err(io_error(
+                io::Error::last_os_error(),
+                "NcAsyncStdEvents::new()",
+            ));
+        }
+        match async_io::Async::new(InputFd(raw)) {
+            Ok(fd) => Ok(Self { nc, fd, flags }),
+            Err(e) => {
+                // the flags could have been changed before failing
+                unsafe { libc::fcntl(raw, libc::F_SETFL, flags) };
+                Err(io_error(e, "NcAsyncStdEvents::new()"))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async-std")]
+impl<'nc> Drop for NcAsyncStdEvents<'nc> {
+    fn drop(&mut self) {
+        unsafe { libc::fcntl(self.fd.get_ref().0, libc::F_SETFL, self.flags) };
+    }
+}
+
+#[cfg(feature = "async-std")]
+impl<'nc> Stream for NcAsyncStdEvents<'nc> {
+    type Item = NcResult<NcEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let fd = &this.fd;
+        poll_event(this.nc, cx, |cx| fd.poll_readable(cx))
+    }
+}
@@ -0,0 +1,104 @@
+//! Test `NcEvent` conversions.
+
+use serial_test::serial;
+
+use crate::{NcEvType, NcEvTypeApi, NcEvent, NcEventKind, NcInput, NcKey, NcKeyCode, NcKeyMods};
+
+fn input(key: char, evtype: NcEvType) -> NcInput {
+    NcInput {
+        id: key as u32,
+        y: -1,
+        x: -1,
+        alt: false,
+        shift: false,
+        ctrl: false,
+        evtype,
+    }
+}
+
+#[test]
+#[serial]
+fn keycode_from_nckey() {
+    assert_eq![NcKeyCode::from(NcKey::UP), NcKeyCode::Up];
+    assert_eq![NcKeyCode::from(NcKey::F00), NcKeyCode::F(0)];
+    assert_eq![NcKeyCode::from(NcKey::F60), NcKeyCode::F(60)];
+    assert_eq![NcKeyCode::from(NcKey::RETURN), NcKeyCode::Enter];
+    assert_eq![NcKeyCode::from(NcKey::SCROLL_DOWN), NcKeyCode::Button(5)];
+    assert_eq![NcKeyCode::from(NcKey::ESC), NcKeyCode::Esc];
+    assert_eq![NcKeyCode::from('x'), NcKeyCode::Char('x')];
+}
+
+#[test]
+#[serial]
+fn keycode_roundtrip() {
+    for key in (NcKey::INVALID as u32..=NcKey::EOF as u32).filter_map(core::char::from_u32) {
+        let code = NcKeyCode::from(key);
+        if code != NcKeyCode::Char(key) {
+            assert_eq![char::from(code), key];
+        }
+    }
+    assert_eq![char::from(NcKeyCode::F(61)), NcKey::INVALID];
+    assert_eq![char::from(NcKeyCode::Button(0)), NcKey::INVALID];
+}
+
+#[test]
+#[serial]
+fn event_from_input() {
+    let text = NcEvent::from(('a', input('a', NcEvType::UNKNOWN)));
+    assert_eq![text, NcEvent::Text('a')];
+
+    let mut ctrl = input('a', NcEvType::UNKNOWN);
+    ctrl.ctrl = true;
+    assert_eq![
+        NcEvent::from(('a', ctrl)),
+        NcEvent::Key {
+            code: NcKeyCode::Char('a'),
+            mods: NcKeyMods {
+                alt: false,
+                shift: false,
+                ctrl: true
+            },
+            kind: NcEventKind::Unknown,
+        }
+    ];
+
+    let release = NcEvent::from((NcKey::LEFT, input(NcKey::LEFT, NcEvType::RELEASE)));
+    assert_eq![release.kind(), NcEventKind::Release];
+
+    let mut click = input(NcKey::BUTTON1, NcEvType::PRESS);
+    click.y = 3;
+    click.x = 7;
+    assert_eq![
+        NcEvent::from((NcKey::BUTTON1, click)),
+        NcEvent::Mouse {
+            button: 1,
+            y: 3,
+            x: 7,
+            mods: NcKeyMods::default(),
+            kind: NcEventKind::Press,
+        }
+    ];
+
+    let resize = NcEvent::from((NcKey::RESIZE, input(NcKey::RESIZE, NcEvType::UNKNOWN)));
+    assert_eq![resize, NcEvent::Resize];
+}
+
+#[test]
+#[serial]
+fn event_roundtrip() {
+    let mut click = input(NcKey::BUTTON3, NcEvType::RELEASE);
+    click.y = 1;
+    click.x = 2;
+    click.shift = true;
+    let inputs = [
+        ('z', input('z', NcEvType::UNKNOWN)),
+        ('z', input('z', NcEvType::REPEAT)),
+        (NcKey::F12, input(NcKey::F12, NcEvType::PRESS)),
+        (NcKey::BUTTON3, click),
+    ];
+    for (key, input) in inputs.iter().copied() {
+        let (key2, input2) = <(char, NcInput)>::from(NcEvent::from((key, input)));
+        assert_eq![key, key2];
+        assert![crate::c_api::ncinput_equal_p(input, input2)];
+    }
+}
@@ -0,0 +1,25 @@
+//! Test `NcEvents`.
+
+use serial_test::serial;
+
+use crate::{NcContext, NcResult, NcTime};
+
+#[test]
+#[serial]
+fn events_timeout() -> NcResult<()> {
+    let mut nc = NcContext::new()?;
+    let mut events = nc.events(Some(NcTime::new(0, 1_000_000)));
+
+    // without input each call times out, and the iterator is not fused
+    assert![events.next().is_none()];
+    assert![events.next().is_none()];
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn events_fused() -> NcResult<()> {
+    let mut nc = NcContext::new()?;
+    assert_eq![nc.events(Some(NcTime::new(0, 0))).fuse().count(), 0];
+    Ok(())
+}
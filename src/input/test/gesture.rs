@@ -0,0 +1,189 @@
+//! Test `NcGestureRecognizer`.
+
+use std::time::Duration;
+
+use serial_test::serial;
+
+use crate::{NcEvent, NcEventKind, NcGesture, NcGestureRecognizer, NcKeyMods};
+
+fn mouse(button: u8, y: i32, x: i32, kind: NcEventKind) -> NcEvent {
+    NcEvent::Mouse {
+        button,
+        y,
+        x,
+        mods: NcKeyMods::default(),
+        kind,
+    }
+}
+
+fn click(g: &mut NcGestureRecognizer, button: u8, y: i32, x: i32) -> Option<NcGesture> {
+    assert_eq![g.feed_event(&mouse(button, y, x, NcEventKind::Press)), None];
+    g.feed_event(&mouse(button, y, x, NcEventKind::Release))
+}
+
+fn count(gesture: Option<NcGesture>) -> u8 {
+    match gesture {
+        Some(NcGesture::Click { count, .. }) => count,
+        g => panic!["not a click: {:?}", g],
+    }
+}
+
+#[test]
+#[serial]
+fn gesture_clicks() {
+    let mut g = NcGestureRecognizer::new();
+    assert_eq![
+        click(&mut g, 1, 2, 3),
+        Some(NcGesture::Click {
+            button: 1,
+            y: 2,
+            x: 3,
+            count: 1,
+            mods: NcKeyMods::default(),
+        })
+    ];
+    assert_eq![count(click(&mut g, 1, 2, 3)), 2];
+    assert_eq![count(click(&mut g, 1, 2, 3)), 3];
+    assert_eq![count(click(&mut g, 1, 2, 3)), 1];
+
+    // another cell or button
+    assert_eq![count(click(&mut g, 1, 2, 4)), 1];
+    assert_eq![count(click(&mut g, 3, 2, 4)), 1];
+
+    // too slow
+    g.set_interval(Duration::from_millis(0));
+    std::thread::sleep(Duration::from_millis(2));
+    assert_eq![count(click(&mut g, 3, 2, 4)), 1];
+
+    // not from the mouse
+    assert_eq![g.feed_event(&NcEvent::Text('x')), None];
+}
+
+#[test]
+#[serial]
+fn gesture_drag() {
+    let mut g = NcGestureRecognizer::new();
+    let ctrl = NcKeyMods {
+        ctrl: true,
+        ..NcKeyMods::default()
+    };
+
+    assert_eq![g.feed_event(&mouse(1, 5, 5, NcEventKind::Press)), None];
+    assert_eq![
+        g.feed_event(&mouse(1, 6, 8, NcEventKind::Press)),
+        Some(NcGesture::DragStart {
+            button: 1,
+            y: 6,
+            x: 8,
+            dy: 1,
+            dx: 3,
+            mods: NcKeyMods::default(),
+        })
+    ];
+    assert_eq![g.dragging(), Some(1)];
+
+    // the releases of other buttons are ignored while dragging
+    assert_eq![g.feed_event(&mouse(3, 6, 8, NcEventKind::Release)), None];
+
+    let moved = NcEvent::Mouse {
+        button: 1,
+        y: 6,
+        x: 6,
+        mods: ctrl,
+        kind: NcEventKind::Press,
+    };
+    assert_eq![
+        g.feed_event(&moved),
+        Some(NcGesture::DragMove {
+            button: 1,
+            y: 6,
+            x: 6,
+            dy: 0,
+            dx: -2,
+            mods: ctrl,
+        })
+    ];
+    assert_eq![
+        g.feed_event(&mouse(1, 5, 5, NcEventKind::Release)),
+        Some(NcGesture::DragEnd {
+            button: 1,
+            y: 5,
+            x: 5,
+            dy: -1,
+            dx: -1,
+            mods: NcKeyMods::default(),
+        })
+    ];
+    assert_eq![g.dragging(), None];
+}
+
+#[test]
+#[serial]
+fn gesture_lost_release() {
+    let mut g = NcGestureRecognizer::with_interval(Duration::from_millis(10));
+
+    // pressing another button releases the dragged one
+    g.feed_event(&mouse(1, 0, 0, NcEventKind::Press));
+    g.feed_event(&mouse(1, 0, 1, NcEventKind::Press));
+    assert_eq![
+        g.feed_event(&mouse(3, 2, 2, NcEventKind::Press)),
+        Some(NcGesture::DragEnd {
+            button: 1,
+            y: 0,
+            x: 1,
+            dy: 0,
+            dx: 0,
+            mods: NcKeyMods::default(),
+        })
+    ];
+    assert_eq![g.dragging(), None];
+    assert_eq![
+        count(g.feed_event(&mouse(3, 2, 2, NcEventKind::Release))),
+        1
+    ];
+
+    // and the held one isn't clicked when it's released
+    g.feed_event(&mouse(1, 4, 4, NcEventKind::Press));
+    assert_eq![g.feed_event(&mouse(3, 4, 4, NcEventKind::Press)), None];
+    assert_eq![g.feed_event(&mouse(1, 4, 4, NcEventKind::Release)), None];
+    assert_eq![
+        count(g.feed_event(&mouse(3, 4, 4, NcEventKind::Release))),
+        1
+    ];
+
+    // pressing the held button on another cell after a gap presses it again
+    g.feed_event(&mouse(1, 6, 6, NcEventKind::Press));
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq![g.feed_event(&mouse(1, 8, 8, NcEventKind::Press)), None];
+    assert_eq![g.dragging(), None];
+    assert_eq![
+        count(g.feed_event(&mouse(1, 8, 8, NcEventKind::Release))),
+        1
+    ];
+}
+
+#[test]
+#[serial]
+fn gesture_scroll() {
+    let mut g = NcGestureRecognizer::new();
+    let scroll = g.feed_event(&mouse(5, 1, 2, NcEventKind::Press));
+    assert_eq![
+        scroll,
+        Some(NcGesture::Scroll {
+            y: 1,
+            x: 2,
+            dy: 1,
+            dx: 0,
+            mods: NcKeyMods::default(),
+        })
+    ];
+    assert_eq![scroll.map(|s| s.yx()), Some((1, 2))];
+    assert_eq![g.feed_event(&mouse(5, 1, 2, NcEventKind::Release)), None];
+
+    // the wheel works while dragging
+    g.feed_event(&mouse(1, 0, 0, NcEventKind::Press));
+    g.feed_event(&mouse(1, 0, 1, NcEventKind::Press));
+    let scroll = g.feed_event(&mouse(6, 0, 1, NcEventKind::Unknown));
+    assert_eq![scroll.map(|s| s.yx()), Some((0, 1))];
+    assert_eq![g.dragging(), Some(1)];
+}
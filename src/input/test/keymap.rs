@@ -0,0 +1,233 @@
+//! Test `NcKeymap` and the key bindings.
+
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use serial_test::serial;
+
+use crate::{
+    NcEvent, NcEventKind, NcKeyBinding, NcKeyChord, NcKeyCode, NcKeyMods, NcKeymap, NcResult,
+};
+
+fn mods(ctrl: bool, alt: bool, shift: bool) -> NcKeyMods {
+    NcKeyMods { alt, shift, ctrl }
+}
+
+fn key(code: NcKeyCode) -> NcEvent {
+    NcEvent::Key {
+        code,
+        mods: NcKeyMods::default(),
+        kind: NcEventKind::Unknown,
+    }
+}
+
+fn counter() -> (Rc<Cell<u32>>, impl FnMut() -> NcResult<()>) {
+    let count = Rc::new(Cell::new(0));
+    let count2 = count.clone();
+    (count, move || {
+        count2.set(count2.get() + 1);
+        Ok(())
+    })
+}
+
+#[test]
+#[serial]
+fn binding_parse() -> NcResult<()> {
+    let b = NcKeyBinding::parse("Ctrl-Alt-x")?;
+    assert_eq![b.code(), NcKeyCode::Char('x')];
+    assert_eq![b.mods(), mods(true, true, false)];
+
+    assert_eq![NcKeyBinding::parse("F5")?.code(), NcKeyCode::F(5)];
+    assert_eq![NcKeyBinding::parse("esc")?.code(), NcKeyCode::Esc];
+    let b = NcKeyBinding::parse("Shift+PgDown")?;
+    assert_eq![
+        (b.code(), b.mods()),
+        (NcKeyCode::PgDown, mods(false, false, true))
+    ];
+    assert_eq![NcKeyBinding::parse("Ctrl--")?.code(), NcKeyCode::Char('-')];
+
+    // the letters are normalized
+    assert_eq![NcKeyBinding::parse("X")?, NcKeyBinding::parse("shift-x")?];
+    assert_eq![
+        NcKeyBinding::parse("\u{18}")?,
+        NcKeyBinding::parse("Ctrl+x")?
+    ];
+
+    assert![NcKeyBinding::parse("Hyper-x").is_err()];
+    assert![NcKeyBinding::parse("F61").is_err()];
+    assert![NcKeyBinding::parse("").is_err()];
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn binding_format() -> NcResult<()> {
+    for s in &[
+        "Ctrl+Alt+x",
+        "F5",
+        "Shift+PgDown",
+        "Esc",
+        "Space",
+        "Tab",
+        "Ctrl+-",
+    ] {
+        assert_eq![NcKeyBinding::parse(s)?.to_string(), *s];
+    }
+    assert_eq![
+        NcKeyBinding::parse("control-X")?.to_string(),
+        "Ctrl+Shift+x"
+    ];
+
+    let chord = NcKeyChord::parse(" Ctrl-x   ctrl-s ")?;
+    assert_eq![chord.len(), 2];
+    assert_eq![chord.to_string(), "Ctrl+x Ctrl+s"];
+    assert![NcKeyChord::parse("  ").is_err()];
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn binding_matches() -> NcResult<()> {
+    let x = NcKeyBinding::parse("x")?;
+    assert![x.matches_event(&NcEvent::Text('x'))];
+    assert![!x.matches_event(&NcEvent::Text('X'))];
+    let release = NcEvent::Key {
+        code: NcKeyCode::Char('x'),
+        mods: NcKeyMods::default(),
+        kind: NcEventKind::Release,
+    };
+    assert![!x.matches_event(&release)];
+
+    let ctrl_x = NcEvent::Key {
+        code: NcKeyCode::Char('x'),
+        mods: mods(true, false, false),
+        kind: NcEventKind::Press,
+    };
+    assert![NcKeyBinding::parse("Ctrl-x")?.matches_event(&ctrl_x)];
+    assert![NcKeyBinding::parse("Space")?.matches_event(&NcEvent::Text(' '))];
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn keymap_dispatch() -> NcResult<()> {
+    let (quit, quit_action) = counter();
+    let (top, top_action) = counter();
+    let (down, down_action) = counter();
+
+    let mut keymap = NcKeymap::new();
+    keymap.bind("Ctrl-q", quit_action)?;
+    keymap.action("top", top_action);
+    keymap.action("down", down_action);
+    keymap.load("# movement\ng g = top\n\nj = down\nDown = down\n")?;
+
+    assert![!keymap.handle_event(&NcEvent::Text('k'))?];
+    assert![keymap.handle_event(&NcEvent::Text('j'))?];
+    assert![keymap.handle_event(&key(NcKeyCode::Down))?];
+    assert_eq![down.get(), 2];
+
+    // a chord
+    assert![keymap.handle_event(&NcEvent::Text('g'))?];
+    assert_eq![keymap.pending().len(), 1];
+    assert![keymap.handle_event(&NcEvent::Text('g'))?];
+    assert_eq![top.get(), 1];
+    assert![keymap.pending().is_empty()];
+
+    // a broken chord
+    assert![keymap.handle_event(&NcEvent::Text('g'))?];
+    assert![keymap.handle_event(&NcEvent::Text('j'))?];
+    assert_eq![(top.get(), down.get()), (1, 3)];
+
+    // an expired chord
+    keymap.set_timeout(Duration::from_millis(0));
+    assert![keymap.handle_event(&NcEvent::Text('g'))?];
+    assert![keymap.handle_event(&NcEvent::Text('g'))?];
+    assert_eq![top.get(), 1];
+    assert![!keymap.flush()?];
+
+    let ctrl_q = NcEvent::Key {
+        code: NcKeyCode::Char('q'),
+        mods: mods(true, false, false),
+        kind: NcEventKind::Press,
+    };
+    assert![keymap.handle_event(&ctrl_q)?];
+    assert_eq![quit.get(), 1];
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn keymap_prefix_chord() -> NcResult<()> {
+    let (g, g_action) = counter();
+    let (gg, gg_action) = counter();
+
+    let mut keymap = NcKeymap::new();
+    keymap.bind("g", g_action)?.bind("g g", gg_action)?;
+
+    // waits for the longer chord
+    assert![keymap.handle_event(&NcEvent::Text('g'))?];
+    assert_eq![g.get(), 0];
+    assert![keymap.flush()?];
+    assert_eq![g.get(), 1];
+
+    // the next key doesn't continue it
+    assert![keymap.handle_event(&NcEvent::Text('g'))?];
+    assert![!keymap.handle_event(&NcEvent::Text('x'))?];
+    assert_eq![(g.get(), gg.get()), (2, 0)];
+
+    assert![keymap.handle_event(&NcEvent::Text('g'))?];
+    assert![keymap.handle_event(&NcEvent::Text('g'))?];
+    assert_eq![(g.get(), gg.get()), (2, 1)];
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn keymap_free_actions() -> NcResult<()> {
+    let (a, a_action) = counter();
+    let (b, b_action) = counter();
+    let (named, named_action) = counter();
+
+    let mut keymap = NcKeymap::new();
+    keymap.bind("x", a_action)?;
+    assert_eq![Rc::strong_count(&a), 2];
+
+    // rebinding the chord frees the previous action
+    keymap.bind("x", b_action)?;
+    assert_eq![Rc::strong_count(&a), 1];
+    assert![keymap.handle_event(&NcEvent::Text('x'))?];
+    assert_eq![(a.get(), b.get()), (0, 1)];
+
+    // and so does unbinding it
+    assert![keymap.unbind("x")?];
+    assert_eq![Rc::strong_count(&b), 1];
+    assert![!keymap.handle_event(&NcEvent::Text('x'))?];
+
+    // but not if it's a named action
+    keymap
+        .action("named", named_action)
+        .bind_action("y", "named")?;
+    assert![keymap.unbind("y")?];
+    assert_eq![Rc::strong_count(&named), 2];
+    keymap.bind_action("y", "named")?;
+    assert![keymap.handle_event(&NcEvent::Text('y'))?];
+    assert_eq![named.get(), 1];
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn keymap_config() -> NcResult<()> {
+    let mut keymap = NcKeymap::new();
+    keymap.action("save", || Ok(()));
+    keymap.action("eq", || Ok(()));
+    keymap.load("Ctrl-x Ctrl-s = save\nCtrl-= = eq\n")?;
+    assert_eq![keymap.config(), "Ctrl+x Ctrl+s = save\nCtrl+= = eq\n"];
+    assert_eq![keymap.keys_of("save").len(), 1];
+
+    assert![keymap.load("Ctrl-x = unknown").is_err()];
+    assert![keymap.load("Ctrl-x save").is_err()];
+    assert![keymap.load(" = save").is_err()];
+    assert![keymap.unbind("Ctrl-x Ctrl-s")?];
+    assert![!keymap.unbind("Ctrl-x Ctrl-s")?];
+    Ok(())
+}
@@ -0,0 +1,19 @@
+//! `NcInput` tests.
+
+#[cfg(test)]
+mod event;
+
+#[cfg(test)]
+mod events;
+
+#[cfg(test)]
+mod gesture;
+
+#[cfg(test)]
+mod keymap;
+
+#[cfg(test)]
+mod router;
+
+#[cfg(all(test, any(feature = "tokio", feature = "async-std")))]
+mod stream;
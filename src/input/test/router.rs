@@ -0,0 +1,171 @@
+//! Test `NcMouseRouter`.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use serial_test::serial;
+
+use crate::{
+    NcAlpha, NcAlphaApi, NcContext, NcError, NcEvent, NcEventKind, NcKeyMods, NcMouseEvent,
+    NcMouseRouter, NcMultiSelector, NcPile, NcPlaneHandle, NcPlaneOptions, NcResult,
+};
+
+fn mouse(y: i32, x: i32, kind: NcEventKind) -> NcEvent {
+    NcEvent::Mouse {
+        button: 1,
+        y,
+        x,
+        mods: NcKeyMods::default(),
+        kind,
+    }
+}
+
+/// New 3x3 plane at `y, x`, transparent except for its origin.
+fn new_plane<'nc>(pile: &NcPile<'nc>, y: i32, x: i32) -> NcResult<NcPlaneHandle<'nc>> {
+    let mut plane = pile.new_plane(NcPlaneOptions::new(y, x, 3, 3))?;
+    plane.set_base("", 0, NcAlpha::TRANSPARENT as u64)?;
+    plane.putstr("x")?;
+    Ok(plane)
+}
+
+#[test]
+#[serial]
+fn router_route() -> NcResult<()> {
+    let nc = NcContext::new()?;
+    let pile = NcPile::new(&nc, 10, 10)?;
+    let mut plane = new_plane(&pile, 1, 1)?;
+
+    let events = Rc::new(RefCell::new(vec![]));
+    let log = events.clone();
+    let mut router = NcMouseRouter::new();
+    router.on(&mut plane, move |_, event| {
+        log.borrow_mut().push(event);
+        Ok(true)
+    });
+    assert![router.route(&pile, &mouse(1, 1, NcEventKind::Press))?];
+    assert![router.is_hovered(&plane)];
+    assert_eq![
+        vec![
+            NcMouseEvent::Enter { y: 0, x: 0 },
+            NcMouseEvent::Button {
+                button: 1,
+                y: 0,
+                x: 0,
+                mods: NcKeyMods::default(),
+                kind: NcEventKind::Press,
+            },
+        ],
+        *events.borrow()
+    ];
+
+    // the button is grabbed until released
+    events.borrow_mut().clear();
+    assert![router.route(&pile, &mouse(5, 5, NcEventKind::Press))?];
+    assert![!router.is_hovered(&plane)];
+    assert_eq![NcMouseEvent::Leave, events.borrow()[0]];
+
+    assert![router.remove(&plane)];
+    unsafe { pile.destroy()? };
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn router_destroyed_plane() -> NcResult<()> {
+    let nc = NcContext::new()?;
+    let pile = NcPile::new(&nc, 10, 10)?;
+    let mut plane = new_plane(&pile, 1, 1)?;
+
+    let events = Rc::new(RefCell::new(vec![]));
+    let log = events.clone();
+    let mut router = NcMouseRouter::new();
+    router.on(&mut plane, move |_, event| {
+        log.borrow_mut().push(event);
+        Ok(true)
+    });
+    assert![router.route(&pile, &mouse(1, 1, NcEventKind::Press))?];
+    assert![router.is_hovered(&plane)];
+    events.borrow_mut().clear();
+
+    // the destroyed plane is neither left nor grabbed
+    unsafe { plane.destroy()? };
+    assert![!router.route(&pile, &mouse(5, 5, NcEventKind::Release))?];
+    assert![events.borrow().is_empty()];
+
+    // and a new plane, even at the same address, doesn't inherit its handler
+    let plane = new_plane(&pile, 1, 1)?;
+    assert![!router.route(&pile, &mouse(1, 1, NcEventKind::Press))?];
+    assert![!router.is_hovered(&plane)];
+    assert![events.borrow().is_empty()];
+
+    unsafe { pile.destroy()? };
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn router_widget_plane() -> NcResult<()> {
+    let nc = NcContext::new()?;
+    let pile = NcPile::new(&nc, 20, 40)?;
+    let mut plane = new_plane(&pile, 1, 1)?;
+    let selector = NcMultiSelector::builder()
+        .item("first", "", false)
+        .build(&mut plane)?;
+    // opaque, whatever the selector draws
+    selector.plane()?.set_base(" ", 0, 0)?;
+
+    let clicks = Rc::new(Cell::new(0));
+    let count = clicks.clone();
+    let mut router = NcMouseRouter::new();
+    router.on(selector.plane()?, move |_, event| {
+        if let NcMouseEvent::Button { .. } = event {
+            count.set(count.get() + 1);
+        }
+        Ok(true)
+    });
+    assert![router.route(&pile, &mouse(1, 1, NcEventKind::Press))?];
+    assert![router.is_hovered(selector.plane()?)];
+    assert_eq![1, clicks.get()];
+
+    assert![router.remove(selector.plane()?)];
+    unsafe { selector.destroy()? };
+    unsafe { pile.destroy()? };
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn router_hover_error() -> NcResult<()> {
+    let nc = NcContext::new()?;
+    let pile = NcPile::new(&nc, 10, 10)?;
+    let mut left = new_plane(&pile, 1, 1)?;
+    let mut entered = new_plane(&pile, 5, 5)?;
+
+    let events = Rc::new(RefCell::new(vec![]));
+    let log = events.clone();
+    let mut router = NcMouseRouter::new();
+    router.on(&mut left, |_, event| match event {
+        NcMouseEvent::Leave => Err(NcError::new_msg("leave")),
+        _ => Ok(true),
+    });
+    router.on(&mut entered, move |_, event| {
+        log.borrow_mut().push(event);
+        Ok(true)
+    });
+    assert![router.route(&pile, &mouse(1, 1, NcEventKind::Press))?];
+    assert![router.route(&pile, &mouse(1, 1, NcEventKind::Release))?];
+
+    // the error of the left plane doesn't keep the entered one from knowing
+    let error = router
+        .route(&pile, &mouse(5, 5, NcEventKind::Press))
+        .unwrap_err();
+    assert_eq!["leave", error.msg];
+    assert_eq![vec![NcMouseEvent::Enter { y: 0, x: 0 }], *events.borrow()];
+    assert![!router.is_hovered(&left)];
+    assert![router.is_hovered(&entered)];
+
+    unsafe { pile.destroy()? };
+    Ok(())
+}
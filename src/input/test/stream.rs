@@ -0,0 +1,70 @@
+//! Test the input event streams.
+
+use core::{
+    pin::Pin,
+    ptr::null,
+    task::{Context, RawWaker, RawWakerVTable, Waker},
+};
+
+use futures_core::Stream;
+use serial_test::serial;
+
+use crate::{NcContext, NcResult};
+
+/// Returns a waker that does nothing, for polling a stream once.
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    unsafe { Waker::from_raw(RawWaker::new(null(), &VTABLE)) }
+}
+
+/// Polls the `stream` once, returning whether it was pending.
+fn is_pending<S: Stream + Unpin>(stream: &mut S) -> bool {
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    Pin::new(stream).poll_next(&mut cx).is_pending()
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "tokio")]
+fn tokio_events_pending() -> NcResult<()> {
+    let rt = tokio_crate::runtime::Builder::new_current_thread()
+        .enable_io()
+        .build()
+        .expect("runtime");
+    let _guard = rt.enter();
+
+    let mut nc = NcContext::new()?;
+    let mut events = nc.tokio_events()?;
+
+    // without input the stream is pending, and it doesn't end
+    assert![is_pending(&mut events)];
+    assert![is_pending(&mut events)];
+    Ok(())
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "async-std")]
+fn async_std_events_flags() -> NcResult<()> {
+    use crate::c_api::libc;
+
+    let mut nc = NcContext::new()?;
+    let fd = nc.inputready_fd()?;
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+    {
+        let mut events = nc.async_std_events()?;
+        let nonblock = unsafe { libc::fcntl(fd, libc::F_GETFL) } & libc::O_NONBLOCK;
+        assert_ne![nonblock, 0];
+
+        // without input the stream is pending
+        assert![is_pending(&mut events)];
+    }
+    // the flags are restored when the stream is dropped
+    assert_eq![flags, unsafe { libc::fcntl(fd, libc::F_GETFL) }];
+    Ok(())
+}
@@ -33,11 +33,30 @@
 //! # fn main() {}
 //! ```
 //!
-//! The `Drop` trait is not implemented for any wrapping type in this library.
+//! The types returned by the constructors of this API, like `&mut Nc` or
+//! `&mut NcPlane`, don't implement the `Drop` trait. This means you still have
+//! to manually call the `stop()` method for `Nc` and `NcDirect` objects, and
+//! the `destroy()` method for the rest of types that allocate, (like `NcPlane`,
+//! `NcMenu`…) at the end of their scope.
 //!
-//! This means you still have to manually call the `stop()` method for `Nc`
-//! and `NcDirect` objects, and the `destroy()` method for the rest of types that
-//! allocate, (like `NcPlane`, `NcMenu`…) at the end of their scope, since the
+//! The exception are the owned [`NcContext`] and [`NcDirectContext`] types,
+//! which dereference to `Nc` and `NcDirect` respectively, and stop the terminal
+//! when they're dropped, even while unwinding from a panic:
+//!
+//! ```ignore
+//! use libnotcurses_sys::*;
+//!
+//! fn main() -> NcResult<()> {
+//!     let nc = NcContext::new_cli()?;
+//!     nc.stdplane().putstr("hello world")?;
+//!     nc.render()?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! With the `owned` feature enabled, the `owned` module also offers
+//! `Drop`-implementing wrappers for the rest of the allocating types, like
+//! `OwnedPlane` or `OwnedVisual`.
 //!
 //! But they do implement methods and use `NcResult` as the return type,
 //! for handling errors in the way we are used to in Rust.
@@ -48,6 +67,9 @@
 //! leverage type checking, and they implement methods through traits
 //! (e.g. `NcChannelApi` must be in scope to use the `NcChannel` methods.
 //!
+//! With the `tokio` or `async-std` features enabled, the input events can
+//! also be read as an asynchronous stream, e.g. with `Nc.tokio_events()`.
+//!
 //! ### even more like Rust
 //!
 //! The *WIP* sister crate
@@ -124,9 +146,11 @@ mod alpha;
 mod bindings;
 mod blitter;
 mod r#box;
+mod callback;
 mod capabilities;
 mod cell;
 mod channel;
+mod context;
 mod dimension;
 mod direct;
 mod error;
@@ -140,6 +164,7 @@ mod macros;
 mod metric;
 mod notcurses;
 mod palette;
+mod pile;
 mod pixel;
 mod plane;
 mod resizecb;
@@ -149,8 +174,12 @@ mod style;
 mod time;
 mod visual;
 
+pub mod string;
 pub mod widgets;
 
+#[cfg(feature = "owned")]
+pub mod owned;
+
 // wrapper types and traits
 //
 // `*Api` traits allows using methods and associated constants over type aliased
@@ -165,22 +194,35 @@ pub use blitter::{NcBlitter, NcBlitterApi, NcBlitterGeometry};
 pub use capabilities::NcCapabilities;
 pub use cell::NcCell;
 pub use channel::{NcChannel, NcChannelApi, NcChannels, NcChannelsApi, NcComponent, NcRgb};
+pub use context::{NcContext, NcDirectContext};
 pub use dimension::{NcDim, NcOffset};
 pub use direct::{NcDirect, NcDirectFlags, NcDirectFlagsApi};
-pub use error::{NcError, NcIntResult, NcIntResultApi, NcResult};
-pub use fade::{NcFadeCb, NcFadeCtx};
-pub use fd::{NcFdPlane, NcFdPlaneOptions, NcSubproc, NcSubprocOptions};
+pub use error::{NcError, NcErrorKind, NcIntResult, NcIntResultApi, NcResult};
+pub use fade::{NcFadeCb, NcFadeCtx, NcFadeIter, NcFadeStep};
+pub use fd::{
+    NcFdCb, NcFdCbUnsafe, NcFdDoneCb, NcFdDoneCbUnsafe, NcFdPlane, NcFdPlaneOptions, NcSubproc,
+    NcSubprocBuilder, NcSubprocOptions,
+};
 pub use file::NcFile;
-pub use input::{NcEvType, NcEvTypeApi, NcInput};
+#[cfg(feature = "async-std")]
+pub use input::NcAsyncStdEvents;
+#[cfg(feature = "tokio")]
+pub use input::NcTokioEvents;
+pub use input::{
+    NcEvType, NcEvTypeApi, NcEvent, NcEventKind, NcEvents, NcGesture, NcGestureRecognizer, NcInput,
+    NcKeyBinding, NcKeyChord, NcKeyCode, NcKeyMods, NcKeymap, NcKeymapAction, NcMouseEvent,
+    NcMouseHandler, NcMouseRouter, NCGESTURE_CLICK_INTERVAL, NCKEYMAP_CHORD_TIMEOUT,
+};
 pub use key::NcKey;
 pub use log_level::{NcLogLevel, NcLogLevelApi};
 pub use macros::*;
 pub use notcurses::{Nc, NcOptions};
 pub use palette::{NcPalette, NcPaletteIndex};
+pub use pile::NcPile;
 pub use pixel::{NcPixel, NcPixelGeometry, NcPixelImpl, NcPixelImplApi};
-pub use plane::{NcPlane, NcPlaneOptions};
+pub use plane::{NcPlane, NcPlaneHandle, NcPlaneOptions};
 pub use r#box::{NcBoxMask, NcBoxMaskApi};
-pub use resizecb::{NcResizeCb, NcResizeCbApi, NcResizeCbUnsafe};
+pub use resizecb::{NcResizeCb, NcResizeCbUnsafe};
 pub use scale::{NcScale, NcScaleApi};
 pub use stats::NcStats;
 pub use style::{NcStyle, NcStyleApi};
@@ -220,7 +262,6 @@ pub mod c_api {
     pub use crate::palette::reimplemented::*;
     pub use crate::pixel::reimplemented::*;
     pub use crate::plane::reimplemented::*;
-    pub use crate::resizecb::reimplemented::*;
 
     // public re-export of constants:
     pub use crate::align::constants::*;
@@ -7,7 +7,7 @@
 // enjoy briefer doc comments
 use crate::{
     c_api::{NCRESULT_ERR, NCRESULT_OK},
-    Nc, NcDirect, NcError, NcIntResultApi, NcPlane, NcResult,
+    Nc, NcContext, NcDirect, NcError, NcIntResultApi, NcPlane, NcPlaneHandle, NcResult,
 };
 
 // Sleep, Render & Flush Macros ------------------------------------------------
@@ -41,11 +41,14 @@ macro_rules! sleep {
 /// Renders the `$nc` [`Nc`] object's standard plane pile and then,
 /// if there's no error, calls the sleep macro with the rest of the arguments.
 ///
+/// `$nc` can also be an [`NcContext`], which can be rendered while there are
+/// [`NcPlaneHandle`]s alive.
+///
 /// Returns [NcResult].
 #[macro_export]
 macro_rules! nrs {
     ($nc:expr, $( $sleep_args:expr),+ ) => {
-        crate::Nc::render($nc)?;
+        $nc.render()?;
         sleep![$( $sleep_args ),+];
     };
     ($nc:expr, $( $sleep_args:expr),+ ,) => {
@@ -124,17 +127,24 @@ macro_rules! rsleep {
 // String & Print Macros -------------------------------------------------------
 
 /// Converts an `&str` into `*const c_char`.
+///
+/// The pointer is only valid until the end of the enclosing statement,
+/// and it panics if the string contains a nul byte.
 #[macro_export]
 #[doc(hidden)]
+#[deprecated = "use the string functions of the crate, which don't panic"]
 macro_rules! cstring {
     ($s:expr) => {
         std::ffi::CString::new($s).unwrap().as_ptr()
     };
 }
 
-/// Converts an `&str` into `*mut c_char`.
+/// Converts an `&str` into `*mut c_char`, leaking it.
+///
+/// It panics if the string contains a nul byte.
 #[macro_export]
 #[doc(hidden)]
+#[deprecated = "use the string functions of the crate, which don't panic"]
 macro_rules! cstring_mut {
     ($s:expr) => {
         std::ffi::CString::new($s).unwrap().into_raw()
@@ -142,38 +152,50 @@ macro_rules! cstring_mut {
 }
 
 /// Converts a `*const c_char` into an `&str`.
+///
+/// It panics if the string is not valid UTF-8.
 #[macro_export]
 #[doc(hidden)]
+#[deprecated = "use rstring_lossy! or the string functions of the crate, which don't panic"]
 macro_rules! rstring {
     ($s:expr) => {
         unsafe { std::ffi::CStr::from_ptr($s).to_str().unwrap() }
-        // possible alternative:
-        // unsafe { std::ffi::CStr::from_ptr($s).to_string_lossy() }
     };
 }
 
-/// Converts a `*const c_char` into a `String`, freeing the original alloc.
+/// Converts a `*const c_char` into a `Cow<str>`, replacing any invalid UTF-8
+/// sequences with `U+FFFD`.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! rstring_lossy {
+    ($s:expr) => {
+        unsafe { std::ffi::CStr::from_ptr($s).to_string_lossy() }
+    };
+}
+
+/// Converts a `*const c_char` into a `String`, freeing the original alloc,
+/// and replacing any invalid UTF-8 sequences with `U+FFFD`.
 #[macro_export]
 #[doc(hidden)]
 macro_rules! rstring_free {
     ($s:expr) => {{
         #[allow(unused_unsafe)]
         let nc_string = unsafe { $s };
-        let string = crate::rstring![nc_string].to_string();
-        unsafe { c_api::libc::free(nc_string as *mut core::ffi::c_void) };
+        let string = $crate::rstring_lossy![nc_string].into_owned();
+        unsafe { $crate::c_api::libc::free(nc_string as *mut core::ffi::c_void) };
         string
     }};
 }
 
 /// Wrapper around [`libc::printf`][c_api::libc::printf].
+///
+/// Returns an `NcResult` with the value returned by `printf`, or an error if
+/// the format string contains a nul byte.
 #[macro_export]
 #[doc(hidden)]
 macro_rules! printf {
-    ($s:expr) => {
-        unsafe { c_api::libc::printf(cstring![$s]) }
-    };
     ($s:expr $(, $opt:expr)*) => {
-        unsafe { c_api::libc::printf(cstring![$s], $($opt),*) }
+        $crate::string::with_cstr($s, |s| unsafe { $crate::c_api::libc::printf(s $(, $opt)*) })
     };
 }
 
@@ -271,13 +293,28 @@ macro_rules! putstrln {
 ///
 /// In other words:
 /// Returns Ok(`$ok`) if `$res` >= [NCRESULT_OK], otherwise returns
-/// Err([NcError]::[new][NcError#method.new](`$res`, `$msg`)).
+/// Err([NcError]::[with_msg][NcError#method.with_msg](`$res`, `$msg`)).
+///
+/// The name of the C function that returned `$res` can be passed first,
+/// [recording][NcError#method.calling] it in the error, like in
+/// `error![@call "ncplane_putc", res, $msg, $ok]`.
 ///
 /// `$ok` & `$msg` are optional. By default they will be the unit
 /// type `()`, and an empty `&str` `""`, respectively.
 #[macro_export]
 #[doc(hidden)]
 macro_rules! error {
+    (@call $call:expr, $res:expr, $msg:expr, $ok:expr) => {{
+        let res = $res;
+        if res >= crate::c_api::NCRESULT_OK {
+            return Ok($ok);
+        } else {
+            return Err(crate::NcError::with_msg(res, $msg).calling($call));
+        }
+    }};
+    (@call $call:expr, $res:expr, $msg:expr) => {
+        error![@call $call, $res, $msg, ()]
+    };
     ($res:expr, $msg:expr, $ok:expr) => {{
         let res = $res;
         if res >= crate::c_api::NCRESULT_OK {
@@ -299,16 +336,36 @@ macro_rules! error {
 ///
 /// In other words:
 /// Returns Ok(&*`$ptr`) if `!$ptr.is_null()`, otherwise returns
-/// Err([NcError]]::[new][NcError#method.new]([NCRESULT_ERR], `$msg`)).
+/// Err([NcError]::[with_kind][NcError#method.with_kind]([NullPointer], `$msg`)).
+///
+/// The name of the C function that returned `$ptr` can be passed first,
+/// like in [`error!`], with `@call`.
 ///
 /// `$msg` is optional. By default it will be an empty `&str` `""`.
+///
+/// [NullPointer]: crate::NcErrorKind::NullPointer
 #[macro_export]
 #[doc(hidden)]
 macro_rules! error_ref {
+    (@call $call:expr, $ptr:expr, $msg:expr, $ok:expr) => {{
+        let ptr = $ptr; // avoid calling a function multiple times
+        if ptr.is_null() {
+            return Err(
+                crate::NcError::with_kind(crate::NcErrorKind::NullPointer, $msg).calling($call),
+            );
+        } else {
+            #[allow(unused_unsafe)]
+            return Ok(unsafe { $ok });
+        }
+    }};
+    (@call $call:expr, $ptr:expr, $msg:expr) => {{
+        let ptr = $ptr;
+        error_ref![@call $call, ptr, $msg, unsafe { &*ptr }];
+    }};
     ($ptr:expr, $msg:expr, $ok:expr) => {{
         let ptr = $ptr; // avoid calling a function multiple times
         if ptr.is_null() {
-            return Err(crate::NcError::with_msg(crate::c_api::NCRESULT_ERR, $msg));
+            return Err(crate::NcError::with_kind(crate::NcErrorKind::NullPointer, $msg));
         } else {
             #[allow(unused_unsafe)]
             return Ok(unsafe { $ok });
@@ -316,11 +373,11 @@ macro_rules! error_ref {
     }};
     ($ptr:expr, $msg:expr) => {{
         let ptr = $ptr;
-        error_ref![$ptr, $msg, unsafe { &*ptr }];
+        error_ref![ptr, $msg, unsafe { &*ptr }];
     }};
     ($ptr:expr) => {{
         let ptr = $ptr;
-        error_ref![$ptr, "", unsafe { &*ptr }];
+        error_ref![ptr, "", unsafe { &*ptr }];
     }};
 }
 
@@ -329,16 +386,36 @@ macro_rules! error_ref {
 ///
 /// In other words:
 /// Returns Ok(&mut *`$ptr`) if `!$ptr._is_null()`, otherwise returns
-/// Err([NcError]]::[new][NcError#method.new]([NCRESULT_ERR], `$msg`)).
+/// Err([NcError]::[with_kind][NcError#method.with_kind]([NullPointer], `$msg`)).
+///
+/// The name of the C function that returned `$ptr` can be passed first,
+/// like in [`error!`], with `@call`.
 ///
 /// `$msg` is optional. By default it will be an empty `&str` `""`.
+///
+/// [NullPointer]: crate::NcErrorKind::NullPointer
 #[macro_export]
 #[doc(hidden)]
 macro_rules! error_ref_mut {
+    (@call $call:expr, $ptr:expr, $msg:expr, $ok:expr) => {{
+        let ptr = $ptr; // avoid calling a function multiple times
+        if ptr.is_null() {
+            return Err(
+                crate::NcError::with_kind(crate::NcErrorKind::NullPointer, $msg).calling($call),
+            );
+        } else {
+            #[allow(unused_unsafe)]
+            return Ok(unsafe { $ok });
+        }
+    }};
+    (@call $call:expr, $ptr:expr, $msg:expr) => {{
+        let ptr = $ptr;
+        error_ref_mut![@call $call, ptr, $msg, unsafe { &mut *ptr }];
+    }};
     ($ptr:expr, $msg:expr, $ok:expr) => {{
         let ptr = $ptr; // avoid calling a function multiple times
         if ptr.is_null() {
-            return Err(crate::NcError::with_msg(crate::c_api::NCRESULT_ERR, $msg));
+            return Err(crate::NcError::with_kind(crate::NcErrorKind::NullPointer, $msg));
         } else {
             #[allow(unused_unsafe)]
             return Ok(unsafe { $ok });
@@ -359,20 +436,40 @@ macro_rules! error_ref_mut {
 ///
 /// In other words:
 /// Returns Ok((&*`$str`).to_string()) if `!$str.is_null()`, otherwise returns
-/// Err([NcError]]::[new][NcError#method.new]([NCRESULT_ERR], `$msg`)).
+/// Err([NcError]::[with_kind][NcError#method.with_kind]([NullPointer], `$msg`)).
+///
+/// The name of the C function that returned `$str` can be passed first,
+/// like in [`error!`], with `@call`.
 ///
 /// `$msg` is optional. By default it will be an empty `&str` `""`.
+///
+/// [NullPointer]: crate::NcErrorKind::NullPointer
 #[macro_export]
 #[doc(hidden)]
 macro_rules! error_str {
-    ($str:expr, $msg:expr) => {
-        if !$str.is_null() {
+    (@call $call:expr, $str:expr, $msg:expr) => {{
+        let string = $str; // avoid calling a function multiple times
+        if !string.is_null() {
             #[allow(unused_unsafe)]
-            return Ok(unsafe { crate::rstring!($str).to_string() });
+            return Ok(unsafe { crate::rstring_lossy!(string).into_owned() });
         } else {
-            return Err(crate::NcError::with_msg(crate::c_api::NCRESULT_ERR, $msg));
+            return Err(
+                crate::NcError::with_kind(crate::NcErrorKind::NullPointer, $msg).calling($call),
+            );
         }
-    };
+    }};
+    ($str:expr, $msg:expr) => {{
+        let string = $str; // avoid calling a function multiple times
+        if !string.is_null() {
+            #[allow(unused_unsafe)]
+            return Ok(unsafe { crate::rstring_lossy!(string).into_owned() });
+        } else {
+            return Err(crate::NcError::with_kind(
+                crate::NcErrorKind::NullPointer,
+                $msg,
+            ));
+        }
+    }};
     ($str:expr) => {
         error_str![$str, ""];
     };
@@ -1,7 +1,7 @@
 //! `NcMetric`
 
 pub(crate) mod reimplemented {
-    use crate::{c_api::ffi, cstring_mut, rstring};
+    use crate::c_api::ffi;
 
     // TODO: clarify, update and visibilize doc-comments
 
@@ -22,25 +22,19 @@ pub(crate) mod reimplemented {
     ///
     /// val: value to print
     /// decimal: scaling. '1' if none has taken place.
-    /// buf: buffer in which string will be generated
     /// omitdec: inhibit printing of all-0 decimal portions
     /// mult: base of suffix system (almost always 1000 or 1024)
     /// uprefix: character to print following suffix ('i' for kibibytes basically).
     /// only printed if suffix is actually printed (input >= mult).
     ///
     /// You are encouraged to consult notcurses_metric(3).
-    ///
-    // FIXME, remove buf, return String, check mem leaks.
-    pub fn ncmetric(
-        val: u64,
-        decimal: u64,
-        buf: &str,
-        omitdec: i32,
-        mult: u64,
-        uprefix: i32,
-    ) -> &str {
-        let buf = cstring_mut![buf];
-        rstring![ffi::ncmetric(val, decimal, buf, omitdec, mult, uprefix)]
+    /// The string is generated in a buffer on the stack, big enough for any
+    /// `mult`, and then copied.
+    pub fn ncmetric(val: u64, decimal: u64, omitdec: i32, mult: u64, uprefix: i32) -> String {
+        // a generous upper bound for [IB]PREFIXSTRLEN + 1
+        let mut buf = [0; 32];
+        let res = unsafe { ffi::ncmetric(val, decimal, buf.as_mut_ptr(), omitdec, mult, uprefix) };
+        unsafe { crate::string::rstr_lossy(res) }.into_owned()
     }
 }
 
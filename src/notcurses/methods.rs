@@ -1,14 +1,20 @@
 //! `Nc*` methods and associated functions.
 
 use core::ptr::{null, null_mut};
+use std::{ffi::OsString, os::raw::c_int};
 
 use crate::{
     c_api::{self, notcurses_init},
-    cstring, error, error_ref_mut, rstring, rstring_free, Nc, NcAlign, NcBlitter, NcChannels,
-    NcDim, NcError, NcFile, NcInput, NcLogLevel, NcOptions, NcPixelImpl, NcPlane, NcResult,
+    error, error_ref_mut, string, Nc, NcAlign, NcBlitter, NcChannels, NcDim, NcError, NcErrorKind,
+    NcEvent, NcEvents, NcFile, NcInput, NcLogLevel, NcOptions, NcPixelImpl, NcPlane, NcResult,
     NcScale, NcStats, NcStyle, NcStyleApi, NcTime,
 };
 
+#[cfg(feature = "async-std")]
+use crate::NcAsyncStdEvents;
+#[cfg(feature = "tokio")]
+use crate::NcTokioEvents;
+
 /// # `NcOptions` Constructors
 impl NcOptions {
     /// New `NcOptions`.
@@ -125,13 +131,47 @@ impl Nc {
     /// New notcurses context, expects [NcOptions].
     pub fn with_options<'a>(options: NcOptions) -> NcResult<&'a mut Nc> {
         let res = unsafe { notcurses_init(&options, null_mut()) };
-        error_ref_mut![res, "Nc.with_options()"]
+        if !res.is_null() {
+            crate::context::NC_TRACKER.register(res as usize);
+        }
+        error_ref_mut![@call "notcurses_init", res, "Nc.with_options()"]
     }
 
     /// New notcurses context, expects [NcLogLevel] and flags.
     pub fn with_debug<'a>(loglevel: NcLogLevel, flags: u64) -> NcResult<&'a mut Nc> {
         Self::with_options(NcOptions::with_all_options(loglevel, 0, 0, 0, 0, flags))
     }
+
+    /// Installs a panic hook that restores the terminal before the panic
+    /// message is printed.
+    ///
+    /// When a thread panics, the hook stops all the [`Nc`] and
+    /// [`NcDirect`][crate::NcDirect] contexts initialized from that thread
+    /// that are still alive, and only then invokes the previous panic hook.
+    /// Contexts initialized from other threads are left alone.
+    ///
+    /// Only the contexts created by the constructors of this library are
+    /// tracked, not the ones initialized with the C API functions.
+    ///
+    /// The panics inside the closures called back from C, like resize
+    /// callbacks, are left alone, since they're caught before returning into
+    /// the C library, and returned as errors.
+    ///
+    /// Each context is stopped only once: stopping it afterwards, or dropping
+    /// its [`NcContext`][crate::NcContext], does nothing. If the panic is
+    /// caught, those contexts must not be used anymore, and the objects
+    /// depending on them are leaked instead of released.
+    ///
+    /// It doesn't depend on the signal handlers of the C library, so it
+    /// works the same with [`NcOptions::NO_QUIT_SIGHANDLERS`].
+    ///
+    /// Installing it more than once does nothing. It must not be called
+    /// from a panicking thread.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn install_panic_hook() {
+        crate::context::install_panic_hook();
+    }
 }
 
 /// # `Nc` methods
@@ -147,7 +187,7 @@ impl Nc {
     //
     // TODO: handle error rightfully.
     pub fn align(availcols: NcDim, align: NcAlign, cols: NcDim) -> NcResult<()> {
-        error![c_api::notcurses_align(availcols, align, cols)]
+        error![@call "notcurses_align", c_api::notcurses_align(availcols, align, cols), "Nc::align()"]
     }
 
     /// Retrieves the current contents of the specified [NcCell][crate::NcCell]
@@ -166,7 +206,7 @@ impl Nc {
         if egc.is_null() {
             return None;
         }
-        Some(rstring_free![egc])
+        Some(unsafe { string::rstring_free_lossy(egc) })
     }
 
     /// Returns the bottommost [`NcPlane`] on the standard pile,
@@ -284,7 +324,7 @@ impl Nc {
     ///
     /// *C style function: [notcurses_cursor_disable()][c_api::notcurses_cursor_disable].*
     pub fn cursor_disable(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::notcurses_cursor_disable(self) }]
+        error![@call "notcurses_cursor_disable", unsafe { c_api::notcurses_cursor_disable(self) }, "Nc.cursor_disable()"]
     }
 
     /// Enables the terminal's cursor, if supported, placing it at `y`, `x`.
@@ -294,7 +334,7 @@ impl Nc {
     ///
     /// *C style function: [notcurses_cursor_enable()][c_api::notcurses_cursor_enable].*
     pub fn cursor_enable(&mut self, y: NcDim, x: NcDim) -> NcResult<()> {
-        error![unsafe { c_api::notcurses_cursor_enable(self, y as i32, x as i32) }]
+        error![@call "notcurses_cursor_enable", unsafe { c_api::notcurses_cursor_enable(self, y as i32, x as i32) }, "Nc.cursor_enable()"]
     }
 
     /// Shifts to the alternate screen, if available.
@@ -308,7 +348,7 @@ impl Nc {
     /// *C style function:
     /// [notcurses_enter_alternate_screen()][c_api::notcurses_enter_alternate_screen].*
     pub fn enter_alternate_screen(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::notcurses_enter_alternate_screen(self) }]
+        error![@call "notcurses_enter_alternate_screen", unsafe { c_api::notcurses_enter_alternate_screen(self) }, "Nc.enter_alternate_screen()"]
     }
 
     /// Exits the alternate screen.
@@ -318,7 +358,7 @@ impl Nc {
     /// *C style function:
     /// [notcurses_leave_alternate_screen()][c_api::notcurses_leave_alternate_screen].*
     pub fn leave_alternate_screen(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::notcurses_leave_alternate_screen(self) }]
+        error![@call "notcurses_leave_alternate_screen", unsafe { c_api::notcurses_leave_alternate_screen(self) }, "Nc.leave_alternate_screen()"]
     }
 
     /// Dumps notcurses state to the supplied `debugfp`.
@@ -335,23 +375,42 @@ impl Nc {
 
     /// Returns the name of the user under which we are running.
     ///
+    /// Any invalid UTF-8 sequences are replaced with `U+FFFD`.
+    ///
     /// *C style function: [notcurses_accountname()][c_api::notcurses_accountname].*
     pub fn accountname() -> String {
-        rstring_free![c_api::notcurses_accountname()]
+        unsafe { string::rstring_free_lossy(c_api::notcurses_accountname()) }
+    }
+
+    /// Returns the name of the user under which we are running,
+    /// as an [`OsString`].
+    ///
+    /// *C style function: [notcurses_accountname()][c_api::notcurses_accountname].*
+    pub fn accountname_os() -> OsString {
+        unsafe { string::ros_string_free(c_api::notcurses_accountname()) }
     }
 
     /// Returns the name of the local hostname.
     ///
+    /// Any invalid UTF-8 sequences are replaced with `U+FFFD`.
+    ///
     /// *C style function: [notcurses_hostname()][c_api::notcurses_hostname].*
     pub fn hostname() -> String {
-        rstring_free![c_api::notcurses_hostname()]
+        unsafe { string::rstring_free_lossy(c_api::notcurses_hostname()) }
+    }
+
+    /// Returns the name of the local hostname, as an [`OsString`].
+    ///
+    /// *C style function: [notcurses_hostname()][c_api::notcurses_hostname].*
+    pub fn hostname_os() -> OsString {
+        unsafe { string::ros_string_free(c_api::notcurses_hostname()) }
     }
 
     /// Returns the name of the detected terminal.
     ///
     /// *C style function: [notcurses_detected_terminal()][c_api::notcurses_detected_terminal].*
     pub fn detected_terminal(&self) -> String {
-        rstring_free![c_api::notcurses_detected_terminal(self)]
+        unsafe { string::rstring_free_lossy(c_api::notcurses_detected_terminal(self)) }
     }
 
     /// Destroys all [`NcPlane`]s other than the stdplane.
@@ -418,7 +477,30 @@ impl Nc {
         let nivec = ni.as_mut_ptr() as *mut NcInput;
 
         let res = unsafe { c_api::notcurses_getvec(self, ntime, nivec, vcount as i32) };
-        error![res, "", res as u32]
+        error![@call "notcurses_getvec", res, "", res as u32]
+    }
+
+    /// Returns the next input [`NcEvent`], or `None` on timeout.
+    ///
+    /// Provide a None `time` to block at length, a `time` of 0 for non-blocking
+    /// operation, and otherwise a timespec to bound blocking.
+    ///
+    /// *C style function: [notcurses_get()][c_api::notcurses_get].*
+    pub fn get_event(&mut self, time: Option<NcTime>) -> NcResult<Option<NcEvent>> {
+        let mut input = NcInput::new_empty();
+        let key = self.get(time, Some(&mut input))?;
+        if key == '\0' {
+            return Ok(None);
+        }
+        Ok(Some(NcEvent::from((key, input))))
+    }
+
+    /// Returns the next input [`NcEvent`] without blocking,
+    /// or `None` if no event is ready.
+    ///
+    /// *C style function: [notcurses_get()][c_api::notcurses_get].*
+    pub fn get_event_nblock(&mut self) -> NcResult<Option<NcEvent>> {
+        self.get_event(Some(NcTime::new(0, 0)))
     }
 
     /// Reads input blocking until an event is processed or a signal is received.
@@ -453,9 +535,47 @@ impl Nc {
     /// This file descriptor is not necessarily the file descriptor associated
     /// with stdin (but it might be!).
     ///
+    /// The asynchronous event streams, like
+    /// [tokio_events()][Nc#method.tokio_events], are built on top of it.
+    ///
     /// *C style function: [notcurses_inputready_fd()][c_api::notcurses_inputready_fd].*
-    pub fn inputready_fd(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::notcurses_inputready_fd(self) }]
+    pub fn inputready_fd(&mut self) -> NcResult<c_int> {
+        let res = unsafe { c_api::notcurses_inputready_fd(self) };
+        error![@call "notcurses_inputready_fd", res, "Nc.inputready_fd()", res]
+    }
+
+    /// Returns a blocking iterator over the input [`NcEvent`]s, that waits
+    /// at most `time` for each one, or blocks at length if `None`.
+    ///
+    /// The iterator is not fused: it returns `None` each time the timeout
+    /// expires, and then it can be iterated again. Call `.fuse()` on it to
+    /// stop at the first timeout.
+    ///
+    /// *C style function: [notcurses_get()][c_api::notcurses_get].*
+    pub fn events(&mut self, time: Option<NcTime>) -> NcEvents<'_> {
+        NcEvents::new(self, time)
+    }
+
+    /// Returns a stream of the input [`NcEvent`]s, for the tokio runtime.
+    ///
+    /// It must be called from within a tokio runtime, with IO enabled.
+    ///
+    /// *Requires the `tokio` feature.*
+    ///
+    /// *C style function: [notcurses_inputready_fd()][c_api::notcurses_inputready_fd].*
+    #[cfg(feature = "tokio")]
+    pub fn tokio_events(&mut self) -> NcResult<NcTokioEvents<'_>> {
+        NcTokioEvents::new(self)
+    }
+
+    /// Returns a stream of the input [`NcEvent`]s, for the async-std runtime.
+    ///
+    /// *Requires the `async-std` feature.*
+    ///
+    /// *C style function: [notcurses_inputready_fd()][c_api::notcurses_inputready_fd].*
+    #[cfg(feature = "async-std")]
+    pub fn async_std_events(&mut self) -> NcResult<NcAsyncStdEvents<'_>> {
+        NcAsyncStdEvents::new(self)
     }
 
     /// Returns an [`NcBlitter`] from a string representation.
@@ -464,7 +584,9 @@ impl Nc {
     pub fn lex_blitter(blitter_str: &str) -> NcResult<NcBlitter> {
         let mut blitter = 0;
         error![
-            unsafe { c_api::notcurses_lex_blitter(cstring![blitter_str], &mut blitter) },
+            @call "notcurses_lex_blitter", string::with_cstr(blitter_str, |blitter_str| unsafe {
+                c_api::notcurses_lex_blitter(blitter_str, &mut blitter)
+            })?,
             "Invalid blitter name", blitter
         ]
     }
@@ -476,7 +598,9 @@ impl Nc {
     ///
     /// *C style function: [notcurses_lex_margins()][c_api::notcurses_lex_margins].*
     pub fn lex_margins(margins_str: &str, options: &mut NcOptions) -> NcResult<()> {
-        error![unsafe { c_api::notcurses_lex_margins(cstring![margins_str], options) }]
+        error![@call "notcurses_lex_margins", string::with_cstr(margins_str, |margins_str| unsafe {
+            c_api::notcurses_lex_margins(margins_str, options)
+        })?, "Nc::lex_margins()"]
     }
 
     /// Returns an [`NcScale`] from a string representation.
@@ -485,8 +609,9 @@ impl Nc {
     pub fn lex_scalemode(scalemode_str: &str) -> NcResult<NcScale> {
         let mut scalemode = 0;
         error![
-            unsafe { c_api::notcurses_lex_scalemode(cstring![scalemode_str], &mut scalemode) },
-            "", scalemode
+            @call "notcurses_lex_scalemode", string::with_cstr(scalemode_str, |scalemode_str| unsafe {
+                c_api::notcurses_lex_scalemode(scalemode_str, &mut scalemode)
+            })?, "Nc::lex_scalemode()", scalemode
         ]
     }
 
@@ -523,10 +648,10 @@ impl Nc {
             Ok(style)
         } else {
             let _ = errstr.pop();
-            Err(NcError::new_msg(&format![
-                "the following styles are not recognized: '{}'",
-                errstr
-            ]))
+            Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                &format!["the following styles are not recognized: '{}'", errstr],
+            ))
         }
     }
 
@@ -535,7 +660,7 @@ impl Nc {
     ///
     /// *C style function: [notcurses_linesigs_disable()][c_api::notcurses_linesigs_disable].*
     pub fn linesigs_disable(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::notcurses_linesigs_disable(self) }]
+        error![@call "notcurses_linesigs_disable", unsafe { c_api::notcurses_linesigs_disable(self) }, "Nc.linesigs_disable()"]
     }
 
     /// Restores signals originating from the terminal's line discipline, i.e.
@@ -543,7 +668,7 @@ impl Nc {
     ///
     /// *C style function: [notcurses_linesigs_enable()][c_api::notcurses_linesigs_enable].*
     pub fn linesigs_enable(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::notcurses_linesigs_enable(self) }]
+        error![@call "notcurses_linesigs_enable", unsafe { c_api::notcurses_linesigs_enable(self) }, "Nc.linesigs_enable()"]
     }
 
     /// Disables mouse events.
@@ -552,7 +677,7 @@ impl Nc {
     ///
     /// *C style function: [notcurses_mouse_disable()][c_api::notcurses_mouse_disable].*
     pub fn mouse_disable(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::notcurses_mouse_disable(self) }]
+        error![@call "notcurses_mouse_disable", unsafe { c_api::notcurses_mouse_disable(self) }, "Nc.mouse_disable()"]
     }
 
     /// Enable the mouse in "button-event tracking" mode with focus detection
@@ -563,7 +688,7 @@ impl Nc {
     /// *C style function: [notcurses_mouse_enable()][c_api::notcurses_mouse_enable].*
     pub fn mouse_enable(&mut self) -> NcResult<()> {
         error![
-            unsafe { c_api::notcurses_mouse_enable(self) },
+            @call "notcurses_mouse_enable", unsafe { c_api::notcurses_mouse_enable(self) },
             "Nc.mouse_enable()"
         ]
     }
@@ -578,7 +703,8 @@ impl Nc {
     pub fn palette_size(&self) -> NcResult<u32> {
         let res = unsafe { c_api::notcurses_palette_size(self) };
         if res == 1 {
-            return Err(NcError::with_msg(1, "No color support ← Nc.palette_size()"));
+            return Err(NcError::with_msg(1, "No color support ← Nc.palette_size()")
+                .of_kind(NcErrorKind::Unsupported));
         }
         Ok(res)
     }
@@ -598,8 +724,7 @@ impl Nc {
     pub fn refresh(&mut self) -> NcResult<(NcDim, NcDim)> {
         let (mut y, mut x) = (0, 0);
         error![
-            unsafe { c_api::notcurses_refresh(self, &mut y, &mut x) },
-            "",
+            @call "notcurses_refresh", unsafe { c_api::notcurses_refresh(self, &mut y, &mut x) }, "Nc.refresh()",
             (y as NcDim, x as NcDim)
         ]
     }
@@ -608,7 +733,7 @@ impl Nc {
     ///
     /// *C style function: [notcurses_render()][c_api::notcurses_render].*
     pub fn render(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::notcurses_render(self) }, "Nc.render()"]
+        error![@call "notcurses_render", unsafe { c_api::notcurses_render(self) }, "Nc.render()"]
     }
 
     /// Performs the rendering and rasterization portion of
@@ -634,7 +759,7 @@ impl Nc {
         #[cfg(not(any(target_arch = "x86_64", target_arch = "i686", target_arch = "x86")))]
         let mut buf = buffer.as_mut_ptr() as *mut u8;
 
-        error![unsafe { c_api::notcurses_render_to_buffer(self, &mut buf, &mut len.into()) }]
+        error![@call "notcurses_render_to_buffer", unsafe { c_api::notcurses_render_to_buffer(self, &mut buf, &mut len.into()) }, "Nc.render_to_buffer()"]
     }
 
     /// Writes the last rendered frame, in its entirety, to 'fp'.
@@ -645,7 +770,7 @@ impl Nc {
     /// *C style function: [notcurses_render_to_file()][c_api::notcurses_render_to_file].*
     #[deprecated]
     pub fn render_to_file(&mut self, fp: &mut NcFile) -> NcResult<()> {
-        error![unsafe { c_api::notcurses_render_to_file(self, fp.as_nc_ptr()) }]
+        error![@call "notcurses_render_to_file", unsafe { c_api::notcurses_render_to_file(self, fp.as_nc_ptr()) }, "Nc.render_to_file()"]
     }
 
     /// Acquires an atomic snapshot of the notcurses object's stats.
@@ -710,7 +835,7 @@ impl Nc {
     /// uppermost, leftmost cell.
     ///
     /// *C style function: [notcurses_stdplane()][c_api::notcurses_stdplane].*
-    pub fn stdplane<'a>(&mut self) -> &'a mut NcPlane {
+    pub fn stdplane(&mut self) -> &mut NcPlane {
         unsafe { &mut *c_api::notcurses_stdplane(self) }
     }
 
@@ -720,29 +845,43 @@ impl Nc {
     /// uppermost, leftmost cell.
     ///
     /// *C style function: [notcurses_stdplane_const()][c_api::notcurses_stdplane_const].*
-    pub fn stdplane_const<'a>(&self) -> &'a NcPlane {
+    pub fn stdplane_const(&self) -> &NcPlane {
         unsafe { &*c_api::notcurses_stdplane_const(self) }
     }
 
     /// Destroys the notcurses context.
     ///
+    /// Returns an error without doing anything if the context is owned by an
+    /// [`NcContext`][crate::NcContext], which stops it when dropped.
+    ///
     /// *C style function: [notcurses_stop()][c_api::notcurses_stop].*
     pub fn stop(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::notcurses_stop(self) }]
+        let tracker = &crate::context::NC_TRACKER;
+        if tracker.owned.contains(self as *mut Nc as usize) {
+            return Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "Nc.stop(): owned by an NcContext, use NcContext.stop() instead",
+            ));
+        }
+        if !tracker.claim(self as *mut Nc as usize) {
+            // already stopped by the panic hook
+            return Ok(());
+        }
+        error![@call "notcurses_stop", unsafe { c_api::notcurses_stop(self) }, "Nc.stop()"]
     }
 
     /// Gets the name of an [`NcBlitter`] blitter.
     ///
     /// *C style function: [notcurses_str_blitter()][c_api::notcurses_str_blitter].*
     pub fn str_blitter(blitter: NcBlitter) -> String {
-        rstring![c_api::notcurses_str_blitter(blitter)].to_string()
+        unsafe { string::rstr_lossy(c_api::notcurses_str_blitter(blitter)) }.into_owned()
     }
 
     /// Gets the name of an [`NcScale`] scaling mode.
     ///
     /// *C style function: [notcurses_str_scalemode()][c_api::notcurses_str_scalemode].*
     pub fn str_scalemode(scalemode: NcScale) -> String {
-        rstring![c_api::notcurses_str_scalemode(scalemode)].to_string()
+        unsafe { string::rstr_lossy(c_api::notcurses_str_scalemode(scalemode)) }.into_owned()
     }
 
     /// Gets the lowercase name (or names) of the styles included in an [`NcStyle`].
@@ -797,7 +936,7 @@ impl Nc {
     ///
     /// *C style function: [notcurses_version()][c_api::notcurses_version].*
     pub fn version() -> String {
-        rstring![c_api::notcurses_version()].to_string()
+        unsafe { string::rstr_lossy(c_api::notcurses_version()) }.into_owned()
     }
 
     /// Returns the running notcurses version components
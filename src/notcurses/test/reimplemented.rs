@@ -191,5 +191,5 @@ fn notcurses_debug() {
 fn notcurses_version() {
     let c_str = unsafe { c_api::notcurses_version() };
     assert!(!c_str.is_null());
-    print!("v{} ", crate::rstring![c_str]);
+    print!("v{} ", crate::rstring_lossy![c_str]);
 }
@@ -0,0 +1,38 @@
+//! `OwnedFadeCtx`
+
+use core::{marker::PhantomData, ptr::NonNull};
+use std::rc::Rc;
+
+use super::Node;
+use crate::{c_api, error_ref_mut, NcFadeCtx, NcPlane, NcResult};
+
+/// An owned [`NcFadeCtx`], freed when dropped.
+pub struct OwnedFadeCtx {
+    ptr: NonNull<NcFadeCtx>,
+    node: Rc<Node>,
+    _marker: PhantomData<NcFadeCtx>,
+}
+
+unsafe fn release(ptr: *mut NcFadeCtx) {
+    c_api::ncfadectx_free(ptr);
+}
+
+impl_owned![[] OwnedFadeCtx, NcFadeCtx, 'static, "OwnedFadeCtx", release];
+
+/// # `OwnedFadeCtx` constructors
+impl OwnedFadeCtx {
+    /// New fade context for `plane`.
+    ///
+    /// *C style function: [ncfadectx_setup()][c_api::ncfadectx_setup].*
+    pub fn setup(plane: &mut NcPlane) -> NcResult<Self> {
+        let ctx = Self::alloc(plane)?;
+        Ok(unsafe { Self::from_raw(ctx) })
+    }
+
+    fn alloc<'a>(plane: &mut NcPlane) -> NcResult<&'a mut NcFadeCtx> {
+        error_ref_mut![
+            @call "ncfadectx_setup", unsafe { c_api::ncfadectx_setup(plane) },
+            "OwnedFadeCtx::setup()"
+        ]
+    }
+}
@@ -0,0 +1,90 @@
+//! `OwnedFdPlane` & `OwnedSubproc`
+
+use core::{marker::PhantomData, ptr::NonNull};
+use std::{os::raw::c_int, rc::Rc};
+
+use super::{Node, OwnedPlane};
+use crate::{Nc, NcFdPlane, NcFdPlaneOptions, NcPlane, NcResult, NcSubproc, NcSubprocBuilder};
+
+/// An owned [`NcFdPlane`], destroyed when dropped, or along with its plane.
+///
+/// It's destroyed once and only once, as the C API requires.
+pub struct OwnedFdPlane<'nc> {
+    ptr: NonNull<NcFdPlane>,
+    node: Rc<Node>,
+    _marker: PhantomData<&'nc Nc>,
+}
+
+unsafe fn release_fdplane(ptr: *mut NcFdPlane) {
+    let _ = NcFdPlane::destroy(&mut *ptr);
+}
+
+impl_owned![['nc] OwnedFdPlane<'nc>, NcFdPlane, 'nc, "OwnedFdPlane", release_fdplane, crate::c_api::ncfdplane_plane];
+
+/// # `OwnedFdPlane` constructors
+impl<'nc> OwnedFdPlane<'nc> {
+    /// New fd plane, that writes the data read from `fd` to `plane`.
+    ///
+    /// *C style function: [ncfdplane_create()][crate::c_api::ncfdplane_create].*
+    pub fn new(
+        plane: &mut OwnedPlane<'nc>,
+        fd: c_int,
+        options: &NcFdPlaneOptions,
+    ) -> NcResult<Self> {
+        let fdplane = unsafe { Self::from_raw(NcFdPlane::new(plane, fd, options)?) };
+        plane.node().adopt(&fdplane.node);
+        Ok(fdplane)
+    }
+
+    /// New fd plane, that calls `on_data` with the data read from `fd`,
+    /// and `on_done` at the end.
+    ///
+    /// *C style function: [ncfdplane_create()][crate::c_api::ncfdplane_create].*
+    pub fn with_callbacks<F, D>(
+        plane: &mut OwnedPlane<'nc>,
+        fd: c_int,
+        options: &NcFdPlaneOptions,
+        on_data: F,
+        on_done: D,
+    ) -> NcResult<Self>
+    where
+        F: FnMut(&mut NcPlane, &[u8]) -> NcResult<()> + 'static,
+        D: FnMut(&mut NcPlane, i32) -> NcResult<()> + 'static,
+    {
+        let fdplane = unsafe {
+            Self::from_raw(NcFdPlane::with_callbacks(
+                plane, fd, options, on_data, on_done,
+            )?)
+        };
+        plane.node().adopt(&fdplane.node);
+        Ok(fdplane)
+    }
+}
+
+/// An owned [`NcSubproc`], destroyed when dropped, or along with its plane.
+///
+/// It's destroyed once and only once, as the C API requires, killing the
+/// subprocess if it's still running.
+pub struct OwnedSubproc<'nc> {
+    ptr: NonNull<NcSubproc>,
+    node: Rc<Node>,
+    _marker: PhantomData<&'nc Nc>,
+}
+
+unsafe fn release_subproc(ptr: *mut NcSubproc) {
+    let _ = NcSubproc::destroy(&mut *ptr);
+}
+
+impl_owned![['nc] OwnedSubproc<'nc>, NcSubproc, 'nc, "OwnedSubproc", release_subproc, crate::c_api::ncsubproc_plane];
+
+/// # `OwnedSubproc` constructors
+impl<'nc> OwnedSubproc<'nc> {
+    /// Spawns a subprocess from a builder, writing its output to `plane`.
+    ///
+    /// *C style function: [ncsubproc_createvp()][crate::c_api::ncsubproc_createvp].*
+    pub fn spawn(plane: &mut OwnedPlane<'nc>, builder: NcSubprocBuilder) -> NcResult<Self> {
+        let subproc = unsafe { Self::from_raw(builder.spawn(plane)?) };
+        plane.node().adopt(&subproc.node);
+        Ok(subproc)
+    }
+}
@@ -0,0 +1,186 @@
+//! Owned types, that release their resources when dropped.
+//!
+//! *Requires the `owned` feature.*
+//!
+//! Each owned type wraps one of the borrowed types of this library, which it
+//! dereferences to, so all of their methods are available, and it calls the
+//! corresponding `destroy()` or `free()` function when it goes out of scope.
+//!
+//! Those functions are unsafe in the borrowed types, so that they can't be
+//! called through the dereference, which would release the object twice.
+//!
+//! [`OwnedPlane`] and the widgets borrow the [`Nc`][crate::Nc] context they
+//! belong to, so they can't outlive it.
+//!
+//! # Parent relationships
+//!
+//! Owned planes and widgets form a tree: the planes created bound to an
+//! [`OwnedPlane`], and the widgets created on them, are released along with
+//! it, from the newest to the oldest. Note that this differs from the C API,
+//! where the bound planes of a destroyed plane are reparented instead.
+//!
+//! Dereferencing an owned object that has already been released along with
+//! its parent panics. This can be checked with its `is_alive()` method.
+//!
+//! # Conversions
+//!
+//! Any owned object can be converted back into a borrowed reference with its
+//! `into_raw()` method, which relinquishes the ownership and detaches it from
+//! its parent. A borrowed reference can be owned with the unsafe `from_raw()`
+//! associated function.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use libnotcurses_sys::{owned::*, *};
+//!
+//! fn main() -> NcResult<()> {
+//!     let nc = NcContext::new()?;
+//!     let handle = nc.stdplane().new_bound(0, 0, 10, 40)?;
+//!     let mut parent = unsafe { OwnedPlane::from_handle(handle) };
+//!     let mut child = parent.new_bound(1, 1, 1, 20)?;
+//!     child.putstr("hello world")?;
+//!     nc.render()?;
+//!
+//!     drop(parent); // also destroys the child plane
+//!     assert![!child.is_alive()];
+//!     Ok(())
+//! }
+//! ```
+
+/// Implements the common parts of an owned type.
+///
+/// The type must have the `ptr`, `node` & `_marker` fields. `$lt` is the
+/// lifetime of the reference returned by `into_raw`, and `$release` is the
+/// unsafe function that releases the object.
+///
+/// The objects that depend on an `Nc` context also pass `$plane`, the unsafe
+/// function that returns their plane, through which the context is found.
+macro_rules! impl_owned {
+    ([$($gen:tt)*] $owned:ty, $target:ty, $lt:lifetime, $name:literal, $release:path
+        $(, $plane:path)?) => {
+        impl<$($gen)*> $owned {
+            /// Takes ownership of an already existing object.
+            ///
+            /// # Safety
+            ///
+            /// The object must be valid, and it must not be released afterwards
+            /// by any other means than the returned owned object.
+            pub unsafe fn from_raw(raw: &mut $target) -> Self {
+                let ptr = raw as *mut $target;
+                Self::from_parts(ptr, crate::owned::Node::new(move || $release(ptr)))
+            }
+
+            /// Relinquishes the ownership of the object, without releasing it.
+            ///
+            /// If it was bound to an owned parent, it won't be released along
+            /// with it anymore.
+            ///
+            /// # Panics
+            /// Panics if it has already been released along with its parent.
+            pub fn into_raw(self) -> &$lt mut $target {
+                let ptr = self.ptr();
+                self.node.disarm();
+                unsafe { &mut *ptr }
+            }
+
+            /// Returns `false` if it has already been released along with its
+            /// parent, in which case it can't be used anymore.
+            pub fn is_alive(&self) -> bool {
+                self.node.is_alive()
+            }
+
+            // assembles the owned object from its parts.
+            fn from_parts(ptr: *mut $target, node: std::rc::Rc<crate::owned::Node>) -> Self {
+                $(
+                    let nc = unsafe { crate::c_api::ncplane_notcurses($plane(ptr)) };
+                    node.set_context(nc as usize);
+                )?
+                Self {
+                    ptr: core::ptr::NonNull::new(ptr).expect(concat![$name, ": null pointer"]),
+                    node,
+                    _marker: core::marker::PhantomData,
+                }
+            }
+
+            // returns the pointer, panicking if it has already been released.
+            fn ptr(&self) -> *mut $target {
+                assert![
+                    self.node.is_alive(),
+                    concat![$name, " was released along with its parent"]
+                ];
+                self.ptr.as_ptr()
+            }
+        }
+
+        impl<$($gen)*> core::ops::Deref for $owned {
+            type Target = $target;
+            fn deref(&self) -> &$target {
+                unsafe { &*self.ptr() }
+            }
+        }
+
+        impl<$($gen)*> core::ops::DerefMut for $owned {
+            fn deref_mut(&mut self) -> &mut $target {
+                unsafe { &mut *self.ptr() }
+            }
+        }
+
+        impl<$($gen)*> AsRef<$target> for $owned {
+            fn as_ref(&self) -> &$target {
+                self
+            }
+        }
+
+        impl<$($gen)*> AsMut<$target> for $owned {
+            fn as_mut(&mut self) -> &mut $target {
+                self
+            }
+        }
+
+        impl<$($gen)*> core::fmt::Debug for $owned {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.debug_struct($name)
+                    .field("ptr", &self.ptr)
+                    .field("alive", &self.node.is_alive())
+                    .finish()
+            }
+        }
+
+        impl<$($gen)*> Drop for $owned {
+            fn drop(&mut self) {
+                // the context may have been stopped by the panic hook
+                if crate::context::stopped_by_panic(self.node.context()) {
+                    self.node.disarm();
+                    return;
+                }
+                self.node.release();
+            }
+        }
+    };
+}
+
+mod fade;
+mod fd;
+mod node;
+mod palette;
+mod plane;
+mod stats;
+mod visual;
+mod widgets;
+
+#[cfg(test)]
+mod test;
+
+pub(crate) use node::Node;
+
+pub use fade::OwnedFadeCtx;
+pub use fd::{OwnedFdPlane, OwnedSubproc};
+pub use palette::OwnedPalette;
+pub use plane::OwnedPlane;
+pub use stats::OwnedStats;
+pub use visual::OwnedVisual;
+pub use widgets::{
+    OwnedMenu, OwnedMultiSelector, OwnedProgBar, OwnedReader, OwnedReel, OwnedSelector,
+    OwnedTabbed, OwnedTree,
+};
@@ -0,0 +1,91 @@
+//! The ownership tree shared by the owned types.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+};
+
+/// A node in the tree of owned objects.
+///
+/// Each owned object has a node holding the closure that releases its C
+/// resources, and the weak references to the nodes of the objects that must
+/// be released along with it.
+pub(crate) struct Node {
+    alive: Cell<bool>,
+    /// The address of the `Nc` context the object depends on, or 0.
+    context: Cell<usize>,
+    release: Cell<Option<Box<dyn FnOnce()>>>,
+    children: RefCell<Vec<Weak<Node>>>,
+}
+
+impl Node {
+    /// New live node, with the closure that releases its object.
+    pub(crate) fn new(release: impl FnOnce() + 'static) -> Rc<Self> {
+        Rc::new(Self {
+            alive: Cell::new(true),
+            context: Cell::new(0),
+            release: Cell::new(Some(Box::new(release))),
+            children: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Returns `false` if the object has already been released.
+    pub(crate) fn is_alive(&self) -> bool {
+        self.alive.get()
+    }
+
+    /// Returns the address of the context the object depends on, or 0 if it
+    /// doesn't depend on any.
+    pub(crate) fn context(&self) -> usize {
+        self.context.get()
+    }
+
+    /// Sets the address of the context the object depends on.
+    pub(crate) fn set_context(&self, context: usize) {
+        self.context.set(context);
+    }
+
+    /// Makes `child` be released along with this node.
+    pub(crate) fn adopt(&self, child: &Rc<Node>) {
+        let mut children = self.children.borrow_mut();
+        children.retain(|c| c.strong_count() > 0);
+        children.push(Rc::downgrade(child));
+    }
+
+    /// Replaces the closure that releases the object.
+    ///
+    /// Used when a widget takes ownership of a plane.
+    pub(crate) fn set_release(&self, release: impl FnOnce() + 'static) {
+        self.release.set(Some(Box::new(release)));
+    }
+
+    /// Releases all the children, from the newest to the oldest.
+    pub(crate) fn release_children(&self) {
+        let children = core::mem::take(&mut *self.children.borrow_mut());
+        for child in children.iter().rev() {
+            if let Some(child) = child.upgrade() {
+                child.release();
+            }
+        }
+    }
+
+    /// Marks the node as released, without releasing its object.
+    ///
+    /// Its children are left alone, no longer bound to it.
+    pub(crate) fn disarm(&self) {
+        self.alive.set(false);
+        drop(self.release.take());
+        self.children.borrow_mut().clear();
+    }
+
+    /// Releases the children and then the object itself, only once.
+    pub(crate) fn release(&self) {
+        if !self.alive.replace(false) {
+            return;
+        }
+        self.release_children();
+        if let Some(release) = self.release.take() {
+            release();
+        }
+    }
+}
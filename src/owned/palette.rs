@@ -0,0 +1,35 @@
+//! `OwnedPalette`
+
+use core::{marker::PhantomData, ptr::NonNull};
+use std::rc::Rc;
+
+use super::Node;
+use crate::{c_api, error_ref_mut, Nc, NcPalette, NcResult};
+
+/// An owned [`NcPalette`], freed when dropped.
+pub struct OwnedPalette {
+    ptr: NonNull<NcPalette>,
+    node: Rc<Node>,
+    _marker: PhantomData<NcPalette>,
+}
+
+unsafe fn release(ptr: *mut NcPalette) {
+    c_api::ncpalette_free(ptr);
+}
+
+impl_owned![[] OwnedPalette, NcPalette, 'static, "OwnedPalette", release];
+
+/// # `OwnedPalette` constructors
+impl OwnedPalette {
+    /// New palette, initialized with the current palette of the context.
+    ///
+    /// *C style function: [ncpalette_new()][c_api::ncpalette_new].*
+    pub fn new(nc: &mut Nc) -> NcResult<Self> {
+        let palette = Self::alloc(nc)?;
+        Ok(unsafe { Self::from_raw(palette) })
+    }
+
+    fn alloc<'a>(nc: &mut Nc) -> NcResult<&'a mut NcPalette> {
+        error_ref_mut![@call "ncpalette_new", unsafe { c_api::ncpalette_new(nc) }, "OwnedPalette::new()"]
+    }
+}
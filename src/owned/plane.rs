@@ -0,0 +1,121 @@
+//! `OwnedPlane`
+
+use core::{marker::PhantomData, mem::ManuallyDrop, ptr::NonNull};
+use std::rc::Rc;
+
+use super::Node;
+use crate::{
+    error, plane::userptr, Nc, NcDim, NcOffset, NcPlane, NcPlaneHandle, NcPlaneOptions, NcResult,
+};
+
+/// An owned [`NcPlane`], destroyed when dropped.
+///
+/// The planes created bound to it with [`new_bound`][OwnedPlane#method.new_bound]
+/// are destroyed along with it.
+pub struct OwnedPlane<'nc> {
+    ptr: NonNull<NcPlane>,
+    node: Rc<Node>,
+    _marker: PhantomData<&'nc Nc>,
+}
+
+// destroys the plane, ignoring the error returned for the standard plane.
+unsafe fn release(ptr: *mut NcPlane) {
+    userptr::destroy(ptr);
+}
+
+impl_owned![['nc] OwnedPlane<'nc>, NcPlane, 'nc, "OwnedPlane", release, core::convert::identity];
+
+/// # `OwnedPlane` constructors & destructors
+impl<'nc> OwnedPlane<'nc> {
+    /// New plane, which will be the top, bottom, and root of a new pile.
+    ///
+    /// *C style function: [ncpile_create()][crate::c_api::ncpile_create].*
+    pub fn new(nc: &'nc Nc, y: NcOffset, x: NcOffset, rows: NcDim, cols: NcDim) -> NcResult<Self> {
+        Self::with_options(nc, NcPlaneOptions::new(y, x, rows, cols))
+    }
+
+    /// New plane, expects an [`NcPlaneOptions`] struct.
+    ///
+    /// The plane will be the top, bottom, and root of a new pile.
+    ///
+    /// *C style function: [ncpile_create()][crate::c_api::ncpile_create].*
+    pub fn with_options(nc: &'nc Nc, options: NcPlaneOptions) -> NcResult<Self> {
+        // the new plane can't be aliased
+        Ok(unsafe { Self::from_handle(NcPlaneHandle::with_options(nc, options)?) })
+    }
+
+    /// New plane, bound to this one, and destroyed along with it.
+    ///
+    /// *C style function: [ncplane_create()][crate::c_api::ncplane_create].*
+    pub fn new_bound(
+        &mut self,
+        y: NcOffset,
+        x: NcOffset,
+        rows: NcDim,
+        cols: NcDim,
+    ) -> NcResult<OwnedPlane<'nc>> {
+        self.with_options_bound(NcPlaneOptions::new(y, x, rows, cols))
+    }
+
+    /// New plane, bound to this one, and destroyed along with it.
+    /// Expects an [`NcPlaneOptions`] struct.
+    ///
+    /// *C style function: [ncplane_create()][crate::c_api::ncplane_create].*
+    pub fn with_options_bound(&mut self, options: NcPlaneOptions) -> NcResult<OwnedPlane<'nc>> {
+        let handle = unsafe { NcPlaneHandle::from_ptr(self.ptr()) }.with_options_bound(options)?;
+        let child = unsafe { Self::from_handle(handle) };
+        self.node.adopt(&child.node);
+        Ok(child)
+    }
+
+    /// Takes ownership of the plane of a handle.
+    ///
+    /// # Safety
+    ///
+    /// The handle must not be a handle to the standard plane, which can't be
+    /// destroyed, and the plane must not be used afterwards through any other
+    /// handle or reference to it, nor destroyed by anything else, since it's
+    /// destroyed when the owned plane is dropped.
+    pub unsafe fn from_handle(handle: NcPlaneHandle<'nc>) -> Self {
+        let ptr = handle.as_ptr();
+        Self::from_parts(ptr, Node::new(move || release(ptr)))
+    }
+
+    /// Relinquishes the ownership of the plane, returning a handle to it.
+    ///
+    /// The plane won't be destroyed along with its owned parent anymore.
+    pub fn into_handle(self) -> NcPlaneHandle<'nc> {
+        unsafe { NcPlaneHandle::from_ptr(self.into_raw()) }
+    }
+
+    /// Destroys the plane, and the owned objects bound to it.
+    ///
+    /// Any other planes bound to it are reparented to its parent.
+    ///
+    /// *C style function: [ncplane_destroy()][crate::c_api::ncplane_destroy].*
+    pub fn destroy(self) -> NcResult<()> {
+        let ptr = self.ptr();
+        self.node.release_children();
+        self.node.disarm();
+        error![
+            @call "ncplane_destroy",
+            unsafe { userptr::destroy(ptr) },
+            "OwnedPlane.destroy()"
+        ]
+    }
+
+    /// Splits the plane into its pointer and its node, without releasing it.
+    ///
+    /// Used by the widgets that take ownership of the plane.
+    pub(crate) fn into_parts(self) -> (*mut NcPlane, Rc<Node>) {
+        let ptr = self.ptr();
+        let this = ManuallyDrop::new(self);
+        let node = unsafe { core::ptr::read(&this.node) };
+        (ptr, node)
+    }
+
+    /// Returns the node of this plane, for adopting the objects bound to it.
+    pub(crate) fn node(&self) -> &Rc<Node> {
+        &self.node
+    }
+}
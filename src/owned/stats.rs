@@ -0,0 +1,39 @@
+//! `OwnedStats`
+
+use core::{marker::PhantomData, ptr::NonNull};
+use std::rc::Rc;
+
+use super::Node;
+use crate::{c_api, error_ref_mut, Nc, NcResult, NcStats};
+
+/// An owned [`NcStats`], freed when dropped.
+pub struct OwnedStats {
+    ptr: NonNull<NcStats>,
+    node: Rc<Node>,
+    _marker: PhantomData<NcStats>,
+}
+
+unsafe fn release(ptr: *mut NcStats) {
+    libc::free(ptr as *mut libc::c_void);
+}
+
+impl_owned![[] OwnedStats, NcStats, 'static, "OwnedStats", release];
+
+/// # `OwnedStats` constructors
+impl OwnedStats {
+    /// Allocates a stats object, and acquires a snapshot of the stats.
+    ///
+    /// *C style function: [notcurses_stats_alloc()][c_api::notcurses_stats_alloc].*
+    pub fn new(nc: &mut Nc) -> NcResult<Self> {
+        let stats = Self::alloc(nc)?;
+        stats.stats(nc);
+        Ok(unsafe { Self::from_raw(stats) })
+    }
+
+    fn alloc<'a>(nc: &mut Nc) -> NcResult<&'a mut NcStats> {
+        error_ref_mut![
+            @call "notcurses_stats_alloc", unsafe { c_api::notcurses_stats_alloc(nc) },
+            "OwnedStats::new()"
+        ]
+    }
+}
@@ -0,0 +1,7 @@
+//! Owned types tests.
+
+#[cfg(test)]
+mod node;
+
+#[cfg(test)]
+mod plane;
@@ -0,0 +1,50 @@
+//! Test the ownership tree.
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::owned::Node;
+
+// returns a node that logs its release into `log`.
+fn logged(log: &Rc<RefCell<Vec<u32>>>, id: u32) -> Rc<Node> {
+    let log = Rc::clone(log);
+    Node::new(move || log.borrow_mut().push(id))
+}
+
+#[test]
+fn release_children_first() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let parent = logged(&log, 0);
+    let child1 = logged(&log, 1);
+    let child2 = logged(&log, 2);
+    let grandchild = logged(&log, 3);
+    parent.adopt(&child1);
+    parent.adopt(&child2);
+    child1.adopt(&grandchild);
+
+    parent.release();
+    assert_eq![vec![2, 3, 1, 0], *log.borrow()];
+    assert![!child1.is_alive() && !grandchild.is_alive()];
+
+    // released only once
+    child1.release();
+    parent.release();
+    assert_eq![4, log.borrow().len()];
+}
+
+#[test]
+fn disarm() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let parent = logged(&log, 0);
+    let child = logged(&log, 1);
+    parent.adopt(&child);
+
+    child.disarm();
+    parent.release();
+    assert_eq![vec![0], *log.borrow()];
+
+    // a dropped child is not released
+    let parent = logged(&log, 2);
+    parent.adopt(&logged(&log, 3));
+    parent.release();
+    assert_eq![vec![0, 2], *log.borrow()];
+}
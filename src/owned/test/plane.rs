@@ -0,0 +1,24 @@
+//! Test `OwnedPlane` and the owned widgets.
+
+use crate::{owned::OwnedPlane, NcContext};
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn bound_planes() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut parent = OwnedPlane::new(&nc, 0, 0, 10, 10)?;
+    let mut child = parent.new_bound(1, 1, 5, 5)?;
+    let grandchild = child.new_bound(1, 1, 2, 2)?;
+    assert_eq![(5, 5), child.dim_yx()];
+
+    // a detached plane is not destroyed along with its parent
+    let detached = parent.new_bound(0, 0, 1, 1)?.into_handle();
+
+    drop(parent);
+    assert![!child.is_alive()];
+    assert![!grandchild.is_alive()];
+    unsafe { detached.destroy()? };
+    Ok(())
+}
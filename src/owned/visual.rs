@@ -0,0 +1,66 @@
+//! `OwnedVisual`
+
+use core::{marker::PhantomData, ptr::NonNull};
+use std::rc::Rc;
+
+use super::Node;
+use crate::{c_api, NcBlitter, NcDim, NcPlane, NcResult, NcVisual};
+
+/// An owned [`NcVisual`], destroyed when dropped.
+pub struct OwnedVisual {
+    ptr: NonNull<NcVisual>,
+    node: Rc<Node>,
+    _marker: PhantomData<NcVisual>,
+}
+
+unsafe fn release(ptr: *mut NcVisual) {
+    c_api::ncvisual_destroy(ptr);
+}
+
+impl_owned![[] OwnedVisual, NcVisual, 'static, "OwnedVisual", release];
+
+/// # `OwnedVisual` constructors
+impl OwnedVisual {
+    /// Like [`from_rgba`][OwnedVisual#method.from_rgba], but `bgra` is
+    /// arranged as BGRA.
+    ///
+    /// *C style function: [ncvisual_from_bgra()][c_api::ncvisual_from_bgra].*
+    pub fn from_bgra(bgra: &[u8], rows: NcDim, rowstride: NcDim, cols: NcDim) -> NcResult<Self> {
+        Ok(unsafe { Self::from_raw(NcVisual::from_bgra(bgra, rows, rowstride, cols)?) })
+    }
+
+    /// Opens a visual at `file`, extracts the codec and parameters and
+    /// decodes the first image to memory.
+    ///
+    /// *C style function: [ncvisual_from_file()][c_api::ncvisual_from_file].*
+    pub fn from_file(file: &str) -> NcResult<Self> {
+        Ok(unsafe { Self::from_raw(NcVisual::from_file(file)?) })
+    }
+
+    /// Promotes an [`NcPlane`] to a visual.
+    ///
+    /// See [`NcVisual::from_plane`][NcVisual#method.from_plane].
+    ///
+    /// *C style function: [ncvisual_from_plane()][c_api::ncvisual_from_plane].*
+    pub fn from_plane(
+        plane: &NcPlane,
+        blitter: NcBlitter,
+        beg_y: NcDim,
+        beg_x: NcDim,
+        len_y: NcDim,
+        len_x: NcDim,
+    ) -> NcResult<Self> {
+        let visual = NcVisual::from_plane(plane, blitter, beg_y, beg_x, len_y, len_x)?;
+        Ok(unsafe { Self::from_raw(visual) })
+    }
+
+    /// Prepares a visual from `rgba` pixels, laid out as `rows` lines, each
+    /// of which is `rowstride` bytes in length.
+    ///
+    /// See [`NcVisual::from_rgba`][NcVisual#method.from_rgba].
+    ///
+    /// *C style function: [ncvisual_from_rgba()][c_api::ncvisual_from_rgba].*
+    pub fn from_rgba(rgba: &[u8], rows: NcDim, rowstride: NcDim, cols: NcDim) -> NcResult<Self> {
+        Ok(unsafe { Self::from_raw(NcVisual::from_rgba(rgba, rows, rowstride, cols)?) })
+    }
+}
@@ -0,0 +1,329 @@
+//! Owned widgets.
+//!
+//! All of them except `OwnedMenu` take ownership of the plane they're
+//! created on, and reuse its place in the tree of owned objects.
+
+use core::{
+    marker::PhantomData,
+    ptr::{null_mut, NonNull},
+};
+use std::rc::Rc;
+
+use super::{Node, OwnedPlane};
+use crate::{
+    c_api, error_ref_mut,
+    widgets::{
+        NcMenu, NcMenuBuilder, NcMenuOptions, NcMultiSelector, NcMultiSelectorBuilder,
+        NcMultiSelectorOptions, NcProgBar, NcProgBarOptions, NcReader, NcReaderOptions, NcReel,
+        NcReelOptions, NcSelector, NcSelectorOptions, NcTree, NcTreeOptions, TreeNode,
+    },
+    Nc, NcDim, NcPlane, NcResult,
+};
+
+// Creates a widget that takes ownership of `plane`, reusing its node.
+//
+// The C constructors destroy the plane on failure, so in that case the node is
+// disarmed, leaking it rather than destroying it twice.
+//
+// The plane and the widget are owned by the node from then on, so their
+// lifetimes are erased here.
+fn consume<T: 'static>(
+    plane: OwnedPlane<'_>,
+    release: unsafe fn(*mut T),
+    create: impl FnOnce(&'static mut NcPlane) -> NcResult<&'static mut T>,
+) -> NcResult<(*mut T, Rc<Node>)> {
+    let (plane, node) = plane.into_parts();
+    match create(unsafe { &mut *plane }) {
+        Ok(widget) => {
+            let widget = widget as *mut T;
+            node.set_release(move || unsafe { release(widget) });
+            Ok((widget, node))
+        }
+        Err(e) => {
+            node.disarm();
+            Err(e)
+        }
+    }
+}
+
+/// An owned [`NcMenu`], destroyed when dropped, or along with its parent plane.
+pub struct OwnedMenu<'nc> {
+    ptr: NonNull<NcMenu>,
+    node: Rc<Node>,
+    _marker: PhantomData<&'nc Nc>,
+}
+
+unsafe fn release_menu(ptr: *mut NcMenu) {
+    let _ = NcMenu::destroy(&mut *ptr);
+}
+
+impl_owned![['nc] OwnedMenu<'nc>, NcMenu, 'nc, "OwnedMenu", release_menu, c_api::ncmenu_plane];
+
+/// # `OwnedMenu` constructors
+impl<'nc> OwnedMenu<'nc> {
+    /// New menu, on a new plane bound to `parent`.
+    ///
+    /// *C style function: [ncmenu_create()][c_api::ncmenu_create].*
+    pub fn new(parent: &mut OwnedPlane<'nc>, options: NcMenuOptions) -> NcResult<Self> {
+        let menu = unsafe { Self::from_raw(NcMenu::new(parent, options)?) };
+        parent.node().adopt(&menu.node);
+        Ok(menu)
+    }
+
+    /// New menu with item actions, on a new plane bound to `parent`.
+    ///
+    /// *C style function: [ncmenu_create()][c_api::ncmenu_create].*
+    pub fn with_builder(parent: &mut OwnedPlane<'nc>, builder: NcMenuBuilder) -> NcResult<Self> {
+        let menu = unsafe { Self::from_raw(builder.build(parent)?) };
+        parent.node().adopt(&menu.node);
+        Ok(menu)
+    }
+}
+
+/// An owned [`NcMultiSelector`], destroyed along with its plane when dropped.
+pub struct OwnedMultiSelector<'nc> {
+    ptr: NonNull<NcMultiSelector>,
+    node: Rc<Node>,
+    _marker: PhantomData<&'nc Nc>,
+}
+
+unsafe fn release_multiselector(ptr: *mut NcMultiSelector) {
+    let _ = NcMultiSelector::destroy(&mut *ptr);
+}
+
+impl_owned![['nc] OwnedMultiSelector<'nc>, NcMultiSelector, 'nc, "OwnedMultiSelector", release_multiselector, c_api::ncmultiselector_plane];
+
+/// # `OwnedMultiSelector` constructors
+impl<'nc> OwnedMultiSelector<'nc> {
+    /// New multiselector, taking ownership of `plane`.
+    ///
+    /// *C style function: [ncmultiselector_create()][c_api::ncmultiselector_create].*
+    pub fn new(plane: OwnedPlane<'nc>, options: &NcMultiSelectorOptions) -> NcResult<Self> {
+        let (ptr, node) = consume(plane, release_multiselector, |plane| {
+            NcMultiSelector::new(plane, options)
+        })?;
+        Ok(Self::from_parts(ptr, node))
+    }
+
+    /// New multiselector from a builder, taking ownership of `plane`.
+    ///
+    /// *C style function: [ncmultiselector_create()][c_api::ncmultiselector_create].*
+    pub fn with_builder(plane: OwnedPlane<'nc>, builder: NcMultiSelectorBuilder) -> NcResult<Self> {
+        let (ptr, node) = consume(plane, release_multiselector, |plane| builder.build(plane))?;
+        Ok(Self::from_parts(ptr, node))
+    }
+}
+
+/// An owned [`NcProgBar`], destroyed along with its plane when dropped.
+pub struct OwnedProgBar<'nc> {
+    ptr: NonNull<NcProgBar>,
+    node: Rc<Node>,
+    _marker: PhantomData<&'nc Nc>,
+}
+
+unsafe fn release_progbar(ptr: *mut NcProgBar) {
+    c_api::ncprogbar_destroy(ptr);
+}
+
+impl_owned![['nc] OwnedProgBar<'nc>, NcProgBar, 'nc, "OwnedProgBar", release_progbar, c_api::ncprogbar_plane];
+
+/// # `OwnedProgBar` constructors
+impl<'nc> OwnedProgBar<'nc> {
+    /// New progress bar, taking ownership of `plane`.
+    ///
+    /// *C style function: [ncprogbar_create()][c_api::ncprogbar_create].*
+    pub fn new(plane: OwnedPlane<'nc>) -> NcResult<Self> {
+        Self::with_options(plane, &NcProgBarOptions::new())
+    }
+
+    /// New progress bar, taking ownership of `plane`.
+    /// Expects an [`NcProgBarOptions`] struct.
+    ///
+    /// *C style function: [ncprogbar_create()][c_api::ncprogbar_create].*
+    pub fn with_options(plane: OwnedPlane<'nc>, options: &NcProgBarOptions) -> NcResult<Self> {
+        let (ptr, node) = consume(plane, release_progbar, |plane| {
+            error_ref_mut![
+                @call "ncprogbar_create", unsafe { c_api::ncprogbar_create(plane, options) },
+                "OwnedProgBar::with_options()"
+            ]
+        })?;
+        Ok(Self::from_parts(ptr, node))
+    }
+}
+
+/// An owned [`NcReader`], destroyed along with its plane when dropped.
+pub struct OwnedReader<'nc> {
+    ptr: NonNull<NcReader>,
+    node: Rc<Node>,
+    _marker: PhantomData<&'nc Nc>,
+}
+
+unsafe fn release_reader(ptr: *mut NcReader) {
+    let _ = NcReader::destroy(&mut *ptr);
+}
+
+impl_owned![['nc] OwnedReader<'nc>, NcReader, 'nc, "OwnedReader", release_reader, c_api::ncreader_plane];
+
+/// # `OwnedReader` constructors
+impl<'nc> OwnedReader<'nc> {
+    /// New reader, taking ownership of `plane`.
+    ///
+    /// *C style function: [ncreader_create()][c_api::ncreader_create].*
+    pub fn new(plane: OwnedPlane<'nc>) -> NcResult<Self> {
+        Self::with_options(plane, NcReaderOptions::new())
+    }
+
+    /// New reader, taking ownership of `plane`.
+    /// Expects an [`NcReaderOptions`] struct.
+    ///
+    /// *C style function: [ncreader_create()][c_api::ncreader_create].*
+    pub fn with_options(plane: OwnedPlane<'nc>, options: NcReaderOptions) -> NcResult<Self> {
+        let (ptr, node) = consume(plane, release_reader, |plane| {
+            NcReader::with_options(plane, options)
+        })?;
+        Ok(Self::from_parts(ptr, node))
+    }
+}
+
+/// An owned [`NcReel`], destroyed along with its plane and its tablets
+/// when dropped.
+pub struct OwnedReel<'nc> {
+    ptr: NonNull<NcReel>,
+    node: Rc<Node>,
+    _marker: PhantomData<&'nc Nc>,
+}
+
+unsafe fn release_reel(ptr: *mut NcReel) {
+    let _ = NcReel::destroy(&mut *ptr);
+}
+
+impl_owned![['nc] OwnedReel<'nc>, NcReel, 'nc, "OwnedReel", release_reel, c_api::ncreel_plane];
+
+/// # `OwnedReel` constructors
+impl<'nc> OwnedReel<'nc> {
+    /// New reel, taking ownership of `plane`.
+    ///
+    /// *C style function: [ncreel_create()][c_api::ncreel_create].*
+    pub fn new(plane: OwnedPlane<'nc>) -> NcResult<Self> {
+        Self::with_options(plane, &NcReelOptions::new())
+    }
+
+    /// New reel, taking ownership of `plane`.
+    /// Expects an [`NcReelOptions`] struct.
+    ///
+    /// *C style function: [ncreel_create()][c_api::ncreel_create].*
+    pub fn with_options(plane: OwnedPlane<'nc>, options: &NcReelOptions) -> NcResult<Self> {
+        let (ptr, node) = consume(plane, release_reel, |plane| {
+            NcReel::with_options(plane, options)
+        })?;
+        Ok(Self::from_parts(ptr, node))
+    }
+}
+
+/// An owned [`NcSelector`], destroyed along with its plane when dropped.
+pub struct OwnedSelector<'nc> {
+    ptr: NonNull<NcSelector>,
+    node: Rc<Node>,
+    _marker: PhantomData<&'nc Nc>,
+}
+
+unsafe fn release_selector(ptr: *mut NcSelector) {
+    c_api::ncselector_destroy(ptr, null_mut());
+}
+
+impl_owned![['nc] OwnedSelector<'nc>, NcSelector, 'nc, "OwnedSelector", release_selector, c_api::ncselector_plane];
+
+/// # `OwnedSelector` constructors
+impl<'nc> OwnedSelector<'nc> {
+    /// New selector, taking ownership of `plane`.
+    ///
+    /// *C style function: [ncselector_create()][c_api::ncselector_create].*
+    pub fn new(plane: OwnedPlane<'nc>, options: NcSelectorOptions) -> NcResult<Self> {
+        let (ptr, node) = consume(plane, release_selector, |plane| {
+            NcSelector::new(plane, options)
+        })?;
+        Ok(Self::from_parts(ptr, node))
+    }
+}
+
+/// An owned [`NcTabbed`], destroyed along with its plane and its tabs
+/// when dropped.
+pub struct OwnedTabbed<'nc> {
+    ptr: NonNull<NcTabbed>,
+    node: Rc<Node>,
+    _marker: PhantomData<&'nc Nc>,
+}
+
+unsafe fn release_tabbed(ptr: *mut NcTabbed) {
+    let _ = NcTabbed::destroy(&mut *ptr);
+}
+
+impl_owned![['nc] OwnedTabbed<'nc>, NcTabbed, 'nc, "OwnedTabbed", release_tabbed, c_api::nctabbed_plane];
+
+/// # `OwnedTabbed` constructors
+impl<'nc> OwnedTabbed<'nc> {
+    /// New tabbed widget, taking ownership of `plane`.
+    /// Expects an [`NcTabbedOptions`] struct.
+    ///
+    /// *C style function: [nctabbed_create()][c_api::nctabbed_create].*
+    pub fn new(plane: OwnedPlane<'nc>, options: &NcTabbedOptions) -> NcResult<Self> {
+        let (ptr, node) = consume(plane, release_tabbed, |plane| {
+            NcTabbed::with_options(plane, options)
+        })?;
+        Ok(Self::from_parts(ptr, node))
+    }
+
+    /// New tabbed widget from a builder, taking ownership of `plane`.
+    ///
+    /// *C style function: [nctabbed_create()][c_api::nctabbed_create].*
+    pub fn with_builder(plane: OwnedPlane<'nc>, builder: NcTabbedBuilder) -> NcResult<Self> {
+        let (ptr, node) = consume(plane, release_tabbed, |plane| builder.build(plane))?;
+        Ok(Self::from_parts(ptr, node))
+    }
+}
+
+/// An owned [`NcTree`], destroyed along with its plane when dropped.
+pub struct OwnedTree<'nc> {
+    ptr: NonNull<NcTree>,
+    node: Rc<Node>,
+    _marker: PhantomData<&'nc Nc>,
+}
+
+unsafe fn release_tree(ptr: *mut NcTree) {
+    let _ = NcTree::destroy(&mut *ptr);
+}
+
+impl_owned![['nc] OwnedTree<'nc>, NcTree, 'nc, "OwnedTree", release_tree, c_api::nctree_plane];
+
+/// # `OwnedTree` constructors
+impl<'nc> OwnedTree<'nc> {
+    /// New tree, taking ownership of `plane`.
+    ///
+    /// *C style function: [nctree_create()][c_api::nctree_create].*
+    pub fn new(plane: OwnedPlane<'nc>, options: NcTreeOptions) -> NcResult<Self> {
+        let (ptr, node) = consume(plane, release_tree, |plane| NcTree::new(plane, options))?;
+        Ok(Self::from_parts(ptr, node))
+    }
+
+    /// New tree from the `nodes` of a recursive structure, whose items are
+    /// drawn by the `draw` closure, taking ownership of `plane`.
+    ///
+    /// See [`NcTree::from_nodes`].
+    ///
+    /// *C style function: [nctree_create()][c_api::nctree_create].*
+    pub fn from_nodes<T, F>(
+        plane: OwnedPlane<'nc>,
+        nodes: &[T],
+        indentcols: NcDim,
+        draw: F,
+    ) -> NcResult<Self>
+    where
+        T: TreeNode + Clone + 'static,
+        F: FnMut(&mut NcPlane, &T, i32) -> NcResult<()> + 'static,
+    {
+        let (ptr, node) = consume(plane, release_tree, |plane| {
+            NcTree::from_nodes(plane, nodes, indentcols, draw)
+        })?;
+        Ok(Self::from_parts(ptr, node))
+    }
+}
@@ -12,18 +12,21 @@ impl NcPalette {
 
     /// Frees this `NcPalette`.
     ///
+    /// # Safety
+    ///
+    /// The palette must not be used after it's freed, and it must not be
+    /// owned by an `OwnedPalette`.
+    ///
     /// *C style function: [ncpalette_free()][c_api::ncpalette_free].*
-    pub fn free(&mut self) {
-        unsafe {
-            c_api::ncpalette_free(self);
-        }
+    pub unsafe fn free(&mut self) {
+        c_api::ncpalette_free(self);
     }
 
     /// Attempts to configure the terminal with this NcPalette.
     ///
     /// *C style function: [ncpalette_use()][c_api::ncpalette_use].*
     pub fn r#use(&self, nc: &mut Nc) -> NcResult<()> {
-        error![unsafe { c_api::ncpalette_use(nc, self) }]
+        error![@call "ncpalette_use", unsafe { c_api::ncpalette_use(nc, self) }, "NcPalette.use()"]
     }
 
     /// Returns the [`NcComponent`]s from the [`NcChannel`] in this `NcPalette`.
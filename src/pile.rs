@@ -0,0 +1,325 @@
+//! `NcPile`
+
+use core::{
+    fmt,
+    marker::PhantomData,
+    ptr::{null_mut, NonNull},
+};
+
+use crate::{
+    c_api, error, plane::userptr, Nc, NcAlpha, NcAlphaApi, NcCell, NcDim, NcError, NcErrorKind,
+    NcFile, NcOffset, NcPlane, NcPlaneHandle, NcPlaneOptions, NcResult,
+};
+
+/// A handle to a pile: a collection of planes that are rendered and
+/// rasterized together.
+///
+/// Each pile is independent of the others, and can be composed and rendered
+/// off-screen. The last pile to be rasterized is the one being shown. The
+/// standard pile is the one that contains the standard plane.
+///
+/// A pile is identified by its root plane, which must not be destroyed while
+/// the pile is in use. That's why the planes returned by
+/// [`top`][NcPile#method.top], [`bottom`][NcPile#method.bottom],
+/// [`planes`][NcPile#method.planes] and [`plane_at`][NcPile#method.plane_at],
+/// which can be the root plane, are borrowed from the pile instead of being
+/// returned as an [`NcPlaneHandle`], which could destroy them.
+///
+/// # Example
+///
+/// ```ignore
+/// use libnotcurses_sys::*;
+///
+/// fn main() -> NcResult<()> {
+///     let nc = NcContext::new()?;
+///     let (rows, cols) = nc.term_dim_yx();
+///
+///     // prepare a screen off-screen, while the standard pile is shown
+///     let mut pile = NcPile::new(&nc, rows, cols)?;
+///     pile.root().putstr("hello from another pile")?;
+///     pile.render()?;
+///
+///     // then flip between them
+///     pile.show()?;
+///     NcPile::stdpile(&nc).show()?;
+///
+///     unsafe { pile.destroy()? };
+///     Ok(())
+/// }
+/// ```
+pub struct NcPile<'nc> {
+    root: NonNull<NcPlane>,
+    _nc: PhantomData<&'nc Nc>,
+}
+
+impl<'nc> fmt::Debug for NcPile<'nc> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NcPile").field(&self.root).finish()
+    }
+}
+
+/// # `NcPile` constructors & destructors
+impl<'nc> NcPile<'nc> {
+    /// New pile, with a root plane of `rows` × `cols`.
+    ///
+    /// *C style function: [ncpile_create()][c_api::ncpile_create].*
+    pub fn new(nc: &'nc Nc, rows: NcDim, cols: NcDim) -> NcResult<Self> {
+        Self::with_options(nc, NcPlaneOptions::new(0, 0, rows, cols))
+    }
+
+    /// New pile, with a root plane created from an [`NcPlaneOptions`] struct.
+    ///
+    /// *C style function: [ncpile_create()][c_api::ncpile_create].*
+    pub fn with_options(nc: &'nc Nc, options: NcPlaneOptions) -> NcResult<Self> {
+        Self::from_root(NcPlaneHandle::with_options(nc, options)?)
+    }
+
+    /// Returns the standard pile, whose root is the standard plane.
+    ///
+    /// *C style function: [notcurses_stdplane()][c_api::notcurses_stdplane].*
+    pub fn stdpile(nc: &'nc Nc) -> Self {
+        Self {
+            root: unsafe { NonNull::new_unchecked(NcPlaneHandle::stdplane(nc).as_ptr()) },
+            _nc: PhantomData,
+        }
+    }
+
+    /// Returns the pile of which `plane` is the root.
+    ///
+    /// It's an error if `plane` is bound to another plane, and so it's not
+    /// the root of a pile.
+    ///
+    /// *C style function: [ncplane_parent()][c_api::ncplane_parent].*
+    pub fn from_root(plane: NcPlaneHandle<'nc>) -> NcResult<Self> {
+        let root = plane.as_ptr();
+        if unsafe { c_api::ncplane_parent(root) } != root {
+            return Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "NcPile::from_root(): the plane is not the root of a pile",
+            ));
+        }
+        Ok(Self {
+            root: unsafe { NonNull::new_unchecked(root) },
+            _nc: PhantomData,
+        })
+    }
+
+    /// Destroys all the planes of the pile, which ceases to exist.
+    ///
+    /// It is an error to attempt to destroy the standard pile.
+    ///
+    /// # Safety
+    ///
+    /// None of the planes of the pile must be used afterwards, through any
+    /// handle or reference to them, like the ones returned by
+    /// [`new_plane`][NcPile#method.new_plane], the handle the pile was
+    /// created [from][NcPile#method.from_root], or any other pile with the
+    /// same root, nor by any widget created on them.
+    ///
+    /// *C style function: [ncplane_destroy()][c_api::ncplane_destroy].*
+    pub unsafe fn destroy(self) -> NcResult<()> {
+        if self.is_stdpile() {
+            return Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "NcPile.destroy(): the standard pile can't be destroyed",
+            ));
+        }
+        let planes: Vec<*mut NcPlane> = self.plane_ptrs().collect();
+        for plane in planes {
+            let res = userptr::destroy(plane);
+            if res < c_api::NCRESULT_OK {
+                return Err(NcError::with_msg(res, "NcPile.destroy()").calling("ncplane_destroy"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// # `NcPile` methods
+impl<'nc> NcPile<'nc> {
+    /// Returns the root plane of the pile.
+    ///
+    /// It's not returned as an [`NcPlaneHandle`], which could destroy it.
+    pub fn root(&mut self) -> &mut NcPlane {
+        unsafe { &mut *self.root.as_ptr() }
+    }
+
+    /// Returns `true` if this is the standard pile.
+    pub fn is_stdpile(&self) -> bool {
+        userptr::is_stdplane(self.root.as_ptr())
+    }
+
+    /// New plane, bound to the root plane of the pile.
+    ///
+    /// *C style function: [ncplane_create()][c_api::ncplane_create].*
+    pub fn new_plane(&self, options: NcPlaneOptions) -> NcResult<NcPlaneHandle<'nc>> {
+        unsafe { NcPlaneHandle::from_ptr(self.root.as_ptr()) }.with_options_bound(options)
+    }
+
+    /// Returns the topmost plane of the pile.
+    ///
+    /// *C style function: [ncpile_top()][c_api::ncpile_top].*
+    pub fn top(&mut self) -> &mut NcPlane {
+        unsafe { &mut *c_api::ncpile_top(self.root.as_ptr()) }
+    }
+
+    /// Returns the bottommost plane of the pile, which is its root.
+    ///
+    /// *C style function: [ncpile_bottom()][c_api::ncpile_bottom].*
+    pub fn bottom(&mut self) -> &mut NcPlane {
+        unsafe { &mut *c_api::ncpile_bottom(self.root.as_ptr()) }
+    }
+
+    /// Returns an iterator over the planes of the pile, from the top to the
+    /// bottom.
+    ///
+    /// *C style function: [ncplane_below()][c_api::ncplane_below].*
+    pub fn planes(&self) -> impl Iterator<Item = &NcPlane> {
+        self.plane_ptrs().map(|plane| unsafe { &*plane })
+    }
+
+    /// Returns `true` if `plane` is one of the planes of the pile.
+    pub(crate) fn contains(&self, plane: *const NcPlane) -> bool {
+        self.plane_ptrs().any(|p| p as *const NcPlane == plane)
+    }
+
+    // returns an iterator over the pointers to the planes, from the top.
+    fn plane_ptrs(&self) -> impl Iterator<Item = *mut NcPlane> {
+        let mut next = unsafe { c_api::ncpile_top(self.root.as_ptr()) };
+        core::iter::from_fn(move || {
+            let plane = NonNull::new(next)?;
+            next = unsafe { c_api::ncplane_below(plane.as_ptr()) };
+            Some(plane.as_ptr())
+        })
+    }
+
+    /// Returns the topmost plane of the pile under the absolute coordinates
+    /// `y`, `x`, e.g. of a mouse event, or `None` if there's no plane there.
+    ///
+    /// The planes whose cell at those coordinates is transparent, are skipped.
+    /// A cell is transparent if it has no glyph, neither its own nor from the
+    /// base cell of its plane, and both its own background, unless it's the
+    /// default, and the background of the base cell are transparent.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn plane_at(&mut self, y: NcOffset, x: NcOffset) -> Option<&mut NcPlane> {
+        self.hit(y, x).map(|plane| unsafe { &mut *plane })
+    }
+
+    /// Returns the topmost plane under the absolute coordinates `y`, `x`.
+    pub(crate) fn hit(&self, y: NcOffset, x: NcOffset) -> Option<*mut NcPlane> {
+        self.plane_ptrs().find(|&ptr| {
+            let plane = unsafe { &mut *ptr };
+            let (ly, lx) = local_yx(plane, y, x);
+            let (rows, cols) = plane.dim_yx();
+            let inside = ly >= 0 && lx >= 0 && (ly as NcDim) < rows && (lx as NcDim) < cols;
+            inside && !is_transparent(plane, ly as NcDim, lx as NcDim)
+        })
+    }
+
+    /// Renders the pile, without writing it out to the terminal.
+    ///
+    /// Rendering it again will blow away the render.
+    ///
+    /// *C style function: [ncpile_render()][c_api::ncpile_render].*
+    pub fn render(&self) -> NcResult<()> {
+        error![
+            @call "ncpile_render", unsafe { c_api::ncpile_render(self.root.as_ptr()) },
+            "NcPile.render()"
+        ]
+    }
+
+    /// Makes the physical screen match the last rendered frame of the pile.
+    ///
+    /// This is a blocking call. Don't call this before the pile has been
+    /// rendered (doing so will likely result in a blank screen).
+    ///
+    /// *C style function: [ncpile_rasterize()][c_api::ncpile_rasterize].*
+    pub fn rasterize(&self) -> NcResult<()> {
+        error![
+            @call "ncpile_rasterize", unsafe { c_api::ncpile_rasterize(self.root.as_ptr()) },
+            "NcPile.rasterize()"
+        ]
+    }
+
+    /// Renders and rasterizes the pile, making it the one shown.
+    ///
+    /// *C style function: [ncpile_render()][c_api::ncpile_render],
+    /// [ncpile_rasterize()][c_api::ncpile_rasterize].*
+    pub fn show(&self) -> NcResult<()> {
+        self.render()?;
+        self.rasterize()
+    }
+
+    /// Renders and rasterizes the pile into a buffer, instead of writing it
+    /// out to the terminal.
+    ///
+    /// *C style function: [ncpile_render_to_buffer()][c_api::ncpile_render_to_buffer].*
+    pub fn render_to_buffer(&self) -> NcResult<Vec<u8>> {
+        render_to_buffer(self.root.as_ptr())
+    }
+
+    /// Writes the last rendered frame of the pile, in its entirety, to `fp`.
+    ///
+    /// If a frame has not yet been rendered, nothing will be written.
+    ///
+    /// *C style function: [ncpile_render_to_file()][c_api::ncpile_render_to_file].*
+    pub fn render_to_file(&self, fp: &mut NcFile) -> NcResult<()> {
+        error![
+            @call "ncpile_render_to_file", unsafe { c_api::ncpile_render_to_file(self.root.as_ptr(), fp.as_nc_ptr()) },
+            "NcPile.render_to_file()"
+        ]
+    }
+}
+
+/// Returns the absolute coordinates `y`, `x`, relative to the origin of
+/// `plane`.
+pub(crate) fn local_yx(plane: &NcPlane, y: NcOffset, x: NcOffset) -> (NcOffset, NcOffset) {
+    let (mut abs_y, mut abs_x) = (0, 0);
+    unsafe { c_api::ncplane_abs_yx(plane, &mut abs_y, &mut abs_x) };
+    (y - abs_y, x - abs_x)
+}
+
+/// Returns `true` if the cell of `plane` at `y`, `x` lets the planes below
+/// show through.
+fn is_transparent(plane: &mut NcPlane, y: NcDim, x: NcDim) -> bool {
+    let mut cell = NcCell::new();
+    let bytes = match plane.at_yx_cell(y, x, &mut cell) {
+        Ok(bytes) => bytes,
+        Err(_) => return false,
+    };
+    // the background of the cell itself, if set, must be transparent too
+    let bg_transparent = cell.bg_default_p() || cell.bg_alpha() == NcAlpha::TRANSPARENT;
+    let transparent = if bytes > 0 || !bg_transparent {
+        false
+    } else {
+        // an empty cell is rendered with the base cell
+        match plane.base() {
+            Ok(mut base) => {
+                let empty = base.strdup(plane).is_empty();
+                let alpha = base.bg_alpha();
+                base.release(plane);
+                empty && alpha == NcAlpha::TRANSPARENT
+            }
+            Err(_) => false,
+        }
+    };
+    cell.release(plane);
+    transparent
+}
+
+/// Renders and rasterizes the pile of `plane` into a new buffer.
+pub(crate) fn render_to_buffer(plane: *mut NcPlane) -> NcResult<Vec<u8>> {
+    let mut buf = null_mut();
+    let mut len = 0;
+    let res = unsafe { c_api::ncpile_render_to_buffer(plane, &mut buf, &mut len) };
+    if res < c_api::NCRESULT_OK || buf.is_null() {
+        return Err(
+            NcError::with_msg(res.min(c_api::NCRESULT_ERR), "NcPile.render_to_buffer()")
+                .calling("ncpile_render_to_buffer"),
+        );
+    }
+    let buffer = unsafe { core::slice::from_raw_parts(buf as *const u8, len as usize) }.to_vec();
+    unsafe { libc::free(buf as *mut libc::c_void) };
+    Ok(buffer)
+}
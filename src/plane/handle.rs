@@ -0,0 +1,255 @@
+//! `NcPlaneHandle`
+
+use core::{
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+use crate::{
+    c_api, error, Nc, NcDim, NcError, NcErrorKind, NcOffset, NcPlane, NcPlaneOptions, NcResult,
+    NcVisual, NcVisualOptions,
+};
+
+/// A handle to an [`NcPlane`] that can't outlive the [`Nc`] context it
+/// belongs to.
+///
+/// It has the same size as a pointer and dereferences to [`NcPlane`], so all
+/// of the plane methods are available on it.
+///
+/// Handles are created from a shared reference to the notcurses context,
+/// which means that any number of planes can be alive at the same time, while
+/// the context can't be stopped until all of them have gone out of scope.
+///
+/// Handles aren't unique: there can be several handles to the same plane,
+/// e.g. to the standard plane, and the widgets created on a plane keep using
+/// it. That's why [`destroy`][NcPlaneHandle#method.destroy] is unsafe, even
+/// though it consumes the handle.
+///
+/// Dropping a handle without destroying it keeps the plane alive in its pile,
+/// until the context is stopped.
+///
+/// # Example
+///
+/// ```ignore
+/// use libnotcurses_sys::*;
+///
+/// fn main() -> NcResult<()> {
+///     let nc = NcContext::new()?;
+///     let mut stdplane = nc.stdplane();
+///     let mut child = stdplane.new_bound(1, 1, 4, 20)?;
+///     child.putstr("hello world")?;
+///     nc.render()?;
+///     unsafe { child.destroy()? };
+///     Ok(())
+/// }
+/// ```
+#[repr(transparent)]
+pub struct NcPlaneHandle<'nc> {
+    plane: NonNull<NcPlane>,
+    _nc: PhantomData<&'nc Nc>,
+}
+
+impl<'nc> fmt::Debug for NcPlaneHandle<'nc> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NcPlaneHandle").field(&self.plane).finish()
+    }
+}
+
+/// # `NcPlaneHandle` constructors & destructors
+impl<'nc> NcPlaneHandle<'nc> {
+    /// Returns a handle to the standard plane of the `nc` context.
+    ///
+    /// The standard plane can't be destroyed.
+    ///
+    /// *C style function: [notcurses_stdplane()][c_api::notcurses_stdplane].*
+    pub fn stdplane(nc: &'nc Nc) -> Self {
+        unsafe { Self::from_ptr(c_api::notcurses_stdplane(nc as *const Nc as *mut Nc)) }
+    }
+
+    /// New plane, which will be the top, bottom, and root of a new pile.
+    ///
+    /// *C style function: [ncpile_create()][c_api::ncpile_create].*
+    pub fn new(nc: &'nc Nc, y: NcOffset, x: NcOffset, rows: NcDim, cols: NcDim) -> NcResult<Self> {
+        Self::with_options(nc, NcPlaneOptions::new(y, x, rows, cols))
+    }
+
+    /// New plane, expects an [`NcPlaneOptions`] struct.
+    ///
+    /// The plane will be the top, bottom, and root of a new pile.
+    ///
+    /// *C style function: [ncpile_create()][c_api::ncpile_create].*
+    pub fn with_options(nc: &'nc Nc, options: NcPlaneOptions) -> NcResult<Self> {
+        let res = unsafe { c_api::ncpile_create(nc as *const Nc as *mut Nc, &options) };
+        Self::from_res(
+            res,
+            &format!["NcPlaneHandle::with_options(Nc, {:?})", &options],
+        )
+    }
+
+    /// New plane, bound to this one.
+    ///
+    /// The new plane doesn't borrow from this one, since bound planes get
+    /// reparented when their parent is destroyed.
+    ///
+    /// *C style function: [ncplane_create()][c_api::ncplane_create].*
+    pub fn new_bound(
+        &self,
+        y: NcOffset,
+        x: NcOffset,
+        rows: NcDim,
+        cols: NcDim,
+    ) -> NcResult<NcPlaneHandle<'nc>> {
+        self.with_options_bound(NcPlaneOptions::new(y, x, rows, cols))
+    }
+
+    /// New plane, bound to this one, expects an [`NcPlaneOptions`] struct.
+    ///
+    /// *C style function: [ncplane_create()][c_api::ncplane_create].*
+    pub fn with_options_bound(&self, options: NcPlaneOptions) -> NcResult<NcPlaneHandle<'nc>> {
+        let res = unsafe { c_api::ncplane_create(self.as_ptr(), &options) };
+        Self::from_res(
+            res,
+            &format!["NcPlaneHandle.with_options_bound({:?})", &options],
+        )
+    }
+
+    /// Duplicates this plane.
+    ///
+    /// See [`NcPlane.dup()`][NcPlane#method.dup].
+    ///
+    /// *C style function: [ncplane_dup()][c_api::ncplane_dup].*
+    pub fn dup(&self) -> NcResult<NcPlaneHandle<'nc>> {
+        let res = unsafe { super::userptr::dup(self.as_ptr()) };
+        Self::from_res(res, "NcPlaneHandle.dup()")
+    }
+
+    /// Renders `visual` into a new plane, which will be the root of a new
+    /// pile, unless `options` specifies a parent plane to bind it to.
+    ///
+    /// Returns an error if `options` already contains a plane to render onto,
+    /// in which case [`blit`][NcPlaneHandle#method.blit] must be used instead.
+    ///
+    /// *C style function: [ncvisual_render()][c_api::ncvisual_render].*
+    pub fn from_visual(
+        nc: &'nc Nc,
+        visual: &mut NcVisual,
+        options: &NcVisualOptions,
+    ) -> NcResult<Self> {
+        if !options.n.is_null() && options.flags & NcVisualOptions::CHILDPLANE as u64 == 0 {
+            return Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "NcPlaneHandle::from_visual(): the options already contain a plane",
+            ));
+        }
+        let res = unsafe { c_api::ncvisual_render(nc as *const Nc as *mut Nc, visual, options) };
+        Self::from_res(
+            res,
+            "NcPlaneHandle::from_visual(Nc, NcVisual, NcVisualOptions)",
+        )
+    }
+
+    /// Takes a handle to a plane from a raw pointer.
+    ///
+    /// # Safety
+    ///
+    /// `plane` must be a valid, non-null plane, which must outlive the
+    /// returned handle, and nothing else may destroy it meanwhile.
+    pub unsafe fn from_ptr(plane: *mut NcPlane) -> Self {
+        Self {
+            plane: NonNull::new_unchecked(plane),
+            _nc: PhantomData,
+        }
+    }
+
+    /// Returns a raw pointer to the plane.
+    pub fn as_ptr(&self) -> *mut NcPlane {
+        self.plane.as_ptr()
+    }
+
+    /// Destroys the plane, consuming the handle.
+    ///
+    /// Any planes bound to it are reparented to its parent.
+    /// It is an error to attempt to destroy the standard plane.
+    ///
+    /// # Safety
+    ///
+    /// The plane must not be used afterwards through any other handle or
+    /// reference to it, nor by any widget created on it.
+    ///
+    /// *C style function: [ncplane_destroy()][c_api::ncplane_destroy].*
+    pub unsafe fn destroy(mut self) -> NcResult<()> {
+        NcPlane::destroy(&mut self)
+    }
+
+    // converts a result pointer into a handle, or an error if it's null.
+    fn from_res(res: *mut NcPlane, msg: &str) -> NcResult<Self> {
+        if res.is_null() {
+            Err(NcError::with_kind(NcErrorKind::NullPointer, msg))
+        } else {
+            Ok(unsafe { Self::from_ptr(res) })
+        }
+    }
+}
+
+/// # `NcPlaneHandle` methods
+impl<'nc> NcPlaneHandle<'nc> {
+    /// Renders `visual` onto this plane.
+    ///
+    /// The plane in `options` is replaced by this one.
+    ///
+    /// *C style function: [ncvisual_render()][c_api::ncvisual_render].*
+    pub fn blit(&mut self, visual: &mut NcVisual, options: &NcVisualOptions) -> NcResult<()> {
+        let mut options = *options;
+        options.n = self.as_ptr();
+        options.flags &= !(NcVisualOptions::CHILDPLANE as u64);
+        let nc = unsafe { c_api::ncplane_notcurses(self.as_ptr()) };
+        let res = unsafe { c_api::ncvisual_render(nc, visual, &options) };
+        if res.is_null() {
+            return Err(NcError::with_kind(
+                NcErrorKind::NullPointer,
+                "NcPlaneHandle.blit(NcVisual, NcVisualOptions)",
+            )
+            .calling("ncvisual_render"));
+        }
+        Ok(())
+    }
+
+    /// Renders and rasterizes the pile of which this plane is a part.
+    ///
+    /// *C style function: [ncpile_render()][c_api::ncpile_render],
+    /// [ncpile_rasterize()][c_api::ncpile_rasterize].*
+    pub fn render_pile(&mut self) -> NcResult<()> {
+        self.render()?;
+        error![
+            @call "ncpile_rasterize", unsafe { c_api::ncpile_rasterize(self.as_ptr()) },
+            "NcPlaneHandle.render_pile()"
+        ]
+    }
+}
+
+impl<'nc> Deref for NcPlaneHandle<'nc> {
+    type Target = NcPlane;
+    fn deref(&self) -> &NcPlane {
+        unsafe { self.plane.as_ref() }
+    }
+}
+
+impl<'nc> DerefMut for NcPlaneHandle<'nc> {
+    fn deref_mut(&mut self) -> &mut NcPlane {
+        unsafe { self.plane.as_mut() }
+    }
+}
+
+impl<'nc> AsRef<NcPlane> for NcPlaneHandle<'nc> {
+    fn as_ref(&self) -> &NcPlane {
+        self
+    }
+}
+
+impl<'nc> AsMut<NcPlane> for NcPlaneHandle<'nc> {
+    fn as_mut(&mut self) -> &mut NcPlane {
+        self
+    }
+}
@@ -6,12 +6,15 @@ use core::{
 
 use crate::{
     c_api::{self, ffi::size_t},
-    cstring, error, error_ref, error_ref_mut, rstring_free, Nc, NcAlign, NcAlpha, NcBlitter,
-    NcBoxMask, NcCell, NcChannel, NcChannels, NcComponent, NcDim, NcError, NcFadeCb, NcFile,
-    NcIntResult, NcIntResultApi, NcOffset, NcPaletteIndex, NcPixelGeometry, NcPlane,
-    NcPlaneOptions, NcResizeCb, NcResult, NcRgb, NcStyle, NcTime,
+    error, error_ref, error_ref_mut, string, Nc, NcAlign, NcAlpha, NcBlitter, NcBoxMask, NcCell,
+    NcChannel, NcChannels, NcComponent, NcDim, NcError, NcErrorKind, NcFadeCb, NcFadeCtx,
+    NcFadeIter, NcFile, NcIntResult, NcIntResultApi, NcOffset, NcPaletteIndex, NcPixelGeometry,
+    NcPlane, NcPlaneOptions, NcResizeCbUnsafe, NcResult, NcRgb, NcStyle, NcTime,
 };
 
+use super::userptr;
+use crate::fade;
+
 /// # NcPlaneOptions Constructors
 impl NcPlaneOptions {
     /// New NcPlaneOptions using the horizontal x.
@@ -25,12 +28,15 @@ impl NcPlaneOptions {
     }
 
     /// New NcPlaneOptions, with flags.
+    ///
+    /// For a resize callback closure, use
+    /// [`NcPlane.set_resizecb()`][NcPlane#method.set_resizecb] instead.
     pub fn with_flags(
         y: NcOffset,
         x: NcOffset,
         rows: NcDim,
         cols: NcDim,
-        resizecb: Option<NcResizeCb>,
+        resizecb: Option<NcResizeCbUnsafe>,
         flags: u64,
         margin_b: NcOffset,
         margin_r: NcOffset,
@@ -42,7 +48,7 @@ impl NcPlaneOptions {
             cols: cols as i32,
             userptr: null_mut(),
             name: null(),
-            resizecb: c_api::ncresizecb_to_c(resizecb),
+            resizecb,
             flags,
             margin_b: margin_b as i32,
             margin_r: margin_r as i32,
@@ -59,7 +65,7 @@ impl NcPlaneOptions {
         align: NcAlign,
         rows: NcDim,
         cols: NcDim,
-        resizecb: Option<NcResizeCb>,
+        resizecb: Option<NcResizeCbUnsafe>,
         flags: u64,
     ) -> Self {
         let flags = NcPlaneOptions::HORALIGNED | flags;
@@ -70,7 +76,7 @@ impl NcPlaneOptions {
             cols: cols as i32,
             userptr: null_mut(),
             name: null(),
-            resizecb: c_api::ncresizecb_to_c(resizecb),
+            resizecb,
             flags,
             margin_b: 0,
             margin_r: 0,
@@ -85,13 +91,13 @@ impl NcPlane {
     /// The returned plane will be the top, bottom, and root of this new pile.
     ///
     /// *C style function: [ncpile_create()][c_api::ncpile_create].*
-    pub fn new<'a>(
-        nc: &mut Nc,
+    pub fn new<'nc>(
+        nc: &'nc mut Nc,
         y: NcOffset,
         x: NcOffset,
         rows: NcDim,
         cols: NcDim,
-    ) -> NcResult<&'a mut NcPlane> {
+    ) -> NcResult<&'nc mut NcPlane> {
         Self::with_options(nc, NcPlaneOptions::new(y, x, rows, cols))
     }
 
@@ -100,9 +106,12 @@ impl NcPlane {
     /// The returned plane will be the top, bottom, and root of this new pile.
     ///
     /// *C style function: [ncpile_create()][c_api::ncpile_create].*
-    pub fn with_options<'a>(nc: &mut Nc, options: NcPlaneOptions) -> NcResult<&'a mut NcPlane> {
+    pub fn with_options<'nc>(
+        nc: &'nc mut Nc,
+        options: NcPlaneOptions,
+    ) -> NcResult<&'nc mut NcPlane> {
         error_ref_mut![
-            unsafe { c_api::ncpile_create(nc, &options) },
+            @call "ncpile_create", unsafe { c_api::ncpile_create(nc, &options) },
             &format!["NcPlane::with_options(Nc, {:?})", &options]
         ]
     }
@@ -110,25 +119,25 @@ impl NcPlane {
     /// New `NcPlane`, bound to another NcPlane.
     ///
     /// *C style function: [ncplane_create()][c_api::ncplane_create].*
-    pub fn new_bound<'a>(
-        bound_to: &mut NcPlane,
+    pub fn new_bound<'p>(
+        bound_to: &'p mut NcPlane,
         y: NcOffset,
         x: NcOffset,
         rows: NcDim,
         cols: NcDim,
-    ) -> NcResult<&'a mut NcPlane> {
+    ) -> NcResult<&'p mut NcPlane> {
         Self::with_options_bound(bound_to, NcPlaneOptions::new(y, x, rows, cols))
     }
 
     /// New `NcPlane`, bound to another plane, expects an [NcPlaneOptions] struct.
     ///
     /// *C style function: [ncplane_create()][c_api::ncplane_create].*
-    pub fn with_options_bound<'a>(
-        bound_to: &mut NcPlane,
+    pub fn with_options_bound<'p>(
+        bound_to: &'p mut NcPlane,
         options: NcPlaneOptions,
-    ) -> NcResult<&'a mut NcPlane> {
+    ) -> NcResult<&'p mut NcPlane> {
         error_ref_mut![
-            unsafe { c_api::ncplane_create(bound_to, &options) },
+            @call "ncplane_create", unsafe { c_api::ncplane_create(bound_to, &options) },
             &format!("NcPlane::with_options_bound(NcPlane, {:?})", &options)
         ]
     }
@@ -138,7 +147,7 @@ impl NcPlane {
     /// The returned plane will be the top, bottom, and root of this new pile.
     ///
     /// *(No equivalent C style function)*
-    pub fn with_termsize<'a>(nc: &mut Nc) -> NcResult<&'a mut NcPlane> {
+    pub fn with_termsize(nc: &mut Nc) -> NcResult<&mut NcPlane> {
         let (trows, tcols) = c_api::notcurses_term_dim_yx(nc);
         assert![(trows > 0) & (tcols > 0)];
         Self::with_options(
@@ -152,9 +161,17 @@ impl NcPlane {
     /// None of its contents will be visible after the next render call.
     /// It is an error to attempt to destroy the standard plane.
     ///
+    /// Its resize callback closure is dropped.
+    ///
+    /// # Safety
+    ///
+    /// The plane must not be accessed after it's destroyed, including through
+    /// any [`NcPlaneHandle`][crate::NcPlaneHandle] or `OwnedPlane` to it,
+    /// which can be destroyed safely by consuming them instead.
+    ///
     /// *C style function: [ncplane_destroy()][c_api::ncplane_destroy].*
-    pub fn destroy(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncplane_destroy(self) }, "NcPlane.destroy()"]
+    pub unsafe fn destroy(&mut self) -> NcResult<()> {
+        error![@call "ncplane_destroy", userptr::destroy(self), "NcPlane.destroy()"]
     }
 }
 
@@ -182,7 +199,7 @@ impl NcPlane {
     /// *C style function: [ncplane_set_fg_alpha()][c_api::ncplane_set_fg_alpha].*
     pub fn set_fg_alpha(&mut self, alpha: NcAlpha) -> NcResult<()> {
         error![
-            unsafe { c_api::ncplane_set_fg_alpha(self, alpha as i32) },
+            @call "ncplane_set_fg_alpha", unsafe { c_api::ncplane_set_fg_alpha(self, alpha as i32) },
             &format!("NcPlane.set_fg_alpha({:0X})", alpha)
         ]
     }
@@ -192,7 +209,7 @@ impl NcPlane {
     /// *C style function: [ncplane_set_bg_alpha()][c_api::ncplane_set_bg_alpha].*
     pub fn set_bg_alpha(&mut self, alpha: NcAlpha) -> NcResult<()> {
         error![
-            unsafe { c_api::ncplane_set_bg_alpha(self, alpha as i32) },
+            @call "ncplane_set_bg_alpha", unsafe { c_api::ncplane_set_bg_alpha(self, alpha as i32) },
             &format!("NcPlane.set_bg_alpha({:0X})", alpha)
         ]
     }
@@ -265,7 +282,7 @@ impl NcPlane {
         let res =
             unsafe { c_api::ncplane_stain(self, y_stop as i32, x_stop as i32, ul, ur, ll, lr) };
         error![
-            res,
+            @call "ncplane_stain", res,
             &format!(
                 "NcPlane.stain({}, {}, {:0X}, {:0X}, {:0X}, {:0X})",
                 y_stop, x_stop, ul, ur, ll, lr
@@ -466,7 +483,7 @@ impl NcPlane {
         let res =
             unsafe { c_api::ncplane_format(self, y_stop as i32, x_stop as i32, stylemask as u32) };
         error![
-            res,
+            @call "ncplane_format", res,
             &format!("NcPlane.format({}, {}, {:0X})", y_stop, x_stop, stylemask),
             res as u32
         ]
@@ -545,12 +562,13 @@ impl NcPlane {
     ) -> NcResult<String> {
         let egc = unsafe { c_api::ncplane_at_cursor(self, stylemask, channels) };
         if egc.is_null() {
-            return Err(NcError::with_msg(
-                NcIntResult::ERR,
+            return Err(NcError::with_kind(
+                NcErrorKind::NullPointer,
                 &format!("NcPlane.at_cursor({:0X}, {:0X})", stylemask, channels),
-            ));
+            )
+            .calling("ncplane_at_cursor"));
         }
-        Ok(rstring_free![egc])
+        unsafe { string::rstring_free(egc) }
     }
 
     /// Retrieves the current contents of the [`NcCell`] under the cursor
@@ -563,7 +581,7 @@ impl NcPlane {
     pub fn at_cursor_cell(&mut self, cell: &mut NcCell) -> NcResult<u32> {
         let bytes = unsafe { c_api::ncplane_at_cursor_cell(self, cell) };
         error![
-            bytes,
+            @call "ncplane_at_cursor_cell", bytes,
             &format!("NcPlane.at_cursor_cell({:?})", cell),
             bytes as u32
         ]
@@ -582,15 +600,21 @@ impl NcPlane {
     ) -> NcResult<String> {
         let egc = unsafe { c_api::ncplane_at_yx(self, y as i32, x as i32, stylemask, channels) };
         if egc.is_null() {
-            return Err(NcError::with_msg(
-                NcIntResult::ERR,
+            let kind = if y >= self.dim_y() || x >= self.dim_x() {
+                NcErrorKind::OutOfBounds
+            } else {
+                NcErrorKind::NullPointer
+            };
+            return Err(NcError::with_kind(
+                kind,
                 &format!(
                     "NcPlane.at_yx({}, {}, {:0X}, {:0X})",
                     y, x, stylemask, channels
                 ),
-            ));
+            )
+            .calling("ncplane_at_yx"));
         }
-        Ok(rstring_free![egc])
+        unsafe { string::rstring_free(egc) }
     }
 
     /// Retrieves the current contents of the specified [`NcCell`] into `cell`.
@@ -603,7 +627,7 @@ impl NcPlane {
     pub fn at_yx_cell(&mut self, y: NcDim, x: NcDim, cell: &mut NcCell) -> NcResult<u32> {
         let bytes = unsafe { c_api::ncplane_at_yx_cell(self, y as i32, x as i32, cell) };
         error![
-            bytes,
+            @call "ncplane_at_yx_cell", bytes,
             &format!("NcPlane.at_yx_cell({}, {}, {:?})", y, x, cell),
             bytes as u32
         ]
@@ -617,7 +641,7 @@ impl NcPlane {
     pub fn base(&mut self) -> NcResult<NcCell> {
         let mut cell = NcCell::new();
         let res = unsafe { c_api::ncplane_base(self, &mut cell) };
-        error![res, "NcPlane.base()", cell]
+        error![@call "ncplane_base", res, "NcPlane.base()", cell]
     }
 
     /// Sets this `NcPlane`'s base [`NcCell`] from its components.
@@ -643,10 +667,11 @@ impl NcPlane {
         stylemask: NcStyle,
         channels: NcChannels,
     ) -> NcResult<u32> {
-        let res =
-            unsafe { c_api::ncplane_set_base(self, cstring![egc], stylemask as u32, channels) };
+        let res = string::with_cstr(egc, |egc| unsafe {
+            c_api::ncplane_set_base(self, egc, stylemask as u32, channels)
+        })?;
         error![
-            res,
+            @call "ncplane_set_base", res,
             &format!(
                 "NcPlane.set_base({:?}, {:0X}, {:0X})",
                 egc, stylemask, channels
@@ -665,7 +690,7 @@ impl NcPlane {
     /// *C style function: [ncplane_set_base_cell()][c_api::ncplane_set_base_cell].*
     pub fn set_base_cell(&mut self, cell: &NcCell) -> NcResult<()> {
         error![
-            unsafe { c_api::ncplane_set_base_cell(self, cell) },
+            @call "ncplane_set_base_cell", unsafe { c_api::ncplane_set_base_cell(self, cell) },
             &format!("NcPlane.base({:?})", cell)
         ]
     }
@@ -688,13 +713,15 @@ impl NcPlane {
         len_y: Option<NcDim>,
         len_x: Option<NcDim>,
     ) -> String {
-        rstring_free![c_api::ncplane_contents(
-            self,
-            beg_y as i32,
-            beg_x as i32,
-            len_y.map_or(-1, |y| y as i32),
-            len_x.map_or(-1, |x| x as i32)
-        )]
+        unsafe {
+            string::rstring_free_lossy(c_api::ncplane_contents(
+                self,
+                beg_y as i32,
+                beg_x as i32,
+                len_y.map_or(-1, |y| y as i32),
+                len_x.map_or(-1, |x| x as i32),
+            ))
+        }
     }
 
     /// Erases every [`NcCell`] in this `NcPlane`, resetting all attributes to
@@ -752,7 +779,7 @@ impl NcPlane {
         len_x: NcOffset,
     ) -> NcResult<()> {
         error![
-            unsafe {
+            @call "ncplane_erase_region", unsafe {
                 c_api::ncplane_erase_region(
                     self,
                     beg_y.unwrap_or(u32::MAX) as i32, // unwrap_or(-1)
@@ -783,7 +810,7 @@ impl NcPlane {
     pub fn putc_yx(&mut self, y: NcDim, x: NcDim, cell: &NcCell) -> NcResult<NcDim> {
         let res = unsafe { c_api::ncplane_putc_yx(self, y as i32, x as i32, cell) };
         error![
-            res,
+            @call "ncplane_putc_yx", res,
             &format!("NcPlane.putc_yx({}, {}, {:?})", y, x, cell),
             res as NcDim
         ]
@@ -803,7 +830,7 @@ impl NcPlane {
     /// *C style function: [ncplane_putc()][c_api::ncplane_putc].*
     pub fn putc(&mut self, cell: &NcCell) -> NcResult<NcDim> {
         let res = c_api::ncplane_putc(self, cell);
-        error![res, &format!("NcPlane.putc({:?})", cell), res as NcDim]
+        error![@call "ncplane_putc", res, &format!("NcPlane.putc({:?})", cell), res as NcDim]
     }
 
     /// Calls [`putchar_yx`][NcPlane#method.putchar_yx] at the current cursor
@@ -818,8 +845,9 @@ impl NcPlane {
     ///
     /// *C style function: [ncplane_putchar()][c_api::ncplane_putchar].*
     pub fn putchar(&mut self, ch: char) -> NcResult<NcDim> {
+        string::check_nul(ch.encode_utf8(&mut [0; 4]))?;
         let res = c_api::ncplane_putchar(self, ch);
-        error![res, &format!("NcPlane.putchar({:?})", ch), res as NcDim]
+        error![@call "ncplane_putchar", res, &format!("NcPlane.putchar({:?})", ch), res as NcDim]
     }
 
     /// Replaces the [`NcCell`] at the current location with the provided `char`,
@@ -835,9 +863,10 @@ impl NcPlane {
     /// *C style function: [ncplane_putchar_stained()][c_api::ncplane_putchar_stained].*
     // WIP
     pub fn putchar_stained(&mut self, ch: char) -> NcResult<NcDim> {
+        string::check_nul(ch.encode_utf8(&mut [0; 4]))?;
         let res = c_api::ncplane_putchar_stained(self, ch);
         error![
-            res,
+            @call "ncplane_putchar_stained", res,
             &format!("NcPlane.putchar_stained({:?})", ch),
             res as NcDim
         ]
@@ -855,9 +884,10 @@ impl NcPlane {
     ///
     /// *C style function: [ncplane_putchar_yx()][c_api::ncplane_putchar_yx].*
     pub fn putchar_yx(&mut self, y: NcDim, x: NcDim, ch: char) -> NcResult<NcDim> {
+        string::check_nul(ch.encode_utf8(&mut [0; 4]))?;
         let res = c_api::ncplane_putchar_yx(self, y, x, ch);
         error![
-            res,
+            @call "ncplane_putchar_yx", res,
             &format!("NcPlane.putchar_yx({}, {}, {:?})", y, x, ch),
             res as NcDim
         ]
@@ -879,8 +909,9 @@ impl NcPlane {
     ///
     /// *C style function: [ncplane_putegc()][c_api::ncplane_putegc].*
     pub fn putegc(&mut self, egc: &str, sbytes: Option<&mut i32>) -> NcResult<NcDim> {
+        string::check_nul(egc)?;
         let res = c_api::ncplane_putegc(self, egc, sbytes);
-        error![res, &format!("NcPlane.putegc({:?}, …)", egc), res as NcDim]
+        error![@call "ncplane_putegc", res, &format!("NcPlane.putegc({:?}, …)", egc), res as NcDim]
     }
 
     /// Replaces the [`NcCell`] at the specified coordinates with the provided
@@ -905,9 +936,10 @@ impl NcPlane {
         egc: &str,
         sbytes: Option<&mut i32>,
     ) -> NcResult<NcDim> {
+        string::check_nul(egc)?;
         let res = c_api::ncplane_putegc_yx(self, y, x, egc, sbytes);
         error![
-            res,
+            @call "ncplane_putegc_yx", res,
             &format!("NcPlane.putegc_yx({}, {}, {:?}, …)", y, x, egc),
             res as NcDim
         ]
@@ -929,9 +961,10 @@ impl NcPlane {
     ///
     /// *C style function: [ncplane_putegc_stained()][c_api::ncplane_putegc_stained].*
     pub fn putegc_stained(&mut self, egc: &str, sbytes: Option<&mut i32>) -> NcResult<NcDim> {
+        string::check_nul(egc)?;
         let res = c_api::ncplane_putegc_stained(self, egc, sbytes);
         error![
-            res,
+            @call "ncplane_putegc_stained", res,
             &format!("NcPlane.putegc_stained({:?}, …)", egc),
             res as NcDim
         ]
@@ -962,9 +995,10 @@ impl NcPlane {
     ///
     /// *C style function: [ncplane_puttext()][c_api::ncplane_puttext].*
     pub fn puttext(&mut self, y: NcDim, align: NcAlign, string: &str) -> NcResult<NcDim> {
-        let res =
-            unsafe { c_api::ncplane_puttext(self, y as i32, align, cstring![string], null_mut()) };
-        error![res, &format!("NcPlane.puttext({:?})", string), res as NcDim]
+        let res = string::with_cstr(string, |s| unsafe {
+            c_api::ncplane_puttext(self, y as i32, align, s, null_mut())
+        })?;
+        error![@call "ncplane_puttext", res, &format!("NcPlane.puttext({:?})", string), res as NcDim]
     }
 
     /// Writes a string to the current location, using the current style.
@@ -981,8 +1015,9 @@ impl NcPlane {
     /// *C style function: [ncplane_putstr()][c_api::ncplane_putstr].*
     #[inline]
     pub fn putstr(&mut self, string: &str) -> NcResult<NcDim> {
+        string::check_nul(string)?;
         let res = c_api::ncplane_putstr(self, string);
-        error![res, &format!("NcPlane.putstr({:?})", string), res as NcDim]
+        error![@call "ncplane_putstr", res, &format!("NcPlane.putstr({:?})", string), res as NcDim]
     }
 
     /// Same as [`putstr`][NcPlane#method.putstr], but it also puts a newline
@@ -1020,9 +1055,11 @@ impl NcPlane {
     ///
     /// *C style function: [ncplane_putstr_stained()][c_api::ncplane_putstr_stained].*
     pub fn putstr_stained(&mut self, string: &str) -> NcResult<NcDim> {
-        let res = unsafe { c_api::ncplane_putstr_stained(self, cstring![string]) };
+        let res = string::with_cstr(string, |s| unsafe {
+            c_api::ncplane_putstr_stained(self, s)
+        })?;
         error![
-            res,
+            @call "ncplane_putstr_stained", res,
             &format!("NcPlane.putstr_stained({:?})", string),
             res as NcDim
         ]
@@ -1042,9 +1079,11 @@ impl NcPlane {
     ///
     /// *C style function: [ncplane_putstr_aligned()][c_api::ncplane_putstr_aligned].*
     pub fn putstr_aligned(&mut self, y: NcDim, align: NcAlign, string: &str) -> NcResult<NcDim> {
-        let res = unsafe { c_api::ncplane_putstr_aligned(self, y as i32, align, cstring![string]) };
+        let res = string::with_cstr(string, |s| unsafe {
+            c_api::ncplane_putstr_aligned(self, y as i32, align, s)
+        })?;
         error![
-            res,
+            @call "ncplane_putstr_aligned", res,
             &format!("NcPlane.putstr_aligned({}, {}, {:?})", y, align, string),
             res as NcDim
         ]
@@ -1063,9 +1102,11 @@ impl NcPlane {
     ///
     /// *C style function: [ncplane_putstr_yx()][c_api::ncplane_putstr_yx].*
     pub fn putstr_yx(&mut self, y: NcDim, x: NcDim, string: &str) -> NcResult<NcDim> {
-        let res = unsafe { c_api::ncplane_putstr_yx(self, y as i32, x as i32, cstring![string]) };
+        let res = string::with_cstr(string, |s| unsafe {
+            c_api::ncplane_putstr_yx(self, y as i32, x as i32, s)
+        })?;
         error![
-            res,
+            @call "ncplane_putstr_yx", res,
             &format!("NcPlane.putstr_yx({}, {}, {:?})", y, x, string),
             res as NcDim
         ]
@@ -1093,9 +1134,11 @@ impl NcPlane {
         let width = string.chars().count() as u32;
         let xpos = self.halign(align, width)?;
         self.cursor_move_yx(y, xpos)?;
-        let res = unsafe { c_api::ncplane_putstr_stained(self, cstring![string]) };
+        let res = string::with_cstr(string, |s| unsafe {
+            c_api::ncplane_putstr_stained(self, s)
+        })?;
         error![
-            res,
+            @call "ncplane_putstr_stained", res,
             &format!(
                 "NcPlane.putstr_aligned_stained({}, {}, {:?})",
                 y, align, string
@@ -1119,9 +1162,11 @@ impl NcPlane {
     /// *(No equivalent C style function)*
     pub fn putstr_yx_stained(&mut self, y: NcDim, x: NcDim, string: &str) -> NcResult<NcDim> {
         self.cursor_move_yx(y, x)?;
-        let res = unsafe { c_api::ncplane_putstr_stained(self, cstring![string]) };
+        let res = string::with_cstr(string, |s| unsafe {
+            c_api::ncplane_putstr_stained(self, s)
+        })?;
         error![
-            res,
+            @call "ncplane_putstr_stained", res,
             &format!("NcPlane.putstr_yx_stained({}, {}, {:?})", y, x, string),
             res as NcDim
         ]
@@ -1142,9 +1187,10 @@ impl NcPlane {
     /// *C style function: [ncplane_putnstr()][c_api::ncplane_putnstr].*
     #[inline]
     pub fn putnstr(&mut self, num_bytes: usize, string: &str) -> NcResult<NcDim> {
+        string::check_nul(string)?;
         let res = c_api::ncplane_putnstr(self, num_bytes, string);
         error![
-            res,
+            @call "ncplane_putnstr", res,
             &format!("NcPlane.puntstr({}, {:?})", num_bytes as size_t, string),
             res as NcDim
         ]
@@ -1170,17 +1216,11 @@ impl NcPlane {
         num_bytes: usize,
         string: &str,
     ) -> NcResult<NcDim> {
-        let res = unsafe {
-            c_api::ncplane_putnstr_aligned(
-                self,
-                y as i32,
-                align,
-                num_bytes as size_t,
-                cstring![string],
-            )
-        };
+        let res = string::with_cstr(string, |s| unsafe {
+            c_api::ncplane_putnstr_aligned(self, y as i32, align, num_bytes as size_t, s)
+        })?;
         error![
-            res,
+            @call "ncplane_putnstr_aligned", res,
             &format!(
                 "NcPlane.putnstr_aligned({}, {}, {}, {:?})",
                 y, align, num_bytes, string
@@ -1209,17 +1249,11 @@ impl NcPlane {
         num_bytes: usize,
         string: &str,
     ) -> NcResult<NcDim> {
-        let res = unsafe {
-            c_api::ncplane_putnstr_yx(
-                self,
-                y as i32,
-                x as i32,
-                num_bytes as size_t,
-                cstring![string],
-            )
-        };
+        let res = string::with_cstr(string, |s| unsafe {
+            c_api::ncplane_putnstr_yx(self, y as i32, x as i32, num_bytes as size_t, s)
+        })?;
         error![
-            res,
+            @call "ncplane_putnstr_yx", res,
             &format!(
                 "NcPlane.putnstr_yx({}, {}, {}, {:?})",
                 y, x, num_bytes, string
@@ -1239,7 +1273,7 @@ impl NcPlane {
     pub fn polyfill_yx(&mut self, y: NcDim, x: NcDim, cell: &NcCell) -> NcResult<usize> {
         let res = unsafe { c_api::ncplane_polyfill_yx(self, y as i32, x as i32, cell) };
         error![
-            res,
+            @call "ncplane_polyfill_yx", res,
             &format!("NcPlane.polyfill_yx({}, {}, {:?})", y, x, cell),
             res as usize
         ]
@@ -1284,11 +1318,11 @@ impl NcPlane {
     /// and will be bound to the same parent. Bound planes are not duplicated;
     /// the new plane is bound to the current parent, but has no bound planes.
     ///
+    /// A resize callback closure is not duplicated.
+    ///
     /// *C style function: [ncplane_dup()][c_api::ncplane_dup].*
-    //
-    // TODO: deal with the opaque field that is stored in NcPlaneOptions.userptr
     pub fn dup(&mut self) -> &mut NcPlane {
-        unsafe { &mut *c_api::ncplane_dup(self, null_mut()) }
+        unsafe { &mut *userptr::dup(self) }
     }
 
     /// Returns the topmost `NcPlane` of the current pile.
@@ -1301,7 +1335,7 @@ impl NcPlane {
     /// Returns the bottommost `NcPlane` of the current pile.
     ///
     /// *C style function: [ncpile_bottom()][c_api::ncpile_bottom].*
-    pub fn bottom<'a>(&mut self) -> &'a mut NcPlane {
+    pub fn bottom(&mut self) -> &mut NcPlane {
         unsafe { &mut *c_api::ncpile_bottom(self) }
     }
 
@@ -1327,7 +1361,7 @@ impl NcPlane {
     /// *C style function: [ncplane_move_yx()][c_api::ncplane_move_yx].*
     pub fn move_yx(&mut self, y: NcOffset, x: NcOffset) -> NcResult<()> {
         error![
-            unsafe { c_api::ncplane_move_yx(self, y, x) },
+            @call "ncplane_move_yx", unsafe { c_api::ncplane_move_yx(self, y, x) },
             &format!("NcPlane.move_yx({}, {})", y, x)
         ]
     }
@@ -1342,7 +1376,7 @@ impl NcPlane {
     /// *C style function: [ncplane_moverel()][c_api::ncplane_moverel].*
     pub fn move_rel(&mut self, rows: NcOffset, cols: NcOffset) -> NcResult<()> {
         error![
-            c_api::ncplane_moverel(self, rows, cols),
+            @call "ncplane_moverel", c_api::ncplane_moverel(self, rows, cols),
             &format!("NcPlane.move_rel({}, {})", rows, cols)
         ]
     }
@@ -1380,7 +1414,7 @@ impl NcPlane {
     /// *C style function: [ncplane_move_above()][c_api::ncplane_move_above].*
     pub fn move_above(&mut self, above: &mut NcPlane) -> NcResult<()> {
         error![
-            unsafe { c_api::ncplane_move_above(self, above) },
+            @call "ncplane_move_above", unsafe { c_api::ncplane_move_above(self, above) },
             "NcPlane.move_above()"
         ]
     }
@@ -1394,7 +1428,7 @@ impl NcPlane {
     /// *C style function: [ncplane_move_below()][c_api::ncplane_move_below].*
     pub fn move_below(&mut self, below: &mut NcPlane) -> NcResult<()> {
         error![
-            unsafe { c_api::ncplane_move_below(self, below) },
+            @call "ncplane_move_below", unsafe { c_api::ncplane_move_below(self, below) },
             "NcPlane.move_below()"
         ]
     }
@@ -1436,7 +1470,7 @@ impl NcPlane {
     /// *C style function: [ncplane_move_family_below()][c_api::ncplane_move_family_below].*
     pub fn move_family_above(&mut self, above: &mut NcPlane) -> NcResult<()> {
         error![
-            unsafe { c_api::ncplane_move_family_above(self, above) },
+            @call "ncplane_move_family_above", unsafe { c_api::ncplane_move_family_above(self, above) },
             "NcPlane.move_family_above()"
         ]
     }
@@ -1452,7 +1486,7 @@ impl NcPlane {
     /// *C style function: [ncplane_move_family_below()][c_api::ncplane_move_family_below].*
     pub fn move_family_below(&mut self, below: &mut NcPlane) -> NcResult<()> {
         error![
-            unsafe { c_api::ncplane_move_family_below(self, below) },
+            @call "ncplane_move_family_below", unsafe { c_api::ncplane_move_family_below(self, below) },
             "NcPlane.move_family_below()"
         ]
     }
@@ -1488,7 +1522,7 @@ impl NcPlane {
         target_x: NcDim,
     ) -> NcResult<()> {
         error![
-            unsafe {
+            @call "ncplane_mergedown", unsafe {
                 c_api::ncplane_mergedown(
                     source,
                     self,
@@ -1521,7 +1555,7 @@ impl NcPlane {
     // for `mergedown` too.
     pub fn mergedown_simple(&mut self, source: &mut NcPlane) -> NcResult<()> {
         error![
-            unsafe { c_api::ncplane_mergedown_simple(source, self) },
+            @call "ncplane_mergedown_simple", unsafe { c_api::ncplane_mergedown_simple(source, self) },
             "NcPlane.mergedown_simple(NcPlane)"
         ]
     }
@@ -1532,7 +1566,7 @@ impl NcPlane {
     //
     // TODO: CHECK: what happens when it's bound to itself.
     pub fn parent(&mut self) -> NcResult<&mut NcPlane> {
-        error_ref_mut![unsafe { c_api::ncplane_parent(self) }, "NcPlane.parent()"]
+        error_ref_mut![@call "ncplane_parent", unsafe { c_api::ncplane_parent(self) }, "NcPlane.parent()"]
     }
 
     /// Gets the parent to which this `NcPlane` is bound, if any.
@@ -1542,7 +1576,7 @@ impl NcPlane {
     // CHECK: what happens when it's bound to itself.
     pub fn parent_const(&self) -> NcResult<&NcPlane> {
         error_ref![
-            unsafe { c_api::ncplane_parent_const(self) },
+            @call "ncplane_parent_const", unsafe { c_api::ncplane_parent_const(self) },
             "NcPlane.parent_const()"
         ]
     }
@@ -1561,7 +1595,7 @@ impl NcPlane {
     /// *C style function: [ncplane_reparent()][c_api::ncplane_reparent].*
     pub fn reparent<'a>(&mut self, newparent: &'a mut NcPlane) -> NcResult<&'a mut NcPlane> {
         error_ref_mut![
-            unsafe { c_api::ncplane_reparent(self, newparent) },
+            @call "ncplane_reparent", unsafe { c_api::ncplane_reparent(self, newparent) },
             "NcPlane.reparent(NcPlane)"
         ]
     }
@@ -1576,7 +1610,7 @@ impl NcPlane {
     // TODO:CHECK: If 'newparent' is an ancestor, NULL is returned & no changes're made.
     pub fn reparent_family<'a>(&mut self, newparent: &'a mut NcPlane) -> NcResult<&'a mut NcPlane> {
         error_ref_mut![
-            unsafe { c_api::ncplane_reparent_family(self, newparent) },
+            @call "ncplane_reparent_family", unsafe { c_api::ncplane_reparent_family(self, newparent) },
             "NcPlane.reparent_family(NcPlane)"
         ]
     }
@@ -1590,7 +1624,7 @@ impl NcPlane {
     /// *C style function: [ncpile_rasterize()][c_api::ncpile_rasterize].*
     pub fn rasterize(&mut self) -> NcResult<()> {
         error![
-            unsafe { c_api::ncpile_rasterize(self) },
+            @call "ncpile_rasterize", unsafe { c_api::ncpile_rasterize(self) },
             "NcPlane.rasterize()"
         ]
     }
@@ -1601,31 +1635,22 @@ impl NcPlane {
     ///
     /// *C style function: [ncpile_render()][c_api::ncpile_render].*
     pub fn render(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncpile_render(self) }, "NcPlane.render()"]
+        error![@call "ncpile_render", unsafe { c_api::ncpile_render(self) }, "NcPlane.render()"]
     }
 
     /// Performs the rendering and rasterization portion of
     /// [`render`][NcPlane#method.render] and [`rasterize`][NcPlane#method.rasterize]
-    /// but doe not write the resulting buffer out to the terminal.
+    /// but does not write the resulting buffer out to the terminal.
     ///
     /// Using this function, the user can control the writeout process.
-    /// The returned buffer must be freed by the caller.
+    /// The contents of `buffer` are replaced by the rasterized frame.
+    ///
+    /// See also [`NcPile.render_to_buffer()`][crate::NcPile#method.render_to_buffer].
     ///
     /// *C style function: [ncpile_render_to_buffer()][c_api::ncpile_render_to_buffer].*
-    // CHECK this works
     pub fn render_to_buffer(&mut self, buffer: &mut Vec<u8>) -> NcResult<()> {
-        let len = buffer.len() as u32;
-
-        // https://github.com/dankamongmen/notcurses/issues/1339
-        #[cfg(any(target_arch = "x86_64", target_arch = "i686", target_arch = "x86"))]
-        let mut buf = buffer.as_mut_ptr() as *mut i8;
-        #[cfg(not(any(target_arch = "x86_64", target_arch = "i686", target_arch = "x86")))]
-        let mut buf = buffer.as_mut_ptr() as *mut u8;
-
-        error![
-            unsafe { c_api::ncpile_render_to_buffer(self, &mut buf, &mut len.into()) },
-            &format!["NcPlane.render_to_buffer(buffer, {})", len]
-        ]
+        *buffer = crate::pile::render_to_buffer(self)?;
+        Ok(())
     }
 
     /// Writes the last rendered frame, in its entirety, to `fp`.
@@ -1634,14 +1659,14 @@ impl NcPlane {
     ///
     /// *C style function: [ncpile_render_to_file()][c_api::ncpile_render_to_file].*
     pub fn render_to_file(&mut self, fp: &mut NcFile) -> NcResult<()> {
-        error![unsafe { c_api::ncpile_render_to_file(self, fp.as_nc_ptr()) }]
+        error![@call "ncpile_render_to_file", unsafe { c_api::ncpile_render_to_file(self, fp.as_nc_ptr()) }, "NcPlane.render_to_file()"]
     }
     /// Gets a mutable reference to the [`Nc`] context of this `NcPlane`.
     ///
     /// *C style function: [ncplane_notcurses()][c_api::ncplane_notcurses].*
-    pub fn notcurses<'a>(&self) -> NcResult<&'a mut Nc> {
+    pub fn notcurses(&mut self) -> NcResult<&mut Nc> {
         error_ref_mut![
-            unsafe { c_api::ncplane_notcurses(self) },
+            @call "ncplane_notcurses", unsafe { c_api::ncplane_notcurses(self) },
             "NcPlane.notcurses()"
         ]
     }
@@ -1649,9 +1674,9 @@ impl NcPlane {
     /// Gets an immutable reference to the [`Nc`] context of this `NcPlane`.
     ///
     /// *C style function: [ncplane_notcurses_const()][c_api::ncplane_notcurses_const].*
-    pub fn notcurses_const<'a>(&self) -> NcResult<&'a Nc> {
+    pub fn notcurses_const(&self) -> NcResult<&Nc> {
         error_ref![
-            unsafe { c_api::ncplane_notcurses_const(self) },
+            @call "ncplane_notcurses_const", unsafe { c_api::ncplane_notcurses_const(self) },
             "NcPlane.notcurses()"
         ]
     }
@@ -1705,7 +1730,7 @@ impl NcPlane {
     /// *C style function: [ncplane_cursor_move_yx()][c_api::ncplane_cursor_move_yx].*
     pub fn cursor_move_yx(&mut self, y: NcDim, x: NcDim) -> NcResult<()> {
         error![
-            unsafe { c_api::ncplane_cursor_move_yx(self, y as i32, x as i32) },
+            @call "ncplane_cursor_move_yx", unsafe { c_api::ncplane_cursor_move_yx(self, y as i32, x as i32) },
             &format!("NcPlane.move_yx({}, {})", y, x)
         ]
     }
@@ -1716,7 +1741,7 @@ impl NcPlane {
     pub fn cursor_move_y(&mut self, y: NcDim) -> NcResult<()> {
         let x = self.cursor_x();
         error![
-            unsafe { c_api::ncplane_cursor_move_yx(self, y as i32, x as i32) },
+            @call "ncplane_cursor_move_yx", unsafe { c_api::ncplane_cursor_move_yx(self, y as i32, x as i32) },
             &format!("NcPlane.move_y({})", y)
         ]
     }
@@ -1727,7 +1752,7 @@ impl NcPlane {
     pub fn cursor_move_x(&mut self, x: NcDim) -> NcResult<()> {
         let y = self.cursor_y();
         error![
-            unsafe { c_api::ncplane_cursor_move_yx(self, y as i32, x as i32) },
+            @call "ncplane_cursor_move_yx", unsafe { c_api::ncplane_cursor_move_yx(self, y as i32, x as i32) },
             &format!("NcPlane.move_x({})", x)
         ]
     }
@@ -1780,7 +1805,7 @@ impl NcPlane {
     pub fn halign(&mut self, align: NcAlign, numcols: NcDim) -> NcResult<NcDim> {
         let res = c_api::ncplane_halign(self, align, numcols);
         error![
-            res,
+            @call "ncplane_halign", res,
             &format!("NcPlane.halign({:?}, {})", align, numcols),
             res as NcDim
         ]
@@ -1797,7 +1822,7 @@ impl NcPlane {
     #[inline]
     pub fn valign(&mut self, align: NcAlign, numrows: NcDim) -> NcResult<()> {
         error![
-            c_api::ncplane_valign(self, align, numrows),
+            @call "ncplane_valign", c_api::ncplane_valign(self, align, numrows),
             &format!("NcPlane.valign({:?}, {})", align, numrows)
         ]
     }
@@ -1895,7 +1920,7 @@ impl NcPlane {
         x_len: NcDim,
     ) -> NcResult<()> {
         error![
-            unsafe {
+            @call "ncplane_resize", unsafe {
                 c_api::ncplane_resize(
                     self,
                     keep_y as i32,
@@ -1924,7 +1949,7 @@ impl NcPlane {
     /// *C style function: [ncplane_resize_marginalized()][c_api::ncplane_resize_marginalized].*
     pub fn resize_marginalized(&mut self) -> NcResult<()> {
         error![
-            unsafe { c_api::ncplane_resize_marginalized(self) },
+            @call "ncplane_resize_marginalized", unsafe { c_api::ncplane_resize_marginalized(self) },
             "NcPlane.resize_marginalized()"
         ]
     }
@@ -1935,7 +1960,7 @@ impl NcPlane {
     /// *C style function: [ncplane_resize_maximize()][c_api::ncplane_resize_maximize].*
     pub fn resize_maximize(&mut self) -> NcResult<()> {
         error![
-            unsafe { c_api::ncplane_resize_maximize(self) },
+            @call "ncplane_resize_maximize", unsafe { c_api::ncplane_resize_maximize(self) },
             "NcPlane.resize_maximize()"
         ]
     }
@@ -1991,7 +2016,7 @@ impl NcPlane {
         };
 
         error_ref_mut![
-            res_array,
+            @call "ncplane_as_rgba", res_array,
             &format![
                 "NcPlane.rgba({}, {}, {}, {:?}, {:?})",
                 blitter, beg_y, beg_x, len_y, len_x
@@ -2051,7 +2076,7 @@ impl NcPlane {
     //
     // TODO: suitable for use as an NcResizeCb?
     pub fn resize_realign(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncplane_resize_realign(self) }]
+        error![@call "ncplane_resize_realign", unsafe { c_api::ncplane_resize_realign(self) }, "NcPlane.resize_realign()"]
     }
 
     /// Resizes this `NcPlane`, retaining what data we can (everything, unless we're
@@ -2060,27 +2085,122 @@ impl NcPlane {
     /// *C style function: [ncplane_resize_simple()][c_api::ncplane_resize_simple].*
     #[inline]
     pub fn resize_simple(&mut self, y_len: NcDim, x_len: NcDim) -> NcResult<()> {
-        error![c_api::ncplane_resize_simple(
+        error![@call "ncplane_resize_simple", c_api::ncplane_resize_simple(
             self,
             y_len as u32,
             x_len as u32
-        )]
+        ), "NcPlane.resize_simple()"]
     }
 
-    /// Returns this `NcPlane`'s current resize callback.
+    /// Returns this `NcPlane`'s current C resize callback.
     ///
     /// *C style function: [ncplane_resizecb()][c_api::ncplane_resizecb].*
-    pub fn resizecb(&self) -> Option<NcResizeCb> {
-        unsafe { c_api::ncresizecb_to_rust(c_api::ncplane_resizecb(self)) }
+    pub fn resizecb(&self) -> Option<NcResizeCbUnsafe> {
+        unsafe { c_api::ncplane_resizecb(self) }
+    }
+
+    /// Returns `true` if this `NcPlane` has a resize callback closure.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn has_resizecb(&self) -> bool {
+        crate::resizecb::is_trampoline(self.resizecb())
+            && unsafe { userptr::get_const(self) }.map_or(false, |data| data.resizecb.is_some())
     }
 
-    /// Replaces this `NcPlane`'s existing resize callback (which may be [None]).
+    /// Replaces this `NcPlane`'s existing resize callback with a closure.
+    ///
+    /// The closure is called with this plane each time its parent is resized,
+    /// and it is dropped when the plane is destroyed from Rust, or the
+    /// callback is replaced. The plane's [user data][NcPlane#method.user_data]
+    /// is available from it. An error or a panic inside the closure won't
+    /// unwind into the C library.
     ///
     /// The standard plane's resizecb may not be changed.
     ///
     /// *C style function: [ncplane_set_resizecb()][c_api::ncplane_set_resizecb].*
-    pub fn set_resizecb(&mut self, resizecb: Option<NcResizeCb>) {
-        unsafe { c_api::ncplane_set_resizecb(self, c_api::ncresizecb_to_c(resizecb)) }
+    pub fn set_resizecb<F>(&mut self, resizecb: F) -> NcResult<()>
+    where
+        F: FnMut(&mut NcPlane) -> NcResult<()> + 'static,
+    {
+        if userptr::is_stdplane(self) {
+            return Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "NcPlane.set_resizecb(): the standard plane's resizecb may not be changed",
+            ));
+        }
+        userptr::get_or_init(self).resizecb = Some(Box::new(resizecb));
+        unsafe { c_api::ncplane_set_resizecb(self, Some(crate::resizecb::resizecb_trampoline)) };
+        Ok(())
+    }
+
+    /// Replaces this `NcPlane`'s existing resize callback with a C function,
+    /// (which may be [None]).
+    ///
+    /// Any previous resize callback closure is dropped.
+    ///
+    /// The standard plane's resizecb may not be changed.
+    ///
+    /// *C style function: [ncplane_set_resizecb()][c_api::ncplane_set_resizecb].*
+    pub fn set_resizecb_unsafe(&mut self, resizecb: Option<NcResizeCbUnsafe>) {
+        unsafe {
+            c_api::ncplane_set_resizecb(self, resizecb);
+            if let Some(data) = userptr::get(self) {
+                data.resizecb = None;
+            }
+        }
+    }
+
+    /// Removes this `NcPlane`'s resize callback, dropping the closure if any.
+    ///
+    /// *C style function: [ncplane_set_resizecb()][c_api::ncplane_set_resizecb].*
+    pub fn unset_resizecb(&mut self) {
+        self.set_resizecb_unsafe(None);
+    }
+
+    /// Attaches `data` to this `NcPlane`, replacing any previous user data.
+    ///
+    /// The data is dropped when the plane is destroyed from Rust. It can be
+    /// accessed from the plane's resize callback closure.
+    ///
+    /// *C style function: [ncplane_set_userptr()][c_api::ncplane_set_userptr].*
+    pub fn set_user_data<T: 'static>(&mut self, data: T) {
+        userptr::get_or_init(self).user_data = Some(Box::new(data));
+    }
+
+    /// Returns a reference to the user data of this `NcPlane`,
+    /// if there's any of type `T`.
+    ///
+    /// *C style function: [ncplane_userptr()][c_api::ncplane_userptr].*
+    pub fn user_data<T: 'static>(&self) -> Option<&T> {
+        unsafe { userptr::get_const(self) }?
+            .user_data
+            .as_ref()?
+            .downcast_ref()
+    }
+
+    /// Returns a mutable reference to the user data of this `NcPlane`,
+    /// if there's any of type `T`.
+    ///
+    /// *C style function: [ncplane_userptr()][c_api::ncplane_userptr].*
+    pub fn user_data_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        unsafe { userptr::get(self) }?
+            .user_data
+            .as_mut()?
+            .downcast_mut()
+    }
+
+    /// Removes and returns the user data of this `NcPlane`,
+    /// if there's any of type `T`.
+    ///
+    /// Data of any other type is left in place.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn take_user_data<T: 'static>(&mut self) -> Option<T> {
+        let data = unsafe { userptr::get(self) }?;
+        if !data.user_data.as_ref()?.is::<T>() {
+            return None;
+        }
+        data.user_data.take()?.downcast().ok().map(|data| *data)
     }
 
     /// Rotate the plane π/2 radians (90°) clockwise.
@@ -2097,7 +2217,7 @@ impl NcPlane {
     ///
     /// *C style function: [ncplane_rotate_cw()][c_api::ncplane_rotate_cw].*
     pub fn rotate_cw(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncplane_rotate_cw(self) }]
+        error![@call "ncplane_rotate_cw", unsafe { c_api::ncplane_rotate_cw(self) }, "NcPlane.rotate_cw()"]
     }
 
     /// Rotate the plane π/2 radians (90°) counter-clockwise.
@@ -2107,7 +2227,7 @@ impl NcPlane {
     ///
     /// *C style function: [ncplane_rotate_ccw()][c_api::ncplane_rotate_ccw].*
     pub fn rotate_ccw(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncplane_rotate_ccw(self) }]
+        error![@call "ncplane_rotate_ccw", unsafe { c_api::ncplane_rotate_ccw(self) }, "NcPlane.rotate_ccw()"]
     }
 
     /// Maps the specified coordinates relative to the origin of this `NcPlane`,
@@ -2189,7 +2309,7 @@ impl NcPlane {
     /// *C style function: [ncplane_scrollup()][c_api::ncplane_scrollup].*
     pub fn scrollup(&mut self, r: NcDim) -> NcResult<NcDim> {
         let res = unsafe { c_api::ncplane_scrollup(self, r as i32) };
-        error![res, "", res as NcDim]
+        error![@call "ncplane_scrollup", res, "", res as NcDim]
     }
 
     /// Scrolls down the current plane until `child` is no longer hidden beneath it.
@@ -2202,7 +2322,7 @@ impl NcPlane {
     /// *C style function: [ncplane_scrollup_child()][c_api::ncplane_scrollup_child].*
     pub fn scrollup_child(&mut self, child: &NcPlane) -> NcResult<NcDim> {
         let res = unsafe { c_api::ncplane_scrollup_child(self, child) };
-        error![res, "", res as NcDim]
+        error![@call "ncplane_scrollup_child", res, "", res as NcDim]
     }
 }
 
@@ -2237,7 +2357,7 @@ impl NcPlane {
         x_stop: NcDim,
         boxmask: NcBoxMask,
     ) -> NcResult<()> {
-        error![unsafe {
+        error![@call "ncplane_box", unsafe {
             c_api::ncplane_box(
                 self,
                 ul,
@@ -2250,7 +2370,7 @@ impl NcPlane {
                 x_stop as i32,
                 boxmask,
             )
-        }]
+        }, "NcPlane.box()"]
     }
 
     /// Draws a box with its upper-left corner at the current cursor position,
@@ -2273,9 +2393,9 @@ impl NcPlane {
         x_len: NcDim,
         boxmask: NcBoxMask,
     ) -> NcResult<()> {
-        error![c_api::ncplane_box_sized(
+        error![@call "ncplane_box_sized", c_api::ncplane_box_sized(
             self, ul, ur, ll, lr, hline, vline, y_len, x_len, boxmask
-        )]
+        ), "NcPlane.box_sized()"]
     }
 
     /// NcPlane.[box()][NcPlane#method.box] with the double box-drawing characters.
@@ -2290,9 +2410,9 @@ impl NcPlane {
         x_stop: NcDim,
         boxmask: NcBoxMask,
     ) -> NcResult<()> {
-        error![c_api::ncplane_double_box(
+        error![@call "ncplane_double_box", c_api::ncplane_double_box(
             self, stylemask, channels, y_stop, x_stop, boxmask
-        )]
+        ), "NcPlane.double_box()"]
     }
 
     ///
@@ -2307,9 +2427,9 @@ impl NcPlane {
         x_len: NcDim,
         boxmask: NcBoxMask,
     ) -> NcResult<()> {
-        error![c_api::ncplane_double_box(
+        error![@call "ncplane_double_box", c_api::ncplane_double_box(
             self, stylemask, channels, y_len, x_len, boxmask
-        )]
+        ), "NcPlane.double_box_sized()"]
     }
 
     /// Draws the perimeter around this `NcPlane`.
@@ -2326,9 +2446,9 @@ impl NcPlane {
         vline: &NcCell,
         boxmask: NcBoxMask,
     ) -> NcResult<()> {
-        error![c_api::ncplane_perimeter(
+        error![@call "ncplane_perimeter", c_api::ncplane_perimeter(
             self, ul, ur, ll, lr, hline, vline, boxmask
-        )]
+        ), "NcPlane.perimeter()"]
     }
 
     /// NcPlane.[perimeter()][NcPlane#method.perimeter] with the double box-drawing characters.
@@ -2342,9 +2462,9 @@ impl NcPlane {
         channels: NcChannels,
         boxmask: NcBoxMask,
     ) -> NcResult<()> {
-        error![c_api::ncplane_perimeter_double(
+        error![@call "ncplane_perimeter_double", c_api::ncplane_perimeter_double(
             self, stylemask, channels, boxmask
-        )]
+        ), "NcPlane.perimeter_double()"]
     }
 
     /// NcPlane.[perimeter()][NcPlane#method.perimeter] with the rounded box-drawing characters.
@@ -2358,16 +2478,16 @@ impl NcPlane {
         channels: NcChannels,
         boxmask: NcBoxMask,
     ) -> NcResult<()> {
-        error![c_api::ncplane_perimeter_rounded(
+        error![@call "ncplane_perimeter_rounded", c_api::ncplane_perimeter_rounded(
             self, stylemask, channels, boxmask
-        )]
+        ), "NcPlane.perimeter_rounded()"]
     }
 }
 
 // -----------------------------------------------------------------------------
 /// ## NcPlane methods: fading, gradients & greyscale
 impl NcPlane {
-    /// Fades this `NcPlane` in, over the specified time, calling 'fader' at
+    /// Fades this `NcPlane` in, over the specified time, calling `fader` at
     /// each iteration.
     ///
     /// Usage:
@@ -2377,41 +2497,163 @@ impl NcPlane {
     /// When it's done, the `NcPlane` will have reached the target levels,
     /// starting from zeroes.
     ///
+    /// The `fader` closure is responsible for rendering. Returning an error
+    /// from it ceases the fading operation, and the error is returned.
+    ///
+    /// *C style function: [ncplane_fadein()][c_api::ncplane_fadein].*
+    pub fn fadein<F>(&mut self, time: &NcTime, fader: F) -> NcResult<()>
+    where
+        F: FnMut(&mut Nc, &mut NcPlane, &NcTime) -> NcResult<()>,
+    {
+        fade::with_fader(
+            fader,
+            "NcPlane.fadein()",
+            "ncplane_fadein",
+            |cb, curry| unsafe { c_api::ncplane_fadein(self, time, cb, curry) },
+        )
+    }
+
+    /// Fades this `NcPlane` in, over the specified time, calling the C
+    /// function `fader` at each iteration, or rendering if it's `None`.
+    ///
     /// *C style function: [ncplane_fadein()][c_api::ncplane_fadein].*
-    pub fn fadein(&mut self, time: &NcTime, fader: NcFadeCb) -> NcResult<()> {
-        error![unsafe { c_api::ncplane_fadein(self, time, fader, null_mut()) }]
+    pub fn fadein_unsafe(&mut self, time: &NcTime, fader: NcFadeCb) -> NcResult<()> {
+        error![
+            @call "ncplane_fadein", unsafe { c_api::ncplane_fadein(self, time, fader, null_mut()) },
+            "NcPlane.fadein_unsafe()"
+        ]
     }
 
-    /// Fades in through 'iter' iterations,
-    /// where 'iter' < 'ncfadectx_iterations(nctx)'.
+    /// Fades in through `iter` iterations,
+    /// where `iter` < [`ctx.iterations()`][NcFadeCtx#method.iterations].
+    ///
+    /// The `fader` closure is responsible for rendering.
+    ///
+    /// See also [`fadein_iter`][NcPlane#method.fadein_iter].
+    ///
+    /// *C style function: [ncplane_fadein_iteration()][c_api::ncplane_fadein_iteration].*
+    pub fn fadein_iteration<F>(&mut self, ctx: &mut NcFadeCtx, iter: u32, fader: F) -> NcResult<()>
+    where
+        F: FnMut(&mut Nc, &mut NcPlane, &NcTime) -> NcResult<()>,
+    {
+        fade::with_fader(
+            fader,
+            "NcPlane.fadein_iteration()",
+            "ncplane_fadein_iteration",
+            |cb, curry| unsafe {
+                c_api::ncplane_fadein_iteration(self, ctx, iter as i32, cb, curry)
+            },
+        )
+    }
+
+    /// Fades in through `iter` iterations, calling the C function `fader`,
+    /// or rendering if it's `None`.
     ///
     /// *C style function: [ncplane_fadein_iteration()][c_api::ncplane_fadein_iteration].*
-    pub fn fadein_iteration(&mut self, time: &NcTime, fader: NcFadeCb) -> NcResult<()> {
-        error![unsafe { c_api::ncplane_fadein(self, time, fader, null_mut()) }]
+    pub fn fadein_iteration_unsafe(
+        &mut self,
+        ctx: &mut NcFadeCtx,
+        iter: u32,
+        fader: NcFadeCb,
+    ) -> NcResult<()> {
+        error![
+            @call "ncplane_fadein_iteration", unsafe { c_api::ncplane_fadein_iteration(self, ctx, iter as i32, fader, null_mut()) },
+            &format!["NcPlane.fadein_iteration_unsafe(ctx, {})", iter]
+        ]
+    }
+
+    /// Returns an iterator that fades this `NcPlane` in, rendering one step
+    /// at a time.
+    ///
+    /// *C style function: [ncfadectx_setup()][c_api::ncfadectx_setup],
+    /// [ncplane_fadein_iteration()][c_api::ncplane_fadein_iteration].*
+    pub fn fadein_iter(&mut self) -> NcResult<NcFadeIter<'_>> {
+        NcFadeIter::new(self, true)
     }
 
-    /// Fades this `NcPlane` out, over the specified time, calling 'fader' at
+    /// Fades this `NcPlane` out, over the specified time, calling `fader` at
     /// each iteration.
     ///
     /// Requires a terminal which supports truecolor, or at least palette
     /// modification (if the terminal uses a palette, our ability to fade planes
     /// is limited, and affected by the complexity of the rest of the screen).
     ///
+    /// The `fader` closure is responsible for rendering. Returning an error
+    /// from it ceases the fading operation, and the error is returned.
+    ///
+    /// *C style function: [ncplane_fadeout()][c_api::ncplane_fadeout].*
+    pub fn fadeout<F>(&mut self, time: &NcTime, fader: F) -> NcResult<()>
+    where
+        F: FnMut(&mut Nc, &mut NcPlane, &NcTime) -> NcResult<()>,
+    {
+        fade::with_fader(
+            fader,
+            "NcPlane.fadeout()",
+            "ncplane_fadeout",
+            |cb, curry| unsafe { c_api::ncplane_fadeout(self, time, cb, curry) },
+        )
+    }
+
+    /// Fades this `NcPlane` out, over the specified time, calling the C
+    /// function `fader` at each iteration, or rendering if it's `None`.
+    ///
     /// *C style function: [ncplane_fadeout()][c_api::ncplane_fadeout].*
-    pub fn fadeout(&mut self, time: &NcTime, fader: NcFadeCb) -> NcResult<()> {
-        error![unsafe { c_api::ncplane_fadeout(self, time, fader, null_mut()) }]
+    pub fn fadeout_unsafe(&mut self, time: &NcTime, fader: NcFadeCb) -> NcResult<()> {
+        error![
+            @call "ncplane_fadeout", unsafe { c_api::ncplane_fadeout(self, time, fader, null_mut()) },
+            "NcPlane.fadeout_unsafe()"
+        ]
     }
 
-    /// Fades out through 'iter' iterations,
-    /// where 'iter' < 'ncfadectx_iterations(nctx)'.
+    /// Fades out through `iter` iterations,
+    /// where `iter` < [`ctx.iterations()`][NcFadeCtx#method.iterations].
+    ///
+    /// The `fader` closure is responsible for rendering.
+    ///
+    /// See also [`fadeout_iter`][NcPlane#method.fadeout_iter].
     ///
     /// *C style function: [ncplane_fadeout_iteration()][c_api::ncplane_fadeout_iteration].*
-    pub fn fadeout_iteration(&mut self, time: &NcTime, fader: NcFadeCb) -> NcResult<()> {
-        error![unsafe { c_api::ncplane_fadeout(self, time, fader, null_mut()) }]
+    pub fn fadeout_iteration<F>(&mut self, ctx: &mut NcFadeCtx, iter: u32, fader: F) -> NcResult<()>
+    where
+        F: FnMut(&mut Nc, &mut NcPlane, &NcTime) -> NcResult<()>,
+    {
+        fade::with_fader(
+            fader,
+            "NcPlane.fadeout_iteration()",
+            "ncplane_fadeout_iteration",
+            |cb, curry| unsafe {
+                c_api::ncplane_fadeout_iteration(self, ctx, iter as i32, cb, curry)
+            },
+        )
     }
 
-    /// Pulses this `NcPlane` in and out until the callback returns non-zero,
-    /// relying on the callback 'fader' to initiate rendering.
+    /// Fades out through `iter` iterations, calling the C function `fader`,
+    /// or rendering if it's `None`.
+    ///
+    /// *C style function: [ncplane_fadeout_iteration()][c_api::ncplane_fadeout_iteration].*
+    pub fn fadeout_iteration_unsafe(
+        &mut self,
+        ctx: &mut NcFadeCtx,
+        iter: u32,
+        fader: NcFadeCb,
+    ) -> NcResult<()> {
+        error![
+            @call "ncplane_fadeout_iteration", unsafe { c_api::ncplane_fadeout_iteration(self, ctx, iter as i32, fader, null_mut()) },
+            &format!["NcPlane.fadeout_iteration_unsafe(ctx, {})", iter]
+        ]
+    }
+
+    /// Returns an iterator that fades this `NcPlane` out, rendering one step
+    /// at a time.
+    ///
+    /// *C style function: [ncfadectx_setup()][c_api::ncfadectx_setup],
+    /// [ncplane_fadeout_iteration()][c_api::ncplane_fadeout_iteration].*
+    pub fn fadeout_iter(&mut self) -> NcResult<NcFadeIter<'_>> {
+        NcFadeIter::new(self, false)
+    }
+
+    /// Pulses this `NcPlane` in and out until the `fader` closure returns
+    /// an error, relying on it to initiate rendering.
     ///
     /// `time` defines the half-period (i.e. the transition from black to full
     /// brightness, or back again).
@@ -2420,9 +2662,30 @@ impl NcPlane {
     /// then calling this method, which will fade in from black to the
     /// specified colors.
     ///
+    /// The error returned by the closure to stop the pulse is returned.
+    ///
     /// *C style function: [ncplane_pulse()][c_api::ncplane_pulse].*
-    pub fn pulse(&mut self, time: &NcTime, fader: NcFadeCb) -> NcResult<()> {
-        error![unsafe { c_api::ncplane_pulse(self, time, fader, null_mut()) }]
+    pub fn pulse<F>(&mut self, time: &NcTime, fader: F) -> NcResult<()>
+    where
+        F: FnMut(&mut Nc, &mut NcPlane, &NcTime) -> NcResult<()>,
+    {
+        fade::with_fader(
+            fader,
+            "NcPlane.pulse()",
+            "ncplane_pulse",
+            |cb, curry| unsafe { c_api::ncplane_pulse(self, time, cb, curry) },
+        )
+    }
+
+    /// Pulses this `NcPlane` in and out until the C function `fader` returns
+    /// non-zero, relying on it to initiate rendering.
+    ///
+    /// *C style function: [ncplane_pulse()][c_api::ncplane_pulse].*
+    pub fn pulse_unsafe(&mut self, time: &NcTime, fader: NcFadeCb) -> NcResult<()> {
+        error![
+            @call "ncplane_pulse", unsafe { c_api::ncplane_pulse(self, time, fader, null_mut()) },
+            "NcPlane.pulse_unsafe()"
+        ]
     }
 
     /// Draws a gradient with its upper-left corner at the current cursor
@@ -2464,8 +2727,9 @@ impl NcPlane {
         y_stop: NcDim,
         x_stop: NcDim,
     ) -> NcResult<NcDim> {
+        string::check_nul(egc)?;
         let res = c_api::ncplane_gradient(self, egc, stylemask, ul, ur, ll, lr, y_stop, x_stop);
-        error![res, "", res as NcDim]
+        error![@call "ncplane_gradient", res, "", res as NcDim]
     }
 
     /// Draw a gradient with its upper-left corner at the current cursor position,
@@ -2486,8 +2750,9 @@ impl NcPlane {
         y_len: NcDim,
         x_len: NcDim,
     ) -> NcResult<NcDim> {
+        string::check_nul(egc)?;
         let res = c_api::ncplane_gradient_sized(self, egc, stylemask, ul, ur, ll, lr, y_len, x_len);
-        error![res, "", res as NcDim]
+        error![@call "ncplane_gradient_sized", res, "", res as NcDim]
     }
 
     /// Draws a high-resolution gradient using upper blocks and synced backgrounds.
@@ -2511,7 +2776,7 @@ impl NcPlane {
         let res = unsafe {
             c_api::ncplane_highgradient(self, ul, ur, ll, lr, y_stop as i32, x_stop as i32)
         };
-        error![res, "", res as NcDim]
+        error![@call "ncplane_highgradient", res, "", res as NcDim]
     }
 
     /// [`gradient_sized`][NcPlane#method.gradient_sized]
@@ -2530,7 +2795,7 @@ impl NcPlane {
         let res = unsafe {
             c_api::ncplane_highgradient_sized(self, ul, ur, ll, lr, y_stop as i32, x_stop as i32)
         };
-        error![res, "", res as NcDim]
+        error![@call "ncplane_highgradient_sized", res, "", res as NcDim]
     }
 
     /// Converts this `NcPlane`'s content to greyscale.
@@ -3,7 +3,7 @@
 // functions already exported by bindgen : 101
 // -------------------------------------------
 // (#) test: 9
-// (W) wrap: 93
+// (W) wrap: 95
 // -------------------------------------------
 //W  ncpile_bottom
 //W# ncpile_create
@@ -96,12 +96,12 @@
 //W  ncplane_set_resizecb
 //W  ncplane_set_scrolling
 //W  ncplane_set_styles
-//   ncplane_set_userptr
+//W  ncplane_set_userptr
 //W  ncplane_stain
 //W  ncplane_styles
 //W  ncplane_translate
 //W  ncplane_translate_abs
-//   ncplane_userptr
+//W  ncplane_userptr
 //   ncplane_vline_interp
 //W  ncplane_x
 //W  ncplane_y
@@ -182,10 +182,14 @@
 //   ncplane_putwstr_yx
 //   ncplane_vprintf
 
+mod handle;
 mod methods;
 
+pub use handle::NcPlaneHandle;
+
 pub(crate) mod helpers;
 pub(crate) mod reimplemented;
+pub(crate) mod userptr;
 
 #[cfg(test)]
 pub(crate) mod test;
@@ -258,6 +262,14 @@ pub(crate) mod test;
 /// geometry is refreshed (similarly to
 /// [`Nc.refresh`][crate::Nc#method.refresh]) following the render.
 ///
+/// # Lifetimes
+///
+/// The references to planes returned by the constructors of this type borrow
+/// from the [`Nc`][crate::Nc] context or the parent plane they were created
+/// from. In order to have several planes alive at the same time, use
+/// [`NcPlaneHandle`] instead, which borrows the context immutably, and is
+/// consumed when destroyed.
+///
 /// # Methods & Associated Functions
 ///
 /// - [Constructors & Destructors](#ncplane-constructors--destructors)
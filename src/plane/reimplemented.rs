@@ -8,8 +8,8 @@ use crate::{
         ffi::{self, size_t},
         nccell_release,
     },
-    cstring, NcAlign, NcAlpha, NcBoxMask, NcCell, NcChannel, NcChannels, NcComponent, NcDim,
-    NcIntResult, NcIntResultApi, NcOffset, NcPlane, NcRgb, NcStyle,
+    NcAlign, NcAlpha, NcBoxMask, NcCell, NcChannel, NcChannels, NcComponent, NcDim, NcIntResult,
+    NcIntResultApi, NcOffset, NcPlane, NcRgb, NcStyle,
 };
 
 // Alpha -----------------------------------------------------------------------
@@ -265,7 +265,11 @@ pub fn ncplane_putchar_yx(plane: &mut NcPlane, y: NcDim, x: NcDim, ch: char) ->
 /// *Method: NcPlane.[putchar_stained()][NcPlane#method.putchar_stained].*
 #[inline]
 pub fn ncplane_putchar_stained(plane: &mut NcPlane, ch: char) -> NcIntResult {
-    unsafe { c_api::ncplane_putstr_stained(plane, cstring![ch.to_string()]) }
+    let mut buf = [0; 4];
+    crate::string::with_cstr(ch.encode_utf8(&mut buf), |egc| unsafe {
+        c_api::ncplane_putstr_stained(plane, egc)
+    })
+    .unwrap_or(c_api::NCRESULT_ERR)
 }
 
 /// Replaces the [`NcCell`] at the current location with the provided `egc`,
@@ -292,7 +296,10 @@ pub fn ncplane_putegc(plane: &mut NcPlane, egc: &str, sbytes: Option<&mut i32>)
         sbytes_ptr = null_mut();
     }
 
-    unsafe { ffi::ncplane_putegc_yx(plane, -1, -1, cstring![egc], sbytes_ptr) }
+    crate::string::with_cstr(egc, |egc| unsafe {
+        ffi::ncplane_putegc_yx(plane, -1, -1, egc, sbytes_ptr)
+    })
+    .unwrap_or(c_api::NCRESULT_ERR)
 }
 
 /// Replaces the [`NcCell`] at the specified coordinates with the provided `egc`,
@@ -325,7 +332,10 @@ pub fn ncplane_putegc_yx(
         sbytes_ptr = null_mut();
     }
 
-    unsafe { ffi::ncplane_putegc_yx(plane, y as i32, x as i32, cstring![egc], sbytes_ptr) }
+    crate::string::with_cstr(egc, |egc| unsafe {
+        ffi::ncplane_putegc_yx(plane, y as i32, x as i32, egc, sbytes_ptr)
+    })
+    .unwrap_or(c_api::NCRESULT_ERR)
 }
 
 /// Replaces the [`NcCell`] at the current location with the provided `egc`,
@@ -356,7 +366,10 @@ pub fn ncplane_putegc_stained(
         sbytes_ptr = null_mut();
     }
 
-    unsafe { ffi::ncplane_putegc_stained(plane, cstring![egc], sbytes_ptr) }
+    crate::string::with_cstr(egc, |egc| unsafe {
+        ffi::ncplane_putegc_stained(plane, egc, sbytes_ptr)
+    })
+    .unwrap_or(c_api::NCRESULT_ERR)
 }
 
 /// Writes a string to the current location, using the current style.
@@ -373,7 +386,10 @@ pub fn ncplane_putegc_stained(
 /// *Method: NcPlane.[putstr()][NcPlane#method.putstr].*
 #[inline]
 pub fn ncplane_putstr(plane: &mut NcPlane, string: &str) -> NcIntResult {
-    unsafe { c_api::ncplane_putstr_yx(plane, -1, -1, cstring![string]) }
+    crate::string::with_cstr(string, |s| unsafe {
+        c_api::ncplane_putstr_yx(plane, -1, -1, s)
+    })
+    .unwrap_or(c_api::NCRESULT_ERR)
 }
 
 /// Writes a string to the current location, using the current style,
@@ -391,7 +407,10 @@ pub fn ncplane_putstr(plane: &mut NcPlane, string: &str) -> NcIntResult {
 /// *Method: NcPlane.[putnstr()][NcPlane#method.putnstr].*
 #[inline]
 pub fn ncplane_putnstr(plane: &mut NcPlane, num_bytes: usize, string: &str) -> NcIntResult {
-    unsafe { c_api::ncplane_putnstr_yx(plane, -1, -1, num_bytes as size_t, cstring![string]) }
+    crate::string::with_cstr(string, |s| unsafe {
+        c_api::ncplane_putnstr_yx(plane, -1, -1, num_bytes as size_t, s)
+    })
+    .unwrap_or(c_api::NCRESULT_ERR)
 }
 
 // movement, size & alignment --------------------------------------------------
@@ -997,15 +1016,10 @@ pub fn ncplane_gradient(
         return NcIntResult::ERR;
     }
 
-    #[cfg(any(target_arch = "armv7l", target_arch = "i686"))]
-    let egc_ptr = cstring![egc] as *const i8;
-    #[cfg(not(any(target_arch = "armv7l", target_arch = "i686")))]
-    let egc_ptr = cstring![egc];
-
-    unsafe {
+    crate::string::with_cstr(egc, |egc| unsafe {
         crate::bindings::ffi::ncplane_gradient(
             plane,
-            egc_ptr,
+            egc,
             stylemask as u32,
             ul,
             ur,
@@ -1014,7 +1028,8 @@ pub fn ncplane_gradient(
             len_y as i32,
             len_x as i32,
         )
-    }
+    })
+    .unwrap_or(c_api::NCRESULT_ERR)
 }
 
 /// Draw a gradient with its upper-left corner at the current cursor position,
@@ -1,4 +1,216 @@
 //! Test `NcPlane` methods and associated functions.
 
-// use crate::NcPlane;
-// use serial_test::serial;
+use crate::{NcContext, NcPlaneHandle};
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn handles() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let stdplane = nc.stdplane();
+
+    // several handles can be alive at the same time
+    let mut bound = stdplane.new_bound(0, 0, 10, 10)?;
+    let mut pile = NcPlaneHandle::new(&nc, 0, 0, 5, 5)?;
+    assert_eq![(10, 10), bound.dim_yx()];
+    assert_eq![(5, 5), pile.dim_yx()];
+    bound.putstr("hello")?;
+    pile.putstr("world")?;
+    nc.render()?;
+
+    // the standard plane can't be destroyed
+    assert![unsafe { nc.stdplane().destroy() }.is_err()];
+
+    unsafe { bound.destroy()? };
+    unsafe { pile.destroy()? };
+    nc.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn resizecb() -> crate::NcResult<()> {
+    use std::{cell::Cell, rc::Rc};
+
+    let nc = NcContext::new()?;
+    let mut parent = NcPlaneHandle::new(&nc, 0, 0, 10, 10)?;
+    let mut child = parent.new_bound(0, 0, 5, 5)?;
+
+    let calls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&calls);
+    child.set_resizecb(move |_plane| {
+        counter.set(counter.get() + 1);
+        Ok(())
+    })?;
+    assert![child.has_resizecb()];
+    parent.resize_simple(12, 12)?;
+    assert_eq![1, calls.get()];
+
+    // a panic doesn't unwind across the C library, and unsets the callback
+    child.set_resizecb(|_plane| panic!["resizecb"])?;
+    let _ = parent.resize_simple(10, 10);
+    assert![!child.has_resizecb()];
+    assert![child.resizecb().is_none()];
+
+    child.unset_resizecb();
+    assert![!child.has_resizecb()];
+    parent.resize_simple(12, 12)?;
+    assert_eq![1, calls.get()];
+
+    // the standard plane's resizecb may not be changed
+    assert![nc.stdplane().set_resizecb(|_plane| Ok(())).is_err()];
+
+    unsafe { child.destroy()? };
+    unsafe { parent.destroy()? };
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn fade() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    if !nc.canfade() {
+        return nc.stop();
+    }
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 5, 5)?;
+    plane.putstr("fade")?;
+
+    // the closure can cancel the fade early
+    let mut calls = 0;
+    let res = plane.fadeout(&crate::NcTime::new(1, 0), |nc, _plane, _time| {
+        calls += 1;
+        if calls == 2 {
+            return Err(crate::NcError::new_msg("cancelled"));
+        }
+        nc.render()
+    });
+    assert_eq!["cancelled", res.unwrap_err().msg];
+    assert_eq![2, calls];
+
+    // the iterator yields the progress of each step
+    let mut last = None;
+    for step in plane.fadein_iter()? {
+        last = Some(step?);
+    }
+    if let Some(step) = last {
+        assert_eq![step.iterations, step.iteration + 1];
+        assert![(step.progress() - 1.0).abs() < f64::EPSILON];
+    }
+
+    unsafe { plane.destroy()? };
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn user_data() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut parent = NcPlaneHandle::new(&nc, 0, 0, 10, 10)?;
+    let mut plane = parent.new_bound(0, 0, 5, 5)?;
+    assert_eq![None, plane.user_data::<u32>()];
+
+    plane.set_user_data(0_u32);
+    assert_eq![Some(&0), plane.user_data::<u32>()];
+    assert_eq![None, plane.user_data::<i32>()];
+
+    // the resize callback can access the user data
+    plane.set_resizecb(|plane| {
+        if let Some(resizes) = plane.user_data_mut::<u32>() {
+            *resizes += 1;
+        }
+        Ok(())
+    })?;
+    parent.resize_simple(12, 12)?;
+    assert_eq![Some(&1), plane.user_data::<u32>()];
+
+    assert_eq![None, plane.take_user_data::<String>()];
+    assert_eq![Some(1), plane.take_user_data::<u32>()];
+    assert_eq![None, plane.user_data::<u32>()];
+
+    plane.set_user_data(String::from("dropped on destroy"));
+    unsafe { plane.destroy()? };
+    unsafe { parent.destroy()? };
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn piles() -> crate::NcResult<()> {
+    use crate::{NcPile, NcPlaneOptions};
+
+    let nc = NcContext::new()?;
+    let mut pile = NcPile::new(&nc, 10, 10)?;
+    assert![!pile.is_stdpile()];
+    assert![NcPile::stdpile(&nc).is_stdpile()];
+
+    let mut plane = pile.new_plane(NcPlaneOptions::new(1, 1, 2, 2))?;
+    plane.putstr("hi")?;
+    assert_eq![plane.as_ptr(), pile.top() as *mut _];
+    assert_eq![pile.root() as *mut _, pile.bottom() as *mut _];
+    assert_eq![2, pile.planes().count()];
+
+    // only root planes identify a pile
+    let bottom = unsafe { NcPlaneHandle::from_ptr(pile.bottom()) };
+    assert![NcPile::from_root(bottom).is_ok()];
+    let top = unsafe { NcPlaneHandle::from_ptr(pile.top()) };
+    assert![NcPile::from_root(top).is_err()];
+
+    pile.render()?;
+    assert![!pile.render_to_buffer()?.is_empty()];
+    pile.show()?;
+    NcPile::stdpile(&nc).show()?;
+
+    assert![unsafe { NcPile::stdpile(&nc).destroy() }.is_err()];
+    unsafe { pile.destroy()? };
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn pile_plane_at() -> crate::NcResult<()> {
+    use crate::{NcAlpha, NcAlphaApi, NcPile, NcPlaneOptions};
+
+    let nc = NcContext::new()?;
+    let mut pile = NcPile::new(&nc, 10, 10)?;
+    let mut plane = pile.new_plane(NcPlaneOptions::new(1, 1, 3, 3))?;
+    plane.set_base("", 0, NcAlpha::TRANSPARENT as u64)?;
+    plane.putstr("x")?;
+
+    assert_eq![
+        Some(plane.as_ptr()),
+        pile.plane_at(1, 1).map(|p| p as *mut _)
+    ];
+    // the transparent cells let the root plane show through
+    let root = pile.root() as *mut _;
+    assert_eq![Some(root), pile.plane_at(2, 2).map(|p| p as *mut _)];
+    assert![pile.plane_at(20, 20).is_none()];
+
+    unsafe { pile.destroy()? };
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn strings() -> crate::NcResult<()> {
+    use crate::NcErrorKind;
+
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 4, 300)?;
+
+    // short strings use the stack buffer, long ones are allocated
+    assert_eq![5, plane.putstr_yx(0, 0, "hello")?];
+    let long = "x".repeat(280);
+    assert_eq![280, plane.putstr_yx(1, 0, &long)?];
+    assert_eq!["hello", plane.contents(0, 0, Some(1), Some(5))];
+
+    // interior nul bytes are an error, not a panic
+    let err = plane.putstr_yx(2, 0, "nul\0byte").unwrap_err();
+    assert_eq![NcErrorKind::InvalidArgument, err.kind()];
+    let err = plane.putstr_yx(2, 0, &format!["{}\0", long]).unwrap_err();
+    assert_eq![NcErrorKind::InvalidArgument, err.kind()];
+
+    unsafe { plane.destroy()? };
+    nc.stop()?;
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! The Rust data stored in the userptr of an `NcPlane`.
+//!
+//! The userptr of the planes is reserved for this data, so it must not be
+//! changed with [`ncplane_set_userptr`][c_api::ncplane_set_userptr] on planes
+//! used with the Rust API.
+//!
+//! The data is freed when the plane is destroyed from Rust. It is leaked if
+//! the plane is destroyed by the C library, e.g. along with a widget or when
+//! the notcurses context is stopped.
+
+use core::{
+    any::Any,
+    ptr::null_mut,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{c_api, NcIntResult, NcPlane, NcResizeCb};
+
+/// The Rust data associated with an [`NcPlane`].
+pub(crate) struct NcPlaneData {
+    /// A unique id, that tells apart the planes allocated at the address of
+    /// a destroyed one.
+    pub(crate) id: usize,
+    /// The resize callback closure, called from the C resize callback.
+    pub(crate) resizecb: Option<NcResizeCb>,
+    /// The user data, downcasted to its type on access.
+    pub(crate) user_data: Option<Box<dyn Any>>,
+    /// The number of items of the widget that owns the plane, for the
+    /// widgets whose C API needs it.
+    pub(crate) widget_items: usize,
+    /// The Rust state of the widget that owns the plane, for the widgets
+    /// that store closures or other data along with their items.
+    pub(crate) widget_data: Option<Box<dyn Any>>,
+}
+
+impl Default for NcPlaneData {
+    fn default() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            resizecb: None,
+            user_data: None,
+            widget_items: 0,
+            widget_data: None,
+        }
+    }
+}
+
+/// Returns the data of the `plane`, if there's any.
+///
+/// # Safety
+/// `plane` must be valid, and there must not be any other live references to
+/// its data.
+pub(crate) unsafe fn get<'a>(plane: *const NcPlane) -> Option<&'a mut NcPlaneData> {
+    (c_api::ncplane_userptr(plane as *mut NcPlane) as *mut NcPlaneData).as_mut()
+}
+
+/// Returns a shared reference to the data of the `plane`, if there's any.
+///
+/// # Safety
+/// `plane` must be valid, and there must not be any live mutable references
+/// to its data.
+pub(crate) unsafe fn get_const<'a>(plane: *const NcPlane) -> Option<&'a NcPlaneData> {
+    (c_api::ncplane_userptr(plane as *mut NcPlane) as *const NcPlaneData).as_ref()
+}
+
+/// Returns the data of the `plane`, allocating it if there's none.
+pub(crate) fn get_or_init(plane: &mut NcPlane) -> &mut NcPlaneData {
+    unsafe {
+        if c_api::ncplane_userptr(plane).is_null() {
+            let data = Box::into_raw(Box::new(NcPlaneData::default()));
+            c_api::ncplane_set_userptr(plane, data as *mut _);
+        }
+        &mut *(c_api::ncplane_userptr(plane) as *mut NcPlaneData)
+    }
+}
+
+/// Frees the data of the `plane`, if there's any.
+///
+/// # Safety
+/// `plane` must be valid, and there must not be any live references to its
+/// data.
+pub(crate) unsafe fn free(plane: *mut NcPlane) {
+    let data = c_api::ncplane_set_userptr(plane, null_mut()) as *mut NcPlaneData;
+    if !data.is_null() {
+        drop(Box::from_raw(data));
+    }
+}
+
+/// Returns `true` if `plane` is the standard plane.
+pub(crate) fn is_stdplane(plane: *const NcPlane) -> bool {
+    unsafe {
+        let nc = c_api::ncplane_notcurses_const(plane);
+        plane == c_api::notcurses_stdplane_const(nc)
+    }
+}
+
+/// Destroys the `plane`, freeing its data first.
+///
+/// # Safety
+/// `plane` must be valid, and it can't be used afterwards, unless it was the
+/// standard plane, which can't be destroyed.
+pub(crate) unsafe fn destroy(plane: *mut NcPlane) -> NcIntResult {
+    if !is_stdplane(plane) {
+        free(plane);
+    }
+    c_api::ncplane_destroy(plane)
+}
+
+/// Duplicates the `plane`, without its data.
+///
+/// The duplicate doesn't keep the resize callback, if it was a closure.
+///
+/// # Safety
+/// `plane` must be valid.
+pub(crate) unsafe fn dup(plane: *mut NcPlane) -> *mut NcPlane {
+    let new = c_api::ncplane_dup(plane, null_mut());
+    if !new.is_null() && crate::resizecb::is_trampoline(c_api::ncplane_resizecb(new)) {
+        c_api::ncplane_set_resizecb(new, None);
+    }
+    new
+}
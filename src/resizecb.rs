@@ -1,63 +1,54 @@
 //! `NcResizeCb`
 
-use crate::{c_api, NcIntResult, NcPlane};
-
-/// A callback function called when an [`NcPlane`] is resized.
+use crate::{
+    c_api,
+    callback::{ffi_guard, int_result},
+    plane::userptr,
+    NcIntResult, NcPlane, NcResult,
+};
+
+/// A callback closure called when an [`NcPlane`] is resized.
 ///
-/// See also [`ncresizecb_to_rust`][c_api::ncresizecb_to_rust]
-/// & [`ncresizecb_to_c`][c_api::ncresizecb_to_c].
+/// It can capture state, and it's stored along with the plane by
+/// [`NcPlane.set_resizecb()`][NcPlane#method.set_resizecb].
 ///
-pub type NcResizeCb = fn(&mut NcPlane) -> NcIntResult;
+/// Returning an error, or panicking, makes the C callback return an error.
+/// A panic also unsets the callback.
+pub type NcResizeCb = Box<dyn FnMut(&mut NcPlane) -> NcResult<()>>;
 
 /// The unsafe version of [`NcResizeCb`] expected by the notcurses C API.
 pub type NcResizeCbUnsafe = unsafe extern "C" fn(*mut NcPlane) -> NcIntResult;
 
-pub(crate) mod reimplemented {
-    use super::*;
-
-    /// Converts [`NcResizeCbUnsafe`] to [`NcResizeCb`].
-    pub fn ncresizecb_to_rust(resizecb: Option<NcResizeCbUnsafe>) -> Option<NcResizeCb> {
-        resizecb.map(|cb| unsafe { core::mem::transmute(cb) })
-    }
-
-    /// Converts [`NcResizeCb`] to [`NcResizeCbUnsafe`].
-    ///
-    // waiting for https://github.com/rust-lang/rust/issues/53605
-    // to make this function const, and then NcPlaneOptions constructors.
-    pub fn ncresizecb_to_c(resizecb: Option<NcResizeCb>) -> Option<NcResizeCbUnsafe> {
-        resizecb.map(|cb| unsafe { core::mem::transmute(cb) })
+/// The C resize callback that calls the [`NcResizeCb`] closure stored in the
+/// userptr of the plane.
+pub(crate) unsafe extern "C" fn resizecb_trampoline(plane: *mut NcPlane) -> NcIntResult {
+    let res = ffi_guard(|| {
+        // the closure is taken out while it's being called, so that it
+        // can replace or unset the resize callback of its own plane.
+        let mut resizecb = match userptr::get(plane).and_then(|data| data.resizecb.take()) {
+            Some(cb) => cb,
+            None => return Ok(()),
+        };
+        let res = resizecb(&mut *plane);
+
+        // restores it, unless it has been replaced or unset meanwhile
+        if is_trampoline(c_api::ncplane_resizecb(plane)) {
+            if let Some(data) = userptr::get(plane) {
+                if data.resizecb.is_none() {
+                    data.resizecb = Some(resizecb);
+                }
+            }
+        }
+        res
+    });
+    // the closure was dropped while unwinding, unless it set another one
+    if res.is_err() && userptr::get(plane).map_or(true, |data| data.resizecb.is_none()) {
+        c_api::ncplane_set_resizecb(plane, None);
     }
+    int_result(&res)
 }
 
-/// Enables the [`NcResizeCb`] associated methods and constants.
-pub trait NcResizeCbApi {
-    fn to_rust(&self) -> Option<NcResizeCb>;
-    fn to_c(&self) -> Option<NcResizeCbUnsafe>;
-}
-
-impl NcResizeCbApi for NcResizeCb {
-    /// Returns [`NcResizeCbUnsafe`].
-    ///
-    /// *C style function: [c_api::ncresizecb_to_c()][c_api::ncresizecb_to_c].*
-    fn to_c(&self) -> Option<NcResizeCbUnsafe> {
-        c_api::ncresizecb_to_c(Some(*self))
-    }
-    /// no-op.
-    fn to_rust(&self) -> Option<NcResizeCb> {
-        Some(*self)
-    }
-}
-
-impl NcResizeCbApi for NcResizeCbUnsafe {
-    /// no-op.
-    fn to_c(&self) -> Option<NcResizeCbUnsafe> {
-        Some(*self)
-    }
-
-    /// Returns [`NcResizeCb`].
-    ///
-    /// *C style function: [c_api::ncresizecb_to_rust()][c_api::ncresizecb_to_rust].*
-    fn to_rust(&self) -> Option<NcResizeCb> {
-        c_api::ncresizecb_to_rust(Some(*self))
-    }
+/// Returns `true` if `resizecb` is the trampoline of the closures.
+pub(crate) fn is_trampoline(resizecb: Option<NcResizeCbUnsafe>) -> bool {
+    resizecb.map_or(false, |cb| cb as usize == resizecb_trampoline as usize)
 }
@@ -0,0 +1,213 @@
+//! Passing strings to the C API, and reading them back.
+//!
+//! The strings passed to the C API are copied into a nul-terminated buffer
+//! that lives for the duration of the call. Short strings use a buffer on the
+//! stack, avoiding an allocation in hot paths like
+//! [`NcPlane.putstr_yx`][crate::NcPlane#method.putstr_yx].
+//!
+//! The strings read back from the C API can be read strictly, returning an
+//! error if they are not valid UTF-8, lossily, or as an [`OsStr`].
+//!
+//! These functions are also useful when calling the C API directly.
+
+use core::ptr::{null, null_mut};
+use std::{
+    borrow::Cow,
+    ffi::{CStr, CString, OsStr, OsString},
+    os::raw::c_char,
+};
+
+use crate::{c_api::libc, NcError, NcErrorKind, NcResult};
+
+/// The size of the stack buffer used by [`with_cstr`], including the
+/// terminating nul byte.
+const STACK_BUF_LEN: usize = 256;
+
+/// Calls `f` with a nul-terminated copy of `string`, that lives for the
+/// duration of the call.
+///
+/// Returns an [`InvalidArgument`][NcErrorKind::InvalidArgument] error if
+/// `string` contains a nul byte.
+pub fn with_cstr<T>(string: &str, f: impl FnOnce(*const c_char) -> T) -> NcResult<T> {
+    check_nul(string)?;
+    let bytes = string.as_bytes();
+    if bytes.len() < STACK_BUF_LEN {
+        let mut buf = [0_u8; STACK_BUF_LEN];
+        buf[..bytes.len()].copy_from_slice(bytes);
+        Ok(f(buf.as_ptr() as *const c_char))
+    } else {
+        Ok(f(cstring(string)?.as_ptr()))
+    }
+}
+
+/// Calls `f` with a nul-terminated copy of `string`, or with a null pointer
+/// if it's `None`.
+pub(crate) fn with_cstr_opt<T>(
+    string: Option<&str>,
+    f: impl FnOnce(*const c_char) -> T,
+) -> NcResult<T> {
+    match string {
+        Some(string) => with_cstr(string, f),
+        None => Ok(f(null())),
+    }
+}
+
+/// Returns an owned, nul-terminated copy of `string`, for the strings that
+/// must outlive a single call.
+///
+/// Returns an [`InvalidArgument`][NcErrorKind::InvalidArgument] error if
+/// `string` contains a nul byte.
+pub fn cstring(string: &str) -> NcResult<CString> {
+    CString::new(string).map_err(|e| nul_error(string, e.nul_position()))
+}
+
+/// Returns an owned, nul-terminated copy of the [`OsStr`] `string`.
+///
+/// Returns an [`InvalidArgument`][NcErrorKind::InvalidArgument] error if
+/// `string` contains a nul byte.
+#[cfg(unix)]
+pub(crate) fn cstring_os(string: &OsStr) -> NcResult<CString> {
+    use std::os::unix::ffi::OsStrExt;
+    CString::new(string.as_bytes())
+        .map_err(|e| nul_error(&string.to_string_lossy(), e.nul_position()))
+}
+
+/// Returns an owned, nul-terminated copy of the [`OsStr`] `string`.
+///
+/// Returns an [`InvalidArgument`][NcErrorKind::InvalidArgument] error if
+/// `string` contains a nul byte, or if it's not valid UTF-8.
+#[cfg(not(unix))]
+pub(crate) fn cstring_os(string: &OsStr) -> NcResult<CString> {
+    cstring(string.to_str().ok_or_else(|| {
+        NcError::with_kind(
+            NcErrorKind::InvalidArgument,
+            &format!["the string {:?} is not valid UTF-8", string],
+        )
+    })?)
+}
+
+/// Returns a copy of `string` allocated with `malloc`, to be freed with
+/// `libc::free`, or by the C library itself.
+///
+/// The string is truncated at its first nul byte, if there's any, which is
+/// what the C library would see of it. It's used for the strings stored in
+/// the C option structs, whose constructors can't fail.
+///
+/// Returns a null pointer if the allocation fails.
+pub(crate) fn cstring_dup(string: &str) -> *mut c_char {
+    let string = string.split('\0').next().unwrap_or_default();
+    with_cstr(string, |s| unsafe { libc::strdup(s) }).unwrap_or(null_mut())
+}
+
+/// Returns an [`InvalidArgument`][NcErrorKind::InvalidArgument] error if
+/// `string` contains a nul byte.
+///
+/// The methods that call the C API wrappers taking a `&str` check it first,
+/// since the wrappers can only return a generic error.
+pub(crate) fn check_nul(string: &str) -> NcResult<()> {
+    match string.bytes().position(|b| b == 0) {
+        Some(pos) => Err(nul_error(string, pos)),
+        None => Ok(()),
+    }
+}
+
+fn nul_error(string: &str, pos: usize) -> NcError {
+    NcError::with_kind(
+        NcErrorKind::InvalidArgument,
+        &format!["the string {:?} contains a nul byte at {}", string, pos],
+    )
+}
+
+/// Returns the `&str` of a C string, or an error if it's null or not valid
+/// UTF-8.
+///
+/// # Safety
+/// `ptr` must be null, or point to a nul-terminated string that outlives `'a`.
+pub unsafe fn rstr<'a>(ptr: *const c_char) -> NcResult<&'a str> {
+    if ptr.is_null() {
+        return Err(NcError::with_kind(NcErrorKind::NullPointer, "null string"));
+    }
+    CStr::from_ptr(ptr).to_str().map_err(|e| {
+        NcError::with_kind(
+            NcErrorKind::InvalidArgument,
+            "the string is not valid UTF-8",
+        )
+        .caused_by(e)
+    })
+}
+
+/// Returns the string of a C string, replacing any invalid UTF-8 sequences
+/// with `U+FFFD`. A null pointer results in an empty string.
+///
+/// # Safety
+/// `ptr` must be null, or point to a nul-terminated string that outlives `'a`.
+pub unsafe fn rstr_lossy<'a>(ptr: *const c_char) -> Cow<'a, str> {
+    if ptr.is_null() {
+        return Cow::Borrowed("");
+    }
+    CStr::from_ptr(ptr).to_string_lossy()
+}
+
+/// Returns the [`OsStr`] of a C string. A null pointer results in an empty
+/// string.
+///
+/// # Safety
+/// `ptr` must be null, or point to a nul-terminated string that outlives `'a`.
+#[cfg(unix)]
+pub unsafe fn ros_str<'a>(ptr: *const c_char) -> &'a OsStr {
+    use std::os::unix::ffi::OsStrExt;
+    if ptr.is_null() {
+        return OsStr::new("");
+    }
+    OsStr::from_bytes(CStr::from_ptr(ptr).to_bytes())
+}
+
+/// Returns the [`OsStr`] of a C string, replacing any invalid UTF-8
+/// sequences with `U+FFFD`. A null pointer results in an empty string.
+///
+/// # Safety
+/// `ptr` must be null, or point to a nul-terminated string that outlives `'a`.
+#[cfg(not(unix))]
+pub unsafe fn ros_str<'a>(ptr: *const c_char) -> Cow<'a, OsStr> {
+    match rstr_lossy(ptr) {
+        Cow::Borrowed(s) => Cow::Borrowed(OsStr::new(s)),
+        Cow::Owned(s) => Cow::Owned(s.into()),
+    }
+}
+
+/// Returns the `String` of a C string allocated by the C library, freeing
+/// it, or an error if it's null or not valid UTF-8.
+///
+/// # Safety
+/// `ptr` must be null, or point to a nul-terminated string allocated with
+/// `malloc`, that can't be used afterwards.
+pub unsafe fn rstring_free(ptr: *mut c_char) -> NcResult<String> {
+    let string = rstr(ptr).map(|s| s.to_owned());
+    libc::free(ptr as *mut libc::c_void);
+    string
+}
+
+/// Returns the `String` of a C string allocated by the C library, freeing
+/// it, and replacing any invalid UTF-8 sequences with `U+FFFD`.
+/// A null pointer results in an empty string.
+///
+/// # Safety
+/// `ptr` must be null, or point to a nul-terminated string allocated with
+/// `malloc`, that can't be used afterwards.
+pub unsafe fn rstring_free_lossy(ptr: *mut c_char) -> String {
+    let string = rstr_lossy(ptr).into_owned();
+    libc::free(ptr as *mut libc::c_void);
+    string
+}
+
+/// Returns the [`OsString`] of a C string allocated by the C library,
+/// freeing it. A null pointer results in an empty string.
+///
+/// # Safety
+/// `ptr` must be null, or point to a nul-terminated string allocated with
+/// `malloc`, that can't be used afterwards.
+pub unsafe fn ros_string_free(ptr: *mut c_char) -> OsString {
+    let string = ros_str(ptr).to_os_string();
+    libc::free(ptr as *mut libc::c_void);
+    string
+}
@@ -4,10 +4,9 @@ use core::ptr::null_mut;
 use libc::c_void;
 
 use crate::{
-    c_api, cstring, error, error_ref_mut, rstring_free, Nc, NcBlitter, NcBlitterApi,
-    NcBlitterGeometry, NcChannel, NcComponent, NcDim, NcDirect, NcDirectF, NcError, NcIntResult,
-    NcIntResultApi, NcPixel, NcPlane, NcResult, NcRgba, NcScale, NcTime, NcVGeom, NcVisual,
-    NcVisualOptions,
+    c_api, error, error_ref_mut, string, Nc, NcBlitter, NcBlitterApi, NcBlitterGeometry, NcChannel,
+    NcComponent, NcDim, NcDirect, NcDirectF, NcError, NcIntResult, NcIntResultApi, NcPixel,
+    NcPlane, NcResult, NcRgba, NcScale, NcTime, NcVGeom, NcVisual, NcVisualOptions,
 };
 
 /// # NcVisualOptions Constructors
@@ -134,7 +133,7 @@ impl NcVisual {
         cols: NcDim,
     ) -> NcResult<&'a mut NcVisual> {
         error_ref_mut![
-            unsafe {
+            @call "ncvisual_from_bgra", unsafe {
                 c_api::ncvisual_from_bgra(
                     bgra.as_ptr() as *const c_void,
                     rows as i32,
@@ -155,7 +154,7 @@ impl NcVisual {
     /// *C style function: [ncvisual_from_file()][c_api::ncvisual_from_file].*
     pub fn from_file<'a>(file: &str) -> NcResult<&'a mut NcVisual> {
         error_ref_mut![
-            unsafe { c_api::ncvisual_from_file(cstring![file]) },
+            @call "ncvisual_from_file", string::with_cstr(file, |file| unsafe { c_api::ncvisual_from_file(file) })?,
             &format!("NcVisual::from_file({})", file)
         ]
     }
@@ -179,7 +178,7 @@ impl NcVisual {
         len_x: NcDim,
     ) -> NcResult<&'a mut NcVisual> {
         error_ref_mut![
-            unsafe {
+            @call "ncvisual_from_plane", unsafe {
                 c_api::ncvisual_from_plane(
                     plane,
                     blitter,
@@ -210,7 +209,7 @@ impl NcVisual {
         alpha: NcComponent,
     ) -> NcResult<&'a mut NcVisual> {
         error_ref_mut![
-            unsafe {
+            @call "ncvisual_from_rgb_loose", unsafe {
                 c_api::ncvisual_from_rgb_loose(
                     rgb.as_ptr() as *const c_void,
                     rows as i32,
@@ -238,7 +237,7 @@ impl NcVisual {
         alpha: NcComponent,
     ) -> NcResult<&'a mut NcVisual> {
         error_ref_mut![
-            unsafe {
+            @call "ncvisual_from_rgb_packed", unsafe {
                 c_api::ncvisual_from_rgb_packed(
                     rgb.as_ptr() as *const c_void,
                     rows as i32,
@@ -272,7 +271,7 @@ impl NcVisual {
         cols: NcDim,
     ) -> NcResult<&'a mut NcVisual> {
         error_ref_mut![
-            unsafe {
+            @call "ncvisual_from_rgba", unsafe {
                 c_api::ncvisual_from_rgba(
                     rgba.as_ptr() as *const c_void,
                     rows as i32,
@@ -317,7 +316,7 @@ impl NcVisual {
     ) -> NcResult<&'a mut NcVisual> {
         // assert![];
         error_ref_mut![
-            unsafe {
+            @call "ncvisual_from_palidx", unsafe {
                 c_api::ncvisual_from_palidx(
                     data.as_ptr() as *const c_void,
                     rows as i32,
@@ -340,9 +339,14 @@ impl NcVisual {
     /// Rendered elements will not be disrupted, but the visual can be neither
     /// decoded nor rendered any further.
     ///
+    /// # Safety
+    ///
+    /// The visual must not be used after it's destroyed, and it must not be
+    /// owned by an `OwnedVisual`, which destroys it when dropped.
+    ///
     /// *C style function: [ncvisual_destroy()][c_api::ncvisual_destroy].*
-    pub fn destroy(&mut self) {
-        unsafe { c_api::ncvisual_destroy(self) }
+    pub unsafe fn destroy(&mut self) {
+        c_api::ncvisual_destroy(self)
     }
 }
 
@@ -354,7 +358,7 @@ impl NcVisual {
     pub fn at_yx(&self, y: NcDim, x: NcDim) -> NcResult<NcPixel> {
         let mut pixel = 0;
         let res = unsafe { c_api::ncvisual_at_yx(self, y as i32, x as i32, &mut pixel) };
-        error![res, "NcVisual.at_yx()", pixel]
+        error![@call "ncvisual_at_yx", res, "NcVisual.at_yx()", pixel]
     }
 
     /// Extracts the next frame from the NcVisual.
@@ -426,7 +430,7 @@ impl NcVisual {
             scale_x: scale_x as NcDim,
             blitter,
         };
-        error![res, "NcVisual.geom()", geom];
+        error![@call "ncvisual_blitter_geom", res, "NcVisual.geom()", geom];
     }
 
     /// Gets the default media (not plot) blitter for this environment when using
@@ -450,7 +454,7 @@ impl NcVisual {
     /// *C style function: [ncvisual_polyfill_yx()][c_api::ncvisual_polyfill_yx].*
     pub fn polyfill_yx(&mut self, y: NcDim, x: NcDim, rgba: NcRgba) -> NcResult<()> {
         error![
-            unsafe { c_api::ncvisual_polyfill_yx(self, y as i32, x as i32, rgba) },
+            @call "ncvisual_polyfill_yx", unsafe { c_api::ncvisual_polyfill_yx(self, y as i32, x as i32, rgba) },
             &format!["NcVisual.polyfill_yx({}, {}, {})", y, x, rgba]
         ]
     }
@@ -463,9 +467,13 @@ impl NcVisual {
     /// See [`NcVisualOptions`].
     ///
     /// *C style function: [ncvisual_render()][c_api::ncvisual_render].*
-    pub fn render(&mut self, nc: &mut Nc, options: &NcVisualOptions) -> NcResult<&mut NcPlane> {
+    pub fn render<'nc>(
+        &mut self,
+        nc: &'nc mut Nc,
+        options: &NcVisualOptions,
+    ) -> NcResult<&'nc mut NcPlane> {
         error_ref_mut![
-            unsafe { c_api::ncvisual_render(nc, self, options) },
+            @call "ncvisual_render", unsafe { c_api::ncvisual_render(nc, self, options) },
             "NcVisual.render(Nc, &NcVisualOptions)"
         ]
     }
@@ -477,7 +485,7 @@ impl NcVisual {
     /// *C style function: [ncvisual_resize()][c_api::ncvisual_resize].*
     pub fn resize(&mut self, rows: NcDim, cols: NcDim) -> NcResult<()> {
         error![
-            unsafe { c_api::ncvisual_resize(self, rows as i32, cols as i32) },
+            @call "ncvisual_resize", unsafe { c_api::ncvisual_resize(self, rows as i32, cols as i32) },
             &format!["NcVisual.resize({}, {})", rows, cols]
         ]
     }
@@ -491,7 +499,7 @@ impl NcVisual {
     /// [ncvisual_resize_noninterpolative()][c_api::ncvisual_resize_noninterpolative].*
     pub fn resize_noninterpolative(&mut self, rows: NcDim, cols: NcDim) -> NcResult<()> {
         error![
-            unsafe { c_api::ncvisual_resize_noninterpolative(self, rows as i32, cols as i32) },
+            @call "ncvisual_resize_noninterpolative", unsafe { c_api::ncvisual_resize_noninterpolative(self, rows as i32, cols as i32) },
             &format!["NcVisual.resize_noninterpolative({}, {})", cols, rows]
         ]
     }
@@ -504,7 +512,7 @@ impl NcVisual {
     /// *C style function: [ncvisual_rotate()][c_api::ncvisual_rotate].*
     pub fn rotate(&mut self, rads: f64) -> NcResult<()> {
         error![
-            unsafe { c_api::ncvisual_rotate(self, rads) },
+            @call "ncvisual_rotate", unsafe { c_api::ncvisual_rotate(self, rads) },
             &format!["NcVisual.rotate({})", rads]
         ]
     }
@@ -514,7 +522,7 @@ impl NcVisual {
     /// *C style function: [ncvisual_set_yx()][c_api::ncvisual_set_yx].*
     pub fn set_yx(&mut self, y: NcDim, x: NcDim, pixel: NcPixel) -> NcResult<()> {
         error![
-            unsafe { c_api::ncvisual_set_yx(self, y as i32, x as i32, pixel) },
+            @call "ncvisual_set_yx", unsafe { c_api::ncvisual_set_yx(self, y as i32, x as i32, pixel) },
             &format!["NcVisual.set_yx({}, {}, {})", y, x, pixel]
         ]
     }
@@ -535,7 +543,7 @@ impl NcVisual {
     ) -> NcResult<()> {
         if let Some(plane) = curry {
             error![
-                unsafe {
+                @call "ncvisual_simple_streamer", unsafe {
                     c_api::ncvisual_simple_streamer(
                         self,
                         options,
@@ -550,7 +558,7 @@ impl NcVisual {
             ]
         } else {
             error![
-                unsafe { c_api::ncvisual_simple_streamer(self, options, time, null_mut()) },
+                @call "ncvisual_simple_streamer", unsafe { c_api::ncvisual_simple_streamer(self, options, time, null_mut()) },
                 &format!["NcVisual.simple_streamer({:?}, {:?}, null)", options, time]
             ]
         }
@@ -596,7 +604,7 @@ impl NcVisual {
     pub fn subtitle(&self) -> NcResult<String> {
         let res = unsafe { c_api::ncvisual_subtitle(self) };
         if !res.is_null() {
-            Ok(rstring_free![res])
+            unsafe { string::rstring_free(res) }
         } else {
             Err(NcError::with_msg(NcIntResult::ERR, "NcVisual.subtitle()"))
         }
@@ -609,7 +617,7 @@ impl NcVisual {
     ///
     /// *C style function: [ncvisual_subtitle_plane()][c_api::ncvisual_subtitle_plane].*
     pub fn subtitle_plane(&self, parent: &mut NcPlane) -> NcResult<&mut NcPlane> {
-        error_ref_mut![unsafe { c_api::ncvisual_subtitle_plane(parent, self) }]
+        error_ref_mut![@call "ncvisual_subtitle_plane", unsafe { c_api::ncvisual_subtitle_plane(parent, self) }, "NcVisual.subtitle_plane()"]
     }
 }
 
@@ -628,7 +636,9 @@ impl NcDirectF {
     /// *C style function: [ncdirectf_from_file()][c_api::ncdirectf_from_file].*
     pub fn ncdirectf_from_file<'a>(ncd: &mut NcDirect, file: &str) -> NcResult<&'a mut NcDirectF> {
         error_ref_mut![
-            unsafe { c_api::ncdirectf_from_file(ncd, cstring![file]) },
+            @call "ncdirectf_from_file", string::with_cstr(file, |file| unsafe {
+                c_api::ncdirectf_from_file(ncd, file)
+            })?,
             &format!("NcDirectF::ncdirectf_from_file(ncd, {})", file)
         ]
     }
@@ -659,7 +669,7 @@ impl NcDirectF {
         options: &NcVisualOptions,
     ) -> NcResult<&mut NcPlane> {
         error_ref_mut![
-            unsafe { c_api::ncdirectf_render(ncd, self, options) },
+            @call "ncdirectf_render", unsafe { c_api::ncdirectf_render(ncd, self, options) },
             "NcVisual.render()"
         ]
     }
@@ -674,7 +684,7 @@ impl NcDirectF {
         let mut geom = NcVGeom::new();
 
         let res = unsafe { c_api::ncdirectf_geom(ncd, self, options, &mut geom) };
-        error![res, "NcDirectF.ncdirectf_geom()", geom];
+        error![@call "ncdirectf_geom", res, "NcDirectF.ncdirectf_geom()", geom];
     }
 }
 
@@ -0,0 +1,281 @@
+//! `NcMenuBuilder`
+
+use core::ptr::null_mut;
+use std::{ffi::CString, fmt, os::raw::c_char};
+
+use super::{
+    methods::{register, MenuData, MenuItemData},
+    NcMenu, NcMenuAction, NcMenuItem, NcMenuOptions, NcMenuSection, NCMENU_OPTION_BOTTOM,
+    NCMENU_OPTION_HIDING,
+};
+use crate::{string, NcChannels, NcError, NcErrorKind, NcInput, NcPlane, NcResult};
+
+/// A builder for [`NcMenu`].
+///
+/// It owns the names of the sections and the descriptions of the items,
+/// which are kept along with the menu until it's
+/// [destroyed][NcMenu#method.destroy], and an [action][NcMenuAction] for
+/// each item, that is invoked by [handle_input()][NcMenu#method.handle_input].
+///
+/// The items are added to the last section added.
+///
+/// # Example
+///
+/// ```ignore
+/// let menu = NcMenu::builder()
+///     .section("File", NcInput::with_alt('f'))
+///     .item("Open", NcInput::with_ctrl('o'), || open())
+///     .separator()
+///     .item("Quit", NcInput::with_ctrl('q'), || quit())
+///     .align_right()
+///     .section("Help", NcInput::with_alt('h'))
+///     .item("About", NcInput::new_empty(), || about())
+///     .build(plane)?;
+///
+/// loop {
+///     let input = next_input()?;
+///     if !menu.handle_input(input)? {
+///         // not a menu input
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct NcMenuBuilder {
+    sections: Vec<SectionSpec>,
+    /// Whether an item was added before any section.
+    orphan_items: bool,
+    header_channels: NcChannels,
+    section_channels: NcChannels,
+    flags: u64,
+}
+
+/// A section, or a separator if it has no name.
+struct SectionSpec {
+    name: Option<String>,
+    shortcut: NcInput,
+    items: Vec<ItemSpec>,
+}
+
+/// An item, or a separator if it has no description.
+struct ItemSpec {
+    desc: Option<String>,
+    shortcut: NcInput,
+    action: Option<NcMenuAction>,
+}
+
+impl fmt::Debug for NcMenuBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sections: Vec<_> = self
+            .sections
+            .iter()
+            .map(|s| {
+                let items: Vec<_> = s.items.iter().map(|i| &i.desc).collect();
+                (&s.name, items)
+            })
+            .collect();
+        f.debug_struct("NcMenuBuilder")
+            .field("sections", &sections)
+            .field("header_channels", &self.header_channels)
+            .field("section_channels", &self.section_channels)
+            .field("flags", &self.flags)
+            .finish()
+    }
+}
+
+/// # `NcMenuBuilder` constructors
+impl NcMenuBuilder {
+    /// New NcMenuBuilder, without sections, with the default channels,
+    /// and the menu at the top.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// # `NcMenuBuilder` methods
+impl NcMenuBuilder {
+    /// Adds a new section, with a `shortcut` that unrolls it.
+    ///
+    /// The shortcut will be underlined if present in the `name`.
+    pub fn section(mut self, name: &str, shortcut: NcInput) -> Self {
+        self.sections.push(SectionSpec {
+            name: Some(name.into()),
+            shortcut,
+            items: vec![],
+        });
+        self
+    }
+
+    /// Aligns the following sections to the right of the menu bar.
+    pub fn align_right(mut self) -> Self {
+        self.sections.push(SectionSpec {
+            name: None,
+            shortcut: NcInput::new_empty(),
+            items: vec![],
+        });
+        self
+    }
+
+    /// Adds a new item to the last section, with a `shortcut` and the
+    /// `action` invoked when it's selected.
+    ///
+    /// The shortcuts of all the items should be distinct.
+    pub fn item<F>(mut self, desc: &str, shortcut: NcInput, action: F) -> Self
+    where
+        F: FnMut() -> NcResult<()> + 'static,
+    {
+        self.push_item(ItemSpec {
+            desc: Some(desc.into()),
+            shortcut,
+            action: Some(Box::new(action)),
+        });
+        self
+    }
+
+    /// Adds a horizontal separator to the last section.
+    pub fn separator(mut self) -> Self {
+        self.push_item(ItemSpec {
+            desc: None,
+            shortcut: NcInput::new_empty(),
+            action: None,
+        });
+        self
+    }
+
+    /// Sets the channels of the menu bar.
+    pub fn header_channels(mut self, channels: NcChannels) -> Self {
+        self.header_channels = channels;
+        self
+    }
+
+    /// Sets the channels of the unrolled sections.
+    pub fn section_channels(mut self, channels: NcChannels) -> Self {
+        self.section_channels = channels;
+        self
+    }
+
+    /// Displays the menu at the bottom row, instead of at the top row.
+    pub fn bottom(mut self, bottom: bool) -> Self {
+        self.set_flag(NCMENU_OPTION_BOTTOM, bottom);
+        self
+    }
+
+    /// Hides the menu when it's not unrolled.
+    pub fn hiding(mut self, hiding: bool) -> Self {
+        self.set_flag(NCMENU_OPTION_HIDING, hiding);
+        self
+    }
+
+    /// Sets the flags.
+    pub fn flags(mut self, flags: u64) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Creates the [`NcMenu`], on a new plane bound to `plane`.
+    ///
+    /// It's an error if there are no sections, if an item was added before
+    /// any section, or if a name or description contains a nul byte.
+    ///
+    /// *C style function: [ncmenu_create()][crate::c_api::ncmenu_create].*
+    pub fn build<'a>(self, plane: &'a mut NcPlane) -> NcResult<&'a mut NcMenu> {
+        if self.orphan_items {
+            return Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "NcMenuBuilder.build(): an item was added before any section",
+            ));
+        }
+        if self.sections.iter().all(|s| s.name.is_none()) {
+            return Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "NcMenuBuilder.build(): a menu needs at least one section",
+            ));
+        }
+
+        let mut strings = vec![];
+        let mut actions = vec![];
+        let mut c_items = vec![];
+        let mut c_names = vec![];
+        for section in self.sections {
+            let name = keep_cstring(&mut strings, section.name.as_deref())?;
+            c_names.push((name, section.shortcut));
+
+            let mut items = vec![];
+            for item in section.items {
+                items.push(NcMenuItem {
+                    desc: keep_cstring(&mut strings, item.desc.as_deref())?,
+                    shortcut: item.shortcut,
+                });
+                if let (Some(desc), Some(action)) = (item.desc, item.action) {
+                    actions.push(MenuItemData {
+                        section: section.name.clone().unwrap_or_default(),
+                        desc,
+                        shortcut: item.shortcut,
+                        enabled: true,
+                        action: Some(action),
+                    });
+                }
+            }
+            c_items.push(items);
+        }
+
+        let mut c_sections: Vec<NcMenuSection> = c_names
+            .into_iter()
+            .zip(c_items.iter_mut())
+            .map(|((name, shortcut), items)| NcMenuSection {
+                name,
+                items: if items.is_empty() {
+                    null_mut()
+                } else {
+                    items.as_mut_ptr()
+                },
+                itemcount: items.len() as i32,
+                shortcut,
+            })
+            .collect();
+        let options = NcMenuOptions::with_all_args(
+            &mut c_sections,
+            self.header_channels,
+            self.section_channels,
+            self.flags,
+        );
+
+        let menu = NcMenu::new(plane, options)?;
+        register(
+            menu,
+            MenuData {
+                _strings: strings,
+                items: actions,
+            },
+        );
+        Ok(menu)
+    }
+
+    fn push_item(&mut self, item: ItemSpec) {
+        match self.sections.last_mut() {
+            Some(section) if section.name.is_some() => section.items.push(item),
+            _ => self.orphan_items = true,
+        }
+    }
+
+    fn set_flag(&mut self, flag: u64, value: bool) {
+        if value {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+    }
+}
+
+/// Returns a pointer to a new C string from `s`, kept in `strings`,
+/// or a null pointer if `s` is `None`.
+fn keep_cstring(strings: &mut Vec<CString>, s: Option<&str>) -> NcResult<*mut c_char> {
+    match s {
+        Some(s) => {
+            let cs = string::cstring(s)?;
+            let ptr = cs.as_ptr() as *mut c_char;
+            strings.push(cs);
+            Ok(ptr)
+        }
+        None => Ok(null_mut()),
+    }
+}
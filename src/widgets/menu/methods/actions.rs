@@ -0,0 +1,112 @@
+//! The Rust data of the menus created with a builder.
+//!
+//! The data is stored in the userptr of the plane of the menu, which is
+//! created by the C library, so it's freed when the menu is destroyed from
+//! Rust, along with its plane.
+
+use std::ffi::CString;
+
+use crate::{
+    c_api,
+    plane::userptr,
+    widgets::{NcMenu, NcMenuAction},
+    NcInput, NcResult,
+};
+
+/// The Rust data of a menu.
+pub(crate) struct MenuData {
+    /// The C strings of the sections and items, which must outlive the menu.
+    pub(crate) _strings: Vec<CString>,
+    pub(crate) items: Vec<MenuItemData>,
+}
+
+/// The Rust data of an item.
+pub(crate) struct MenuItemData {
+    pub(crate) section: String,
+    pub(crate) desc: String,
+    pub(crate) shortcut: NcInput,
+    pub(crate) enabled: bool,
+    /// The action, taken out while it runs.
+    pub(crate) action: Option<NcMenuAction>,
+}
+
+/// Stores the `data` of the `menu` in the userptr of its plane.
+pub(crate) fn register(menu: &mut NcMenu, data: MenuData) {
+    unsafe {
+        let plane = &mut *c_api::ncmenu_plane(menu);
+        userptr::get_or_init(plane).widget_data = Some(Box::new(data));
+    }
+}
+
+/// Returns the data of the `menu`, if it was created with a builder.
+///
+/// # Safety
+/// `menu` must be valid, and there must not be any other live references
+/// to its data.
+unsafe fn get<'a>(menu: *const NcMenu) -> Option<&'a mut MenuData> {
+    userptr::get(c_api::ncmenu_plane(menu as *mut NcMenu))?
+        .widget_data
+        .as_mut()?
+        .downcast_mut()
+}
+
+/// Returns `true` if the `menu` was created with a builder.
+pub(crate) fn is_registered(menu: &NcMenu) -> bool {
+    unsafe { get(menu) }.is_some()
+}
+
+/// Sets whether the item `desc` of `section` is enabled.
+pub(crate) fn set_enabled(menu: &mut NcMenu, section: &str, desc: &str, enabled: bool) {
+    with_items(menu, |items| {
+        for item in items.iter_mut() {
+            if item.section == section && item.desc == desc {
+                item.enabled = enabled;
+            }
+        }
+    });
+}
+
+/// Returns `true` if both inputs are the same key with the same modifiers.
+pub(crate) fn same_shortcut(shortcut: &NcInput, input: &NcInput) -> bool {
+    shortcut.id != 0
+        && shortcut.id == input.id
+        && shortcut.alt == input.alt
+        && shortcut.shift == input.shift
+        && shortcut.ctrl == input.ctrl
+}
+
+/// Runs the action of the first enabled item of the `menu` for which
+/// `select` returns `true`.
+///
+/// Returns `Ok(true)` if an action was run.
+pub(crate) fn run(
+    menu: &mut NcMenu,
+    mut select: impl FnMut(&MenuItemData) -> bool,
+) -> NcResult<bool> {
+    // the action is taken out of the data while it runs, so that it can
+    // change the menu
+    let found = with_items(menu, |items| {
+        items
+            .iter_mut()
+            .enumerate()
+            .find(|(_, item)| item.enabled && item.action.is_some() && select(item))
+            .and_then(|(index, item)| Some((index, item.desc.clone(), item.action.take()?)))
+    })
+    .flatten();
+    let (index, desc, mut action) = match found {
+        Some(found) => found,
+        None => return Ok(false),
+    };
+    let res = action();
+    with_items(menu, |items| {
+        if let Some(item) = items.get_mut(index).filter(|item| item.desc == desc) {
+            item.action = Some(action);
+        }
+    });
+    res.map(|_| true)
+}
+
+fn with_items<T>(menu: &mut NcMenu, f: impl FnOnce(&mut Vec<MenuItemData>) -> T) -> Option<T> {
+    let data = unsafe { get(menu) }?;
+    Some(f(&mut data.items))
+}
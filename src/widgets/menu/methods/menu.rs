@@ -1,10 +1,13 @@
-use core::ptr::null_mut;
+use core::{char, ptr::null_mut};
 
+use super::actions;
 use crate::{
     c_api::{self, ncmenu_create},
-    cstring, error, error_ref_mut, error_str, rstring,
-    widgets::{NcMenu, NcMenuOptions},
-    NcInput, NcPlane, NcResult,
+    error, error_ref_mut, error_str,
+    plane::userptr,
+    string,
+    widgets::{NcMenu, NcMenuBuilder, NcMenuOptions},
+    NcEvType, NcEvTypeApi, NcInput, NcKey, NcPlane, NcResult,
 };
 
 #[allow(unused_imports)]
@@ -19,15 +22,30 @@ impl NcMenu {
     /// [`NcPlane`]s kept atop other NcPlanes.
     ///
     /// *C style function: [ncmenu_create()][c_api::ncmenu_create].*
-    pub fn new<'a>(plane: &mut NcPlane, options: NcMenuOptions) -> NcResult<&'a mut Self> {
-        error_ref_mut![unsafe { ncmenu_create(plane, &options) }, "Creating NcMenu"]
+    pub fn new<'a>(plane: &'a mut NcPlane, options: NcMenuOptions) -> NcResult<&'a mut Self> {
+        error_ref_mut![@call "ncmenu_create", unsafe { ncmenu_create(plane, &options) }, "Creating NcMenu"]
     }
 
-    /// Destroys an `NcMenu` created with [`new`][NcMenu#method.new].
+    /// Returns a new [`NcMenuBuilder`], for a menu with item actions.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn builder() -> NcMenuBuilder {
+        NcMenuBuilder::new()
+    }
+
+    /// Destroys an `NcMenu` created with [`new`][NcMenu#method.new]
+    /// or with a [builder][NcMenu#method.builder], along with its actions.
+    ///
+    /// # Safety
+    ///
+    /// Neither the menu nor its plane may be used afterwards, and it must not
+    /// be owned by an `OwnedMenu`. It must not be called from the actions of
+    /// the menu.
     ///
     /// *C style function: [ncmenu_destroy()][c_api::ncmenu_destroy].*
-    pub fn destroy(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncmenu_destroy(self) }]
+    pub unsafe fn destroy(&mut self) -> NcResult<()> {
+        userptr::free(c_api::ncmenu_plane(self));
+        error![@call "ncmenu_destroy", c_api::ncmenu_destroy(self), "NcMenu.destroy()"]
     }
 }
 
@@ -35,19 +53,83 @@ impl NcMenu {
 impl NcMenu {
     /// Disables or enables an [`NcMenuItem`].
     ///
+    /// The action of a disabled item is not invoked by
+    /// [handle_input()][NcMenu#method.handle_input].
+    ///
     /// *C style function: [ncmenu_item_set_status()][c_api::ncmenu_item_set_status].*
     pub fn item_set_status(&mut self, section: &str, item: &str, enabled: bool) -> NcResult<()> {
         error![
-            unsafe {
-                c_api::ncmenu_item_set_status(self, cstring![section], cstring![item], enabled)
-            },
+            @call "ncmenu_item_set_status", string::with_cstr(section, |section| string::with_cstr(item, |item| unsafe {
+                c_api::ncmenu_item_set_status(self, section, item, enabled)
+            }))??,
             &format!(
                 ".item_set_status({:?}, {:?}, {:?}, {})",
                 self, section, item, enabled
-            )
+            ),
+            actions::set_enabled(self, section, item, enabled)
         ]
     }
 
+    /// Offers the `input` to this `NcMenu`, and invokes the action of the
+    /// item it selects, if the menu was created with a
+    /// [builder][NcMenu#method.builder].
+    ///
+    /// The action of an enabled item is invoked, rolling up the menu, when:
+    /// - the item is clicked on an unrolled section
+    /// - enter is pressed while the item is selected
+    /// - the shortcut of the item is pressed
+    ///
+    /// Any other input is [offered][NcMenu#method.offer_input] to the menu.
+    ///
+    /// Returns `true` if the input was consumed, and it ought not be processed
+    /// further, or the error returned by the action.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn handle_input(&mut self, input: NcInput) -> NcResult<bool> {
+        if !actions::is_registered(self) {
+            return self.offer_input(input);
+        }
+        let key = char::from_u32(input.id).unwrap_or_default();
+        let mut shortcut = NcInput::new_empty();
+
+        // a click on an item of the unrolled section
+        if c_api::nckey_mouse_p(key) && input.evtype != NcEvType::PRESS {
+            if let Ok(desc) = self.mouse_selected(input, Some(&mut shortcut)) {
+                return self.run_selected(&desc, &shortcut);
+            }
+        }
+
+        // enter on the selected item of the unrolled section
+        if key == NcKey::ENTER && input.evtype != NcEvType::RELEASE {
+            if let Some(desc) = self.selected(Some(&mut shortcut)) {
+                return self.run_selected(&desc, &shortcut);
+            }
+        }
+
+        if self.offer_input(input)? {
+            return Ok(true);
+        }
+
+        // the shortcut of an item
+        if input.evtype != NcEvType::RELEASE
+            && actions::run(self, |item| actions::same_shortcut(&item.shortcut, &input))?
+        {
+            self.rollup()?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    // Rolls up the menu, and runs the action of the item `desc`.
+    fn run_selected(&mut self, desc: &str, shortcut: &NcInput) -> NcResult<bool> {
+        self.rollup()?;
+        actions::run(self, |item| {
+            item.desc == desc
+                && (shortcut.id == 0 || actions::same_shortcut(&item.shortcut, shortcut))
+        })?;
+        Ok(true)
+    }
+
     /// Returns the [`NcMenuItem`] description
     /// corresponding to the mouse `click`.
     ///
@@ -70,7 +152,7 @@ impl NcMenu {
             ninput = null_mut();
         }
         error_str![
-            unsafe { c_api::ncmenu_mouse_selected(self, &click, ninput) },
+            @call "ncmenu_mouse_selected", unsafe { c_api::ncmenu_mouse_selected(self, &click, ninput) },
             "Getting NcMenuItem description"
         ]
     }
@@ -81,7 +163,7 @@ impl NcMenu {
     ///
     /// *C style function: [ncmenu_nextitem()][c_api::ncmenu_nextitem].*
     pub fn nextitem(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncmenu_nextitem(self) }]
+        error![@call "ncmenu_nextitem", unsafe { c_api::ncmenu_nextitem(self) }, "NcMenu.nextitem()"]
     }
 
     /// Unrolls the next section (relative to current unrolled).
@@ -90,7 +172,7 @@ impl NcMenu {
     ///
     /// *C style function: [ncmenu_nextsection()][c_api::ncmenu_nextsection].*
     pub fn nextsection(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncmenu_nextsection(self) }]
+        error![@call "ncmenu_nextsection", unsafe { c_api::ncmenu_nextsection(self) }, "NcMenu.nextsection()"]
     }
 
     /// Offers the `input` to this `NcMenu`.
@@ -108,16 +190,16 @@ impl NcMenu {
     /// - escape on an unrolled menu (the menu is rolled up)
     ///
     /// *C style function: [ncmenu_offer_input()][c_api::ncmenu_offer_input].*
-    pub fn offer_input(&mut self, input: NcInput) -> bool {
-        unsafe { c_api::ncmenu_offer_input(self, &input) }
+    pub fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        Ok(unsafe { c_api::ncmenu_offer_input(self, &input) })
     }
 
     /// Returns the [`NcPlane`] backing this `NcMenu`.
     ///
     /// *C style function: [ncmenu_plane()][c_api::ncmenu_plane].*
-    pub fn plane(&mut self) -> NcResult<&NcPlane> {
+    pub fn plane(&mut self) -> NcResult<&mut NcPlane> {
         error_ref_mut![
-            unsafe { c_api::ncmenu_plane(self) },
+            @call "ncmenu_plane", unsafe { c_api::ncmenu_plane(self) },
             "Getting the backing NcPlane"
         ]
     }
@@ -128,7 +210,7 @@ impl NcMenu {
     ///
     /// *C style function: [ncmenu_previtem()][c_api::ncmenu_previtem].*
     pub fn previtem(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncmenu_previtem(self) }]
+        error![@call "ncmenu_previtem", unsafe { c_api::ncmenu_previtem(self) }, "NcMenu.previtem()"]
     }
 
     /// Unrolls the previous section (relative to current unrolled).
@@ -137,7 +219,7 @@ impl NcMenu {
     ///
     /// *C style function: [ncmenu_prevsection()][c_api::ncmenu_prevsection].*
     pub fn prevsection(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncmenu_prevsection(self) }]
+        error![@call "ncmenu_prevsection", unsafe { c_api::ncmenu_prevsection(self) }, "NcMenu.prevsection()"]
     }
 
     /// Rolls up any unrolled [`NcMenuSection`]
@@ -145,7 +227,7 @@ impl NcMenu {
     ///
     /// *C style function: [ncmenu_rollup()][c_api::ncmenu_rollup].*
     pub fn rollup(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::ncmenu_rollup(self) }]
+        error![@call "ncmenu_rollup", unsafe { c_api::ncmenu_rollup(self) }, "NcMenu.rollup()"]
     }
 
     /// Returns the selected item description, if there's an unrolled section.
@@ -163,7 +245,7 @@ impl NcMenu {
         }
         let res = unsafe { c_api::ncmenu_selected(self, ninput) };
         if !res.is_null() {
-            Some(rstring![&*res].to_string())
+            Some(unsafe { string::rstr_lossy(res) }.into_owned())
         } else {
             None
         }
@@ -175,6 +257,6 @@ impl NcMenu {
     ///
     /// *C style function: [ncmenu_unroll()][c_api::ncmenu_unroll].*
     pub fn unroll(&mut self, sectionindex: u32) -> NcResult<()> {
-        error![unsafe { c_api::ncmenu_unroll(self, sectionindex as i32) }]
+        error![@call "ncmenu_unroll", unsafe { c_api::ncmenu_unroll(self, sectionindex as i32) }, "NcMenu.unroll()"]
     }
 }
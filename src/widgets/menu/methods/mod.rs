@@ -1,25 +1,29 @@
 //! `NcMenu*` methods and associated functions.
 
 use super::{NcMenuItem, NcMenuSection};
-use crate::{cstring_mut, NcInput};
+use crate::{c_api::libc, string, NcInput};
 use core::ptr::null_mut;
 
 #[allow(unused_imports)]
 use crate::widgets::NcMenu;
 
+mod actions;
 mod menu;
 mod options;
 
+pub(crate) use actions::{register, MenuData, MenuItemData};
 pub use menu::*;
 pub use options::*;
 
 /// # `NcMenuItem` Constructors
 impl NcMenuItem {
     /// New NcMenuItem for [`NcMenu`].
+    ///
+    /// The `desc` is truncated at its first nul byte, if there's any.
     pub fn new(desc: &str, shortcut: NcInput) -> Self {
         Self {
             // utf-8 menu item, NULL for horizontal separator
-            desc: cstring_mut![desc],
+            desc: string::cstring_dup(desc),
 
             // ´NcInput´ shortcut, all should be distinct
             shortcut,
@@ -33,6 +37,17 @@ impl NcMenuItem {
             shortcut: NcInput::new_empty(),
         }
     }
+
+    /// Frees the `desc` allocated by [`new`][NcMenuItem#method.new].
+    ///
+    /// The menu copies its strings, so they can be freed once it's created.
+    ///
+    /// # Safety
+    /// The `desc` must have been allocated by `new`, and not be used afterwards.
+    pub unsafe fn free(&mut self) {
+        libc::free(self.desc as *mut libc::c_void);
+        self.desc = null_mut();
+    }
 }
 
 /// # `NcMenuSection` Constructors
@@ -40,10 +55,12 @@ impl NcMenuItem {
 // Must contain at least 1 NcMenuItem.
 impl NcMenuSection {
     /// New NcMenuSection for [`NcMenu`].
+    ///
+    /// The `name` is truncated at its first nul byte, if there's any.
     pub fn new(name: &str, items: &mut [NcMenuItem], shortcut: NcInput) -> Self {
         Self {
             // utf-8 name string
-            name: cstring_mut![name],
+            name: string::cstring_dup(name),
 
             // array of itemcount `NcMenuItem`s
             items: items.as_mut_ptr(),
@@ -66,4 +83,16 @@ impl NcMenuSection {
             shortcut: NcInput::new_empty(),
         }
     }
+
+    /// Frees the `name` allocated by [`new`][NcMenuSection#method.new],
+    /// but not its items.
+    ///
+    /// The menu copies its strings, so they can be freed once it's created.
+    ///
+    /// # Safety
+    /// The `name` must have been allocated by `new`, and not be used afterwards.
+    pub unsafe fn free(&mut self) {
+        libc::free(self.name as *mut libc::c_void);
+        self.name = null_mut();
+    }
 }
@@ -19,8 +19,16 @@
 //W ncmenu_selected
 //W ncmenu_unroll
 
+mod builder;
 mod methods;
 
+#[cfg(test)]
+mod test;
+
+pub use builder::NcMenuBuilder;
+
+use crate::NcResult;
+
 /// menus on the top or bottom rows
 ///
 /// An [Nc][crate::Nc] instance supports menu bars on the top or bottom row
@@ -39,6 +47,10 @@ mod methods;
 /// - [destroy()][NcMenu#method.destroy]
 ///     removes a menu bar, and frees all associated resources.
 ///
+/// A menu created with a [builder][NcMenu#method.builder] has an
+/// [action][NcMenuAction] for each item, which is invoked by
+/// [handle_input()][NcMenu#method.handle_input] when the item is selected.
+///
 /// `type in C: ncmenu (struct)`
 pub type NcMenu = crate::bindings::ffi::ncmenu;
 
@@ -51,6 +63,9 @@ pub type NcMenuItem = crate::bindings::ffi::ncmenu_item;
 /// Section for [`NcMenu`].
 pub type NcMenuSection = crate::bindings::ffi::ncmenu_section;
 
+/// An [`NcMenu`] item action closure, invoked when the item is selected.
+pub type NcMenuAction = Box<dyn FnMut() -> NcResult<()>>;
+
 /// [NcMenuOptions] flag: Bottom row (as opposed to top row).
 pub const NCMENU_OPTION_BOTTOM: u64 = crate::bindings::ffi::NCMENU_OPTION_BOTTOM as u64;
 
@@ -0,0 +1,61 @@
+//! Test `NcMenu` methods.
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{NcContext, NcError, NcInput, NcKey, NcMenu, NcPlaneHandle};
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn handle_input() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 20, 40)?;
+
+    let log = Rc::new(RefCell::new(vec![]));
+    let (open, quit) = (log.clone(), log.clone());
+    let menu = NcMenu::builder()
+        .section("File", NcInput::with_alt('f'))
+        .item("Open", NcInput::with_ctrl('o'), move || {
+            open.borrow_mut().push("open");
+            Ok(())
+        })
+        .separator()
+        .item("Quit", NcInput::with_ctrl('q'), move || {
+            quit.borrow_mut().push("quit");
+            Err(NcError::new_msg("quit"))
+        })
+        .build(&mut plane)?;
+
+    // the shortcut of an item
+    assert![menu.handle_input(NcInput::with_ctrl('o'))?];
+    assert_eq![vec!["open"], *log.borrow()];
+
+    // enter on the selected item of the unrolled section, which is rolled up
+    menu.unroll(0)?;
+    assert![menu.handle_input(NcInput::new(NcKey::ENTER))?];
+    assert_eq![vec!["open", "open"], *log.borrow()];
+    assert_eq![None, menu.selected(None)];
+
+    // the error of an action is returned
+    assert_eq![
+        "quit",
+        menu.handle_input(NcInput::with_ctrl('q')).unwrap_err().msg
+    ];
+    assert_eq![vec!["open", "open", "quit"], *log.borrow()];
+
+    // the action of a disabled item is not invoked
+    menu.item_set_status("File", "Open", false)?;
+    assert![!menu.handle_input(NcInput::with_ctrl('o'))?];
+    assert_eq![3, log.borrow().len()];
+
+    // an input that's not for the menu
+    assert![!menu.handle_input(NcInput::new('x'))?];
+
+    // the actions are dropped along with the menu
+    assert_eq![3, Rc::strong_count(&log)];
+    unsafe { menu.destroy()? };
+    assert_eq![1, Rc::strong_count(&log)];
+    unsafe { plane.destroy()? };
+    Ok(())
+}
@@ -0,0 +1,4 @@
+//! `NcMenu` tests.
+
+#[cfg(test)]
+mod methods;
@@ -9,6 +9,10 @@ mod reel;
 mod selector;
 mod tabbed;
 mod tree;
+mod widget;
+
+#[cfg(test)]
+mod test;
 
 pub use menu::*;
 pub use multiselector::*;
@@ -19,3 +23,4 @@ pub use reel::*;
 pub use selector::*;
 pub use tabbed::*;
 pub use tree::*;
+pub use widget::NcWidget;
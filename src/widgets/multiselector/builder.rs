@@ -0,0 +1,162 @@
+//! `NcMultiSelectorBuilder`
+
+use core::ptr::null;
+use std::ffi::CString;
+
+use super::{NcMultiSelector, NcMultiSelectorItem, NcMultiSelectorOptions};
+use crate::{string, NcChannels, NcPlane, NcResult};
+
+/// A builder for [`NcMultiSelector`].
+///
+/// It owns the strings of the title, the items, etc., which are converted to
+/// C strings when the multiselector is [built][NcMultiSelectorBuilder#method.build].
+/// The C library keeps its own copies, so the strings don't need to outlive
+/// the builder.
+///
+/// # Example
+///
+/// ```ignore
+/// let selector = NcMultiSelector::builder()
+///     .title("pick your toppings")
+///     .item("cheese", "extra cheese", true)
+///     .item("olives", "black olives", false)
+///     .max_display(4)
+///     .build(plane)?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct NcMultiSelectorBuilder {
+    title: Option<String>,
+    secondary: Option<String>,
+    footer: Option<String>,
+    items: Vec<(String, String, bool)>,
+    max_display: u32,
+    option_channels: NcChannels,
+    desc_channels: NcChannels,
+    title_channels: NcChannels,
+    footer_channels: NcChannels,
+    box_channels: NcChannels,
+    flags: u64,
+}
+
+/// # `NcMultiSelectorBuilder` constructors
+impl NcMultiSelectorBuilder {
+    /// New NcMultiSelectorBuilder, without items.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// # `NcMultiSelectorBuilder` methods
+impl NcMultiSelectorBuilder {
+    /// Sets the title. Without a title the riser is inhibited, saving two rows.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the secondary title.
+    pub fn secondary(mut self, secondary: &str) -> Self {
+        self.secondary = Some(secondary.into());
+        self
+    }
+
+    /// Sets the footer.
+    pub fn footer(mut self, footer: &str) -> Self {
+        self.footer = Some(footer.into());
+        self
+    }
+
+    /// Adds an item, with its `option` and `desc`ription, and whether it's
+    /// preselected.
+    pub fn item(mut self, option: &str, desc: &str, selected: bool) -> Self {
+        self.items.push((option.into(), desc.into(), selected));
+        self
+    }
+
+    /// Sets the maximum number of items to display at once,
+    /// 0 to use all the available space.
+    pub fn max_display(mut self, max_display: u32) -> Self {
+        self.max_display = max_display;
+        self
+    }
+
+    /// Sets the styling for the options.
+    pub fn option_channels(mut self, channels: NcChannels) -> Self {
+        self.option_channels = channels;
+        self
+    }
+
+    /// Sets the styling for the descriptions.
+    pub fn desc_channels(mut self, channels: NcChannels) -> Self {
+        self.desc_channels = channels;
+        self
+    }
+
+    /// Sets the styling for the title.
+    pub fn title_channels(mut self, channels: NcChannels) -> Self {
+        self.title_channels = channels;
+        self
+    }
+
+    /// Sets the styling for the footer.
+    pub fn footer_channels(mut self, channels: NcChannels) -> Self {
+        self.footer_channels = channels;
+        self
+    }
+
+    /// Sets the styling for the box.
+    pub fn box_channels(mut self, channels: NcChannels) -> Self {
+        self.box_channels = channels;
+        self
+    }
+
+    /// Sets the flags.
+    pub fn flags(mut self, flags: u64) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Creates the [`NcMultiSelector`], taking ownership of the `plane`.
+    ///
+    /// Returns an error if any string contains a nul byte, in which case the
+    /// `plane` is left untouched, or if the multiselector couldn't be created,
+    /// in which case the `plane` is destroyed.
+    ///
+    /// *C style function: [ncmultiselector_create()][crate::c_api::ncmultiselector_create].*
+    pub fn build<'a>(self, plane: &'a mut NcPlane) -> NcResult<&'a mut NcMultiSelector> {
+        let title = self.title.as_deref().map(string::cstring).transpose()?;
+        let secondary = self.secondary.as_deref().map(string::cstring).transpose()?;
+        let footer = self.footer.as_deref().map(string::cstring).transpose()?;
+        let strings = self
+            .items
+            .iter()
+            .map(|(option, desc, _)| Ok((string::cstring(option)?, string::cstring(desc)?)))
+            .collect::<NcResult<Vec<(CString, CString)>>>()?;
+
+        let mut items: Vec<NcMultiSelectorItem> = strings
+            .iter()
+            .zip(self.items.iter())
+            .map(|((option, desc), (_, _, selected))| NcMultiSelectorItem {
+                option: option.as_ptr() as *mut _,
+                desc: desc.as_ptr() as *mut _,
+                selected: *selected,
+            })
+            .collect();
+        items.push(NcMultiSelectorItem::new_empty());
+
+        let options = NcMultiSelectorOptions {
+            title: title.as_ref().map_or(null(), |s| s.as_ptr()),
+            secondary: secondary.as_ref().map_or(null(), |s| s.as_ptr()),
+            footer: footer.as_ref().map_or(null(), |s| s.as_ptr()),
+            items: items.as_ptr(),
+            maxdisplay: self.max_display,
+            opchannels: self.option_channels,
+            descchannels: self.desc_channels,
+            titlechannels: self.title_channels,
+            footchannels: self.footer_channels,
+            boxchannels: self.box_channels,
+            flags: self.flags,
+        };
+        NcMultiSelector::new(plane, &options)
+    }
+}
@@ -0,0 +1,145 @@
+//! `NcMultiSelector*` methods and associated functions.
+
+use core::ptr::null_mut;
+
+use super::{NcMultiSelector, NcMultiSelectorBuilder, NcMultiSelectorItem, NcMultiSelectorOptions};
+use crate::{c_api, error, error_ref_mut, plane::userptr, string, NcInput, NcPlane, NcResult};
+
+/// # `NcMultiSelectorItem` constructors
+impl NcMultiSelectorItem {
+    /// New NcMultiSelectorItem for [`NcMultiSelector`].
+    ///
+    /// The `option` and `desc` are truncated at their first nul byte,
+    /// if there's any.
+    pub fn new(option: &str, desc: &str, selected: bool) -> Self {
+        Self {
+            option: string::cstring_dup(option),
+            desc: string::cstring_dup(desc),
+            selected,
+        }
+    }
+
+    /// New empty NcMultiSelectorItem, which marks the end of the items.
+    pub fn new_empty() -> Self {
+        Self {
+            option: null_mut(),
+            desc: null_mut(),
+            selected: false,
+        }
+    }
+    /// Frees the strings allocated by [`new`][NcMultiSelectorItem#method.new].
+    ///
+    /// The multiselector copies its strings, so they can be freed once it's
+    /// created.
+    ///
+    /// # Safety
+    /// The strings must have been allocated by `new`, and not be used
+    /// afterwards.
+    pub unsafe fn free(&mut self) {
+        c_api::libc::free(self.option as *mut c_api::libc::c_void);
+        c_api::libc::free(self.desc as *mut c_api::libc::c_void);
+        self.option = null_mut();
+        self.desc = null_mut();
+    }
+}
+
+/// # `NcMultiSelector` constructors & destructors
+impl NcMultiSelector {
+    /// Returns a new [`NcMultiSelectorBuilder`], which owns the strings of
+    /// the options until the multiselector is created.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn builder() -> NcMultiSelectorBuilder {
+        NcMultiSelectorBuilder::new()
+    }
+
+    /// New NcMultiSelector. Expects an [`NcMultiSelectorOptions`] struct,
+    /// whose items must end with an [empty item][NcMultiSelectorItem#method.new_empty].
+    ///
+    /// Takes ownership of the `plane`, which will be destroyed by
+    /// [destroy][NcMultiSelector#method.destroy](), or on error.
+    ///
+    /// *C style function: [ncmultiselector_create()][c_api::ncmultiselector_create].*
+    pub fn new<'a>(
+        plane: &'a mut NcPlane,
+        options: &NcMultiSelectorOptions,
+    ) -> NcResult<&'a mut Self> {
+        let mut count = 0;
+        if !options.items.is_null() {
+            while !unsafe { (*options.items.add(count)).option.is_null() } {
+                count += 1;
+            }
+        }
+        let res = unsafe { c_api::ncmultiselector_create(plane, options) };
+        if !res.is_null() {
+            // the number of items is needed to retrieve the selection
+            userptr::get_or_init(plane).widget_items = count;
+        }
+        error_ref_mut![@call "ncmultiselector_create", res, "NcMultiSelector::new()"]
+    }
+
+    /// Destroys the NcMultiSelector and its underlying plane.
+    ///
+    /// # Safety
+    ///
+    /// Neither the multiselector nor its plane may be used afterwards, and it
+    /// must not be owned by an `OwnedMultiSelector`.
+    ///
+    /// *C style function: [ncmultiselector_destroy()][c_api::ncmultiselector_destroy].*
+    pub unsafe fn destroy(&mut self) -> NcResult<()> {
+        userptr::free(c_api::ncmultiselector_plane(self));
+        c_api::ncmultiselector_destroy(self);
+        Ok(())
+    }
+}
+
+/// # `NcMultiSelector` methods
+impl NcMultiSelector {
+    /// Offers the input to the NcMultiSelector.
+    ///
+    /// Returns `true` if it was relevant.
+    ///
+    /// *C style function: [ncmultiselector_offer_input()][c_api::ncmultiselector_offer_input].*
+    pub fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        Ok(unsafe { c_api::ncmultiselector_offer_input(self, &input) })
+    }
+
+    /// Returns a reference to the NcMultiSelector's underlying plane.
+    ///
+    /// *C style function: [ncmultiselector_plane()][c_api::ncmultiselector_plane].*
+    pub fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![
+            @call "ncmultiselector_plane", unsafe { c_api::ncmultiselector_plane(self) },
+            "NcMultiSelector.plane()"
+        ]
+    }
+
+    /// Returns whether each item is selected, in order.
+    ///
+    /// It's an error if the NcMultiSelector wasn't created with
+    /// [new][NcMultiSelector#method.new] or a [builder][NcMultiSelectorBuilder],
+    /// which keep track of the number of items.
+    ///
+    /// *C style function: [ncmultiselector_selected()][c_api::ncmultiselector_selected].*
+    pub fn selected(&mut self) -> NcResult<Vec<bool>> {
+        let plane = unsafe { c_api::ncmultiselector_plane(self) };
+        let count = unsafe { userptr::get_const(plane) }.map_or(0, |data| data.widget_items);
+        let mut selected = vec![false; count];
+        error![
+            @call "ncmultiselector_selected", unsafe { c_api::ncmultiselector_selected(self, selected.as_mut_ptr(), count as u32) },
+            "NcMultiSelector.selected()", selected
+        ]
+    }
+
+    /// Returns the indices of the selected items, in ascending order.
+    ///
+    /// *C style function: [ncmultiselector_selected()][c_api::ncmultiselector_selected].*
+    pub fn selected_indices(&mut self) -> NcResult<Vec<usize>> {
+        Ok(self
+            .selected()?
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, selected)| if selected { Some(i) } else { None })
+            .collect())
+    }
+}
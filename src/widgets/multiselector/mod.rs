@@ -1,10 +1,55 @@
 //! `NcMultiSelector` widget.
+//!                                  ╭────────────────────────╮
+//!                                  │This is the primary text│
+//!   ╭──────────────────────this is the secondary text───────╮
+//!   │        ↑                                              │
+//!   │ ☐ option1 Long text #1                                │
+//!   │ ☒ option2 Long text #2                                │
+//!   │ ☐ option3 Long text #3                                │
+//!   │        ↓                                              │
+//!   ╰──────────────────────────────────press q to exit (q)──╯
+//!
+//! selection widget -- an ncplane with a title header and a body section. the
+//! body section supports infinite scrolling up and down.
+//!
+//! Any number of items can be selected at the same time.
 
-/// high-level widget for selecting items from a set
+// functions already exported by bindgen : 5
+// -----------------------------------------
+// (#) test: 0
+// (W) wrap: 5 / 0
+// -----------------------------------------
+//W ncmultiselector_create,
+//W ncmultiselector_destroy,
+//W ncmultiselector_offer_input,
+//W ncmultiselector_plane,
+//W ncmultiselector_selected,
+
+mod builder;
+mod methods;
+
+#[cfg(test)]
+mod test;
+
+pub use builder::NcMultiSelectorBuilder;
+
+/// High-level widget for selecting items from a set.
+///
+/// Takes ownership of its [`NcPlane`][crate::NcPlane], destroying it on any
+/// error (`ncmultiselector_destroy`() otherwise destroys the ncplane).
+///
+/// `type in C: ncmultiselector (struct)`
+///
 pub type NcMultiSelector = crate::bindings::ffi::ncmultiselector;
 
-/// an item for [`NcMultiSelector`]
+/// An item for [`NcMultiSelector`].
+///
+/// `type in C: ncmselector_item (struct)`
+///
 pub type NcMultiSelectorItem = crate::bindings::ffi::ncmselector_item;
 
-/// Options structure for [`NcMultiSelector`]
+/// Options structure for [`NcMultiSelector`].
+///
+/// `type in C: ncmultiselector_options (struct)`
+///
 pub type NcMultiSelectorOptions = crate::bindings::ffi::ncmultiselector_options;
@@ -0,0 +1,57 @@
+//! Test `NcMultiSelector` methods and its builder.
+
+use crate::{NcContext, NcErrorKind, NcInput, NcMultiSelector, NcPlaneHandle};
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn selected() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 10, 40)?;
+    let selector = NcMultiSelector::builder()
+        .item("cheese", "extra cheese", true)
+        .item("olives", "black olives", false)
+        .item("onions", "red onions", true)
+        .build(&mut plane)?;
+    assert_eq![vec![true, false, true], selector.selected()?];
+    assert_eq![vec![0, 2], selector.selected_indices()?];
+
+    // the space bar toggles the current item
+    assert![selector.offer_input(NcInput::new(' '))?];
+    assert_eq![vec![false, false, true], selector.selected()?];
+    assert_eq![vec![2], selector.selected_indices()?];
+    unsafe { selector.destroy()? };
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn builder_owns_strings() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 10, 40)?;
+
+    // the strings the builder was given are dropped before it's built
+    let builder = {
+        let title = String::from("toppings");
+        let options: Vec<String> = (0..3).map(|i| format!["option {}", i]).collect();
+        options.iter().fold(
+            NcMultiSelector::builder().title(&title),
+            |builder, option| builder.item(option, "desc", false),
+        )
+    };
+    let selector = builder.clone().build(&mut plane)?;
+    assert_eq![vec![false; 3], selector.selected()?];
+    unsafe { selector.destroy()? };
+
+    // a nul byte is an error, and leaves the plane untouched
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 10, 40)?;
+    let error = builder
+        .item("nul\0byte", "desc", false)
+        .build(&mut plane)
+        .unwrap_err();
+    assert_eq![NcErrorKind::InvalidArgument, error.kind()];
+    plane.putstr("still usable")?;
+    unsafe { plane.destroy()? };
+    Ok(())
+}
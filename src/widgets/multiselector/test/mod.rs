@@ -0,0 +1,4 @@
+//! `NcMultiSelector` tests.
+
+#[cfg(test)]
+mod methods;
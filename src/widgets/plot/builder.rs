@@ -0,0 +1,168 @@
+//! `NcPlotBuilder`
+
+use core::{marker::PhantomData, ptr::null};
+
+use super::{
+    NcPlot, NcPlotOptions, NcPlotSample, NCPLOT_OPTION_DETECTMAXONLY, NCPLOT_OPTION_EXPONENTIALD,
+    NCPLOT_OPTION_LABELTICKSD, NCPLOT_OPTION_NODEGRADE, NCPLOT_OPTION_VERTICALI,
+};
+use crate::{string, NcBlitter, NcBlitterApi, NcChannels, NcPlane, NcResult, NcStyle};
+
+/// A builder for [`NcPlot`].
+///
+/// It owns the title, which is converted to a C string when the plot is
+/// [built][NcPlotBuilder#method.build]. The C library keeps its own copy.
+///
+/// # Example
+///
+/// ```ignore
+/// let plot = NcPlot::<u64>::builder()
+///     .blitter(NcBlitter::BRAILLE)
+///     .range_x(60)
+///     .label_ticks(true)
+///     .build(plane)?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct NcPlotBuilder<T: NcPlotSample> {
+    title: Option<String>,
+    blitter: NcBlitter,
+    range_x: i32,
+    miny: T,
+    maxy: T,
+    max_channels: NcChannels,
+    min_channels: NcChannels,
+    legend_style: NcStyle,
+    flags: u64,
+    _sample: PhantomData<T>,
+}
+
+impl<T: NcPlotSample> Default for NcPlotBuilder<T> {
+    fn default() -> Self {
+        Self {
+            title: None,
+            blitter: NcBlitter::DEFAULT,
+            range_x: 0,
+            miny: T::default(),
+            maxy: T::default(),
+            max_channels: 0,
+            min_channels: 0,
+            legend_style: 0,
+            flags: 0,
+            _sample: PhantomData,
+        }
+    }
+}
+
+/// # `NcPlotBuilder` constructors
+impl<T: NcPlotSample> NcPlotBuilder<T> {
+    /// New NcPlotBuilder, with the default blitter and a detected domain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// # `NcPlotBuilder` methods
+impl<T: NcPlotSample> NcPlotBuilder<T> {
+    /// Sets the title.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the blitter used to draw the plot.
+    pub fn blitter(mut self, blitter: NcBlitter) -> Self {
+        self.blitter = blitter;
+        self
+    }
+
+    /// Sets the number of samples shown in the independent axis,
+    /// 0 to use the full width of the plane.
+    pub fn range_x(mut self, range_x: i32) -> Self {
+        self.range_x = range_x;
+        self
+    }
+
+    /// Sets the domain of the dependent axis.
+    ///
+    /// If both `miny` and `maxy` are 0 (the default), the domain is
+    /// detected automatically.
+    pub fn range_y(mut self, miny: T, maxy: T) -> Self {
+        self.miny = miny;
+        self.maxy = maxy;
+        self
+    }
+
+    /// Sets the channels for the maximum levels.
+    pub fn max_channels(mut self, channels: NcChannels) -> Self {
+        self.max_channels = channels;
+        self
+    }
+
+    /// Sets the channels for the minimum levels.
+    pub fn min_channels(mut self, channels: NcChannels) -> Self {
+        self.min_channels = channels;
+        self
+    }
+
+    /// Sets the styling of the legend.
+    pub fn legend_style(mut self, style: NcStyle) -> Self {
+        self.legend_style = style;
+        self
+    }
+
+    /// Shows the labels for the dependent axis.
+    pub fn label_ticks(self, label_ticks: bool) -> Self {
+        self.flag(NCPLOT_OPTION_LABELTICKSD, label_ticks)
+    }
+
+    /// Makes the independent axis vertical.
+    pub fn vertical(self, vertical: bool) -> Self {
+        self.flag(NCPLOT_OPTION_VERTICALI, vertical)
+    }
+
+    /// Makes the dependent axis exponential.
+    pub fn exponential(self, exponential: bool) -> Self {
+        self.flag(NCPLOT_OPTION_EXPONENTIALD, exponential)
+    }
+
+    /// Uses the domain detection only for the maximum.
+    pub fn detect_max_only(self, detect_max_only: bool) -> Self {
+        self.flag(NCPLOT_OPTION_DETECTMAXONLY, detect_max_only)
+    }
+
+    /// Fails instead of degrading the blitter when it's not supported.
+    pub fn no_degrade(self, no_degrade: bool) -> Self {
+        self.flag(NCPLOT_OPTION_NODEGRADE, no_degrade)
+    }
+
+    fn flag(mut self, flag: u32, set: bool) -> Self {
+        if set {
+            self.flags |= flag as u64;
+        } else {
+            self.flags &= !(flag as u64);
+        }
+        self
+    }
+
+    /// Creates the [`NcPlot`], taking ownership of the `plane`.
+    ///
+    /// Returns an error if the title contains a nul byte, in which case the
+    /// `plane` is left untouched, or if the plot couldn't be created,
+    /// in which case the `plane` is destroyed.
+    ///
+    /// *C style function: [ncuplot_create()][crate::c_api::ncuplot_create],
+    /// [ncdplot_create()][crate::c_api::ncdplot_create].*
+    pub fn build<'a>(self, plane: &'a mut NcPlane) -> NcResult<NcPlot<'a, T>> {
+        let title = self.title.as_deref().map(string::cstring).transpose()?;
+        let options = NcPlotOptions {
+            maxchannels: self.max_channels,
+            minchannels: self.min_channels,
+            legendstyle: self.legend_style,
+            gridtype: self.blitter,
+            rangex: self.range_x,
+            title: title.as_ref().map_or(null(), |s| s.as_ptr()),
+            flags: self.flags,
+        };
+        NcPlot::with_options(plane, &options, self.miny, self.maxy)
+    }
+}
@@ -0,0 +1,216 @@
+//! `NcPlot` & `NcPlotOptions` methods and associated functions.
+
+use core::{
+    fmt,
+    marker::PhantomData,
+    ptr::{null, null_mut},
+};
+
+use super::{NcPlotBuilder, NcPlotOptions, NcPlotSample};
+use crate::{
+    error, error_ref_mut, widgets::NcWidget, NcBlitter, NcBlitterApi, NcError, NcErrorKind,
+    NcInput, NcPlane, NcResult,
+};
+
+/// # `NcPlotOptions` constructors
+impl NcPlotOptions {
+    /// New NcPlotOptions, with the default blitter, no title and no flags.
+    pub fn new() -> Self {
+        Self::with_blitter(NcBlitter::DEFAULT)
+    }
+
+    /// New NcPlotOptions, drawn with a `blitter`.
+    pub fn with_blitter(blitter: NcBlitter) -> Self {
+        Self {
+            maxchannels: 0,
+            minchannels: 0,
+            legendstyle: 0,
+            gridtype: blitter,
+            rangex: 0,
+            title: null(),
+            flags: 0,
+        }
+    }
+}
+
+/// A histogram, bound to an [`NcPlane`], with samples of type `T`,
+/// which can be either `u64` or `f64`.
+///
+/// The independent variable is always a `u64`.
+///
+/// Takes ownership of its plane, which is destroyed along with it when it's
+/// dropped or [destroy][NcPlot#method.destroy]ed, or on error.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut plot = NcPlot::<f64>::builder()
+///     .range_y(0.0, 1.0)
+///     .label_ticks(true)
+///     .title("load")
+///     .build(plane)?;
+/// plot.add_sample(0, 0.5)?;
+/// ```
+pub struct NcPlot<'a, T: NcPlotSample> {
+    /// The C plot, null once it's destroyed.
+    plot: *mut T::Plot,
+    _plane: PhantomData<&'a mut NcPlane>,
+    _sample: PhantomData<T>,
+}
+
+impl<'a, T: NcPlotSample> fmt::Debug for NcPlot<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NcPlot").field(&self.plot).finish()
+    }
+}
+
+impl<'a, T: NcPlotSample> Drop for NcPlot<'a, T> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+/// # `NcPlot` constructors & destructors
+impl<'a, T: NcPlotSample> NcPlot<'a, T> {
+    /// New NcPlot, with the default options.
+    ///
+    /// If both `miny` and `maxy` are 0, the domain is detected automatically.
+    ///
+    /// *C style function: [ncuplot_create()][crate::c_api::ncuplot_create],
+    /// [ncdplot_create()][crate::c_api::ncdplot_create].*
+    pub fn new(plane: &'a mut NcPlane, miny: T, maxy: T) -> NcResult<Self> {
+        Self::with_options(plane, &NcPlotOptions::new(), miny, maxy)
+    }
+
+    /// New NcPlot. Expects an [`NcPlotOptions`] struct.
+    ///
+    /// If both `miny` and `maxy` are 0, the domain is detected automatically.
+    ///
+    /// *C style function: [ncuplot_create()][crate::c_api::ncuplot_create],
+    /// [ncdplot_create()][crate::c_api::ncdplot_create].*
+    pub fn with_options(
+        plane: &'a mut NcPlane,
+        options: &NcPlotOptions,
+        miny: T,
+        maxy: T,
+    ) -> NcResult<Self> {
+        let plot = unsafe { T::create(plane, options, miny, maxy) };
+        if plot.is_null() {
+            return Err(
+                NcError::with_kind(NcErrorKind::NullPointer, "NcPlot::with_options()")
+                    .calling(&format!["{}_create", T::PREFIX]),
+            );
+        }
+        Ok(Self {
+            plot,
+            _plane: PhantomData,
+            _sample: PhantomData,
+        })
+    }
+
+    /// Returns a new [`NcPlotBuilder`].
+    ///
+    /// *(No equivalent C style function)*
+    pub fn builder() -> NcPlotBuilder<T> {
+        NcPlotBuilder::new()
+    }
+
+    /// Destroys the NcPlot and its underlying plane.
+    ///
+    /// *C style function: [ncuplot_destroy()][crate::c_api::ncuplot_destroy],
+    /// [ncdplot_destroy()][crate::c_api::ncdplot_destroy].*
+    pub fn destroy(mut self) {
+        self.release();
+    }
+
+    /// Destroys the C plot, if it's not destroyed yet.
+    fn release(&mut self) {
+        if !self.plot.is_null() {
+            unsafe { T::destroy(self.plot) };
+            self.plot = null_mut();
+        }
+    }
+
+    /// Returns a raw pointer to the C plot.
+    pub fn as_ptr(&self) -> *mut T::Plot {
+        self.plot
+    }
+}
+
+/// # `NcPlot` methods
+impl<'a, T: NcPlotSample> NcPlot<'a, T> {
+    /// Returns a reference to the NcPlot's underlying plane.
+    ///
+    /// *C style function: [ncuplot_plane()][crate::c_api::ncuplot_plane],
+    /// [ncdplot_plane()][crate::c_api::ncdplot_plane].*
+    pub fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![
+            @call &format!["{}_plane", T::PREFIX],
+            unsafe { T::plane(self.plot) },
+            "NcPlot.plane()"
+        ]
+    }
+
+    /// Adds `y` to the current value of the sample at `x`.
+    ///
+    /// If `x` is beyond the current window, the window is shifted forward,
+    /// and any samples left behind are lost.
+    ///
+    /// *C style function: [ncuplot_add_sample()][crate::c_api::ncuplot_add_sample],
+    /// [ncdplot_add_sample()][crate::c_api::ncdplot_add_sample].*
+    pub fn add_sample(&mut self, x: u64, y: T) -> NcResult<()> {
+        error![
+            @call &format!["{}_add_sample", T::PREFIX],
+            unsafe { T::add_sample(self.plot, x, y) },
+            &format!["NcPlot.add_sample({}, …)", x]
+        ]
+    }
+
+    /// Replaces the value of the sample at `x` with `y`.
+    ///
+    /// *C style function: [ncuplot_set_sample()][crate::c_api::ncuplot_set_sample],
+    /// [ncdplot_set_sample()][crate::c_api::ncdplot_set_sample].*
+    pub fn set_sample(&mut self, x: u64, y: T) -> NcResult<()> {
+        error![
+            @call &format!["{}_set_sample", T::PREFIX],
+            unsafe { T::set_sample(self.plot, x, y) },
+            &format!["NcPlot.set_sample({}, …)", x]
+        ]
+    }
+
+    /// Returns the value of the sample at `x`.
+    ///
+    /// It's an error if `x` is outside of the current window.
+    ///
+    /// *C style function: [ncuplot_sample()][crate::c_api::ncuplot_sample],
+    /// [ncdplot_sample()][crate::c_api::ncdplot_sample].*
+    pub fn sample(&self, x: u64) -> NcResult<T> {
+        let mut y = T::default();
+        let res = unsafe { T::sample(self.plot, x, &mut y) };
+        if res < crate::c_api::NCRESULT_OK {
+            return Err(NcError::with_msg(res, &format!["NcPlot.sample({})", x])
+                .of_kind(NcErrorKind::OutOfBounds)
+                .calling(&format!["{}_sample", T::PREFIX]));
+        }
+        Ok(y)
+    }
+}
+
+impl<'a, T: NcPlotSample> NcWidget for NcPlot<'a, T> {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        NcPlot::plane(self)
+    }
+
+    fn offer_input(&mut self, _input: NcInput) -> NcResult<bool> {
+        Ok(false)
+    }
+
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+
+    unsafe fn destroy(&mut self) -> NcResult<()> {
+        self.release();
+        Ok(())
+    }
+}
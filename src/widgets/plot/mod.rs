@@ -1,5 +1,34 @@
 //! `NcPlot[F|U]64` widget.
 
+// functions already exported by bindgen : 12
+// ------------------------------------------
+// (#) test: 0
+// (W) wrap: 12 / 0
+// ------------------------------------------
+//W ncdplot_add_sample,
+//W ncdplot_create,
+//W ncdplot_destroy,
+//W ncdplot_plane,
+//W ncdplot_sample,
+//W ncdplot_set_sample,
+//W ncuplot_add_sample,
+//W ncuplot_create,
+//W ncuplot_destroy,
+//W ncuplot_plane,
+//W ncuplot_sample,
+//W ncuplot_set_sample,
+
+use crate::{c_api, NcIntResult, NcPlane};
+
+mod builder;
+mod methods;
+
+#[cfg(test)]
+mod test;
+
+pub use builder::NcPlotBuilder;
+pub use methods::NcPlot;
+
 /// A histogram, bound to an [`NcPlane`][crate::NcPlane]
 /// (uses non-negative `f64`s)
 pub type NcPlotF64 = crate::bindings::ffi::ncdplot;
@@ -25,3 +54,109 @@ pub const NCPLOT_OPTION_NODEGRADE: u32 = crate::bindings::ffi::NCPLOT_OPTION_NOD
 
 /// Independent axis is vertical
 pub const NCPLOT_OPTION_VERTICALI: u32 = crate::bindings::ffi::NCPLOT_OPTION_VERTICALI;
+
+/// The type of the samples of an [`NcPlot`]: either `u64` or `f64`.
+///
+/// This trait is sealed, and its methods are not part of the public API.
+pub trait NcPlotSample: Copy + Default + private::Sealed {
+    /// The C plot type for this sample type.
+    #[doc(hidden)]
+    type Plot;
+
+    /// The prefix of the names of the C plot functions for this sample type.
+    #[doc(hidden)]
+    const PREFIX: &'static str;
+
+    #[doc(hidden)]
+    unsafe fn create(
+        plane: *mut NcPlane,
+        options: *const NcPlotOptions,
+        miny: Self,
+        maxy: Self,
+    ) -> *mut Self::Plot;
+
+    #[doc(hidden)]
+    unsafe fn destroy(plot: *mut Self::Plot);
+
+    #[doc(hidden)]
+    unsafe fn plane(plot: *mut Self::Plot) -> *mut NcPlane;
+
+    #[doc(hidden)]
+    unsafe fn add_sample(plot: *mut Self::Plot, x: u64, y: Self) -> NcIntResult;
+
+    #[doc(hidden)]
+    unsafe fn set_sample(plot: *mut Self::Plot, x: u64, y: Self) -> NcIntResult;
+
+    #[doc(hidden)]
+    unsafe fn sample(plot: *const Self::Plot, x: u64, y: *mut Self) -> NcIntResult;
+}
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for u64 {}
+    impl Sealed for f64 {}
+}
+
+macro_rules! impl_plot_sample {
+    ($sample:ty, $plot:ty, $prefix:literal, $create:ident, $destroy:ident, $plane:ident,
+     $add_sample:ident, $set_sample:ident, $get_sample:ident) => {
+        impl NcPlotSample for $sample {
+            type Plot = $plot;
+
+            const PREFIX: &'static str = $prefix;
+
+            unsafe fn create(
+                plane: *mut NcPlane,
+                options: *const NcPlotOptions,
+                miny: Self,
+                maxy: Self,
+            ) -> *mut Self::Plot {
+                c_api::$create(plane, options, miny, maxy)
+            }
+
+            unsafe fn destroy(plot: *mut Self::Plot) {
+                c_api::$destroy(plot)
+            }
+
+            unsafe fn plane(plot: *mut Self::Plot) -> *mut NcPlane {
+                c_api::$plane(plot)
+            }
+
+            unsafe fn add_sample(plot: *mut Self::Plot, x: u64, y: Self) -> NcIntResult {
+                c_api::$add_sample(plot, x, y)
+            }
+
+            unsafe fn set_sample(plot: *mut Self::Plot, x: u64, y: Self) -> NcIntResult {
+                c_api::$set_sample(plot, x, y)
+            }
+
+            unsafe fn sample(plot: *const Self::Plot, x: u64, y: *mut Self) -> NcIntResult {
+                c_api::$get_sample(plot, x, y)
+            }
+        }
+    };
+}
+
+impl_plot_sample![
+    u64,
+    NcPlotU64,
+    "ncuplot",
+    ncuplot_create,
+    ncuplot_destroy,
+    ncuplot_plane,
+    ncuplot_add_sample,
+    ncuplot_set_sample,
+    ncuplot_sample
+];
+
+impl_plot_sample![
+    f64,
+    NcPlotF64,
+    "ncdplot",
+    ncdplot_create,
+    ncdplot_destroy,
+    ncdplot_plane,
+    ncdplot_add_sample,
+    ncdplot_set_sample,
+    ncdplot_sample
+];
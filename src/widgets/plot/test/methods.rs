@@ -0,0 +1,44 @@
+//! Test `NcPlot` methods.
+
+use crate::{NcContext, NcErrorKind, NcPlaneHandle, NcPlot};
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn samples_u64() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 5, 20)?;
+    let mut plot = NcPlot::<u64>::new(&mut plane, 0, 0)?;
+
+    plot.add_sample(0, 3)?;
+    plot.add_sample(0, 2)?;
+    assert_eq![5, plot.sample(0)?];
+    plot.set_sample(0, 1)?;
+    assert_eq![1, plot.sample(0)?];
+
+    // moving the window forward loses the samples left behind
+    plot.add_sample(1000, 7)?;
+    assert_eq![7, plot.sample(1000)?];
+    assert_eq![NcErrorKind::OutOfBounds, plot.sample(0).unwrap_err().kind()];
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn samples_f64() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 5, 20)?;
+    {
+        // destroyed on drop
+        let mut plot = NcPlot::<f64>::builder()
+            .range_y(0.0, 1.0)
+            .build(&mut plane)?;
+        plot.add_sample(0, 0.25)?;
+        plot.add_sample(0, 0.5)?;
+        assert_eq![0.75, plot.sample(0)?];
+        plot.set_sample(0, 0.125)?;
+        assert_eq![0.125, plot.sample(0)?];
+    }
+    Ok(())
+}
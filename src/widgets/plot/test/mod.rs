@@ -0,0 +1,4 @@
+//! `NcPlot` tests.
+
+#[cfg(test)]
+mod methods;
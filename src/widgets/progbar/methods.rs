@@ -1,7 +1,7 @@
 //! `NcProgBar` & `NcProgBarOptions` methods and associated functions.
 
 use super::{NcProgBar, NcProgBarOptions};
-use crate::{c_api, error, NcPlane, NcResult};
+use crate::{c_api, error, error_ref_mut, NcPlane, NcResult};
 
 /// # `NcProgBarOptions` Methods
 impl NcProgBarOptions {
@@ -36,18 +36,22 @@ impl NcProgBar {
 
     /// Destroy the progress bar and its underlying ncplane.
     ///
+    /// # Safety
+    ///
+    /// Neither the progress bar nor its plane may be used afterwards, and it
+    /// must not be owned by an `OwnedProgBar`.
+    ///
     /// *C style function: [ncprogbar_destroy()][c_api::ncprogbar_destroy].*
-    pub fn destroy(&mut self) {
-        unsafe {
-            c_api::ncprogbar_destroy(self);
-        }
+    pub unsafe fn destroy(&mut self) -> NcResult<()> {
+        c_api::ncprogbar_destroy(self);
+        Ok(())
     }
 
     /// Return a reference to the ncprogbar's underlying ncplane.
     ///
     /// *C style function: [ncprogbar_plane()][c_api::ncprogbar_plane].*
-    pub fn plane(&mut self) -> &mut NcPlane {
-        unsafe { &mut *c_api::ncprogbar_plane(self) }
+    pub fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![@call "ncprogbar_plane", unsafe { c_api::ncprogbar_plane(self) }, "NcProgBar.plane()"]
     }
 
     /// Get the progress bar's completion, an [f64] on [0, 1].
@@ -64,6 +68,6 @@ impl NcProgBar {
     ///
     /// *C style function: [ncprogbar_set_progress()][c_api::ncprogbar_set_progress].*
     pub fn set_progress(&mut self, progress: f64) -> NcResult<()> {
-        error![unsafe { c_api::ncprogbar_set_progress(self, progress) }]
+        error![@call "ncprogbar_set_progress", unsafe { c_api::ncprogbar_set_progress(self, progress) }, "NcProgBar.set_progress()"]
     }
 }
@@ -0,0 +1,420 @@
+//! `NcReader` line-editing extensions.
+//!
+//! The state of the extensions is stored in the userptr of the plane of the
+//! reader, and it's only allocated when any of them is set.
+
+use core::mem;
+
+use super::NcReader;
+use crate::{
+    c_api::{self, ncinput_nomod_p, nckey_supppuab_p},
+    plane::userptr,
+    string, NcError, NcErrorKind, NcEvType, NcEvTypeApi, NcInput, NcKey, NcResult,
+};
+
+/// A completion callback: returns the completed contents, if there's any.
+type CompletionCb = Box<dyn FnMut(&str) -> Option<String>>;
+
+/// A character filter: returns `false` for the rejected characters.
+type CharFilterCb = Box<dyn FnMut(char) -> bool>;
+
+/// A validation callback: returns an error for the rejected contents.
+type ValidationCb = Box<dyn FnMut(&str) -> NcResult<()>>;
+
+/// The line-editing state of a reader.
+#[derive(Default)]
+pub(super) struct ReaderData {
+    /// The maximum number of entries in the history. 0 disables it.
+    history_len: usize,
+    history: Vec<String>,
+    /// The position in the history, while it's being recalled.
+    history_pos: Option<usize>,
+    /// The contents being edited before recalling the history.
+    draft: String,
+    completion: Option<CompletionCb>,
+    char_filter: Option<CharFilterCb>,
+    validation: Option<ValidationCb>,
+    max_len: Option<usize>,
+    /// The masking character.
+    mask: Option<char>,
+    /// The actual contents, while masked.
+    secret: String,
+}
+
+/// Returns the line-editing state of the `reader`, if there's any.
+///
+/// # Safety
+/// `reader` must be valid, and there must not be any other live references
+/// to its state.
+pub(super) unsafe fn get<'a>(reader: *mut NcReader) -> Option<&'a mut ReaderData> {
+    userptr::get(c_api::ncreader_plane(reader))?
+        .widget_data
+        .as_mut()?
+        .downcast_mut()
+}
+
+/// Returns a shared reference to the line-editing state of the `reader`,
+/// if there's any.
+///
+/// # Safety
+/// `reader` must be valid, and there must not be any live mutable references
+/// to its state.
+unsafe fn get_ref<'a>(reader: *const NcReader) -> Option<&'a ReaderData> {
+    userptr::get_const(c_api::ncreader_plane(reader as *mut NcReader))?
+        .widget_data
+        .as_ref()?
+        .downcast_ref()
+}
+
+/// Returns an error if the `reader` is masked, for the `method`s that would
+/// unsync its actual contents.
+///
+/// # Safety
+/// `reader` must be valid, and there must not be any live mutable references
+/// to its state.
+pub(super) unsafe fn check_unmasked(reader: *const NcReader, method: &str) -> NcResult<()> {
+    match get_ref(reader) {
+        Some(data) if data.mask.is_some() => Err(NcError::with_kind(
+            NcErrorKind::Unsupported,
+            &format!["{}: the reader is masked", method],
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the line-editing state of the `reader`, allocating it if there's
+/// none.
+///
+/// # Safety
+/// `reader` must be valid, and there must not be any other live references
+/// to its state.
+unsafe fn get_or_init<'a>(reader: *mut NcReader) -> &'a mut ReaderData {
+    let data = userptr::get_or_init(&mut *c_api::ncreader_plane(reader));
+    if !data
+        .widget_data
+        .as_ref()
+        .map_or(false, |d| d.is::<ReaderData>())
+    {
+        data.widget_data = Some(Box::new(ReaderData::default()));
+    }
+    data.widget_data
+        .as_mut()
+        .and_then(|d| d.downcast_mut())
+        .expect("the reader state was just set")
+}
+
+/// Returns the contents of the C `reader`.
+pub(super) unsafe fn raw_contents(reader: *mut NcReader) -> String {
+    string::rstring_free_lossy(c_api::ncreader_contents(reader))
+}
+
+/// Writes a `char` to the C `reader`.
+unsafe fn raw_write(reader: *mut NcReader, c: char) {
+    let mut buf = [0; 4];
+    let _ = string::with_cstr(c.encode_utf8(&mut buf), |egc| {
+        c_api::ncreader_write_egc(reader, egc)
+    });
+}
+
+/// Returns `true` for the backspace keys.
+fn is_backspace(c: char) -> bool {
+    c == NcKey::BACKSPACE || c == '\x7f' || c == '\x08'
+}
+
+impl ReaderData {
+    /// Returns the contents of the `reader`.
+    pub(super) unsafe fn contents(&self, reader: *mut NcReader) -> String {
+        if self.mask.is_some() {
+            self.secret.clone()
+        } else {
+            raw_contents(reader)
+        }
+    }
+
+    /// Forgets the state of the current edit, when the reader is cleared.
+    pub(super) fn forget_edit(&mut self) {
+        self.secret.clear();
+        self.history_pos = None;
+    }
+
+    /// Records the written `egc`, and returns the masking character,
+    /// if the reader is masked.
+    pub(super) fn mask_for(&mut self, egc: &str) -> Option<char> {
+        let mask = self.mask?;
+        self.secret.push_str(egc);
+        Some(mask)
+    }
+
+    /// Replaces the contents of the `reader` with `text`, truncated to the
+    /// maximum length, and masked.
+    unsafe fn replace(&mut self, reader: *mut NcReader, text: &str) {
+        c_api::ncreader_clear(reader);
+        self.secret.clear();
+        let len = self.max_len.unwrap_or(usize::MAX);
+        for c in text.chars().take(len) {
+            match self.mask {
+                Some(mask) => {
+                    self.secret.push(c);
+                    raw_write(reader, mask);
+                }
+                None => raw_write(reader, c),
+            }
+        }
+    }
+
+    /// Recalls the previous entry of the history, or the next one.
+    unsafe fn recall(&mut self, reader: *mut NcReader, previous: bool) {
+        let last = self.history.len() - 1;
+        let pos = match (self.history_pos, previous) {
+            (None, true) => {
+                self.draft = self.contents(reader);
+                Some(last)
+            }
+            (None, false) => return,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if i < last => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        let text = match pos {
+            Some(i) => self.history[i].clone(),
+            None => mem::take(&mut self.draft),
+        };
+        self.replace(reader, &text);
+        self.history_pos = pos;
+    }
+
+    /// Adds a `line` to the history, unless it's empty or repeated,
+    /// or the reader is masked.
+    fn push_history(&mut self, line: &str) {
+        if self.history_len == 0
+            || self.mask.is_some()
+            || line.is_empty()
+            || self.history.last().map_or(false, |l| l == line)
+        {
+            return;
+        }
+        self.history.push(line.into());
+        self.trim_history();
+    }
+
+    fn trim_history(&mut self) {
+        if self.history.len() > self.history_len {
+            let excess = self.history.len() - self.history_len;
+            self.history.drain(..excess);
+        }
+    }
+
+    /// Handles the `input` before the C reader.
+    ///
+    /// Returns whether it was consumed, or `None` if it must be offered to
+    /// the C reader.
+    pub(super) unsafe fn handle(&mut self, reader: *mut NcReader, input: &NcInput) -> Option<bool> {
+        if input.evtype == NcEvType::RELEASE {
+            return None;
+        }
+        let key = core::char::from_u32(input.id)?;
+        let nomod = ncinput_nomod_p(input);
+
+        if nomod
+            && (key == NcKey::UP || key == NcKey::DOWN)
+            && !self.history.is_empty()
+            && self.mask.is_none()
+        {
+            self.recall(reader, key == NcKey::UP);
+            return Some(true);
+        }
+        if nomod && key == '\t' {
+            if let Some(mut completion) = self.completion.take() {
+                let completed = completion(&self.contents(reader));
+                self.completion = Some(completion);
+                if let Some(completed) = completed {
+                    self.replace(reader, &completed);
+                    self.history_pos = None;
+                }
+                return Some(true);
+            }
+        }
+        if !input.ctrl && !input.alt && !key.is_control() && !nckey_supppuab_p(key) {
+            if let Some(filter) = &mut self.char_filter {
+                if !filter(key) {
+                    return Some(true);
+                }
+            }
+            if let Some(max_len) = self.max_len {
+                if self.contents(reader).chars().count() >= max_len {
+                    return Some(true);
+                }
+            }
+            self.history_pos = None;
+            if let Some(mask) = self.mask {
+                self.secret.push(key);
+                raw_write(reader, mask);
+                return Some(true);
+            }
+            return None;
+        }
+        if self.mask.is_some() {
+            if nomod && is_backspace(key) {
+                self.secret.pop();
+                return None;
+            }
+            // other editing keys would unsync the actual contents
+            return Some(false);
+        }
+        None
+    }
+}
+
+/// # `NcReader` line-editing methods
+///
+/// These extensions are applied by [offer_input][NcReader#method.offer_input],
+/// before offering the input to the C reader.
+impl NcReader {
+    /// Sets the maximum number of entries of the history.
+    ///
+    /// While there are entries, the up and down arrows recall them, instead
+    /// of moving the cursor. The entries are added by
+    /// [submit][NcReader#method.submit].
+    ///
+    /// While the reader is [masked][NcReader#method.set_mask], the history is
+    /// neither recalled nor added to.
+    ///
+    /// The default is 0, which disables the history.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn set_history_len(&mut self, len: usize) {
+        let data = unsafe { get_or_init(self) };
+        data.history_len = len;
+        data.trim_history();
+    }
+
+    /// Replaces the entries of the history, from the oldest to the newest.
+    ///
+    /// Only the newest entries up to the
+    /// [history length][NcReader#method.set_history_len] are kept.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn set_history(&mut self, history: Vec<String>) {
+        let data = unsafe { get_or_init(self) };
+        data.history = history;
+        data.history_pos = None;
+        data.trim_history();
+    }
+
+    /// Returns the entries of the history, from the oldest to the newest.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn history(&self) -> &[String] {
+        match unsafe { get_ref(self) } {
+            Some(data) => &data.history,
+            None => &[],
+        }
+    }
+
+    /// Sets a completion callback, called with the contents when the
+    /// tab key is pressed, that returns the completed contents, if any.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn set_completion<F>(&mut self, completion: F)
+    where
+        F: FnMut(&str) -> Option<String> + 'static,
+    {
+        unsafe { get_or_init(self) }.completion = Some(Box::new(completion));
+    }
+
+    /// Unsets the completion callback.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn unset_completion(&mut self) {
+        if let Some(data) = unsafe { get(self) } {
+            data.completion = None;
+        }
+    }
+
+    /// Masks the contents with the `mask` character, e.g. for passwords,
+    /// or unmasks them with `None`.
+    ///
+    /// While masked, the actual contents are kept apart, and only typing
+    /// and deleting with backspace are allowed. Moving the cursor is an
+    /// error, and the contents are not added to the history.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn set_mask(&mut self, mask: Option<char>) {
+        let data = unsafe { get_or_init(self) };
+        let contents = unsafe { data.contents(self) };
+        data.mask = mask;
+        unsafe { data.replace(self, &contents) };
+    }
+
+    /// Sets the maximum length of the contents, in characters,
+    /// or removes the limit with `None`.
+    ///
+    /// The current contents are not truncated.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn set_max_len(&mut self, max_len: Option<usize>) {
+        unsafe { get_or_init(self) }.max_len = max_len;
+    }
+
+    /// Sets a filter that rejects the typed characters for which it
+    /// returns `false`.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn set_char_filter<F>(&mut self, filter: F)
+    where
+        F: FnMut(char) -> bool + 'static,
+    {
+        unsafe { get_or_init(self) }.char_filter = Some(Box::new(filter));
+    }
+
+    /// Unsets the character filter.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn unset_char_filter(&mut self) {
+        if let Some(data) = unsafe { get(self) } {
+            data.char_filter = None;
+        }
+    }
+
+    /// Sets a validation callback, that rejects the contents on
+    /// [submit][NcReader#method.submit] by returning an error.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn set_validation<F>(&mut self, validation: F)
+    where
+        F: FnMut(&str) -> NcResult<()> + 'static,
+    {
+        unsafe { get_or_init(self) }.validation = Some(Box::new(validation));
+    }
+
+    /// Unsets the validation callback.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn unset_validation(&mut self) {
+        if let Some(data) = unsafe { get(self) } {
+            data.validation = None;
+        }
+    }
+
+    /// Submits the contents, e.g. when the enter key is pressed.
+    ///
+    /// If they are accepted by the validation callback, they are added to
+    /// the history, unless the reader is masked, the reader is cleared,
+    /// and they are returned.
+    /// Otherwise the error of the validation callback is returned,
+    /// and the contents are kept.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn submit(&mut self) -> NcResult<String> {
+        let contents = self.contents();
+        if let Some(data) = unsafe { get(self) } {
+            if let Some(validation) = &mut data.validation {
+                validation(&contents)?;
+            }
+            data.push_history(&contents);
+            data.draft.clear();
+        }
+        self.clear()?;
+        Ok(contents)
+    }
+}
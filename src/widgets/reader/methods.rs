@@ -1,7 +1,9 @@
 //! `NcReader*` methods and associated functions.
 
-use super::{NcReader, NcReaderOptions};
-use crate::{c_api::ncreader_create, error_ref_mut, NcPlane, NcResult};
+use core::ptr::null_mut;
+
+use super::{editor, NcReader, NcReaderOptions};
+use crate::{c_api, error, error_ref_mut, plane::userptr, string, NcInput, NcPlane, NcResult};
 
 /// # `NcReaderOptions` Constructors
 impl NcReaderOptions {
@@ -18,7 +20,7 @@ impl NcReaderOptions {
     }
 }
 
-/// # `NcReader` Constructors
+/// # `NcReader` Constructors & destructors
 impl NcReader {
     /// `NcReader` simple constructor
     pub fn new<'a>(plane: &mut NcPlane) -> NcResult<&'a mut Self> {
@@ -30,6 +32,143 @@ impl NcReader {
         plane: &mut NcPlane,
         options: NcReaderOptions,
     ) -> NcResult<&'a mut Self> {
-        error_ref_mut![unsafe { ncreader_create(plane, &options) }]
+        error_ref_mut![@call "ncreader_create", unsafe { c_api::ncreader_create(plane, &options) }, "NcReader::with_options()"]
+    }
+
+    /// Destroys the NcReader and its underlying plane, along with its
+    /// line-editing state.
+    ///
+    /// # Safety
+    ///
+    /// Neither the reader nor its plane may be used afterwards, and it must
+    /// not be owned by an `OwnedReader`.
+    ///
+    /// *C style function: [ncreader_destroy()][c_api::ncreader_destroy].*
+    pub unsafe fn destroy(&mut self) -> NcResult<()> {
+        userptr::free(c_api::ncreader_plane(self));
+        c_api::ncreader_destroy(self, null_mut());
+        Ok(())
+    }
+}
+
+/// # `NcReader` methods
+impl NcReader {
+    /// Clears the contents of the NcReader.
+    ///
+    /// *C style function: [ncreader_clear()][c_api::ncreader_clear].*
+    pub fn clear(&mut self) -> NcResult<()> {
+        if let Some(data) = unsafe { editor::get(self) } {
+            data.forget_edit();
+        }
+        error![@call "ncreader_clear", unsafe { c_api::ncreader_clear(self) }, "NcReader.clear()"]
+    }
+
+    /// Returns the contents of the NcReader.
+    ///
+    /// If it's [masked][NcReader#method.set_mask], returns the actual
+    /// contents, not the masking characters.
+    ///
+    /// *C style function: [ncreader_contents()][c_api::ncreader_contents].*
+    pub fn contents(&mut self) -> String {
+        match unsafe { editor::get(self) } {
+            Some(data) => unsafe { data.contents(self) },
+            None => unsafe { editor::raw_contents(self) },
+        }
+    }
+
+    /// Offers the input to the NcReader.
+    ///
+    /// Returns `true` if it was relevant. The input is first checked by the
+    /// line-editing extensions, if any is set.
+    ///
+    /// *C style function: [ncreader_offer_input()][c_api::ncreader_offer_input].*
+    pub fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        if let Some(data) = unsafe { editor::get(self) } {
+            if let Some(consumed) = unsafe { data.handle(self, &input) } {
+                return Ok(consumed);
+            }
+        }
+        Ok(unsafe { c_api::ncreader_offer_input(self, &input) })
+    }
+
+    /// Moves the cursor one cell to the left.
+    ///
+    /// It's an error if it can't be moved, or if the reader is
+    /// [masked][NcReader#method.set_mask].
+    ///
+    /// *C style function: [ncreader_move_left()][c_api::ncreader_move_left].*
+    pub fn move_left(&mut self) -> NcResult<()> {
+        unsafe { editor::check_unmasked(self, "NcReader.move_left()")? };
+        error![
+            @call "ncreader_move_left", unsafe { c_api::ncreader_move_left(self) },
+            "NcReader.move_left()"
+        ]
+    }
+
+    /// Moves the cursor one cell to the right.
+    ///
+    /// It's an error if it can't be moved, or if the reader is
+    /// [masked][NcReader#method.set_mask].
+    ///
+    /// *C style function: [ncreader_move_right()][c_api::ncreader_move_right].*
+    pub fn move_right(&mut self) -> NcResult<()> {
+        unsafe { editor::check_unmasked(self, "NcReader.move_right()")? };
+        error![
+            @call "ncreader_move_right", unsafe { c_api::ncreader_move_right(self) },
+            "NcReader.move_right()"
+        ]
+    }
+
+    /// Moves the cursor one line up.
+    ///
+    /// It's an error if it can't be moved, or if the reader is
+    /// [masked][NcReader#method.set_mask].
+    ///
+    /// *C style function: [ncreader_move_up()][c_api::ncreader_move_up].*
+    pub fn move_up(&mut self) -> NcResult<()> {
+        unsafe { editor::check_unmasked(self, "NcReader.move_up()")? };
+        error![
+            @call "ncreader_move_up", unsafe { c_api::ncreader_move_up(self) },
+            "NcReader.move_up()"
+        ]
+    }
+
+    /// Moves the cursor one line down.
+    ///
+    /// It's an error if it can't be moved, or if the reader is
+    /// [masked][NcReader#method.set_mask].
+    ///
+    /// *C style function: [ncreader_move_down()][c_api::ncreader_move_down].*
+    pub fn move_down(&mut self) -> NcResult<()> {
+        unsafe { editor::check_unmasked(self, "NcReader.move_down()")? };
+        error![
+            @call "ncreader_move_down", unsafe { c_api::ncreader_move_down(self) },
+            "NcReader.move_down()"
+        ]
+    }
+
+    /// Writes an `egc` at the cursor, as if it had been typed.
+    ///
+    /// Unlike [offer_input][NcReader#method.offer_input], it's not checked
+    /// by the filter nor by the maximum length, but it's masked.
+    ///
+    /// *C style function: [ncreader_write_egc()][c_api::ncreader_write_egc].*
+    pub fn write_egc(&mut self, egc: &str) -> NcResult<()> {
+        let mut buf = [0; 4];
+        let egc = match unsafe { editor::get(self) }.and_then(|data| data.mask_for(egc)) {
+            Some(mask) => &*mask.encode_utf8(&mut buf),
+            None => egc,
+        };
+        error![
+            @call "ncreader_write_egc", string::with_cstr(egc, |egc| unsafe { c_api::ncreader_write_egc(self, egc) })?,
+            "NcReader.write_egc()"
+        ]
+    }
+
+    /// Returns a reference to the NcReader's underlying plane.
+    ///
+    /// *C style function: [ncreader_plane()][c_api::ncreader_plane].*
+    pub fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![@call "ncreader_plane", unsafe { c_api::ncreader_plane(self) }, "NcReader.plane()"]
     }
 }
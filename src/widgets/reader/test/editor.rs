@@ -0,0 +1,101 @@
+//! Test the `NcReader` line-editing extensions.
+
+use crate::{NcContext, NcInput, NcKey, NcPlaneHandle, NcReader};
+
+use serial_test::serial;
+
+fn type_str(reader: &mut NcReader, text: &str) -> crate::NcResult<()> {
+    for c in text.chars() {
+        reader.offer_input(NcInput::new(c))?;
+    }
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn history() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 1, 20)?;
+    let reader = NcReader::new(&mut plane)?;
+    assert![reader.history().is_empty()];
+
+    // disabled by default
+    type_str(reader, "ls")?;
+    assert_eq!["ls", reader.submit()?];
+    assert![reader.history().is_empty()];
+
+    reader.set_history_len(2);
+    for line in &["ls", "", "cd", "cd", "pwd"] {
+        type_str(reader, line)?;
+        reader.submit()?;
+    }
+    // the empty and repeated lines are skipped, and the oldest are trimmed
+    assert_eq![&["cd", "pwd"], reader.history()];
+
+    reader.set_history(vec!["a".into(), "b".into(), "c".into()]);
+    assert_eq![&["b", "c"], reader.history()];
+    unsafe { reader.destroy()? };
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn recall() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 1, 20)?;
+    let reader = NcReader::new(&mut plane)?;
+    reader.set_history_len(10);
+    reader.set_history(vec!["first".into(), "second".into()]);
+
+    type_str(reader, "draft")?;
+    assert![reader.offer_input(NcInput::new(NcKey::UP))?];
+    assert_eq!["second", reader.contents()];
+    reader.offer_input(NcInput::new(NcKey::UP))?;
+    assert_eq!["first", reader.contents()];
+    // the oldest entry is kept
+    reader.offer_input(NcInput::new(NcKey::UP))?;
+    assert_eq!["first", reader.contents()];
+    reader.offer_input(NcInput::new(NcKey::DOWN))?;
+    assert_eq!["second", reader.contents()];
+    // going past the newest entry restores the draft
+    reader.offer_input(NcInput::new(NcKey::DOWN))?;
+    assert_eq!["draft", reader.contents()];
+    unsafe { reader.destroy()? };
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn mask() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 1, 20)?;
+    let reader = NcReader::new(&mut plane)?;
+    reader.set_history_len(10);
+    reader.set_mask(Some('*'));
+
+    type_str(reader, "secreT")?;
+    reader.offer_input(NcInput::new(NcKey::BACKSPACE))?;
+    reader.write_egc("t")?;
+    assert_eq!["secret", reader.contents()];
+
+    // moving the cursor would unsync the actual contents
+    assert![reader.move_left().is_err()];
+    assert![reader.move_right().is_err()];
+    assert![!reader.offer_input(NcInput::new(NcKey::LEFT))?];
+    assert_eq!["secret", reader.contents()];
+
+    // the masked contents are not added to the history, nor recalled
+    assert_eq!["secret", reader.submit()?];
+    assert![reader.history().is_empty()];
+    reader.set_history(vec!["old".into()]);
+    assert![!reader.offer_input(NcInput::new(NcKey::UP))?];
+    assert_eq!["", reader.contents()];
+
+    // unmasking keeps the actual contents
+    type_str(reader, "pass")?;
+    reader.set_mask(None);
+    assert_eq!["pass", reader.contents()];
+    reader.move_left()?;
+    unsafe { reader.destroy()? };
+    Ok(())
+}
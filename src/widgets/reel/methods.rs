@@ -0,0 +1,336 @@
+//! `NcReel*` & `NcTablet` methods and associated functions.
+
+use core::{any::Any, ptr::null_mut};
+use std::os::raw::{c_int, c_void};
+
+use super::{NcReel, NcReelOptions, NcTablet, NcTabletCb};
+use crate::{
+    c_api, callback::ffi_guard, error, error_ref_mut, plane::userptr, NcChannels, NcDim, NcError,
+    NcErrorKind, NcInput, NcPlane, NcResult,
+};
+
+/// The Rust data of a tablet, pointed to by its userptr.
+struct TabletData {
+    draw: NcTabletCb,
+    payload: Box<dyn Any>,
+}
+
+/// The Rust data of a reel, stored in the userptr of its plane.
+///
+/// It owns the data of its tablets, which is freed along with it.
+#[derive(Default)]
+struct ReelData {
+    tablets: Vec<*mut TabletData>,
+}
+
+impl Drop for ReelData {
+    fn drop(&mut self) {
+        for tablet in self.tablets.drain(..) {
+            drop(unsafe { Box::from_raw(tablet) });
+        }
+    }
+}
+
+/// Returns the data of the `reel`, if it was created from Rust.
+unsafe fn reel_data<'a>(reel: *mut NcReel) -> Option<&'a mut ReelData> {
+    userptr::get(c_api::ncreel_plane(reel))?
+        .widget_data
+        .as_mut()?
+        .downcast_mut()
+}
+
+/// Returns the data of the `tablet`, if it has a userptr.
+///
+/// The tablet must have been added from Rust, since any other userptr would
+/// be cast to a `TabletData` all the same.
+unsafe fn tablet_data<'a>(tablet: *const NcTablet) -> Option<&'a mut TabletData> {
+    (c_api::nctablet_userptr(tablet as *mut NcTablet) as *mut TabletData).as_mut()
+}
+
+/// Frees the `data` of a tablet deleted from the `reel`.
+unsafe fn free_tablet(reel: *mut NcReel, data: *mut TabletData) {
+    if let Some(reel) = reel_data(reel) {
+        if let Some(i) = reel.tablets.iter().position(|t| *t == data) {
+            drop(Box::from_raw(reel.tablets.swap_remove(i)));
+        }
+    }
+}
+
+/// The C tablet callback that calls the [`NcTabletCb`] closure stored in the
+/// userptr of the tablet.
+unsafe extern "C" fn tabletcb_trampoline(tablet: *mut NcTablet, drawfromtop: bool) -> c_int {
+    let res = ffi_guard(|| {
+        let data = tablet_data(tablet)?;
+        let plane = c_api::nctablet_plane(tablet).as_mut()?;
+        (data.draw)(plane, drawfromtop).ok()
+    });
+    match res {
+        Ok(Some(lines)) => lines.min(c_int::MAX as NcDim) as c_int,
+        _ => 0,
+    }
+}
+
+/// # `NcReelOptions` constructors
+impl NcReelOptions {
+    /// New NcReelOptions for [`NcReel`], with all the borders drawn with the
+    /// default channels, and no flags.
+    pub const fn new() -> Self {
+        Self::with_all_args(0, 0, 0, 0, 0, 0)
+    }
+
+    /// New NcReelOptions for [`NcReel`], with all args.
+    pub const fn with_all_args(
+        // mask of the reel borders *not* to draw
+        bordermask: u32,
+
+        // channels for the reel border
+        borderchan: NcChannels,
+
+        // mask of the tablet borders *not* to draw
+        tabletmask: u32,
+
+        // channels for the unfocused tablet borders
+        tabletchan: NcChannels,
+
+        // channels for the focused tablet border
+        focusedchan: NcChannels,
+
+        // bitfield of `NCREEL_OPTION_*`
+        flags: u64,
+    ) -> Self {
+        Self {
+            bordermask,
+            borderchan,
+            tabletmask,
+            tabletchan,
+            focusedchan,
+            flags,
+        }
+    }
+}
+
+/// # `NcReel` constructors & destructors
+impl NcReel {
+    /// New NcReel, with the default options.
+    ///
+    /// *C style function: [ncreel_create()][c_api::ncreel_create].*
+    pub fn new<'a>(plane: &'a mut NcPlane) -> NcResult<&'a mut Self> {
+        Self::with_options(plane, &NcReelOptions::new())
+    }
+
+    /// New NcReel. Expects an [`NcReelOptions`] struct.
+    ///
+    /// Takes ownership of the `plane`, which will be destroyed by
+    /// [destroy][NcReel#method.destroy]().
+    ///
+    /// *C style function: [ncreel_create()][c_api::ncreel_create].*
+    pub fn with_options<'a>(
+        plane: &'a mut NcPlane,
+        options: &NcReelOptions,
+    ) -> NcResult<&'a mut Self> {
+        let res = unsafe { c_api::ncreel_create(plane, options) };
+        if !res.is_null() {
+            // the reel keeps the data of its tablets
+            userptr::get_or_init(plane).widget_data = Some(Box::new(ReelData::default()));
+        }
+        error_ref_mut![@call "ncreel_create", res, "NcReel::with_options()"]
+    }
+
+    /// Destroys the NcReel, its tablets, and its underlying plane.
+    ///
+    /// # Safety
+    ///
+    /// Neither the reel, its tablets, nor its plane may be used afterwards,
+    /// and it must not be owned by an `OwnedReel`.
+    ///
+    /// *C style function: [ncreel_destroy()][c_api::ncreel_destroy].*
+    pub unsafe fn destroy(&mut self) -> NcResult<()> {
+        userptr::free(c_api::ncreel_plane(self));
+        c_api::ncreel_destroy(self);
+        Ok(())
+    }
+}
+
+/// # `NcReel` methods
+impl NcReel {
+    /// Adds a new tablet, drawn by the `draw` closure, and holding a
+    /// `payload`, that can be accessed with
+    /// [`NcTablet.payload()`][NcTablet#method.payload].
+    ///
+    /// The payload of any tablet added with [`ncreel_add()`][c_api::ncreel_add]
+    /// instead must not be accessed that way.
+    ///
+    /// The tablet is placed after `after`, or before `before`. If both are
+    /// `None`, it's placed after the focused tablet, or it becomes the only
+    /// tablet. If both are given, they must be adjacent.
+    ///
+    /// The closure and the payload are dropped when the tablet is deleted,
+    /// or when the reel is destroyed.
+    ///
+    /// It's an error if the reel wasn't created with
+    /// [with_options][NcReel#method.with_options], which keeps the data of
+    /// the tablets.
+    ///
+    /// *C style function: [ncreel_add()][c_api::ncreel_add].*
+    pub fn add<T, F>(
+        &mut self,
+        after: Option<&mut NcTablet>,
+        before: Option<&mut NcTablet>,
+        payload: T,
+        draw: F,
+    ) -> NcResult<&mut NcTablet>
+    where
+        T: 'static,
+        F: FnMut(&mut NcPlane, bool) -> NcResult<NcDim> + 'static,
+    {
+        let reel = unsafe { reel_data(self) }.ok_or_else(|| {
+            NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "NcReel.add(): the reel wasn't created from Rust",
+            )
+        })?;
+        let data = Box::into_raw(Box::new(TabletData {
+            draw: Box::new(draw),
+            payload: Box::new(payload),
+        }));
+        let after = after.map_or(null_mut(), |t| t as *mut NcTablet);
+        let before = before.map_or(null_mut(), |t| t as *mut NcTablet);
+
+        let res = unsafe {
+            c_api::ncreel_add(
+                self,
+                after,
+                before,
+                Some(tabletcb_trampoline),
+                data as *mut c_void,
+            )
+        };
+        if res.is_null() {
+            drop(unsafe { Box::from_raw(data) });
+        } else {
+            reel.tablets.push(data);
+        }
+        error_ref_mut![@call "ncreel_add", res, "NcReel.add()"]
+    }
+
+    /// Deletes the `tablet`, dropping its closure and its payload.
+    ///
+    /// *C style function: [ncreel_del()][c_api::ncreel_del].*
+    pub fn del(&mut self, tablet: &mut NcTablet) -> NcResult<()> {
+        let data = unsafe { c_api::nctablet_userptr(tablet) } as *mut TabletData;
+        error![
+            @call "ncreel_del", unsafe { c_api::ncreel_del(self, tablet) },
+            "NcReel.del()",
+            unsafe { free_tablet(self, data) }
+        ]
+    }
+
+    /// Returns the focused tablet, if there's any.
+    ///
+    /// *C style function: [ncreel_focused()][c_api::ncreel_focused].*
+    pub fn focused(&mut self) -> Option<&mut NcTablet> {
+        unsafe { c_api::ncreel_focused(self).as_mut() }
+    }
+
+    /// Changes the focus to the next tablet, and returns it, if there's any.
+    ///
+    /// *C style function: [ncreel_next()][c_api::ncreel_next].*
+    pub fn next(&mut self) -> Option<&mut NcTablet> {
+        unsafe { c_api::ncreel_next(self).as_mut() }
+    }
+
+    /// Changes the focus to the previous tablet, and returns it,
+    /// if there's any.
+    ///
+    /// *C style function: [ncreel_prev()][c_api::ncreel_prev].*
+    pub fn prev(&mut self) -> Option<&mut NcTablet> {
+        unsafe { c_api::ncreel_prev(self).as_mut() }
+    }
+
+    /// Offers the input to the NcReel.
+    ///
+    /// Returns `true` if it was relevant. The up and down arrows, and the
+    /// scroll wheel, move the focus.
+    ///
+    /// *C style function: [ncreel_offer_input()][c_api::ncreel_offer_input].*
+    pub fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        Ok(unsafe { c_api::ncreel_offer_input(self, &input) })
+    }
+
+    /// Returns a reference to the NcReel's underlying plane.
+    ///
+    /// *C style function: [ncreel_plane()][c_api::ncreel_plane].*
+    pub fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![@call "ncreel_plane", unsafe { c_api::ncreel_plane(self) }, "NcReel.plane()"]
+    }
+
+    /// Redraws the NcReel, calling the closures of the visible tablets.
+    ///
+    /// *C style function: [ncreel_redraw()][c_api::ncreel_redraw].*
+    pub fn redraw(&mut self) -> NcResult<()> {
+        error![@call "ncreel_redraw", unsafe { c_api::ncreel_redraw(self) }, "NcReel.redraw()"]
+    }
+
+    /// Returns the number of tablets.
+    ///
+    /// *C style function: [ncreel_tabletcount()][c_api::ncreel_tabletcount].*
+    pub fn tabletcount(&self) -> u32 {
+        unsafe { c_api::ncreel_tabletcount(self) as u32 }
+    }
+}
+
+/// # `NcTablet` methods
+impl NcTablet {
+    /// Returns a reference to the NcTablet's plane.
+    ///
+    /// It's an error if the tablet is not visible, in which case it has
+    /// no plane.
+    ///
+    /// *C style function: [nctablet_plane()][c_api::nctablet_plane].*
+    pub fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![@call "nctablet_plane", unsafe { c_api::nctablet_plane(self) }, "NcTablet.plane()"]
+    }
+
+    /// Returns a reference to the payload of the tablet,
+    /// if it's of type `T`.
+    ///
+    /// # Safety
+    ///
+    /// The tablet must have been added with [`NcReel.add()`][NcReel#method.add],
+    /// since the userptr of any other tablet is not checked before being
+    /// read as its data.
+    ///
+    /// *C style function: [nctablet_userptr()][c_api::nctablet_userptr].*
+    pub unsafe fn payload<T: 'static>(&self) -> Option<&T> {
+        tablet_data(self)?.payload.downcast_ref()
+    }
+
+    /// Returns a mutable reference to the payload of the tablet,
+    /// if it's of type `T`.
+    ///
+    /// # Safety
+    ///
+    /// The tablet must have been added with [`NcReel.add()`][NcReel#method.add].
+    ///
+    /// *C style function: [nctablet_userptr()][c_api::nctablet_userptr].*
+    pub unsafe fn payload_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        tablet_data(self)?.payload.downcast_mut()
+    }
+
+    /// Replaces the closure that draws the tablet.
+    ///
+    /// # Safety
+    ///
+    /// The tablet must have been added with [`NcReel.add()`][NcReel#method.add].
+    ///
+    /// *(No equivalent C style function)*
+    pub unsafe fn set_draw<F>(&mut self, draw: F) -> NcResult<()>
+    where
+        F: FnMut(&mut NcPlane, bool) -> NcResult<NcDim> + 'static,
+    {
+        let data = tablet_data(self)
+            .ok_or_else(|| NcError::with_kind(NcErrorKind::NullPointer, "NcTablet.set_draw()"))?;
+        data.draw = Box::new(draw);
+        Ok(())
+    }
+}
@@ -1,5 +1,31 @@
 //! `NcReel` widget.
 
+// functions already exported by bindgen : 13
+// ------------------------------------------
+// (#) test: 0
+// (W) wrap: 13 / 0
+// ------------------------------------------
+//W ncreel_add,
+//W ncreel_create,
+//W ncreel_del,
+//W ncreel_destroy,
+//W ncreel_focused,
+//W ncreel_next,
+//W ncreel_offer_input,
+//W ncreel_plane,
+//W ncreel_prev,
+//W ncreel_redraw,
+//W ncreel_tabletcount,
+//W nctablet_plane,
+//W nctablet_userptr,
+
+use crate::{NcDim, NcPlane, NcResult};
+
+mod methods;
+
+#[cfg(test)]
+mod test;
+
 /// A wheel with [`NcTablet`]s on the outside.
 ///
 /// An `NcReel` is projected onto the 2d rendering area, showing some portion of
@@ -21,6 +47,17 @@ pub type NcReelOptions = crate::bindings::ffi::ncreel_options;
 /// Visual tablet for [`NcReel`]
 pub type NcTablet = crate::bindings::ffi::nctablet;
 
+/// A callback closure that draws an [`NcTablet`] on its plane.
+///
+/// It's called with the plane of the tablet, and whether it's to be drawn
+/// from the top, and returns the number of lines it used.
+///
+/// It can capture state, and it's stored along with the tablet by
+/// [`NcReel.add()`][NcReel#method.add].
+///
+/// Returning an error, or panicking, leaves the tablet empty.
+pub type NcTabletCb = Box<dyn FnMut(&mut NcPlane, bool) -> NcResult<NcDim>>;
+
 /// is navigation circular (does moving down from the last tablet move to the
 /// first, and vice versa)? only meaningful when infinitescroll is true. if
 /// infinitescroll is false, this must be false.
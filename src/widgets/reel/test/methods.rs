@@ -0,0 +1,89 @@
+//! Test `NcReel` & `NcTablet` methods.
+
+use std::{cell::Cell, rc::Rc};
+
+use crate::{NcContext, NcPlaneHandle, NcReel, NcTablet};
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn tablets() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 20, 20)?;
+    let reel = NcReel::new(&mut plane)?;
+    assert_eq![0, reel.tabletcount()];
+    assert![reel.focused().is_none()];
+
+    let draws = Rc::new(Cell::new(0));
+    for i in 0..3_u32 {
+        let draws = draws.clone();
+        reel.add(None, None, i, move |_plane, _drawfromtop| {
+            draws.set(draws.get() + 1);
+            Ok(1)
+        })?;
+    }
+    assert_eq![3, reel.tabletcount()];
+
+    // the first tablet gets the focus
+    let focused = reel.focused().expect("focused tablet");
+    assert_eq![Some(&0), unsafe { focused.payload::<u32>() }];
+    // a payload of another type is not returned
+    assert_eq![None, unsafe { focused.payload::<i64>() }];
+    *unsafe { focused.payload_mut::<u32>() }.expect("payload") = 10;
+
+    let next = reel.next().expect("next tablet");
+    assert_ne![Some(&10), unsafe { next.payload::<u32>() }];
+    let prev = reel.prev().expect("previous tablet");
+    assert_eq![Some(&10), unsafe { prev.payload::<u32>() }];
+
+    // the visible tablets are drawn by their closures
+    draws.set(0);
+    reel.redraw()?;
+    assert![draws.get() > 0];
+
+    let focused = reel.focused().expect("focused tablet");
+    let drawn = Rc::new(Cell::new(false));
+    let drawn2 = drawn.clone();
+    unsafe {
+        focused.set_draw(move |_plane, _drawfromtop| {
+            drawn2.set(true);
+            Ok(2)
+        })?;
+    }
+    reel.redraw()?;
+    assert![drawn.get()];
+
+    unsafe { reel.destroy()? };
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn tablets_drop() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 20, 20)?;
+    let reel = NcReel::new(&mut plane)?;
+
+    let payload = Rc::new(());
+    let draw_payload = payload.clone();
+    // the tablet borrows the reel, so it's passed back to it as a pointer
+    let tablet: *mut NcTablet =
+        reel.add(None, None, payload.clone(), move |_plane, _drawfromtop| {
+            let _ = &draw_payload;
+            Ok(1)
+        })?;
+    assert_eq![3, Rc::strong_count(&payload)];
+
+    // deleting a tablet drops its payload and its closure
+    reel.del(unsafe { &mut *tablet })?;
+    assert_eq![0, reel.tabletcount()];
+    assert_eq![1, Rc::strong_count(&payload)];
+
+    // destroying the reel drops the payloads of its tablets
+    reel.add(None, None, payload.clone(), |_plane, _drawfromtop| Ok(1))?;
+    assert_eq![2, Rc::strong_count(&payload)];
+    unsafe { reel.destroy()? };
+    assert_eq![1, Rc::strong_count(&payload)];
+    Ok(())
+}
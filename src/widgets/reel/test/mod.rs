@@ -0,0 +1,4 @@
+//! `NcReel` tests.
+
+#[cfg(test)]
+mod methods;
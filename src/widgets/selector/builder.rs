@@ -0,0 +1,236 @@
+//! `NcSelectorBuilder`
+
+use core::ptr::null;
+use std::{ffi::CString, fmt};
+
+use super::{typed::c_item, NcSelector, NcSelectorItem, NcSelectorOptions, NcSelectorTyped};
+use crate::{string, NcChannels, NcError, NcErrorKind, NcPlaneHandle, NcResult};
+
+/// A builder for [`NcSelectorTyped`].
+///
+/// It owns the strings of the title, the items, etc., which are converted to
+/// C strings when the selector is [built][NcSelectorBuilder#method.build],
+/// and the values of the items, which are moved to the selector.
+///
+/// # Example
+///
+/// ```ignore
+/// let selector = NcSelectorTyped::builder()
+///     .title("pick a level")
+///     .item("easy", "for beginners", Level::Easy)
+///     .item("hard", "for experts", Level::Hard)
+///     .default_index(1)
+///     .build(plane)?;
+/// ```
+pub struct NcSelectorBuilder<T> {
+    title: Option<String>,
+    secondary: Option<String>,
+    footer: Option<String>,
+    items: Vec<(String, String, T)>,
+    default_index: u32,
+    max_display: u32,
+    option_channels: NcChannels,
+    desc_channels: NcChannels,
+    title_channels: NcChannels,
+    footer_channels: NcChannels,
+    box_channels: NcChannels,
+    flags: u64,
+}
+
+impl<T> Default for NcSelectorBuilder<T> {
+    fn default() -> Self {
+        Self {
+            title: None,
+            secondary: None,
+            footer: None,
+            items: vec![],
+            default_index: 0,
+            max_display: 0,
+            option_channels: 0,
+            desc_channels: 0,
+            title_channels: 0,
+            footer_channels: 0,
+            box_channels: 0,
+            flags: 0,
+        }
+    }
+}
+
+impl<T> fmt::Debug for NcSelectorBuilder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let items: Vec<_> = self.items.iter().map(|(o, d, _)| (o, d)).collect();
+        f.debug_struct("NcSelectorBuilder")
+            .field("title", &self.title)
+            .field("secondary", &self.secondary)
+            .field("footer", &self.footer)
+            .field("items", &items)
+            .field("default_index", &self.default_index)
+            .field("max_display", &self.max_display)
+            .field("flags", &self.flags)
+            .finish()
+    }
+}
+
+/// # `NcSelectorBuilder` constructors
+impl<T> NcSelectorBuilder<T> {
+    /// New NcSelectorBuilder, without items.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// # `NcSelectorBuilder` methods
+impl<T> NcSelectorBuilder<T> {
+    /// Sets the title. Without a title the riser is inhibited, saving two rows.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the secondary title.
+    pub fn secondary(mut self, secondary: &str) -> Self {
+        self.secondary = Some(secondary.into());
+        self
+    }
+
+    /// Sets the footer.
+    pub fn footer(mut self, footer: &str) -> Self {
+        self.footer = Some(footer.into());
+        self
+    }
+
+    /// Adds an item, with its unique `option`, its `desc`ription,
+    /// and the `value` returned when it's selected.
+    pub fn item(mut self, option: &str, desc: &str, value: T) -> Self {
+        self.items.push((option.into(), desc.into(), value));
+        self
+    }
+
+    /// Sets the index of the item selected initially.
+    pub fn default_index(mut self, index: u32) -> Self {
+        self.default_index = index;
+        self
+    }
+
+    /// Sets the maximum number of items to display at once,
+    /// 0 to use all the available space.
+    pub fn max_display(mut self, max_display: u32) -> Self {
+        self.max_display = max_display;
+        self
+    }
+
+    /// Sets the styling for the options.
+    pub fn option_channels(mut self, channels: NcChannels) -> Self {
+        self.option_channels = channels;
+        self
+    }
+
+    /// Sets the styling for the descriptions.
+    pub fn desc_channels(mut self, channels: NcChannels) -> Self {
+        self.desc_channels = channels;
+        self
+    }
+
+    /// Sets the styling for the title.
+    pub fn title_channels(mut self, channels: NcChannels) -> Self {
+        self.title_channels = channels;
+        self
+    }
+
+    /// Sets the styling for the footer.
+    pub fn footer_channels(mut self, channels: NcChannels) -> Self {
+        self.footer_channels = channels;
+        self
+    }
+
+    /// Sets the styling for the box.
+    pub fn box_channels(mut self, channels: NcChannels) -> Self {
+        self.box_channels = channels;
+        self
+    }
+
+    /// Sets the flags.
+    pub fn flags(mut self, flags: u64) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Creates the [`NcSelectorTyped`], taking ownership of the `plane`.
+    ///
+    /// Returns an error if two items have the same option, if any string
+    /// contains a nul byte, or if the selector couldn't be created, in which
+    /// case the `plane` is destroyed.
+    ///
+    /// *C style function: [ncselector_create()][crate::c_api::ncselector_create].*
+    pub fn build<'nc>(self, mut plane: NcPlaneHandle<'nc>) -> NcResult<NcSelectorTyped<'nc, T>> {
+        let strings = match self.c_strings() {
+            Ok(strings) => strings,
+            Err(e) => {
+                let _ = unsafe { plane.destroy() };
+                return Err(e);
+            }
+        };
+
+        let mut items: Vec<NcSelectorItem> = strings
+            .items
+            .iter()
+            .map(|(option, desc)| c_item(option, desc))
+            .collect();
+        items.push(NcSelectorItem::new_empty());
+
+        let options = NcSelectorOptions {
+            title: strings.title.as_ref().map_or(null(), |s| s.as_ptr()),
+            secondary: strings.secondary.as_ref().map_or(null(), |s| s.as_ptr()),
+            footer: strings.footer.as_ref().map_or(null(), |s| s.as_ptr()),
+            items: items.as_ptr(),
+            defidx: self.default_index,
+            maxdisplay: self.max_display,
+            opchannels: self.option_channels,
+            descchannels: self.desc_channels,
+            titlechannels: self.title_channels,
+            footchannels: self.footer_channels,
+            boxchannels: self.box_channels,
+            flags: self.flags,
+        };
+        // the C library destroys the plane on error
+        let selector: *mut NcSelector = NcSelector::new(&mut plane, options)?;
+        let values = self
+            .items
+            .into_iter()
+            .map(|(option, _, value)| (option, value))
+            .collect();
+        Ok(unsafe { NcSelectorTyped::from_parts(selector, values) })
+    }
+
+    /// Returns the C strings of the title, the secondary title, the footer,
+    /// and the items, or an error if two items have the same option,
+    /// or if any string contains a nul byte.
+    fn c_strings(&self) -> NcResult<CStrings> {
+        for (i, (option, _, _)) in self.items.iter().enumerate() {
+            if self.items[..i].iter().any(|(o, _, _)| o == option) {
+                return Err(NcError::with_kind(
+                    NcErrorKind::InvalidArgument,
+                    &format!["NcSelectorBuilder.build(): repeated option {:?}", option],
+                ));
+            }
+        }
+        Ok(CStrings {
+            title: self.title.as_deref().map(string::cstring).transpose()?,
+            secondary: self.secondary.as_deref().map(string::cstring).transpose()?,
+            footer: self.footer.as_deref().map(string::cstring).transpose()?,
+            items: self
+                .items
+                .iter()
+                .map(|(option, desc, _)| Ok((string::cstring(option)?, string::cstring(desc)?)))
+                .collect::<NcResult<_>>()?,
+        })
+    }
+}
+
+/// The C strings of a selector, which the C library copies.
+struct CStrings {
+    title: Option<CString>,
+    secondary: Option<CString>,
+    footer: Option<CString>,
+    items: Vec<(CString, CString)>,
+}
@@ -16,17 +16,27 @@
 //! body section supports infinite scrolling up and down.
 //!
 //! At all times, exactly one item is selected.
+//!
+//! An [`NcSelectorTyped`] maps each item to a Rust value, and owns its strings.
 
 use core::ptr::null_mut;
 
+mod builder;
+mod typed;
+
+#[cfg(test)]
+mod test;
+
+pub use builder::NcSelectorBuilder;
+pub use typed::NcSelectorTyped;
+
 use crate::{
     c_api::{
-        ncselector_additem, ncselector_create, ncselector_delitem, ncselector_destroy,
+        libc, ncselector_additem, ncselector_create, ncselector_delitem, ncselector_destroy,
         ncselector_nextitem, ncselector_offer_input, ncselector_plane, ncselector_previtem,
         ncselector_selected,
     },
-    cstring, cstring_mut, error, error_ref_mut, error_str, NcChannels, NcChannelsApi, NcInput,
-    NcPlane, NcResult,
+    error, error_ref_mut, error_str, string, NcChannels, NcChannelsApi, NcInput, NcPlane, NcResult,
 };
 
 /// High-level widget for selecting one item from a set
@@ -42,19 +52,24 @@ pub type NcSelectorOptions = crate::bindings::ffi::ncselector_options;
 impl NcSelector {
     pub fn new<'a>(plane: &mut NcPlane, options: NcSelectorOptions) -> NcResult<&'a mut Self> {
         error_ref_mut![
-            unsafe { ncselector_create(plane, &options) },
+            @call "ncselector_create", unsafe { ncselector_create(plane, &options) },
             "Creating NcSelector"
         ]
     }
 
-    pub fn offer_input(&mut self, input: NcInput) -> bool {
-        unsafe { ncselector_offer_input(self, &input) }
+    pub fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        Ok(unsafe { ncselector_offer_input(self, &input) })
     }
 
     /// Destroy the ncselector. If 'item' is not NULL, the last selected option will
     /// be strdup()ed and assigned to '*item' (and must be free()d by the caller).
-    pub fn destroy(&mut self) -> NcResult<()> {
-        unsafe { ncselector_destroy(self, null_mut()) };
+    ///
+    /// # Safety
+    ///
+    /// Neither the selector nor its plane may be used afterwards, and it must
+    /// not be owned by an `OwnedSelector`.
+    pub unsafe fn destroy(&mut self) -> NcResult<()> {
+        ncselector_destroy(self, null_mut());
         Ok(())
     }
 
@@ -62,7 +77,7 @@ impl NcSelector {
     /// list of items via ncselector_options->items.
     pub fn additem(&mut self, item: NcSelectorItem) -> NcResult<i32> {
         error![
-            unsafe { ncselector_additem(self, &item) },
+            @call "ncselector_additem", unsafe { ncselector_additem(self, &item) },
             "Calling selector.additem", -1
         ]
     }
@@ -71,7 +86,7 @@ impl NcSelector {
     // TODO API int ncselector_delitem(struct ncselector* n, const char* item);
     pub fn delitem(&mut self, item: &str) -> NcResult<i32> {
         error![
-            unsafe { ncselector_delitem(self, cstring![item]) },
+            @call "ncselector_delitem", string::with_cstr(item, |item| unsafe { ncselector_delitem(self, item) })?,
             "Calling selector.delitem", -1
         ]
     }
@@ -79,36 +94,44 @@ impl NcSelector {
     /// Return reference to the selected option, or NULL if there are no items.
     pub fn selected(&mut self) -> NcResult<String> {
         error_str![
-            unsafe { ncselector_selected(self) },
+            @call "ncselector_selected", unsafe { ncselector_selected(self) },
             "Calling selector.selected"
         ]
     }
 
     /// Return a reference to the ncselector's underlying ncplane.
-    pub fn plane<'a>(&mut self) -> NcResult<&'a mut NcPlane> {
-        error_ref_mut![unsafe { ncselector_plane(self) }, "Calling selector.plane"]
+    pub fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![@call "ncselector_plane", unsafe { ncselector_plane(self) }, "Calling selector.plane"]
     }
 
     /// Move down in the list. A reference to the newly-selected item is
     /// returned, or NULL if there are no items in the list.
     pub fn nextitem(&mut self) -> NcResult<String> {
-        let cstr: *const i8 = unsafe { ncselector_nextitem(self) };
-        error_str![cstr, "Calling selector.nextitem"]
+        error_str![
+            @call "ncselector_nextitem", unsafe { ncselector_nextitem(self) },
+            "Calling selector.nextitem"
+        ]
     }
 
     /// Move up in the list. A reference to the newly-selected item is
     /// returned, or NULL if there are no items in the list.
     pub fn previtem(&mut self) -> NcResult<String> {
-        let cstr: *const i8 = unsafe { ncselector_previtem(self) };
-        error_str![cstr, "Calling selector.previtem"]
+        error_str![
+            @call "ncselector_previtem", unsafe { ncselector_previtem(self) },
+            "Calling selector.previtem"
+        ]
     }
 }
 
 impl NcSelectorItem {
+    /// New NcSelectorItem for [`NcSelector`].
+    ///
+    /// The `option` and `desc` are truncated at their first nul byte,
+    /// if there's any.
     pub fn new(option: &str, desc: &str) -> Self {
         Self {
-            option: cstring_mut![option],
-            desc: cstring_mut![desc],
+            option: string::cstring_dup(option),
+            desc: string::cstring_dup(desc),
             opcolumns: 0,
             desccolumns: 0,
         }
@@ -123,6 +146,20 @@ impl NcSelectorItem {
             desccolumns: 0,
         }
     }
+    /// Frees the strings allocated by [`new`][NcSelectorItem#method.new].
+    ///
+    /// The selector copies its strings, so they can be freed once it's
+    /// created.
+    ///
+    /// # Safety
+    /// The strings must have been allocated by `new`, and not be used
+    /// afterwards.
+    pub unsafe fn free(&mut self) {
+        libc::free(self.option as *mut libc::c_void);
+        libc::free(self.desc as *mut libc::c_void);
+        self.option = null_mut();
+        self.desc = null_mut();
+    }
 }
 
 /// # `NcMenuOptions` constructors
@@ -130,6 +167,8 @@ impl NcSelectorOptions {
     /// New NcMenuOptions for [`crate::widgets::NcMenu`].
     ///
     /// `sections` must contain at least 1 [`NcMenuSection`][crate::widgets::NcMenuSection].
+    ///
+    /// The strings are truncated at their first nul byte, if there's any.
     pub fn new(
         title: &str,
         secondary: &str,
@@ -138,9 +177,9 @@ impl NcSelectorOptions {
     ) -> Self {
         //assert![!selector_item.is_empty()];
         Self {
-            title: cstring_mut![title], // title may be null, inhibiting riser, saving two rows.
-            secondary: cstring_mut![secondary], // secondary may be null
-            footer: cstring_mut![footer], // footer may be null
+            title: string::cstring_dup(title), // title may be null, inhibiting riser, saving two rows.
+            secondary: string::cstring_dup(secondary), // secondary may be null
+            footer: string::cstring_dup(footer), // footer may be null
 
             items: selector_item.as_mut_ptr(), // initial items and descriptions
             defidx: 1,
@@ -154,4 +193,21 @@ impl NcSelectorOptions {
             flags: 0x0,
         }
     }
+    /// Frees the strings allocated by [`new`][NcSelectorOptions#method.new],
+    /// but not its items.
+    ///
+    /// The selector copies its strings, so they can be freed once it's
+    /// created.
+    ///
+    /// # Safety
+    /// The strings must have been allocated by `new`, and not be used
+    /// afterwards.
+    pub unsafe fn free(&mut self) {
+        libc::free(self.title as *mut libc::c_void);
+        libc::free(self.secondary as *mut libc::c_void);
+        libc::free(self.footer as *mut libc::c_void);
+        self.title = null_mut();
+        self.secondary = null_mut();
+        self.footer = null_mut();
+    }
 }
@@ -0,0 +1,4 @@
+//! `NcSelector` tests.
+
+#[cfg(test)]
+mod typed;
@@ -0,0 +1,90 @@
+//! Test `NcSelectorTyped` methods.
+
+use std::rc::Rc;
+
+use crate::{NcContext, NcErrorKind, NcInput, NcKey, NcPlaneHandle, NcSelectorTyped};
+
+use serial_test::serial;
+
+#[derive(Debug, PartialEq)]
+enum Fruit {
+    Apple,
+    Banana,
+    Cherry,
+}
+
+#[test]
+#[serial]
+fn selected() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let plane = NcPlaneHandle::new(&nc, 0, 0, 15, 40)?;
+    let mut selector = NcSelectorTyped::builder()
+        .title("fruits")
+        .item("apple", "red", Fruit::Apple)
+        .item("banana", "yellow", Fruit::Banana)
+        .item("cherry", "dark red", Fruit::Cherry)
+        .default_index(1)
+        .build(plane)?;
+    assert_eq![3, selector.len()];
+    assert_eq![Some(&Fruit::Banana), selector.selected()];
+    assert_eq![Some("banana"), selector.selected_option()];
+
+    assert_eq![Some(&Fruit::Cherry), selector.next()];
+    assert_eq![Some(&Fruit::Cherry), selector.selected()];
+    assert_eq![Some(&Fruit::Banana), selector.prev()];
+    assert![selector.offer_input(NcInput::new(NcKey::UP))?];
+    assert_eq![Some(&Fruit::Apple), selector.selected()];
+
+    assert_eq![Some(&Fruit::Cherry), selector.get("cherry")];
+    assert_eq![None, selector.get("durian")];
+    assert_eq![
+        vec![
+            ("apple", &Fruit::Apple),
+            ("banana", &Fruit::Banana),
+            ("cherry", &Fruit::Cherry)
+        ],
+        selector.iter().collect::<Vec<_>>()
+    ];
+
+    // destroying the selector returns the value of the selected item
+    assert_eq![Some(Fruit::Apple), selector.destroy()];
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn add_remove() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let plane = NcPlaneHandle::new(&nc, 0, 0, 15, 40)?;
+    let value = Rc::new(());
+    let mut selector = NcSelectorTyped::builder().build(plane)?;
+    assert![selector.is_empty()];
+    assert_eq![None, selector.selected()];
+
+    selector.add("first", "", value.clone())?;
+    selector.add("second", "", value.clone())?;
+    let error = selector.add("first", "", value.clone()).unwrap_err();
+    assert_eq![NcErrorKind::InvalidArgument, error.kind()];
+    assert_eq![3, Rc::strong_count(&value)];
+    assert_eq![Some("first"), selector.selected_option()];
+
+    // replacing a value
+    *selector.get_mut("second").expect("value") = Rc::new(());
+    assert_eq![2, Rc::strong_count(&value)];
+
+    // removing an item returns its value
+    let removed = selector.remove("first")?;
+    assert![Rc::ptr_eq(&value, &removed)];
+    drop(removed);
+    assert_eq![1, Rc::strong_count(&value)];
+    let error = selector.remove("first").unwrap_err();
+    assert_eq![NcErrorKind::InvalidArgument, error.kind()];
+    assert_eq![Some("second"), selector.selected_option()];
+
+    // the values are dropped along with the selector
+    selector.add("third", "", value.clone())?;
+    assert_eq![2, Rc::strong_count(&value)];
+    drop(selector);
+    assert_eq![1, Rc::strong_count(&value)];
+    Ok(())
+}
@@ -0,0 +1,310 @@
+//! `NcSelectorTyped<T>`
+
+use core::{fmt, marker::PhantomData, ptr::null_mut};
+use std::{ffi::CString, os::raw::c_char};
+
+use super::{NcSelector, NcSelectorBuilder, NcSelectorItem};
+use crate::widgets::NcWidget;
+use crate::{c_api, error_ref_mut, string, Nc, NcError, NcErrorKind, NcInput, NcPlane, NcResult};
+
+/// An [`NcSelector`] whose items are mapped to values of type `T`.
+///
+/// Each item has an option, which must be unique, a description, and a
+/// value, which is returned when the item is selected. The strings and the
+/// values are owned by the selector, so items can be added and removed at
+/// any time.
+///
+/// It's created with a [builder][NcSelectorTyped#method.builder], taking
+/// ownership of a plane, and it can't outlive the [`Nc`] context the plane
+/// belongs to. It's destroyed along with its plane when dropped, or with
+/// [destroy][NcSelectorTyped#method.destroy], which returns the selected value.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut selector = NcSelectorTyped::builder()
+///     .title("pick a color")
+///     .item("red", "warm", NcRgb(0xff0000))
+///     .item("blue", "cold", NcRgb(0x0000ff))
+///     .build(plane)?;
+///
+/// selector.add("green", "natural", NcRgb(0x00ff00))?;
+/// if let Some(rgb) = selector.selected() {
+///     // …
+/// }
+/// ```
+pub struct NcSelectorTyped<'nc, T> {
+    /// The C selector, null once it's destroyed.
+    selector: *mut NcSelector,
+    items: Vec<SelectorItem<T>>,
+    _nc: PhantomData<&'nc Nc>,
+}
+
+struct SelectorItem<T> {
+    option: String,
+    value: T,
+}
+
+impl<'nc, T> fmt::Debug for NcSelectorTyped<'nc, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let options: Vec<_> = self.items.iter().map(|i| &i.option).collect();
+        f.debug_struct("NcSelectorTyped")
+            .field("selector", &self.selector)
+            .field("options", &options)
+            .finish()
+    }
+}
+
+impl<'nc, T> Drop for NcSelectorTyped<'nc, T> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+/// # `NcSelectorTyped` constructors & destructors
+impl<'nc, T> NcSelectorTyped<'nc, T> {
+    /// Returns a new [`NcSelectorBuilder`].
+    ///
+    /// *(No equivalent C style function)*
+    pub fn builder() -> NcSelectorBuilder<T> {
+        NcSelectorBuilder::new()
+    }
+
+    /// Returns a new NcSelectorTyped, from an already created `selector`
+    /// and the options and values of its items, in order.
+    ///
+    /// # Safety
+    /// `selector` must be valid, owned by the new NcSelectorTyped, and
+    /// belong to a context that outlives `'nc`.
+    pub(crate) unsafe fn from_parts(selector: *mut NcSelector, items: Vec<(String, T)>) -> Self {
+        Self {
+            selector,
+            items: items
+                .into_iter()
+                .map(|(option, value)| SelectorItem { option, value })
+                .collect(),
+            _nc: PhantomData,
+        }
+    }
+
+    /// Destroys the NcSelectorTyped and its underlying plane, and returns the
+    /// value of the item that was selected, if there was any.
+    ///
+    /// *C style function: [ncselector_destroy()][c_api::ncselector_destroy].*
+    pub fn destroy(mut self) -> Option<T> {
+        let index = self.selected_index();
+        self.release();
+        index.map(|i| self.items.swap_remove(i).value)
+    }
+
+    /// Destroys the C selector, if it's not destroyed yet.
+    fn release(&mut self) {
+        if !self.selector.is_null() {
+            unsafe { c_api::ncselector_destroy(self.selector, null_mut()) };
+            self.selector = null_mut();
+        }
+    }
+
+    /// Returns a raw pointer to the C selector.
+    pub fn as_ptr(&self) -> *mut NcSelector {
+        self.selector
+    }
+}
+
+/// # `NcSelectorTyped` methods
+impl<'nc, T> NcSelectorTyped<'nc, T> {
+    /// Adds a new item, at the end.
+    ///
+    /// It's an error if there's already an item with the same `option`,
+    /// or if `option` or `desc` contain a nul byte.
+    ///
+    /// *C style function: [ncselector_additem()][c_api::ncselector_additem].*
+    pub fn add(&mut self, option: &str, desc: &str, value: T) -> NcResult<()> {
+        if self.index_of(option).is_some() {
+            return Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                &format![
+                    "NcSelectorTyped.add(): the option {:?} already exists",
+                    option
+                ],
+            ));
+        }
+        let (c_option, c_desc) = (string::cstring(option)?, string::cstring(desc)?);
+        let item = c_item(&c_option, &c_desc);
+        let res = unsafe { c_api::ncselector_additem(self.selector, &item) };
+        if res < c_api::NCRESULT_OK {
+            return Err(
+                NcError::with_msg(res, "NcSelectorTyped.add()").calling("ncselector_additem")
+            );
+        }
+        self.items.push(SelectorItem {
+            option: option.into(),
+            value,
+        });
+        Ok(())
+    }
+
+    /// Removes the item with `option`, and returns its value.
+    ///
+    /// It's an error if there's no item with that `option`.
+    ///
+    /// *C style function: [ncselector_delitem()][c_api::ncselector_delitem].*
+    pub fn remove(&mut self, option: &str) -> NcResult<T> {
+        let index = self.index_of(option).ok_or_else(|| {
+            NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                &format![
+                    "NcSelectorTyped.remove(): the option {:?} doesn't exist",
+                    option
+                ],
+            )
+        })?;
+        let res = string::with_cstr(option, |o| unsafe {
+            c_api::ncselector_delitem(self.selector, o)
+        })?;
+        if res < c_api::NCRESULT_OK {
+            return Err(
+                NcError::with_msg(res, "NcSelectorTyped.remove()").calling("ncselector_delitem")
+            );
+        }
+        Ok(self.items.remove(index).value)
+    }
+
+    /// Returns the value of the selected item, or `None` if there are no items.
+    ///
+    /// *C style function: [ncselector_selected()][c_api::ncselector_selected].*
+    pub fn selected(&mut self) -> Option<&T> {
+        let index = self.selected_index()?;
+        Some(&self.items[index].value)
+    }
+
+    /// Returns the option of the selected item, or `None` if there are no items.
+    ///
+    /// *C style function: [ncselector_selected()][c_api::ncselector_selected].*
+    pub fn selected_option(&mut self) -> Option<&str> {
+        let index = self.selected_index()?;
+        Some(&self.items[index].option)
+    }
+
+    /// Selects the next item, and returns its value,
+    /// or `None` if there are no items.
+    ///
+    /// *C style function: [ncselector_nextitem()][c_api::ncselector_nextitem].*
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&T> {
+        let option = unsafe { c_api::ncselector_nextitem(self.selector) };
+        self.value_of(option)
+    }
+
+    /// Selects the previous item, and returns its value,
+    /// or `None` if there are no items.
+    ///
+    /// *C style function: [ncselector_previtem()][c_api::ncselector_previtem].*
+    pub fn prev(&mut self) -> Option<&T> {
+        let option = unsafe { c_api::ncselector_previtem(self.selector) };
+        self.value_of(option)
+    }
+
+    /// Returns the value of the item with `option`, if there's any.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn get(&self, option: &str) -> Option<&T> {
+        self.index_of(option).map(|i| &self.items[i].value)
+    }
+
+    /// Returns a mutable reference to the value of the item with `option`,
+    /// if there's any.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn get_mut(&mut self, option: &str) -> Option<&mut T> {
+        let index = self.index_of(option)?;
+        Some(&mut self.items[index].value)
+    }
+
+    /// Returns an iterator over the options and values of the items, in order.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.items.iter().map(|i| (i.option.as_str(), &i.value))
+    }
+
+    /// Returns the number of items.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if there are no items.
+    ///
+    /// *(No equivalent C style function)*
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Offers the `input` to this NcSelectorTyped.
+    ///
+    /// If it's relevant, this function returns true,
+    /// and the input ought not be processed further.
+    ///
+    /// *C style function: [ncselector_offer_input()][c_api::ncselector_offer_input].*
+    pub fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        Ok(unsafe { c_api::ncselector_offer_input(self.selector, &input) })
+    }
+
+    /// Returns a reference to the underlying plane.
+    ///
+    /// *C style function: [ncselector_plane()][c_api::ncselector_plane].*
+    pub fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![
+            @call "ncselector_plane", unsafe { c_api::ncselector_plane(self.selector) },
+            "NcSelectorTyped.plane()"
+        ]
+    }
+
+    fn index_of(&self, option: &str) -> Option<usize> {
+        self.items.iter().position(|i| i.option == option)
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        // it's a copy of the option, allocated by the C library
+        let option = unsafe { c_api::ncselector_selected(self.selector) };
+        let option = unsafe { string::rstring_free(option) }.ok()?;
+        self.index_of(&option)
+    }
+
+    fn value_of(&self, option: *const c_char) -> Option<&T> {
+        let option = unsafe { string::rstr(option) }.ok()?;
+        self.get(option)
+    }
+}
+
+impl<'nc, T> NcWidget for NcSelectorTyped<'nc, T> {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        NcSelectorTyped::plane(self)
+    }
+
+    fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        NcSelectorTyped::offer_input(self, input)
+    }
+
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+
+    unsafe fn destroy(&mut self) -> NcResult<()> {
+        self.release();
+        self.items.clear();
+        Ok(())
+    }
+}
+
+/// Returns a C item pointing to the strings, which the C library copies.
+pub(super) fn c_item(option: &CString, desc: &CString) -> NcSelectorItem {
+    NcSelectorItem {
+        option: option.as_ptr() as *mut _,
+        desc: desc.as_ptr() as *mut _,
+        opcolumns: 0,
+        desccolumns: 0,
+    }
+}
@@ -0,0 +1,108 @@
+//! `NcTabbedBuilder`
+
+use core::ptr::null_mut;
+
+use super::{NcTabbed, NcTabbedOptions, NCTABBED_OPTION_BOTTOM};
+use crate::{string, NcChannels, NcPlane, NcResult};
+
+/// A builder for [`NcTabbed`].
+///
+/// It owns the separator, which is converted to a C string when the widget
+/// is [built][NcTabbedBuilder#method.build]. The C library keeps its own copy.
+///
+/// # Example
+///
+/// ```ignore
+/// let tabbed = NcTabbed::builder()
+///     .separator(" | ")
+///     .bottom(true)
+///     .build(plane)?;
+/// tabbed.add(None, None, "first", |plane| {
+///     plane.putstr_yx(0, 0, "first tab")?;
+///     Ok(())
+/// })?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct NcTabbedBuilder {
+    separator: Option<String>,
+    selchan: NcChannels,
+    hdrchan: NcChannels,
+    sepchan: NcChannels,
+    flags: u64,
+}
+
+/// # `NcTabbedBuilder` constructors
+impl NcTabbedBuilder {
+    /// New NcTabbedBuilder, with the default channels, no separator,
+    /// and the tab list at the top.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// # `NcTabbedBuilder` methods
+impl NcTabbedBuilder {
+    /// Sets the separator drawn between the tab headers.
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = Some(separator.into());
+        self
+    }
+
+    /// Sets the channels of the selected tab header.
+    pub fn selchan(mut self, channels: NcChannels) -> Self {
+        self.selchan = channels;
+        self
+    }
+
+    /// Sets the channels of the unselected tab headers.
+    pub fn hdrchan(mut self, channels: NcChannels) -> Self {
+        self.hdrchan = channels;
+        self
+    }
+
+    /// Sets the channels of the tab separators.
+    pub fn sepchan(mut self, channels: NcChannels) -> Self {
+        self.sepchan = channels;
+        self
+    }
+
+    /// Displays the tab list at the bottom of the plane, instead of at the top.
+    pub fn bottom(mut self, bottom: bool) -> Self {
+        if bottom {
+            self.flags |= NCTABBED_OPTION_BOTTOM as u64;
+        } else {
+            self.flags &= !(NCTABBED_OPTION_BOTTOM as u64);
+        }
+        self
+    }
+
+    /// Sets the flags.
+    pub fn flags(mut self, flags: u64) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Creates the [`NcTabbed`], taking ownership of the `plane`.
+    ///
+    /// Returns an error if the separator contains a nul byte, in which case
+    /// the `plane` is left untouched, or if the widget couldn't be created,
+    /// in which case the `plane` is destroyed.
+    ///
+    /// *C style function: [nctabbed_create()][crate::c_api::nctabbed_create].*
+    pub fn build<'a>(self, plane: &'a mut NcPlane) -> NcResult<&'a mut NcTabbed> {
+        let separator = self.separator.as_deref().map(string::cstring).transpose()?;
+        let options = NcTabbedOptions {
+            separator: separator
+                .as_ref()
+                .map_or(null_mut(), |s| s.as_ptr() as *mut _),
+            ..NcTabbedOptions::with_all_args(
+                self.selchan,
+                self.hdrchan,
+                self.sepchan,
+                "",
+                self.flags,
+            )
+        };
+        NcTabbed::with_options(plane, &options)
+    }
+}
@@ -1,35 +1,541 @@
-//! `NcTabe` & `NcTabbed*` methods and associated functions.
+//! `NcTab` & `NcTabbed*` methods and associated functions.
 
-use super::{NcTab, NcTabbed, NcTabbedOptions};
-// use crate::{error_ref_mut, ncreader_create, NcPlane, NcResult};
+use core::ptr::null_mut;
+use std::os::raw::c_void;
 
-/// # `NcTabbedOptions` Constructors
+use super::{NcTab, NcTabCb, NcTabbed, NcTabbedBuilder, NcTabbedOptions};
+use crate::{
+    c_api, callback::ffi_guard, error, error_ref_mut, plane::userptr, string, NcChannels, NcError,
+    NcErrorKind, NcPlane, NcResult,
+};
+
+/// The Rust data of a tab, pointed to by its userptr.
+struct TabData {
+    content: NcTabCb,
+    /// The error of the last call to the closure, returned by `redraw`.
+    error: Option<NcError>,
+}
+
+/// The Rust data of a tabbed widget, stored in the userptr of its plane.
+///
+/// It owns the data of its tabs, which is freed along with it.
+#[derive(Default)]
+struct TabbedData {
+    tabs: Vec<*mut TabData>,
+}
+
+impl Drop for TabbedData {
+    fn drop(&mut self) {
+        for tab in self.tabs.drain(..) {
+            drop(unsafe { Box::from_raw(tab) });
+        }
+    }
+}
+
+/// Returns the data of the `tabbed` widget, if it was created from Rust.
+unsafe fn tabbed_data<'a>(tabbed: *mut NcTabbed) -> Option<&'a mut TabbedData> {
+    userptr::get(c_api::nctabbed_plane(tabbed))?
+        .widget_data
+        .as_mut()?
+        .downcast_mut()
+}
+
+/// Returns the data of the `tab`, if it has a userptr.
+///
+/// The tab must have been added from Rust, since any other userptr would be
+/// cast to a `TabData` all the same.
+unsafe fn tab_data<'a>(tab: *mut NcTab) -> Option<&'a mut TabData> {
+    (c_api::nctab_userptr(tab) as *mut TabData).as_mut()
+}
+
+/// Frees the `data` of a tab deleted from the `tabbed` widget.
+unsafe fn free_tab(tabbed: *mut NcTabbed, data: *mut TabData) {
+    if let Some(tabbed) = tabbed_data(tabbed) {
+        if let Some(i) = tabbed.tabs.iter().position(|t| *t == data) {
+            drop(Box::from_raw(tabbed.tabs.swap_remove(i)));
+        }
+    }
+}
+
+/// The C tab callback that calls the [`NcTabCb`] closure stored in the
+/// userptr of the tab.
+unsafe extern "C" fn tabcb_trampoline(_tab: *mut NcTab, plane: *mut NcPlane, curry: *mut c_void) {
+    let data = match (curry as *mut TabData).as_mut() {
+        Some(data) => data,
+        None => return,
+    };
+    let res = ffi_guard(|| match plane.as_mut() {
+        Some(plane) => (data.content)(plane),
+        None => Ok(()),
+    });
+    match res {
+        Ok(Ok(())) => (),
+        Ok(Err(e)) => data.error = Some(e),
+        Err(e) => {
+            data.content = Box::new(|_| Ok(()));
+            data.error = Some(e);
+        }
+    }
+}
+
+/// # `NcTabbedOptions` constructors
 impl NcTabbedOptions {
-    /// `NcTabbedOptions` simple constructor
+    /// New NcTabbedOptions for [`NcTabbed`], with the default channels,
+    /// no separator, and the tab list at the top.
     pub const fn new() -> Self {
         Self {
-            // channels used for input
-            tchannels: 0,
-            // attributes used for input
-            tattrword: 0,
-            // bitfield of NCREADER_OPTION_*
+            selchan: 0,
+            hdrchan: 0,
+            sepchan: 0,
+            separator: null_mut(),
             flags: 0,
         }
     }
+
+    /// New NcTabbedOptions for [`NcTabbed`], with all args.
+    ///
+    /// The `separator` is truncated at its first nul byte, if there's any,
+    /// and an empty one means no separator. It must be
+    /// [freed][NcTabbedOptions#method.free] once the widget is created.
+    /// An [`NcTabbedBuilder`] takes care of that.
+    pub fn with_all_args(
+        // channels for the selected tab header
+        selchan: NcChannels,
+
+        // channels for the unselected tab headers
+        hdrchan: NcChannels,
+
+        // channels for the tab separators
+        sepchan: NcChannels,
+
+        // separator string, copied by the widget
+        separator: &str,
+
+        // bitfield of `NCTABBED_OPTION_*`
+        flags: u64,
+    ) -> Self {
+        Self {
+            selchan,
+            hdrchan,
+            sepchan,
+            separator: if separator.is_empty() {
+                null_mut()
+            } else {
+                string::cstring_dup(separator)
+            },
+            flags,
+        }
+    }
+
+    /// Frees the `separator` allocated by
+    /// [`with_all_args`][NcTabbedOptions#method.with_all_args].
+    ///
+    /// The widget copies the separator, so it can be freed once it's created.
+    ///
+    /// # Safety
+    /// The `separator` must have been allocated by `with_all_args`, and not
+    /// be used afterwards.
+    pub unsafe fn free(&mut self) {
+        c_api::libc::free(self.separator as *mut c_api::libc::c_void);
+        self.separator = null_mut();
+    }
 }
 
-/// # `NcTabbed` Constructors
+/// # `NcTabbed` constructors & destructors
 impl NcTabbed {
-    /// `NcTabbed` simple constructor
-    pub fn new<'a>(plane: &mut NcPlane) -> NcResult<&'a mut Self> {
-        Self::with_options(plane, NcTabbedOptions::new())
+    /// New NcTabbed, with the default options.
+    ///
+    /// *C style function: [nctabbed_create()][c_api::nctabbed_create].*
+    pub fn new<'a>(plane: &'a mut NcPlane) -> NcResult<&'a mut Self> {
+        Self::with_options(plane, &NcTabbedOptions::new())
     }
 
-    /// `NcTabbed` constructor with options
+    /// New NcTabbed. Expects an [`NcTabbedOptions`] struct.
+    ///
+    /// Takes ownership of the `plane`, which will be destroyed by
+    /// [destroy][NcTabbed#method.destroy](), or on error.
+    ///
+    /// *C style function: [nctabbed_create()][c_api::nctabbed_create].*
     pub fn with_options<'a>(
-        plane: &mut NcPlane,
-        options: NcTabbedOptions,
+        plane: &'a mut NcPlane,
+        options: &NcTabbedOptions,
     ) -> NcResult<&'a mut Self> {
-        error_ref_mut![unsafe { nctabbed_create(plane, &options) }]
+        let res = unsafe { c_api::nctabbed_create(plane, options) };
+        if !res.is_null() {
+            // the tabbed widget keeps the data of its tabs
+            userptr::get_or_init(plane).widget_data = Some(Box::new(TabbedData::default()));
+        }
+        error_ref_mut![@call "nctabbed_create", res, "NcTabbed::with_options()"]
+    }
+
+    /// Returns a new [`NcTabbedBuilder`].
+    ///
+    /// *(No equivalent C style function)*
+    pub fn builder() -> NcTabbedBuilder {
+        NcTabbedBuilder::new()
+    }
+
+    /// Destroys the NcTabbed, its tabs, and its underlying plane.
+    ///
+    /// # Safety
+    ///
+    /// Neither the widget, its tabs, nor its plane may be used afterwards,
+    /// and it must not be owned by an `OwnedTabbed`.
+    ///
+    /// *C style function: [nctabbed_destroy()][c_api::nctabbed_destroy].*
+    pub unsafe fn destroy(&mut self) -> NcResult<()> {
+        userptr::free(c_api::nctabbed_plane(self));
+        c_api::nctabbed_destroy(self);
+        Ok(())
+    }
+}
+
+/// # `NcTabbed` methods
+impl NcTabbed {
+    /// Adds a new tab named `name`, whose content is drawn by the `content`
+    /// closure while it's selected.
+    ///
+    /// The tab is placed after `after`, or before `before`. If both are
+    /// `None`, it's placed after the selected tab, or it becomes the only
+    /// tab, and the selected one. If both are given, they must be adjacent.
+    ///
+    /// The closure is dropped when the tab is deleted, or when the widget
+    /// is destroyed.
+    ///
+    /// It's an error if the widget wasn't created with
+    /// [with_options][NcTabbed#method.with_options], which keeps the data
+    /// of the tabs.
+    ///
+    /// *C style function: [nctabbed_add()][c_api::nctabbed_add].*
+    pub fn add<F>(
+        &mut self,
+        after: Option<&mut NcTab>,
+        before: Option<&mut NcTab>,
+        name: &str,
+        content: F,
+    ) -> NcResult<&mut NcTab>
+    where
+        F: FnMut(&mut NcPlane) -> NcResult<()> + 'static,
+    {
+        let tabbed = unsafe { tabbed_data(self) }.ok_or_else(|| {
+            NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "NcTabbed.add(): the widget wasn't created from Rust",
+            )
+        })?;
+        let after = after.map_or(null_mut(), |t| t as *mut NcTab);
+        let before = before.map_or(null_mut(), |t| t as *mut NcTab);
+        let data = Box::into_raw(Box::new(TabData {
+            content: Box::new(content),
+            error: None,
+        }));
+
+        let res = string::with_cstr(name, |name| unsafe {
+            c_api::nctabbed_add(
+                self,
+                after,
+                before,
+                Some(tabcb_trampoline),
+                name,
+                data as *mut c_void,
+            )
+        });
+        match &res {
+            Ok(tab) if !tab.is_null() => tabbed.tabs.push(data),
+            _ => drop(unsafe { Box::from_raw(data) }),
+        }
+        error_ref_mut![@call "nctabbed_add", res?, "NcTabbed.add()"]
+    }
+
+    /// Deletes the `tab`, dropping its closure.
+    ///
+    /// If it was selected, the next tab is selected.
+    ///
+    /// *C style function: [nctabbed_del()][c_api::nctabbed_del].*
+    pub fn del(&mut self, tab: &mut NcTab) -> NcResult<()> {
+        let data = unsafe { c_api::nctab_userptr(tab) } as *mut TabData;
+        error![
+            @call "nctabbed_del", unsafe { c_api::nctabbed_del(self, tab) },
+            "NcTabbed.del()",
+            unsafe { free_tab(self, data) }
+        ]
+    }
+
+    /// Selects the `tab`, and returns the previously selected one.
+    ///
+    /// *C style function: [nctabbed_select()][c_api::nctabbed_select].*
+    pub fn select(&mut self, tab: &mut NcTab) -> Option<&mut NcTab> {
+        unsafe { c_api::nctabbed_select(self, tab).as_mut() }
+    }
+
+    /// Returns the selected tab, if there's any.
+    ///
+    /// *C style function: [nctabbed_selected()][c_api::nctabbed_selected].*
+    pub fn selected(&mut self) -> Option<&mut NcTab> {
+        unsafe { c_api::nctabbed_selected(self).as_mut() }
+    }
+
+    /// Returns the leftmost tab, if there's any.
+    ///
+    /// *C style function: [nctabbed_leftmost()][c_api::nctabbed_leftmost].*
+    pub fn leftmost(&mut self) -> Option<&mut NcTab> {
+        unsafe { c_api::nctabbed_leftmost(self).as_mut() }
+    }
+
+    /// Selects the next tab, and returns it, if there's any.
+    ///
+    /// *C style function: [nctabbed_next()][c_api::nctabbed_next].*
+    pub fn next(&mut self) -> Option<&mut NcTab> {
+        unsafe { c_api::nctabbed_next(self).as_mut() }
+    }
+
+    /// Selects the previous tab, and returns it, if there's any.
+    ///
+    /// *C style function: [nctabbed_prev()][c_api::nctabbed_prev].*
+    pub fn prev(&mut self) -> Option<&mut NcTab> {
+        unsafe { c_api::nctabbed_prev(self).as_mut() }
+    }
+
+    /// Rotates the tab headers `amount` tabs to the right,
+    /// or to the left if it's negative, without changing the selected tab.
+    ///
+    /// *C style function: [nctabbed_rotate()][c_api::nctabbed_rotate].*
+    pub fn rotate(&mut self, amount: i32) {
+        unsafe { c_api::nctabbed_rotate(self, amount) }
+    }
+
+    /// Returns the number of tabs.
+    ///
+    /// *C style function: [nctabbed_tabcount()][c_api::nctabbed_tabcount].*
+    pub fn tabcount(&self) -> u32 {
+        unsafe { c_api::nctabbed_tabcount(self as *const NcTabbed as *mut NcTabbed) as u32 }
+    }
+
+    /// Redraws the NcTabbed, calling the closure of the selected tab.
+    ///
+    /// Returns the error returned by the closure, if any, or the message of
+    /// its panic, in which case the closure is replaced by one that draws
+    /// nothing.
+    ///
+    /// *C style function: [nctabbed_redraw()][c_api::nctabbed_redraw].*
+    pub fn redraw(&mut self) -> NcResult<()> {
+        unsafe { c_api::nctabbed_redraw(self) };
+        let selected = unsafe { c_api::nctabbed_selected(self) };
+        if selected.is_null() {
+            return Ok(());
+        }
+        match unsafe { tab_data(selected) }.and_then(|data| data.error.take()) {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Rotates the tab headers, if needed, so that the selected tab header
+    /// is visible. It doesn't redraw.
+    ///
+    /// *C style function: [nctabbed_ensure_selected_header_visible()][c_api::nctabbed_ensure_selected_header_visible].*
+    pub fn ensure_selected_header_visible(&mut self) {
+        unsafe { c_api::nctabbed_ensure_selected_header_visible(self) }
+    }
+
+    /// Returns a reference to the NcTabbed's underlying plane.
+    ///
+    /// *C style function: [nctabbed_plane()][c_api::nctabbed_plane].*
+    pub fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![@call "nctabbed_plane", unsafe { c_api::nctabbed_plane(self) }, "NcTabbed.plane()"]
+    }
+
+    /// Returns a reference to the plane where the content of the tabs
+    /// is drawn.
+    ///
+    /// *C style function: [nctabbed_content_plane()][c_api::nctabbed_content_plane].*
+    pub fn content_plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![
+            @call "nctabbed_content_plane", unsafe { c_api::nctabbed_content_plane(self) },
+            "NcTabbed.content_plane()"
+        ]
+    }
+
+    /// Returns the channels of the (headers, selected header, separators).
+    ///
+    /// *C style function: [nctabbed_channels()][c_api::nctabbed_channels].*
+    pub fn channels(&self) -> (NcChannels, NcChannels, NcChannels) {
+        let (mut hdrchan, mut selchan, mut sepchan) = (0, 0, 0);
+        unsafe {
+            c_api::nctabbed_channels(
+                self as *const NcTabbed as *mut NcTabbed,
+                &mut hdrchan,
+                &mut selchan,
+                &mut sepchan,
+            )
+        };
+        (hdrchan, selchan, sepchan)
+    }
+
+    /// Returns the channels of the unselected tab headers.
+    ///
+    /// *C style function: [nctabbed_channels()][c_api::nctabbed_channels].*
+    pub fn hdrchan(&self) -> NcChannels {
+        self.channels().0
+    }
+
+    /// Returns the channels of the selected tab header.
+    ///
+    /// *C style function: [nctabbed_channels()][c_api::nctabbed_channels].*
+    pub fn selchan(&self) -> NcChannels {
+        self.channels().1
+    }
+
+    /// Returns the channels of the tab separators.
+    ///
+    /// *C style function: [nctabbed_channels()][c_api::nctabbed_channels].*
+    pub fn sepchan(&self) -> NcChannels {
+        self.channels().2
+    }
+
+    /// Sets the channels of the unselected tab headers.
+    ///
+    /// *C style function: [nctabbed_set_hdrchan()][c_api::nctabbed_set_hdrchan].*
+    pub fn set_hdrchan(&mut self, channels: NcChannels) {
+        unsafe { c_api::nctabbed_set_hdrchan(self, channels) }
+    }
+
+    /// Sets the channels of the selected tab header.
+    ///
+    /// *C style function: [nctabbed_set_selchan()][c_api::nctabbed_set_selchan].*
+    pub fn set_selchan(&mut self, channels: NcChannels) {
+        unsafe { c_api::nctabbed_set_selchan(self, channels) }
+    }
+
+    /// Sets the channels of the tab separators.
+    ///
+    /// *C style function: [nctabbed_set_sepchan()][c_api::nctabbed_set_sepchan].*
+    pub fn set_sepchan(&mut self, channels: NcChannels) {
+        unsafe { c_api::nctabbed_set_sepchan(self, channels) }
+    }
+
+    /// Returns the tab separator, or an empty string if there's none.
+    ///
+    /// *C style function: [nctabbed_separator()][c_api::nctabbed_separator].*
+    pub fn separator(&self) -> String {
+        unsafe {
+            string::rstr_lossy(c_api::nctabbed_separator(
+                self as *const NcTabbed as *mut NcTabbed,
+            ))
+            .into_owned()
+        }
+    }
+
+    /// Returns the width of the tab separator, in columns.
+    ///
+    /// *C style function: [nctabbed_separator_width()][c_api::nctabbed_separator_width].*
+    pub fn separator_width(&self) -> u32 {
+        unsafe { c_api::nctabbed_separator_width(self as *const NcTabbed as *mut NcTabbed) as u32 }
+    }
+
+    /// Sets the tab separator.
+    ///
+    /// *C style function: [nctabbed_set_separator()][c_api::nctabbed_set_separator].*
+    pub fn set_separator(&mut self, separator: &str) -> NcResult<()> {
+        error![
+            @call "nctabbed_set_separator", string::with_cstr(separator, |s| unsafe {
+                c_api::nctabbed_set_separator(self, s)
+            })?,
+            "NcTabbed.set_separator()"
+        ]
+    }
+}
+
+/// # `NcTab` methods
+impl NcTab {
+    /// Returns the name of the tab.
+    ///
+    /// *C style function: [nctab_name()][c_api::nctab_name].*
+    pub fn name(&self) -> String {
+        unsafe {
+            string::rstr_lossy(c_api::nctab_name(self as *const NcTab as *mut NcTab)).into_owned()
+        }
+    }
+
+    /// Returns the width of the name of the tab, in columns.
+    ///
+    /// *C style function: [nctab_name_width()][c_api::nctab_name_width].*
+    pub fn name_width(&self) -> u32 {
+        unsafe { c_api::nctab_name_width(self as *const NcTab as *mut NcTab) as u32 }
+    }
+
+    /// Sets the name of the tab.
+    ///
+    /// *C style function: [nctab_set_name()][c_api::nctab_set_name].*
+    pub fn set_name(&mut self, name: &str) -> NcResult<()> {
+        error![
+            @call "nctab_set_name", string::with_cstr(name, |name| unsafe { c_api::nctab_set_name(self, name) })?,
+            "NcTab.set_name()"
+        ]
+    }
+
+    /// Returns the next tab, which is this one if it's the only tab.
+    ///
+    /// *C style function: [nctab_next()][c_api::nctab_next].*
+    pub fn next(&mut self) -> NcResult<&mut NcTab> {
+        error_ref_mut![@call "nctab_next", unsafe { c_api::nctab_next(self) }, "NcTab.next()"]
+    }
+
+    /// Returns the previous tab, which is this one if it's the only tab.
+    ///
+    /// *C style function: [nctab_prev()][c_api::nctab_prev].*
+    pub fn prev(&mut self) -> NcResult<&mut NcTab> {
+        error_ref_mut![@call "nctab_prev", unsafe { c_api::nctab_prev(self) }, "NcTab.prev()"]
+    }
+
+    /// Moves the tab in `tabbed`, after `after`, or before `before`.
+    /// If both are given, they must be adjacent.
+    ///
+    /// *C style function: [nctab_move()][c_api::nctab_move].*
+    pub fn move_to(
+        &mut self,
+        tabbed: &mut NcTabbed,
+        after: Option<&mut NcTab>,
+        before: Option<&mut NcTab>,
+    ) -> NcResult<()> {
+        let after = after.map_or(null_mut(), |t| t as *mut NcTab);
+        let before = before.map_or(null_mut(), |t| t as *mut NcTab);
+        error![
+            @call "nctab_move", unsafe { c_api::nctab_move(tabbed, self, after, before) },
+            "NcTab.move_to()"
+        ]
+    }
+
+    /// Moves the tab one place to the left in `tabbed`.
+    ///
+    /// *C style function: [nctab_move_left()][c_api::nctab_move_left].*
+    pub fn move_left(&mut self, tabbed: &mut NcTabbed) {
+        unsafe { c_api::nctab_move_left(tabbed, self) }
+    }
+
+    /// Moves the tab one place to the right in `tabbed`.
+    ///
+    /// *C style function: [nctab_move_right()][c_api::nctab_move_right].*
+    pub fn move_right(&mut self, tabbed: &mut NcTabbed) {
+        unsafe { c_api::nctab_move_right(tabbed, self) }
+    }
+
+    /// Replaces the closure that draws the content of the tab.
+    ///
+    /// # Safety
+    ///
+    /// The tab must have been added with [`NcTabbed.add()`][NcTabbed#method.add],
+    /// since the userptr of any other tab is not checked before being read
+    /// as its data.
+    ///
+    /// *(No equivalent C style function)*
+    pub unsafe fn set_content<F>(&mut self, content: F) -> NcResult<()>
+    where
+        F: FnMut(&mut NcPlane) -> NcResult<()> + 'static,
+    {
+        let data = tab_data(self)
+            .ok_or_else(|| NcError::with_kind(NcErrorKind::NullPointer, "NcTab.set_content()"))?;
+        data.content = Box::new(content);
+        Ok(())
     }
 }
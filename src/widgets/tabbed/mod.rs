@@ -1,44 +1,57 @@
 //! `NcTabbed` widget.
 
-// functions already exported by bindgen : 39
+// functions already exported by bindgen : 35
 // ------------------------------------------
-//   nctab_cb
-//   nctab_move
-//   nctab_move_left
-//   nctab_move_right
-//   nctab_name
-//   nctab_name_width
-//   nctab_next
-//   nctab_prev
-//   nctab_set_cb
-//   nctab_set_name
-//   nctab_set_userptr
-//   nctab_userptr
-//   nctabbed_add
-//   nctabbed_channels
-//   nctabbed_content_plane
-//   nctabbed_create
-//   nctabbed_del
-//   nctabbed_destroy
-//   nctabbed_ensure_selected_header_visible
-//   nctabbed_leftmost
-//   nctabbed_next
-//   nctabbed_plane
-//   nctabbed_prev
-//   nctabbed_redraw
-//   nctabbed_rotate
-//   nctabbed_select
-//   nctabbed_selected
-//   nctabbed_separator
-//   nctabbed_separator_width
-//   nctabbed_set_hdrchan
-//   nctabbed_set_selchan
-//   nctabbed_set_separator
-//   nctabbed_set_sepchan
-//   nctabbed_tabcount
-//   nctablet_ncplane
-
-// mod methods;
+// (#) test: 0
+// (W) wrap: 31
+// ------------------------------------------
+//  nctab_cb,
+//W nctab_move,
+//W nctab_move_left,
+//W nctab_move_right,
+//W nctab_name,
+//W nctab_name_width,
+//W nctab_next,
+//W nctab_prev,
+//  nctab_set_cb,
+//W nctab_set_name,
+//  nctab_set_userptr,
+//W nctab_userptr,
+//W nctabbed_add,
+//W nctabbed_channels,
+//W nctabbed_content_plane,
+//W nctabbed_create,
+//W nctabbed_del,
+//W nctabbed_destroy,
+//W nctabbed_ensure_selected_header_visible,
+//W nctabbed_leftmost,
+//W nctabbed_next,
+//W nctabbed_plane,
+//W nctabbed_prev,
+//W nctabbed_redraw,
+//W nctabbed_rotate,
+//W nctabbed_select,
+//W nctabbed_selected,
+//W nctabbed_separator,
+//W nctabbed_separator_width,
+//W nctabbed_set_hdrchan,
+//W nctabbed_set_selchan,
+//W nctabbed_set_separator,
+//W nctabbed_set_sepchan,
+//W nctabbed_tabcount,
+//  nctablet_ncplane,
+
+use std::os::raw::c_void;
+
+use crate::{NcPlane, NcResult};
+
+mod builder;
+mod methods;
+
+#[cfg(test)]
+mod test;
+
+pub use builder::NcTabbedBuilder;
 
 /// A tab for [`NcTabbed`].
 ///
@@ -58,5 +71,19 @@ pub type NcTabbed = crate::bindings::ffi::nctabbed;
 /// `type in C: nctabbed_options (struct)`
 pub type NcTabbedOptions = crate::bindings::ffi::nctabbed_options;
 
+/// A callback closure that draws the content of the selected [`NcTab`].
+///
+/// It's called with the content plane of the [`NcTabbed`], whenever it's
+/// redrawn, and it's stored along with the tab by
+/// [`NcTabbed.add()`][NcTabbed#method.add].
+///
+/// Its error, or its panic, is returned by
+/// [`NcTabbed.redraw()`][NcTabbed#method.redraw]. After a panic, it's replaced
+/// by a closure that draws nothing.
+pub type NcTabCb = Box<dyn FnMut(&mut NcPlane) -> NcResult<()>>;
+
+/// The unsafe version of [`NcTabCb`] expected by the notcurses C API.
+pub type NcTabCbUnsafe = unsafe extern "C" fn(*mut NcTab, *mut NcPlane, *mut c_void);
+
 /// To display the tab list at the bottom instead of at the top of the plane.
 pub const NCTABBED_OPTION_BOTTOM: u32 = crate::bindings::ffi::NCTABBED_OPTION_BOTTOM;
@@ -0,0 +1,99 @@
+//! Test `NcTabbed` & `NcTab` methods.
+
+use std::{cell::Cell, rc::Rc};
+
+use crate::{NcContext, NcPlaneHandle, NcTab, NcTabbed};
+
+use serial_test::serial;
+
+// the tabs borrow the widget, so they're passed back to it as pointers
+fn add(tabbed: &mut NcTabbed, name: &str) -> crate::NcResult<*mut NcTab> {
+    tabbed
+        .add(None, None, name, |_plane| Ok(()))
+        .map(|tab| tab as *mut NcTab)
+}
+
+fn selected(tabbed: &mut NcTabbed) -> Option<String> {
+    tabbed.selected().map(|tab| tab.name())
+}
+
+fn leftmost(tabbed: &mut NcTabbed) -> Option<String> {
+    tabbed.leftmost().map(|tab| tab.name())
+}
+
+#[test]
+#[serial]
+fn add_del() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 10, 40)?;
+    let tabbed = NcTabbed::builder().separator("|").build(&mut plane)?;
+    assert_eq![0, tabbed.tabcount()];
+    assert_eq![None, selected(tabbed)];
+
+    // the first tab becomes the selected one
+    let drawn = Rc::new(Cell::new(0));
+    let content = drawn.clone();
+    let first: *mut NcTab = tabbed.add(None, None, "first", move |plane| {
+        content.set(content.get() + 1);
+        plane.putstr("first tab")?;
+        Ok(())
+    })?;
+    let second = add(tabbed, "second")?;
+    assert_eq![2, tabbed.tabcount()];
+    assert_eq![Some("first".into()), selected(tabbed)];
+    assert_eq!["second", unsafe { &*second }.name()];
+
+    // the selected tab is drawn by its closure
+    tabbed.redraw()?;
+    assert![drawn.get() > 0];
+
+    // deleting the selected tab selects the next one, and drops its closure
+    tabbed.del(unsafe { &mut *first })?;
+    assert_eq![1, tabbed.tabcount()];
+    assert_eq![Some("second".into()), selected(tabbed)];
+    assert_eq![1, Rc::strong_count(&drawn)];
+
+    tabbed.del(unsafe { &mut *second })?;
+    assert_eq![0, tabbed.tabcount()];
+    assert_eq![None, selected(tabbed)];
+    unsafe { tabbed.destroy()? };
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn select_rotate() -> crate::NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut plane = NcPlaneHandle::new(&nc, 0, 0, 10, 40)?;
+    let tabbed = NcTabbed::new(&mut plane)?;
+
+    // each tab is added after the selected one
+    let a = add(tabbed, "a")?;
+    let b = add(tabbed, "b")?;
+    tabbed.select(unsafe { &mut *b });
+    let c = add(tabbed, "c")?;
+    assert_eq![Some("a".into()), leftmost(tabbed)];
+    assert_eq!["b", unsafe { &mut *a }.next()?.name()];
+
+    let previous = tabbed.select(unsafe { &mut *c }).map(|tab| tab.name());
+    assert_eq![Some("b".into()), previous];
+    assert_eq![Some("c".into()), selected(tabbed)];
+
+    // the tabs wrap around
+    assert_eq![Some("a".into()), tabbed.next().map(|tab| tab.name())];
+    assert_eq![Some("c".into()), tabbed.prev().map(|tab| tab.name())];
+    assert_eq!["a", unsafe { &mut *c }.next()?.name()];
+    assert_eq!["b", unsafe { &mut *c }.prev()?.name()];
+
+    // rotating the headers doesn't change the selected tab
+    tabbed.rotate(1);
+    assert_eq![Some("c".into()), leftmost(tabbed)];
+    assert_eq![Some("c".into()), selected(tabbed)];
+    tabbed.rotate(-2);
+    assert_eq![Some("b".into()), leftmost(tabbed)];
+    tabbed.rotate(1);
+    assert_eq![Some("a".into()), leftmost(tabbed)];
+
+    unsafe { tabbed.destroy()? };
+    Ok(())
+}
@@ -0,0 +1,4 @@
+//! `NcTabbed` tests.
+
+#[cfg(test)]
+mod methods;
@@ -0,0 +1,4 @@
+//! Widgets tests.
+
+#[cfg(test)]
+mod widget;
@@ -0,0 +1,53 @@
+//! Test the `NcWidget` dispatch.
+
+use crate::{
+    widgets::NcWidget, NcContext, NcInput, NcMultiSelector, NcPlaneHandle, NcReader, NcResult,
+};
+
+use serial_test::serial;
+
+/// Offers the `input` to each widget in turn, and returns the index of the
+/// one that consumed it, if any.
+fn dispatch(widgets: &mut [&mut dyn NcWidget], input: NcInput) -> NcResult<Option<usize>> {
+    for (index, widget) in widgets.iter_mut().enumerate() {
+        if widget.offer_input(input)? {
+            widget.redraw()?;
+            return Ok(Some(index));
+        }
+    }
+    Ok(None)
+}
+
+#[test]
+#[serial]
+fn dispatch_dyn() -> NcResult<()> {
+    let nc = NcContext::new()?;
+    let mut selector_plane = NcPlaneHandle::new(&nc, 0, 0, 10, 30)?;
+    let mut reader_plane = NcPlaneHandle::new(&nc, 12, 0, 1, 30)?;
+    let selector = NcMultiSelector::builder()
+        .item("first", "", false)
+        .item("second", "", false)
+        .build(&mut selector_plane)?;
+    let reader = NcReader::new(&mut reader_plane)?;
+
+    {
+        let mut widgets: Vec<&mut dyn NcWidget> = vec![&mut *selector, &mut *reader];
+        // the space bar is taken by the selector, the letters by the reader
+        assert_eq![Some(0), dispatch(&mut widgets, NcInput::new(' '))?];
+        assert_eq![Some(1), dispatch(&mut widgets, NcInput::new('h'))?];
+        assert_eq![Some(1), dispatch(&mut widgets, NcInput::new('i'))?];
+
+        for widget in widgets.iter_mut() {
+            widget.plane()?;
+        }
+    }
+    assert_eq![vec![true, false], selector.selected()?];
+    assert_eq!["hi", reader.contents()];
+
+    // destroyed through the trait
+    let widgets: Vec<&mut dyn NcWidget> = vec![selector, reader];
+    for widget in widgets {
+        unsafe { widget.destroy()? };
+    }
+    Ok(())
+}
@@ -21,8 +21,7 @@ impl NcTreeOptions {
         // size of |items|
         count: usize,
 
-        // item callback function
-        // TODO: use NcTreeItemCb and convert to NcTreeItemCbUnsafe
+        // item callback function (see `NcTree::from_nodes` for closures)
         nctreecb: Option<NcTreeItemCbUnsafe>,
 
         // columns to indent per level of hierarchy
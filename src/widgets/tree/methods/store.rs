@@ -0,0 +1,190 @@
+//! The Rust backing store of the `NcTree` items.
+
+use core::{
+    any::{Any, TypeId},
+    ptr::null_mut,
+};
+use std::os::raw::{c_int, c_void};
+
+use crate::{
+    c_api,
+    callback::{ffi_guard, int_result},
+    plane::userptr,
+    widgets::{NcTree, NcTreeItem, TreeNode},
+    NcError, NcErrorKind, NcPlane, NcResult,
+};
+
+/// The type-erased drawing closure of a tree.
+type DrawCb = Box<dyn FnMut(&mut NcPlane, &dyn Any, i32) -> NcResult<()>>;
+
+/// The Rust data of a tree, stored in the userptr of its plane.
+pub(super) struct TreeData {
+    draw: DrawCb,
+    /// The type of the values of the items.
+    type_id: TypeId,
+    pub(super) items: Vec<ItemNode>,
+}
+
+/// The Rust data of an item, pointed to by its curry.
+pub(super) struct ItemData {
+    pub(super) value: Box<dyn Any>,
+    /// The tree it belongs to, to reach the drawing closure.
+    tree: *mut TreeData,
+}
+
+/// An item of the backing store, with its subitems.
+pub(super) struct ItemNode {
+    pub(super) data: Box<ItemData>,
+    pub(super) subs: Vec<ItemNode>,
+}
+
+/// The C items of some [`ItemNode`]s, with their subitems, which must be
+/// alive while they're used.
+pub(super) struct CItems {
+    pub(super) items: Vec<NcTreeItem>,
+    _subs: Vec<CItems>,
+}
+
+impl TreeData {
+    /// Returns a new tree data, drawn with `draw`, without items.
+    pub(super) fn new<T, F>(mut draw: F) -> Box<Self>
+    where
+        T: 'static,
+        F: FnMut(&mut NcPlane, &T, i32) -> NcResult<()> + 'static,
+    {
+        Box::new(TreeData {
+            draw: Box::new(move |plane, value, pos| match value.downcast_ref::<T>() {
+                Some(value) => draw(plane, value, pos),
+                None => Ok(()),
+            }),
+            type_id: TypeId::of::<T>(),
+            items: Vec::new(),
+        })
+    }
+
+    /// Returns an error if the values of the items are not of type `T`.
+    pub(super) fn check_type<T: 'static>(&self) -> NcResult<()> {
+        if self.type_id != TypeId::of::<T>() {
+            return Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "the type of the node is not the type of the tree",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns a new item node, with its subitems, from a tree `node`.
+    pub(super) fn store<T: TreeNode + Clone + 'static>(&mut self, node: &T) -> ItemNode {
+        let tree = self as *mut TreeData;
+        store(node, tree)
+    }
+
+    /// Returns the siblings of the item at `path`, and its index among them.
+    ///
+    /// It's an error if `path` is empty, or if any of its parents doesn't
+    /// exist. The index can be the number of siblings.
+    pub(super) fn siblings_mut(&mut self, path: &[u32]) -> NcResult<(&mut Vec<ItemNode>, usize)> {
+        let (index, parents) = path
+            .split_last()
+            .ok_or_else(|| NcError::with_kind(NcErrorKind::InvalidArgument, "the path is empty"))?;
+        let mut siblings = &mut self.items;
+        for (depth, i) in parents.iter().enumerate() {
+            siblings = match siblings.get_mut(*i as usize) {
+                Some(node) => &mut node.subs,
+                None => return Err(out_of_bounds(path, depth)),
+            };
+        }
+        if *index as usize > siblings.len() {
+            return Err(out_of_bounds(path, parents.len()));
+        }
+        Ok((siblings, *index as usize))
+    }
+
+    /// Removes the item at `path`, with its subitems, if it exists.
+    pub(super) fn remove(&mut self, path: &[u32]) {
+        if let Ok((siblings, index)) = self.siblings_mut(path) {
+            if index < siblings.len() {
+                siblings.remove(index);
+            }
+        }
+    }
+}
+
+fn store<T: TreeNode + Clone + 'static>(node: &T, tree: *mut TreeData) -> ItemNode {
+    ItemNode {
+        data: Box::new(ItemData {
+            value: Box::new(node.clone()),
+            tree,
+        }),
+        subs: node.children().iter().map(|n| store(n, tree)).collect(),
+    }
+}
+
+fn out_of_bounds(path: &[u32], depth: usize) -> NcError {
+    NcError::with_kind(
+        NcErrorKind::OutOfBounds,
+        &format!["the path {:?} doesn't exist at depth {}", path, depth],
+    )
+}
+
+/// Returns the C items of the `nodes`.
+pub(super) fn c_items(nodes: &mut [ItemNode]) -> CItems {
+    let mut subs: Vec<CItems> = nodes.iter_mut().map(|n| c_items(&mut n.subs)).collect();
+    let items = nodes
+        .iter_mut()
+        .zip(subs.iter_mut())
+        .map(|(node, sub)| NcTreeItem {
+            curry: &mut *node.data as *mut ItemData as *mut c_void,
+            subs: if sub.items.is_empty() {
+                null_mut()
+            } else {
+                sub.items.as_mut_ptr()
+            },
+            subcount: sub.items.len() as u32,
+        })
+        .collect();
+    CItems { items, _subs: subs }
+}
+
+/// Returns the C spec of a `path`, terminated by `u32::MAX`.
+pub(super) fn spec(path: &[u32]) -> Vec<u32> {
+    path.iter().copied().chain(Some(u32::MAX)).collect()
+}
+
+/// Returns the data of the `tree`, if it was created from Rust.
+pub(super) unsafe fn tree_data<'a>(tree: *mut NcTree) -> Option<&'a mut TreeData> {
+    userptr::get(c_api::nctree_plane(tree))?
+        .widget_data
+        .as_mut()?
+        .downcast_mut()
+}
+
+/// Returns the value of type `T` of an item of the `tree`, from its `curry`.
+pub(super) unsafe fn item_value<'a, T: 'static>(
+    tree: *mut NcTree,
+    curry: *mut c_void,
+) -> Option<&'a T> {
+    // the curry is only known to be an ItemData in the trees created from Rust
+    tree_data(tree)?;
+    (curry as *const ItemData).as_ref()?.value.downcast_ref()
+}
+
+/// The C item callback that calls the drawing closure of the tree of the
+/// item in the curry.
+pub(super) unsafe extern "C" fn treecb_trampoline(
+    plane: *mut NcPlane,
+    curry: *mut c_void,
+    pos: c_int,
+) -> c_int {
+    let res = ffi_guard(|| {
+        let item = match (curry as *const ItemData).as_ref() {
+            Some(item) => item,
+            None => return Ok(()),
+        };
+        match (item.tree.as_mut(), plane.as_mut()) {
+            (Some(tree), Some(plane)) => (tree.draw)(plane, &*item.value, pos),
+            _ => Ok(()),
+        }
+    });
+    int_result(&res)
+}
@@ -0,0 +1,4 @@
+//! `NcTree` tests.
+
+#[cfg(test)]
+mod store;
@@ -0,0 +1,121 @@
+//! Test the Rust backing store of the `NcTree` items.
+
+use std::os::raw::c_void;
+
+use super::super::store::{c_items, ItemData, ItemNode, TreeData};
+use crate::{widgets::TreeNode, NcErrorKind};
+
+use serial_test::serial;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Node(&'static str, Vec<Node>);
+
+impl TreeNode for Node {
+    fn label(&self) -> String {
+        self.0.into()
+    }
+    fn children(&self) -> &[Self] {
+        &self.1
+    }
+}
+
+/// Returns a tree data with the items:
+/// `a` (`a0`, `a1` (`a10`)), `b`.
+fn tree_data() -> Box<TreeData> {
+    let nodes = vec![
+        Node(
+            "a",
+            vec![Node("a0", vec![]), Node("a1", vec![Node("a10", vec![])])],
+        ),
+        Node("b", vec![]),
+    ];
+    let mut data = TreeData::new(|_, _: &Node, _| Ok(()));
+    data.items = nodes.iter().map(|n| data.store(n)).collect();
+    data
+}
+
+/// Returns the labels of the `items`.
+fn labels(items: &[ItemNode]) -> Vec<&'static str> {
+    items
+        .iter()
+        .map(|item| item.data.value.downcast_ref::<Node>().map_or("", |n| n.0))
+        .collect()
+}
+
+#[test]
+#[serial]
+fn siblings_mut() -> crate::NcResult<()> {
+    let mut data = tree_data();
+
+    let (siblings, index) = data.siblings_mut(&[1])?;
+    assert_eq![(vec!["a", "b"], 1), (labels(siblings), index)];
+    let (siblings, index) = data.siblings_mut(&[0, 1, 0])?;
+    assert_eq![(vec!["a10"], 0), (labels(siblings), index)];
+
+    // the index can be the number of siblings, to insert after the last one
+    let (siblings, index) = data.siblings_mut(&[0, 2])?;
+    assert_eq![(vec!["a0", "a1"], 2), (labels(siblings), index)];
+
+    assert_eq![
+        NcErrorKind::InvalidArgument,
+        data.siblings_mut(&[]).unwrap_err().kind()
+    ];
+    assert_eq![
+        NcErrorKind::OutOfBounds,
+        data.siblings_mut(&[3]).unwrap_err().kind()
+    ];
+    assert_eq![
+        NcErrorKind::OutOfBounds,
+        data.siblings_mut(&[2, 0]).unwrap_err().kind()
+    ];
+    assert_eq![
+        NcErrorKind::OutOfBounds,
+        data.siblings_mut(&[0, 0, 0, 0]).unwrap_err().kind()
+    ];
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn remove() -> crate::NcResult<()> {
+    let mut data = tree_data();
+
+    // the subitems are removed along with their parent
+    data.remove(&[0, 1]);
+    assert_eq![vec!["a0"], labels(data.siblings_mut(&[0, 0])?.0)];
+
+    // the missing items are ignored
+    data.remove(&[0, 1]);
+    data.remove(&[5, 0]);
+    data.remove(&[]);
+    assert_eq![vec!["a", "b"], labels(&data.items)];
+
+    data.remove(&[0]);
+    assert_eq![vec!["b"], labels(&data.items)];
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn c_items_tree() -> crate::NcResult<()> {
+    let mut data = tree_data();
+    let citems = c_items(&mut data.items);
+
+    let label = |curry: *mut c_void| {
+        unsafe { (*(curry as *const ItemData)).value.downcast_ref::<Node>() }.map_or("", |n| n.0)
+    };
+
+    assert_eq![2, citems.items.len()];
+    let (a, b) = (&citems.items[0], &citems.items[1]);
+    assert_eq![("a", 2), (label(a.curry), a.subcount)];
+    assert_eq![("b", 0), (label(b.curry), b.subcount)];
+    // the leaves have no subitems
+    assert![b.subs.is_null()];
+
+    let a_subs = unsafe { core::slice::from_raw_parts(a.subs, a.subcount as usize) };
+    assert_eq![("a0", 0), (label(a_subs[0].curry), a_subs[0].subcount)];
+    assert_eq![("a1", 1), (label(a_subs[1].curry), a_subs[1].subcount)];
+    let a1_subs = unsafe { core::slice::from_raw_parts(a_subs[1].subs, 1) };
+    assert_eq!["a10", label(a1_subs[0].curry)];
+    Ok(())
+}
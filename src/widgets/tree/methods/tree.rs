@@ -1,8 +1,12 @@
+use std::os::raw::c_int;
+
+use super::store::{self, c_items, spec, tree_data, TreeData};
 use crate::{
     c_api::{self, nctree_create},
     error, error_ref_mut,
-    widgets::{NcTree, NcTreeItem, NcTreeOptions},
-    NcError, NcInput, NcIntResult, NcIntResultApi, NcPlane, NcResult,
+    plane::userptr,
+    widgets::{NcTree, NcTreeOptions, TreeNode},
+    NcDim, NcError, NcErrorKind, NcInput, NcPlane, NcResult,
 };
 
 /// # `NcTree` constructors & destructors
@@ -11,77 +15,193 @@ impl NcTree {
     ///
     /// *C style function: [nctree_create()][c_api::nctree_create].*
     pub fn new<'a>(plane: &mut NcPlane, options: NcTreeOptions) -> NcResult<&'a mut Self> {
-        error_ref_mut![unsafe { nctree_create(plane, &options) }, "Creating NcTree"]
+        error_ref_mut![@call "nctree_create", unsafe { nctree_create(plane, &options) }, "Creating NcTree"]
     }
 
-    /// Destroys an NcTree created with [new()][NcTree#method.new].
+    /// Creates an [NcTree] from the `nodes` of a recursive structure,
+    /// whose items are drawn by the `draw` closure.
+    ///
+    /// The tree keeps a clone of each node as the value of its item, which
+    /// is passed to `draw` along with the plane of the item and its position
+    /// relative to the focused item.
+    ///
+    /// Takes ownership of the `plane`, which will be destroyed by
+    /// [destroy][NcTree#method.destroy](), or on error.
+    /// It's an error if there are no `nodes`.
+    ///
+    /// *C style function: [nctree_create()][c_api::nctree_create].*
+    pub fn from_nodes<'a, T, F>(
+        plane: &mut NcPlane,
+        nodes: &[T],
+        indentcols: NcDim,
+        draw: F,
+    ) -> NcResult<&'a mut Self>
+    where
+        T: TreeNode + Clone + 'static,
+        F: FnMut(&mut NcPlane, &T, i32) -> NcResult<()> + 'static,
+    {
+        if nodes.is_empty() {
+            return Err(NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "NcTree::from_nodes(): an NcTree can't be empty",
+            ));
+        }
+        let mut data = TreeData::new(draw);
+        let nodes: Vec<_> = nodes.iter().map(|node| data.store(node)).collect();
+        data.items = nodes;
+        let items = c_items(&mut data.items);
+        let options = NcTreeOptions::with_all_args(
+            &items.items,
+            items.items.len(),
+            Some(store::treecb_trampoline),
+            indentcols,
+            0,
+        );
+        let res = unsafe { nctree_create(plane, &options) };
+        if !res.is_null() {
+            // the tree keeps the values of its items
+            userptr::get_or_init(plane).widget_data = Some(data);
+        }
+        error_ref_mut![@call "nctree_create", res, "NcTree::from_nodes()"]
+    }
+
+    /// Creates an [NcTree] from the `nodes` of a recursive structure,
+    /// whose items are drawn with their [label][TreeNode#tymethod.label].
+    ///
+    /// *C style function: [nctree_create()][c_api::nctree_create].*
+    pub fn from_labels<'a, T>(
+        plane: &mut NcPlane,
+        nodes: &[T],
+        indentcols: NcDim,
+    ) -> NcResult<&'a mut Self>
+    where
+        T: TreeNode + Clone + 'static,
+    {
+        Self::from_nodes(plane, nodes, indentcols, |plane, node: &T, _pos| {
+            plane.putstr_yx(0, 0, &node.label())?;
+            Ok(())
+        })
+    }
+
+    /// Destroys an NcTree created with [new()][NcTree#method.new], along
+    /// with the values of its items.
+    ///
+    /// # Safety
+    ///
+    /// Neither the tree nor its plane may be used afterwards, and it must
+    /// not be owned by an `OwnedTree`.
     ///
     /// *C style function: [nctree_destroy()][c_api::nctree_destroy].*
-    pub fn destroy(&mut self) {
-        unsafe { c_api::nctree_destroy(self) };
+    pub unsafe fn destroy(&mut self) -> NcResult<()> {
+        userptr::free(c_api::nctree_plane(self));
+        c_api::nctree_destroy(self);
+        Ok(())
     }
 }
 
 /// # `NcTree` methods
 impl NcTree {
-    // NOTE: not implemented yet in C API
-    //
-    // /// Goes to the item specified by the array |spec|, terminated by UINT_MAX.
-    // ///
-    // /// If the spec is invalid, returns an error and the depth of the first
-    // /// invalid spec is written to *|failspec|.
-    // ///
-    // /// Otherwise, the true depth is written to *|failspec|,
-    // /// and the curry is returned (|failspec| is necessary because the
-    // /// curry could itself be NULL).
-    // ///
-    // /// *C style function: [nctree_goto()][c_api::nctree_goto].*
-    // pub fn goto(&mut self, spec: ... , failspec: ...) -> NcResult<&mut NcTreeItem> {
-    //     let res = unsafe { c_api::nctree_goto(self) };
-    //     if !res.is_null() {
-    //         Ok(unsafe { &mut *(res as *mut NcTreeItem) })
-    //     } else {
-    //         Err(NcError::with_msg(NcIntResult::ERR, "NcTree.goto()"))
-    //     }
-    // }
+    /// Inserts the `node`, with its children, at `path`, moving the item
+    /// already there, if any, and the ones after it.
+    ///
+    /// The `path` is the index of the item at each level. All its parents
+    /// must exist, and its last index can be at most the number of siblings.
+    ///
+    /// It's an error if the tree wasn't created with
+    /// [from_nodes][NcTree#method.from_nodes], or with nodes of another type.
+    ///
+    /// *C style function: [nctree_add()][c_api::nctree_add].*
+    pub fn add<T: TreeNode + Clone + 'static>(&mut self, path: &[u32], node: &T) -> NcResult<()> {
+        let data = unsafe { tree_data(self) }.ok_or_else(|| {
+            NcError::with_kind(
+                NcErrorKind::InvalidArgument,
+                "NcTree.add(): the tree wasn't created from Rust",
+            )
+        })?;
+        data.check_type::<T>()?;
+        let mut item = vec![data.store(node)];
+        let (siblings, index) = data.siblings_mut(path)?;
+        let items = c_items(&mut item);
+        let spec = spec(path);
+        error![
+            @call "nctree_add", unsafe { c_api::nctree_add(self, spec.as_ptr(), items.items.as_ptr()) },
+            "NcTree.add()",
+            siblings.insert(index, item.remove(0))
+        ]
+    }
+
+    /// Removes the item at `path`, with its subitems.
+    ///
+    /// *C style function: [nctree_del()][c_api::nctree_del].*
+    pub fn del(&mut self, path: &[u32]) -> NcResult<()> {
+        let spec = spec(path);
+        let data = unsafe { tree_data(self) };
+        error![
+            @call "nctree_del", unsafe { c_api::nctree_del(self, spec.as_ptr()) },
+            "NcTree.del()",
+            if let Some(data) = data {
+                data.remove(path)
+            }
+        ]
+    }
 
-    /// Returns the focused item, if any items are present.
+    /// Focuses the item at `path`.
     ///
-    /// This is not a copy; be careful to use it only for the duration of a
-    /// critical section.
+    /// It's an error if it doesn't exist.
+    ///
+    /// *C style function: [nctree_goto()][c_api::nctree_goto].*
+    pub fn goto(&mut self, path: &[u32]) -> NcResult<()> {
+        let spec = spec(path);
+        // the depth where the path doesn't exist, if it doesn't
+        let mut failspec = path.len() as c_int;
+        let curry = unsafe { c_api::nctree_goto(self, spec.as_ptr(), &mut failspec) };
+        // only the trees created from Rust are known not to have null curries
+        if failspec as usize != path.len()
+            || (curry.is_null() && unsafe { tree_data(self) }.is_some())
+        {
+            return Err(NcError::with_kind(
+                NcErrorKind::OutOfBounds,
+                &format![
+                    "NcTree.goto(): the path {:?} doesn't exist at depth {}",
+                    path, failspec
+                ],
+            )
+            .calling("nctree_goto"));
+        }
+        Ok(())
+    }
+
+    /// Returns the value of the focused item, if any items are present,
+    /// and they are of type `T`.
     ///
     /// *C style function: [nctree_focused()][c_api::nctree_focused].*
-    pub fn focused(&mut self) -> NcResult<&mut NcTreeItem> {
-        let res = unsafe { c_api::nctree_focused(self) };
-        if !res.is_null() {
-            Ok(unsafe { &mut *(res as *mut NcTreeItem) })
-        } else {
-            Err(NcError::with_msg(NcIntResult::ERR, "NcTree.focused()"))
+    pub fn focused<T: 'static>(&mut self) -> Option<&T> {
+        unsafe {
+            let curry = c_api::nctree_focused(self);
+            store::item_value(self, curry)
         }
     }
 
-    /// Changes the focus to the next item, and returns it.
+    /// Changes the focus to the next item, and returns its value, if it's
+    /// of type `T`.
     ///
     /// *C style function: [nctree_next()][c_api::nctree_next].*
     #[allow(clippy::should_implement_trait)]
-    pub fn next(&mut self) -> NcResult<&mut NcTreeItem> {
-        let res = unsafe { c_api::nctree_next(self) };
-        if !res.is_null() {
-            Ok(unsafe { &mut *(res as *mut NcTreeItem) })
-        } else {
-            Err(NcError::with_msg(NcIntResult::ERR, "NcTree.next()"))
+    pub fn next<T: 'static>(&mut self) -> Option<&T> {
+        unsafe {
+            let curry = c_api::nctree_next(self);
+            store::item_value(self, curry)
         }
     }
 
-    /// Changes the focus to the previous item, and returns it.
+    /// Changes the focus to the previous item, and returns its value, if
+    /// it's of type `T`.
     ///
     /// *C style function: [nctree_prev()][c_api::nctree_prev].*
-    pub fn prev(&mut self) -> NcResult<&mut NcTreeItem> {
-        let res = unsafe { c_api::nctree_prev(self) };
-        if !res.is_null() {
-            Ok(unsafe { &mut *(res as *mut NcTreeItem) })
-        } else {
-            Err(NcError::with_msg(NcIntResult::ERR, "NcTree.prev()"))
+    pub fn prev<T: 'static>(&mut self) -> Option<&T> {
+        unsafe {
+            let curry = c_api::nctree_prev(self);
+            store::item_value(self, curry)
         }
     }
 
@@ -98,15 +218,15 @@ impl NcTree {
     /// - up, down, pgup, or pgdown (navigates among items)
     ///
     /// *C style function: [nctree_offer_input()][c_api::nctree_offer_input].*
-    pub fn offer_input(&mut self, input: NcInput) -> bool {
-        unsafe { c_api::nctree_offer_input(self, &input) }
+    pub fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        Ok(unsafe { c_api::nctree_offer_input(self, &input) })
     }
 
     /// Returns the [NcPlane] backing this NcTree.
     ///
     /// *C style function: [nctree_plane()][c_api::nctree_plane].*
-    pub fn plane(&mut self) -> NcResult<&NcPlane> {
-        error_ref_mut![unsafe { c_api::nctree_plane(self) }, "NcTree.plane()"]
+    pub fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        error_ref_mut![@call "nctree_plane", unsafe { c_api::nctree_plane(self) }, "NcTree.plane()"]
     }
 
     /// Redraws the NcTree in its entirety.
@@ -118,6 +238,6 @@ impl NcTree {
     ///
     /// *C style function: [nctree_redraw()][c_api::nctree_redraw].*
     pub fn redraw(&mut self) -> NcResult<()> {
-        error![unsafe { c_api::nctree_redraw(self) }, "NcTree.redraw()"]
+        error![@call "nctree_redraw", unsafe { c_api::nctree_redraw(self) }, "NcTree.redraw()"]
     }
 }
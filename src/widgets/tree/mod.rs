@@ -1,14 +1,16 @@
 //! `NcTree` widget
 
-// functions already exported by bindgen : 13
+// functions already exported by bindgen : 11
 // ------------------------------------------
 // (#) test:  0
-// (W) wrap: 13
+// (W) wrap: 11
 // ------------------------------------------
+//W nctree_add,
 //W nctree_create,
+//W nctree_del,
 //W nctree_destroy,
 //W nctree_focused,
-//~ nctree_goto,
+//W nctree_goto,
 //W nctree_next,
 //W nctree_offer_input,
 //W nctree_plane,
@@ -18,7 +20,7 @@
 use cty::c_int;
 use std::ffi::c_void;
 
-use crate::NcPlane;
+use crate::{NcPlane, NcResult};
 
 mod methods;
 
@@ -31,12 +33,14 @@ mod methods;
 /// - Each item can have arbitrary subitems.
 /// - Items can be collapsed and expanded.
 /// - The display supports scrolling and searching.
-/// - Items cannot be added or removed, however; they must be provided in their
-///   entirety at creation time.
+/// - Items can be added and removed by their path, which is the index of the
+///   item at each level of the hierarchy.
 ///
 /// NOTE: `NcTree` shares many properties with `NcReel`. Unlike the latter,
-/// `NcTree`s support arbitrary hierarchical levels, but they do not allow
-/// elements to come and go across the lifetime of the widget.
+/// `NcTree`s support arbitrary hierarchical levels.
+///
+/// An `NcTree` can be built from any recursive Rust structure implementing
+/// [`TreeNode`], with [`from_nodes`][NcTree#method.from_nodes].
 ///
 /// `type in C: nctree (struct)`
 pub type NcTree = crate::bindings::ffi::nctree;
@@ -62,28 +66,44 @@ pub type NcTreeOptions = crate::bindings::ffi::nctree_options;
 /// An [NcTreeItem] callback function (unsafe).
 pub type NcTreeItemCbUnsafe = unsafe extern "C" fn(*mut NcPlane, *mut c_void, c_int) -> c_int;
 
-/// An [NcTreeItem] callback function.
-pub type NcTreeItemCb = fn(&mut NcPlane, &str, i32);
+/// An [NcTreeItem] callback closure, that draws the value of an item.
+///
+/// It's called with the plane of the item, its value, and its position
+/// relative to the focused item, which is 0.
+///
+/// It can capture state, and it's stored along with the tree by
+/// [`NcTree::from_nodes()`][NcTree#method.from_nodes].
+pub type NcTreeItemCb<T> = Box<dyn FnMut(&mut NcPlane, &T, i32) -> NcResult<()>>;
 
-// WIP TODO: create callback type and conversion functions
-//
-// /// Converts [NcTreeItemCbUnsafe] to [NcTreeItemCb].
-// pub fn nctreeitemcb_to_rust(resizecb: Option<NcTreeItemCbUnsafe>) -> Option<NcTreeItemCb> {
-//     if let Some(cb) = resizecb {
-//         return Some(unsafe { core::mem::transmute(cb) });
-//     } else {
-//         None
-//     }
-// }
-//
-// /// Converts [NcTreeItemCb] to [NcTreeItemCbUnsafe].
-// ///
-// // waiting for https://github.com/rust-lang/rust/issues/53605
-// // to make this function const, and then NcPlaneOptions constructors.
-// pub fn nctreeitemcb_to_c(resizecb: Option<NcTreeItemCb>) -> Option<NcTreeItemCbUnsafe> {
-//     if let Some(cb) = resizecb {
-//         return Some(unsafe { core::mem::transmute(cb) });
-//     } else {
-//         None
-//     }
-// }
+/// A node of a recursive structure, that can be shown in an [`NcTree`].
+///
+/// The tree keeps a clone of each node as the value of its item.
+///
+/// # Example
+///
+/// ```
+/// use libnotcurses_sys::widgets::TreeNode;
+///
+/// #[derive(Clone)]
+/// struct Dir {
+///     name: String,
+///     entries: Vec<Dir>,
+/// }
+///
+/// impl TreeNode for Dir {
+///     fn label(&self) -> String {
+///         self.name.clone()
+///     }
+///     fn children(&self) -> &[Self] {
+///         &self.entries
+///     }
+/// }
+/// ```
+pub trait TreeNode: Sized {
+    /// Returns the label of the node, drawn by
+    /// [`NcTree::from_labels()`][NcTree#method.from_labels].
+    fn label(&self) -> String;
+
+    /// Returns the children of the node.
+    fn children(&self) -> &[Self];
+}
@@ -0,0 +1,248 @@
+//! `NcWidget`
+
+use crate::{
+    widgets::{NcMenu, NcMultiSelector, NcProgBar, NcReader, NcReel, NcSelector, NcTabbed, NcTree},
+    NcFdPlane, NcInput, NcPlane, NcResult, NcSubproc,
+};
+
+/// The common interface of all the widgets.
+///
+/// It allows to keep different widgets in the same collection, and to drive
+/// them all from the same event loop.
+///
+/// The widgets also have inherent methods with the same names and
+/// signatures, which are preferred when called on a concrete widget.
+/// They behave the same, except for [`NcMenu`], whose `offer_input` here
+/// invokes the actions of its items, like [`NcMenu::handle_input`].
+///
+/// The widgets owned from Rust, like [`NcPlot`][crate::NcPlot] and
+/// [`NcSelectorTyped`][crate::NcSelectorTyped], are destroyed when dropped,
+/// and their inherent `destroy` consumes them.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut widgets: Vec<&mut dyn NcWidget> = vec![tree, reader, selector];
+///
+/// while let Some(input) = next_input()? {
+///     for widget in widgets.iter_mut() {
+///         if widget.offer_input(input)? {
+///             widget.redraw()?;
+///             break;
+///         }
+///     }
+/// }
+/// ```
+pub trait NcWidget {
+    /// Returns the [`NcPlane`] backing the widget.
+    fn plane(&mut self) -> NcResult<&mut NcPlane>;
+
+    /// Offers the `input` to the widget.
+    ///
+    /// Returns `true` if it was relevant, and it ought not be processed
+    /// further, or `false` if it was irrelevant, or if the widget doesn't
+    /// accept input.
+    fn offer_input(&mut self, input: NcInput) -> NcResult<bool>;
+
+    /// Redraws the widget.
+    ///
+    /// It does nothing for the widgets that are redrawn whenever they change.
+    fn redraw(&mut self) -> NcResult<()>;
+
+    /// Destroys the widget, and the plane it was created on, if it was
+    /// given its ownership.
+    ///
+    /// # Safety
+    ///
+    /// The widget must not be used afterwards, through this or any other
+    /// reference, which must be removed from any collection holding it.
+    /// It must not be destroyed again, with this method or with its inherent
+    /// `destroy`, nor be owned by one of the owned widgets.
+    unsafe fn destroy(&mut self) -> NcResult<()>;
+}
+
+impl NcWidget for NcMenu {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        NcMenu::plane(self)
+    }
+
+    /// Invokes the action of the selected item, if there's any,
+    /// like [`NcMenu::handle_input`].
+    fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        self.handle_input(input)
+    }
+
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+
+    unsafe fn destroy(&mut self) -> NcResult<()> {
+        NcMenu::destroy(self)
+    }
+}
+
+impl NcWidget for NcMultiSelector {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        NcMultiSelector::plane(self)
+    }
+
+    fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        NcMultiSelector::offer_input(self, input)
+    }
+
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+
+    unsafe fn destroy(&mut self) -> NcResult<()> {
+        NcMultiSelector::destroy(self)
+    }
+}
+
+impl NcWidget for NcProgBar {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        NcProgBar::plane(self)
+    }
+
+    fn offer_input(&mut self, _input: NcInput) -> NcResult<bool> {
+        Ok(false)
+    }
+
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+
+    unsafe fn destroy(&mut self) -> NcResult<()> {
+        NcProgBar::destroy(self)
+    }
+}
+
+impl NcWidget for NcReader {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        NcReader::plane(self)
+    }
+
+    fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        NcReader::offer_input(self, input)
+    }
+
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+
+    unsafe fn destroy(&mut self) -> NcResult<()> {
+        NcReader::destroy(self)
+    }
+}
+
+impl NcWidget for NcReel {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        NcReel::plane(self)
+    }
+
+    fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        NcReel::offer_input(self, input)
+    }
+
+    fn redraw(&mut self) -> NcResult<()> {
+        NcReel::redraw(self)
+    }
+
+    unsafe fn destroy(&mut self) -> NcResult<()> {
+        NcReel::destroy(self)
+    }
+}
+
+impl NcWidget for NcSelector {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        NcSelector::plane(self)
+    }
+
+    fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        NcSelector::offer_input(self, input)
+    }
+
+    fn redraw(&mut self) -> NcResult<()> {
+        Ok(())
+    }
+
+    unsafe fn destroy(&mut self) -> NcResult<()> {
+        NcSelector::destroy(self)
+    }
+}
+
+impl NcWidget for NcTabbed {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        NcTabbed::plane(self)
+    }
+
+    fn offer_input(&mut self, _input: NcInput) -> NcResult<bool> {
+        Ok(false)
+    }
+
+    fn redraw(&mut self) -> NcResult<()> {
+        NcTabbed::redraw(self)
+    }
+
+    unsafe fn destroy(&mut self) -> NcResult<()> {
+        NcTabbed::destroy(self)
+    }
+}
+
+impl NcWidget for NcTree {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        NcTree::plane(self)
+    }
+
+    fn offer_input(&mut self, input: NcInput) -> NcResult<bool> {
+        NcTree::offer_input(self, input)
+    }
+
+    fn redraw(&mut self) -> NcResult<()> {
+        NcTree::redraw(self)
+    }
+
+    unsafe fn destroy(&mut self) -> NcResult<()> {
+        NcTree::destroy(self)
+    }
+}
+
+impl NcWidget for NcFdPlane {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        NcFdPlane::plane(self)
+    }
+
+    fn offer_input(&mut self, _input: NcInput) -> NcResult<bool> {
+        Ok(false)
+    }
+
+    /// Handles the data read since the last call,
+    /// like [`NcFdPlane::poll`].
+    fn redraw(&mut self) -> NcResult<()> {
+        self.poll().map(|_| ())
+    }
+
+    unsafe fn destroy(&mut self) -> NcResult<()> {
+        NcFdPlane::destroy(self)
+    }
+}
+
+impl NcWidget for NcSubproc {
+    fn plane(&mut self) -> NcResult<&mut NcPlane> {
+        NcSubproc::plane(self)
+    }
+
+    fn offer_input(&mut self, _input: NcInput) -> NcResult<bool> {
+        Ok(false)
+    }
+
+    /// Handles the data read since the last call,
+    /// like [`NcSubproc::poll`].
+    fn redraw(&mut self) -> NcResult<()> {
+        self.poll().map(|_| ())
+    }
+
+    unsafe fn destroy(&mut self) -> NcResult<()> {
+        NcSubproc::destroy(self)
+    }
+}